@@ -132,6 +132,70 @@ fn generate_include_generated() {
         .unwrap();
 }
 
+fn generate_mods_by_package() {
+    fs::create_dir_all("src/mods_by_package").unwrap();
+    copy_from_protobuf_test("src/mods_by_package/mod.rs");
+
+    let dir = format!("{}/mods_by_package", env::var("OUT_DIR").unwrap());
+    if Path::new(&dir).exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir(&dir).unwrap();
+    protobuf_codegen_pure::Codegen::new()
+        .out_dir(dir)
+        .input("../protobuf-test/src/mods_by_package/com/example/common.proto")
+        .input("../protobuf-test/src/mods_by_package/com/example/msg.proto")
+        .input("../protobuf-test/src/mods_by_package/org/common.proto")
+        .input("../protobuf-test/src/mods_by_package/root.proto")
+        .customize(Customize {
+            mods_by_package: Some(true),
+            ..Default::default()
+        })
+        .include("../protobuf-test/src/mods_by_package")
+        .run()
+        .unwrap();
+}
+
+fn generate_extern_path() {
+    fs::create_dir_all("src/extern_path").unwrap();
+    copy_from_protobuf_test("src/extern_path/mod.rs");
+
+    let dir = format!("{}/extern_path", env::var("OUT_DIR").unwrap());
+    if Path::new(&dir).exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir_all(format!("{}/common", dir)).unwrap();
+    fs::create_dir_all(format!("{}/service", dir)).unwrap();
+    protobuf_codegen_pure::Codegen::new()
+        .out_dir(format!("{}/common", dir))
+        .input("../protobuf-test/src/extern_path/common.proto")
+        .input("../protobuf-test/src/extern_path/other.proto")
+        .customize(Customize {
+            gen_mod_rs: Some(true),
+            ..Default::default()
+        })
+        .include("../protobuf-test/src/extern_path")
+        .run()
+        .unwrap();
+    protobuf_codegen_pure::Codegen::new()
+        .out_dir(format!("{}/service", dir))
+        .input("../protobuf-test/src/extern_path/common.proto")
+        .input("../protobuf-test/src/extern_path/other.proto")
+        .input("../protobuf-test/src/extern_path/service.proto")
+        .customize(Customize {
+            gen_mod_rs: Some(true),
+            ..Default::default()
+        })
+        .extern_path(".ext.common", "crate::extern_path::common_protos::common")
+        .extern_path(
+            ".ext.other.Thing",
+            "crate::extern_path::common_protos::other::Thing",
+        )
+        .include("../protobuf-test/src/extern_path")
+        .run()
+        .unwrap();
+}
+
 fn generate_pb_rs() {
     print_rerun_if_changed_recursively("../protobuf-test");
 
@@ -153,6 +217,10 @@ fn generate_pb_rs() {
     generate_interop();
 
     generate_include_generated();
+
+    generate_mods_by_package();
+
+    generate_extern_path();
 }
 
 fn main() {
@@ -1,5 +1,8 @@
 //! Convert parser model to rust-protobuf model
 
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::iter;
 use std::path::Path;
 
@@ -8,11 +11,15 @@ use crate::model;
 use protobuf;
 use protobuf::descriptor::field_descriptor_proto;
 use protobuf::json::json_name;
+use protobuf::wire_format;
+use protobuf::CodedInputStream;
+use protobuf::CodedOutputStream;
 use protobuf::Message;
 use protobuf::UnknownFields;
 use protobuf::UnknownValue;
 
 use crate::model::ProtobufOptionName;
+use crate::model::ProtobufOptionNameComponent;
 use crate::path::fs_path_to_proto_path;
 use crate::protobuf_codegen::case_convert::camel_case;
 use crate::protobuf_codegen::ProtobufAbsolutePath;
@@ -20,6 +27,7 @@ use crate::protobuf_codegen::ProtobufIdent;
 use crate::protobuf_codegen::ProtobufRelativePath;
 use protobuf::descriptor::descriptor_proto::ReservedRange;
 use protobuf::descriptor::field_descriptor_proto::Type;
+use protobuf::reflect::MessageDescriptor;
 use protobuf::reflect::RuntimeTypeBox;
 use protobuf::text_format::lexer::StrLitDecodeError;
 use protobuf::text_format::quote_bytes_to;
@@ -34,13 +42,13 @@ pub enum ConvertError {
     DefaultValueIsNotStringLiteral,
     WrongOptionType,
     InconvertibleValue(RuntimeTypeBox, model::ProtobufConstant),
-    ConstantsOfTypeMessageEnumGroupNotImplemented,
     NotFoundByAbsPath(ProtobufAbsolutePath),
     NotFoundByRelPath(ProtobufRelativePath, ProtobufAbsolutePath),
     ExpectingMessage(ProtobufAbsolutePath),
     ExpectingEnum(ProtobufAbsolutePath),
     UnknownEnumValue(String),
     UnknownFieldName(String),
+    NonRepeatedFieldSpecifiedMultipleTimes(String),
 }
 
 impl From<StrLitDecodeError> for ConvertError {
@@ -256,6 +264,126 @@ impl TypeResolved {
     }
 }
 
+/// Field of a message used as an option value
+#[derive(Clone)]
+struct OptionField {
+    field: model::Field,
+    /// Scope where field type is resolved
+    scope: ProtobufAbsolutePath,
+    /// Syntax of the file where field is declared
+    syntax: model::Syntax,
+    in_oneof: bool,
+    extension: bool,
+}
+
+impl OptionField {
+    fn is_repeated(&self) -> bool {
+        match self.field.typ {
+            model::FieldType::Map(..) => true,
+            _ => self.field.rule == model::Rule::Repeated,
+        }
+    }
+
+    /// Fields without presence are not serialized when equal to default value
+    fn has_presence(&self) -> bool {
+        self.syntax == model::Syntax::Proto2
+            || self.field.proto3_optional
+            || self.in_oneof
+            || self.extension
+    }
+
+    /// Serialized message is stored as length-delimited value or as a group
+    fn message_value(&self, bytes: Vec<u8>) -> OptionWireValue {
+        match self.field.typ {
+            model::FieldType::Group(..) => OptionWireValue::Group(bytes),
+            _ => OptionWireValue::Value(UnknownValue::LengthDelimited(bytes)),
+        }
+    }
+}
+
+/// Message type of an option value
+struct OptionMessage {
+    path: ProtobufAbsolutePath,
+    fields: Vec<OptionField>,
+    message_set_wire_format: bool,
+}
+
+impl OptionMessage {
+    fn field_by_name(&self, name: &str) -> Option<&OptionField> {
+        self.fields.iter().find(|f| f.field.name == name)
+    }
+
+    /// Groups are referred by type name in text format
+    fn field_by_text_format_name(&self, name: &str) -> Option<&OptionField> {
+        self.fields.iter().find(|f| match &f.field.typ {
+            model::FieldType::Group(g) => g.name == name,
+            _ => f.field.name == name,
+        })
+    }
+}
+
+/// Option field value as it is serialized by `protoc`
+enum OptionWireValue {
+    Value(UnknownValue),
+    /// Group content
+    Group(Vec<u8>),
+}
+
+fn option_wire_values_to_bytes(values: &[(u32, OptionWireValue)]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    {
+        let mut os = CodedOutputStream::vec(&mut bytes);
+        for (number, value) in values {
+            match value {
+                OptionWireValue::Value(value) => os.write_unknown(*number, value.get_ref()),
+                OptionWireValue::Group(content) => os
+                    .write_tag(*number, wire_format::WireTypeStartGroup)
+                    .and_then(|()| os.write_raw_bytes(content))
+                    .and_then(|()| os.write_tag(*number, wire_format::WireTypeEndGroup)),
+            }
+            .expect("write to vec");
+        }
+        os.flush().expect("write to vec");
+    }
+    bytes
+}
+
+/// Group is stored in unknown fields as nested unknown fields,
+/// the same way as options parsed from `protoc` output
+fn option_group_content_to_unknown_fields(content: &[u8]) -> UnknownFields {
+    let mut is = CodedInputStream::from_bytes(content);
+    let mut fields = UnknownFields::new();
+    while !is.eof().expect("read from vec") {
+        let (number, wire_type) = is.read_tag_unpack().expect("read from vec");
+        let value = is
+            .read_unknown_field(number, wire_type)
+            .expect("read from vec");
+        fields.add_value(number, value);
+    }
+    fields
+}
+
+fn option_packed_values_to_bytes(values: &[UnknownValue]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    {
+        let mut os = CodedOutputStream::vec(&mut bytes);
+        for value in values {
+            os.write_unknown_no_tag(value.get_ref())
+                .expect("write to vec");
+        }
+        os.flush().expect("write to vec");
+    }
+    bytes
+}
+
+fn is_default_option_value(value: &UnknownValue) -> bool {
+    match value {
+        UnknownValue::Varint(0) | UnknownValue::Fixed32(0) | UnknownValue::Fixed64(0) => true,
+        UnknownValue::LengthDelimited(bytes) => bytes.is_empty(),
+        _ => false,
+    }
+}
+
 struct Resolver<'a> {
     current_file: &'a model::FileDescriptor,
     deps: &'a [model::FileDescriptor],
@@ -323,12 +451,38 @@ impl<'a> Resolver<'a> {
         fields: &[model::WithLoc<model::Field>],
         path_in_file: &ProtobufRelativePath,
     ) -> ConvertResult<protobuf::descriptor::DescriptorProto> {
+        let nested_path_in_file = path_in_file.append_ident(&ProtobufIdent::from(name));
+
         let mut output = protobuf::descriptor::DescriptorProto::new();
 
         output.set_name(name.to_owned());
 
         for f in fields {
-            output.field.push(self.field(f, None, path_in_file)?);
+            match &f.t.typ {
+                model::FieldType::Map(t) => {
+                    output.nested_type.push(self.map_entry_message(
+                        &f.t.name,
+                        &t.0,
+                        &t.1,
+                        &nested_path_in_file,
+                    )?);
+                }
+                model::FieldType::Group(model::Group {
+                    name: group_name,
+                    fields,
+                    ..
+                }) => {
+                    output.nested_type.push(self.group_message(
+                        group_name,
+                        fields,
+                        &nested_path_in_file,
+                    )?);
+                }
+                _ => (),
+            }
+            output
+                .field
+                .push(self.field(f, None, &nested_path_in_file)?);
         }
 
         Ok(output)
@@ -417,15 +571,17 @@ impl<'a> Resolver<'a> {
                                 &nested_path_in_file,
                             )?);
                         }
-                        output.oneof_decl.push(self.oneof(o, path_in_file)?);
+                        output.oneof_decl.push(self.oneof(o, &nested_path_in_file)?);
                     }
                 }
             }
 
+            self.add_synthetic_oneofs(&mut fields, &mut output.oneof_decl);
+
             output.field = fields;
         }
 
-        output.options = Some(self.message_options(&input.options, path_in_file)?).into();
+        output.options = Some(self.message_options(&input.options, &nested_path_in_file)?).into();
 
         for ext in &input.extension_ranges {
             let mut extension_range = protobuf::descriptor::descriptor_proto::ExtensionRange::new();
@@ -474,10 +630,26 @@ impl<'a> Resolver<'a> {
     ) -> ConvertResult<protobuf::descriptor::MethodDescriptorProto> {
         let mut output = protobuf::descriptor::MethodDescriptorProto::new();
         output.set_name(input.name.clone());
+        output.set_input_type(self.service_method_type(&input.input_type)?);
+        output.set_output_type(self.service_method_type(&input.output_type)?);
+        if input.client_streaming {
+            output.set_client_streaming(true);
+        }
+        if input.server_streaming {
+            output.set_server_streaming(true);
+        }
         output.options = Some(self.service_method_options(&input.options)?).into();
         Ok(output)
     }
 
+    fn service_method_type(&self, name: &str) -> ConvertResult<String> {
+        let (path, me) = self.resolve_message_or_enum_leg(name, &ProtobufRelativePath::empty())?;
+        match me {
+            MessageOrEnum::Message(..) => Ok(path.path),
+            MessageOrEnum::Enum(..) => Err(ConvertError::ExpectingMessage(path)),
+        }
+    }
+
     fn service(
         &self,
         input: &model::Service,
@@ -504,7 +676,11 @@ impl<'a> Resolver<'a> {
         M: Message,
     {
         let mut options = M::new();
-        let extendee = M::descriptor_static().full_name().to_owned();
+        let options_path =
+            ProtobufAbsolutePath::from_path_without_dot(M::descriptor_static().full_name());
+        let mut scope =
+            ProtobufAbsolutePath::from_package_path(self.current_file.package.as_deref());
+        scope.push_relative(path_in_file);
 
         for option in input {
             match option.name.get_simple() {
@@ -526,36 +702,54 @@ impl<'a> Resolver<'a> {
                 }
             }
 
-            let extension = match self.find_extension(&option.name.full_name()) {
-                Ok(e) => e,
+            let option_name_for_diag = format!("{}", option.name);
+            let fields = match self.resolve_option_name(&option.name, &options_path, &scope) {
+                Ok(fields) => fields,
                 // TODO: return error
-                Err(_) => continue,
+                Err(ConvertError::ExtensionNotFound(..))
+                | Err(ConvertError::UnsupportedOption(..)) => continue,
+                Err(e) => return Err(e),
             };
-            if extension.extendee != extendee {
-                return Err(ConvertError::WrongExtensionType(
-                    format!("{}", option.name),
-                    extendee,
-                ));
-            }
 
-            let value = match self.option_value_to_unknown_value_leg(
-                &option.value,
-                &extension.field.t.name,
-                &extension.field.t.typ,
-                &format!("{}", option.name),
-                path_in_file,
-            ) {
-                Ok(value) => value,
-                Err(ConvertError::ConstantsOfTypeMessageEnumGroupNotImplemented) => {
-                    // TODO: return error
-                    continue;
-                }
-                Err(e) => return Err(e),
+            let (last, intermediate) = fields.split_last().unwrap();
+            let mut number = last.field.number as u32;
+            let mut value = match self.option_message(last)? {
+                Some(message) => match &option.value {
+                    model::ProtobufConstant::Message(value) => last.message_value(
+                        self.option_message_value(value, &message, &option_name_for_diag)?,
+                    ),
+                    value => {
+                        return Err(ConvertError::UnsupportedExtensionType(
+                            option_name_for_diag,
+                            message.path.to_string(),
+                            value.clone(),
+                        ))
+                    }
+                },
+                None => OptionWireValue::Value(self.option_value_to_unknown_value(
+                    &option.value,
+                    &last.field.name,
+                    &last.field.typ,
+                    &option_name_for_diag,
+                    &last.scope,
+                )?),
             };
+            // `(a).b.c = v` is stored as `(a) = { b { c: v } }`
+            for field in intermediate.iter().rev() {
+                value = field.message_value(option_wire_values_to_bytes(&[(number, value)]));
+                number = field.field.number as u32;
+            }
 
-            options
-                .mut_unknown_fields()
-                .add_value(extension.field.t.number as u32, value);
+            match value {
+                OptionWireValue::Value(value) => {
+                    options.mut_unknown_fields().add_value(number, value);
+                }
+                OptionWireValue::Group(content) => {
+                    options
+                        .mut_unknown_fields()
+                        .add_group(number, option_group_content_to_unknown_fields(&content));
+                }
+            }
         }
         Ok(options)
     }
@@ -590,6 +784,9 @@ impl<'a> Resolver<'a> {
         }
 
         output.set_number(input.t.number);
+        if input.t.proto3_optional {
+            output.set_proto3_optional(true);
+        }
         if let Some(ref default) = input.t.options.as_slice().by_name("default") {
             let default = match output.get_field_type() {
                 protobuf::descriptor::field_descriptor_proto::Type::TYPE_STRING => {
@@ -632,22 +829,15 @@ impl<'a> Resolver<'a> {
         iter::once(self.current_file).chain(self.deps).collect()
     }
 
-    fn package_files(&self, package: Option<&str>) -> Vec<&model::FileDescriptor> {
-        self.all_files()
-            .into_iter()
-            .filter(|f| f.package.as_deref() == package)
-            .collect()
-    }
-
-    fn find_message_or_enum_by_abs_name(
+    fn find_message_or_enum_with_file_by_abs_name(
         &self,
         absolute_path: &ProtobufAbsolutePath,
-    ) -> ConvertResult<MessageOrEnum<'a>> {
+    ) -> ConvertResult<(&'a model::FileDescriptor, MessageOrEnum<'a>)> {
         for file in self.all_files() {
             let file_package = ProtobufAbsolutePath::from_package_path(file.package.as_deref());
             if let Some(relative) = absolute_path.remove_prefix(&file_package) {
                 if let Some((_, t)) = LookupScope::File(file).find_message_or_enum(&relative) {
-                    return Ok(t);
+                    return Ok((file, t));
                 }
             }
         }
@@ -655,14 +845,13 @@ impl<'a> Resolver<'a> {
         return Err(ConvertError::NotFoundByAbsPath(absolute_path.clone()));
     }
 
-    fn find_message_by_abs_name(
+    fn find_message_or_enum_by_abs_name(
         &self,
-        abs_path: &ProtobufAbsolutePath,
-    ) -> ConvertResult<&'a model::Message> {
-        match self.find_message_or_enum_by_abs_name(abs_path)? {
-            MessageOrEnum::Message(m) => Ok(m),
-            MessageOrEnum::Enum(..) => Err(ConvertError::ExpectingMessage(abs_path.clone())),
-        }
+        absolute_path: &ProtobufAbsolutePath,
+    ) -> ConvertResult<MessageOrEnum<'a>> {
+        Ok(self
+            .find_message_or_enum_with_file_by_abs_name(absolute_path)?
+            .1)
     }
 
     fn find_enum_by_abs_name(
@@ -771,27 +960,6 @@ impl<'a> Resolver<'a> {
         self.field_type(name, input, &scope)
     }
 
-    fn _runtime_type_for_field_type(&self, ft: &TypeResolved) -> ConvertResult<RuntimeTypeBox> {
-        Ok(match ft {
-            TypeResolved::Bool => RuntimeTypeBox::Bool,
-            TypeResolved::Int32 | TypeResolved::Sint32 | TypeResolved::Sfixed32 => {
-                RuntimeTypeBox::I32
-            }
-            TypeResolved::Int64 | TypeResolved::Sint64 | TypeResolved::Sfixed64 => {
-                RuntimeTypeBox::I64
-            }
-            TypeResolved::Uint32 | TypeResolved::Fixed32 => RuntimeTypeBox::U32,
-            TypeResolved::Uint64 | TypeResolved::Fixed64 => RuntimeTypeBox::U64,
-            TypeResolved::Float => RuntimeTypeBox::F32,
-            TypeResolved::Double => RuntimeTypeBox::F64,
-            TypeResolved::String => RuntimeTypeBox::String,
-            TypeResolved::Bytes => RuntimeTypeBox::VecU8,
-            TypeResolved::Message(_) | TypeResolved::Enum(_) | TypeResolved::Group(_) => {
-                return Err(ConvertError::ConstantsOfTypeMessageEnumGroupNotImplemented)
-            }
-        })
-    }
-
     fn enum_value(
         &self,
         input: &model::EnumValue,
@@ -836,6 +1004,37 @@ impl<'a> Resolver<'a> {
         Ok(output)
     }
 
+    /// Each proto3 `optional` field gets its own oneof, placed after regular oneofs,
+    /// the same way as `protoc` does.
+    fn add_synthetic_oneofs(
+        &self,
+        fields: &mut [protobuf::descriptor::FieldDescriptorProto],
+        oneofs: &mut Vec<protobuf::descriptor::OneofDescriptorProto>,
+    ) {
+        let mut names: HashSet<String> = fields
+            .iter()
+            .map(|f| f.get_name().to_owned())
+            .chain(oneofs.iter().map(|o| o.get_name().to_owned()))
+            .collect();
+
+        for field in fields.iter_mut().filter(|f| f.get_proto3_optional()) {
+            let mut name = if field.get_name().starts_with('_') {
+                field.get_name().to_owned()
+            } else {
+                format!("_{}", field.get_name())
+            };
+            while names.contains(&name) {
+                name = format!("X{}", name);
+            }
+            names.insert(name.clone());
+
+            field.set_oneof_index(oneofs.len() as i32);
+            let mut oneof = protobuf::descriptor::OneofDescriptorProto::new();
+            oneof.set_name(name);
+            oneofs.push(oneof);
+        }
+    }
+
     fn oneof_options(
         &self,
         input: &[model::ProtobufOption],
@@ -855,45 +1054,379 @@ impl<'a> Resolver<'a> {
         Ok(output)
     }
 
-    fn find_extension_by_path(&self, path: &str) -> ConvertResult<&model::Extension> {
-        let (package, name) = match path.rfind('.') {
-            Some(dot) => (Some(&path[..dot]), &path[dot + 1..]),
-            None => (self.current_file.package.as_deref(), path),
+    /// Extension declared at top level of a file or in a message
+    fn find_extension_by_abs_name(
+        &self,
+        absolute_path: &ProtobufAbsolutePath,
+    ) -> Option<(&'a model::Extension, OptionField)> {
+        for file in self.all_files() {
+            let file_package = ProtobufAbsolutePath::from_package_path(file.package.as_deref());
+            let relative = match absolute_path.remove_prefix(&file_package) {
+                Some(relative) if !relative.is_empty() => relative,
+                _ => continue,
+            };
+            let (parent, name) = match relative.get().rfind('.') {
+                Some(dot) => (
+                    ProtobufRelativePath::new(relative.get()[..dot].to_owned()),
+                    &relative.get()[dot + 1..],
+                ),
+                None => (ProtobufRelativePath::empty(), relative.get()),
+            };
+            let extensions = if parent.is_empty() {
+                &file.extensions
+            } else {
+                match LookupScope::File(file).find_message_or_enum(&parent) {
+                    Some((_, MessageOrEnum::Message(message))) => &message.extensions,
+                    _ => continue,
+                }
+            };
+            if let Some(extension) = extensions.iter().find(|e| e.t.field.t.name == name) {
+                let mut scope = file_package;
+                scope.push_relative(&parent);
+                let field = OptionField {
+                    field: extension.t.field.t.clone(),
+                    scope,
+                    syntax: file.syntax,
+                    in_oneof: false,
+                    extension: true,
+                };
+                return Some((&extension.t, field));
+            }
+        }
+        None
+    }
+
+    /// Find extension of `extendee` by name relative to `scope`
+    fn resolve_extension(
+        &self,
+        name: &str,
+        scope: &ProtobufAbsolutePath,
+        extendee: &ProtobufAbsolutePath,
+    ) -> ConvertResult<OptionField> {
+        let candidates = if ProtobufAbsolutePath::is_abs(name) {
+            vec![ProtobufAbsolutePath::new(name.to_owned())]
+        } else {
+            let relative = ProtobufRelativePath::from(name);
+            scope
+                .self_and_parents()
+                .into_iter()
+                .map(|mut candidate| {
+                    candidate.push_relative(&relative);
+                    candidate
+                })
+                .collect()
         };
 
-        for file in self.package_files(package) {
-            for ext in &file.extensions {
-                if ext.t.field.t.name == name {
-                    return Ok(&ext.t);
+        for candidate in candidates {
+            if let Some((extension, field)) = self.find_extension_by_abs_name(&candidate) {
+                let (extension_extendee, _) =
+                    self.resolve_message_or_enum(&extension.extendee, &field.scope)?;
+                if &extension_extendee != extendee {
+                    return Err(ConvertError::WrongExtensionType(
+                        name.to_owned(),
+                        extendee.to_string(),
+                    ));
                 }
+                return Ok(field);
             }
         }
 
-        Err(ConvertError::ExtensionNotFound(path.to_owned()))
+        Err(ConvertError::ExtensionNotFound(name.to_owned()))
     }
 
-    fn find_extension(&self, option_name: &str) -> ConvertResult<&model::Extension> {
-        if !option_name.starts_with('(') || !option_name.ends_with(')') {
-            return Err(ConvertError::UnsupportedOption(option_name.to_owned()));
+    /// Fields referred by custom option name like `(a).b.(c)`,
+    /// the first one is an extension of the options message
+    fn resolve_option_name(
+        &self,
+        name: &ProtobufOptionName,
+        options: &ProtobufAbsolutePath,
+        scope: &ProtobufAbsolutePath,
+    ) -> ConvertResult<Vec<OptionField>> {
+        let mut fields: Vec<OptionField> = Vec::new();
+        for component in &name.components {
+            let field = match fields.last() {
+                None => match component {
+                    ProtobufOptionNameComponent::Ext(extension_name) => {
+                        self.resolve_extension(extension_name, scope, options)?
+                    }
+                    ProtobufOptionNameComponent::Direct(..) => {
+                        return Err(ConvertError::UnsupportedOption(name.full_name()))
+                    }
+                },
+                Some(last) => {
+                    // Only singular message fields can be followed by subfield name
+                    let message = match self.option_message(last)? {
+                        Some(message) if !last.is_repeated() => message,
+                        _ => return Err(ConvertError::UnsupportedOption(name.full_name())),
+                    };
+                    match component {
+                        ProtobufOptionNameComponent::Direct(ident) => message
+                            .field_by_name(ident.get())
+                            .cloned()
+                            .ok_or_else(|| ConvertError::UnknownFieldName(ident.to_string()))?,
+                        ProtobufOptionNameComponent::Ext(extension_name) => {
+                            self.resolve_extension(extension_name, scope, &message.path)?
+                        }
+                    }
+                }
+            };
+            fields.push(field);
         }
-        let path = &option_name[1..option_name.len() - 1];
-        self.find_extension_by_path(path)
+        Ok(fields)
+    }
+
+    /// Message type of the field, `None` if the field is not a message, a group or a map
+    fn option_message(&self, field: &OptionField) -> ConvertResult<Option<OptionMessage>> {
+        let nested_field = |f: &model::Field, path: &ProtobufAbsolutePath| OptionField {
+            field: f.clone(),
+            scope: path.clone(),
+            syntax: field.syntax,
+            in_oneof: false,
+            extension: false,
+        };
+        Ok(Some(match &field.field.typ {
+            model::FieldType::Group(group) => {
+                let mut path = field.scope.clone();
+                path.push_simple(ProtobufIdent::from(group.name.clone()));
+                OptionMessage {
+                    fields: group
+                        .fields
+                        .iter()
+                        .map(|f| nested_field(&f.t, &path))
+                        .collect(),
+                    path,
+                    message_set_wire_format: false,
+                }
+            }
+            model::FieldType::Map(t) => {
+                let mut path = field.scope.clone();
+                path.push_simple(Resolver::map_entry_name_for_field_name(&field.field.name));
+                let entry_field = |name: &str, number, typ: &model::FieldType| model::Field {
+                    name: name.to_owned(),
+                    rule: model::Rule::Optional,
+                    proto3_optional: false,
+                    typ: typ.clone(),
+                    number,
+                    options: Vec::new(),
+                };
+                OptionMessage {
+                    fields: vec![
+                        nested_field(&entry_field("key", 1, &t.0), &path),
+                        nested_field(&entry_field("value", 2, &t.1), &path),
+                    ],
+                    path,
+                    message_set_wire_format: false,
+                }
+            }
+            model::FieldType::MessageOrEnum(name) => {
+                let (path, _) = self.resolve_message_or_enum(name, &field.scope)?;
+                let (file, message) =
+                    match self.find_message_or_enum_with_file_by_abs_name(&path)? {
+                        (file, MessageOrEnum::Message(message)) => (file, message),
+                        (_, MessageOrEnum::Enum(..)) => return Ok(None),
+                    };
+                let fields = message
+                    .fields
+                    .iter()
+                    .flat_map(|fo| match &fo.t {
+                        model::FieldOrOneOf::Field(f) => vec![(f, false)],
+                        model::FieldOrOneOf::OneOf(o) => {
+                            o.fields.iter().map(|f| (f, true)).collect()
+                        }
+                    })
+                    .map(|(f, in_oneof)| OptionField {
+                        field: f.t.clone(),
+                        scope: path.clone(),
+                        syntax: file.syntax,
+                        in_oneof,
+                        extension: false,
+                    })
+                    .collect();
+                let message_set_wire_format = message
+                    .options
+                    .as_slice()
+                    .by_name_bool("message_set_wire_format")?
+                    .unwrap_or(false);
+                OptionMessage {
+                    path,
+                    fields,
+                    message_set_wire_format,
+                }
+            }
+            _ => return Ok(None),
+        }))
     }
 
-    fn option_value_to_unknown_value_leg(
+    /// Serialize message constant the same way `protoc` serializes message parsed from text format:
+    /// fields are ordered by number, values of repeated fields are kept in order of appearance
+    fn option_message_value(
         &self,
-        value: &model::ProtobufConstant,
-        name: &str,
-        field_type: &model::FieldType,
+        value: &model::ProtobufConstantMessage,
+        message: &OptionMessage,
         option_name_for_diag: &str,
-        path_in_file: &ProtobufRelativePath,
-    ) -> ConvertResult<UnknownValue> {
-        let mut scope = ProtobufAbsolutePath::from_package_path(
-            self.current_file.package.as_ref().map(|s| s.as_ref()),
-        );
-        scope.push_relative(path_in_file);
+    ) -> ConvertResult<Vec<u8>> {
+        let mut fields: BTreeMap<i32, (OptionField, Vec<&model::ProtobufConstant>)> =
+            BTreeMap::new();
+        for (name, value) in &value.fields {
+            let field = message
+                .field_by_text_format_name(name)
+                .ok_or_else(|| ConvertError::UnknownFieldName(name.clone()))?;
+            fields
+                .entry(field.field.number)
+                .or_insert_with(|| (field.clone(), Vec::new()))
+                .1
+                .push(value);
+        }
+        for (name, value) in &value.extensions {
+            let field = self.resolve_extension(name, &message.path, &message.path)?;
+            fields
+                .entry(field.field.number)
+                .or_insert_with(|| (field, Vec::new()))
+                .1
+                .push(value);
+        }
+
+        let mut wire_values = Vec::new();
+        for (field, values) in fields.values() {
+            let number = field.field.number as u32;
+            if !field.is_repeated() && values.len() > 1 {
+                return Err(ConvertError::NonRepeatedFieldSpecifiedMultipleTimes(
+                    field.field.name.clone(),
+                ));
+            }
+
+            if let Some(field_message) = self.option_message(field)? {
+                for value in values {
+                    let bytes = match value {
+                        model::ProtobufConstant::Message(value) => {
+                            self.option_message_value(value, &field_message, option_name_for_diag)?
+                        }
+                        value => {
+                            return Err(ConvertError::UnsupportedExtensionType(
+                                option_name_for_diag.to_owned(),
+                                field_message.path.to_string(),
+                                (*value).clone(),
+                            ))
+                        }
+                    };
+                    if message.message_set_wire_format && field.extension && !field.is_repeated() {
+                        // Message set item is a group with type id and message
+                        let item = option_wire_values_to_bytes(&[
+                            (
+                                2,
+                                OptionWireValue::Value(UnknownValue::Varint(number as u64)),
+                            ),
+                            (
+                                3,
+                                OptionWireValue::Value(UnknownValue::LengthDelimited(bytes)),
+                            ),
+                        ]);
+                        wire_values.push((1, OptionWireValue::Group(item)));
+                    } else {
+                        wire_values.push((number, field.message_value(bytes)));
+                    }
+                }
+                continue;
+            }
 
-        self.option_value_to_unknown_value(value, name, field_type, option_name_for_diag, &scope)
+            let values = values
+                .iter()
+                .map(|value| {
+                    self.option_value_to_unknown_value(
+                        value,
+                        &field.field.name,
+                        &field.field.typ,
+                        option_name_for_diag,
+                        &field.scope,
+                    )
+                })
+                .collect::<ConvertResult<Vec<_>>>()?;
+
+            if field.is_repeated() && self.option_field_packed(field)? {
+                if !values.is_empty() {
+                    let packed = option_packed_values_to_bytes(&values);
+                    wire_values.push((
+                        number,
+                        OptionWireValue::Value(UnknownValue::LengthDelimited(packed)),
+                    ));
+                }
+                continue;
+            }
+
+            for value in values {
+                if field.is_repeated() || field.has_presence() || !is_default_option_value(&value) {
+                    wire_values.push((number, OptionWireValue::Value(value)));
+                }
+            }
+        }
+        Ok(option_wire_values_to_bytes(&wire_values))
+    }
+
+    /// Repeated scalar field is packed when declared with `[packed = true]` or by default in proto3
+    fn option_field_packed(&self, field: &OptionField) -> ConvertResult<bool> {
+        Ok(match field.field.typ {
+            model::FieldType::String | model::FieldType::Bytes => false,
+            _ => field
+                .field
+                .options
+                .as_slice()
+                .by_name_bool("packed")?
+                .unwrap_or(field.syntax == model::Syntax::Proto3),
+        })
+    }
+
+    /// Field numbers of the option name components, and whether the last field is repeated
+    fn option_path(
+        &self,
+        options: &MessageDescriptor,
+        name: &ProtobufOptionName,
+    ) -> Option<(Vec<i32>, bool)> {
+        if let ProtobufOptionNameComponent::Direct(ident) = name.components.first()? {
+            // Builtin options are not messages
+            if name.components.len() != 1 {
+                return None;
+            }
+            let field = options.get_field_by_name(ident.get())?;
+            return Some((vec![field.get_proto().get_number()], field.is_repeated()));
+        }
+
+        let options_path = ProtobufAbsolutePath::from_path_without_dot(options.full_name());
+        let scope = ProtobufAbsolutePath::from_package_path(self.current_file.package.as_deref());
+        let fields = self.resolve_option_name(name, &options_path, &scope).ok()?;
+        let path = fields.iter().map(|f| f.field.number).collect();
+        Some((path, fields.last()?.is_repeated()))
+    }
+
+    /// Source code info with option paths completed the same way `protoc` does,
+    /// locations of options which cannot be resolved are dropped
+    fn source_code_info(&self) -> protobuf::descriptor::SourceCodeInfo {
+        let mut source_code_info = self.current_file.source_code_info.clone();
+        let mut unresolved = HashSet::new();
+        let mut repeated_counts = HashMap::new();
+        for option in &self.current_file.option_locations {
+            match self.option_path(&option.options, &option.name) {
+                Some((option_path, repeated)) => {
+                    let path = &mut source_code_info.location[option.location].path;
+                    path.extend(option_path);
+                    if repeated {
+                        let count = repeated_counts.entry(path.clone()).or_insert(0);
+                        path.push(*count);
+                        *count += 1;
+                    }
+                }
+                None => {
+                    unresolved.insert(option.location);
+                }
+            }
+        }
+        source_code_info.location = source_code_info
+            .location
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| !unresolved.contains(i))
+            .map(|(_, location)| location)
+            .collect();
+        source_code_info
     }
 
     fn option_value_to_unknown_value(
@@ -925,7 +1458,8 @@ impl<'a> Resolver<'a> {
                 TypeResolved::Int64
                 | TypeResolved::Int32
                 | TypeResolved::Uint64
-                | TypeResolved::Uint32 => return Ok(UnknownValue::Varint(v)),
+                | TypeResolved::Uint32
+                | TypeResolved::Enum(..) => return Ok(UnknownValue::Varint(v)),
                 TypeResolved::Sint64 => return Ok(UnknownValue::sint64(v as i64)),
                 TypeResolved::Sint32 => return Ok(UnknownValue::sint32(v as i32)),
                 TypeResolved::Float => return Ok(UnknownValue::float(v as f32)),
@@ -942,7 +1476,8 @@ impl<'a> Resolver<'a> {
                 TypeResolved::Int64
                 | TypeResolved::Int32
                 | TypeResolved::Uint64
-                | TypeResolved::Uint32 => return Ok(UnknownValue::Varint(v as u64)),
+                | TypeResolved::Uint32
+                | TypeResolved::Enum(..) => return Ok(UnknownValue::Varint(v as u64)),
                 TypeResolved::Sint64 => return Ok(UnknownValue::sint64(v as i64)),
                 TypeResolved::Sint32 => return Ok(UnknownValue::sint32(v as i32)),
                 TypeResolved::Float => return Ok(UnknownValue::float(v as f32)),
@@ -984,45 +1519,14 @@ impl<'a> Resolver<'a> {
                 }
                 _ => {}
             },
-            model::ProtobufConstant::Message(mo) => match &field_type {
-                TypeResolved::Message(ma) => {
-                    let m = self.find_message_by_abs_name(ma)?;
-                    let mut unknown_fields = UnknownFields::new();
-                    for (n, v) in &mo.fields {
-                        let f = match m.field_by_name(n.as_str()) {
-                            Some(f) => f,
-                            None => return Err(ConvertError::UnknownFieldName(n.clone())),
-                        };
-                        let u = self.option_value_to_unknown_value(
-                            v,
-                            n,
-                            &f.typ,
-                            option_name_for_diag,
-                            ma,
-                        )?;
-                        unknown_fields.add_value(f.number as u32, u);
-                    }
-                    for (_n, _v) in &mo.extensions {
-                        // TODO
-                    }
-                    return Ok(UnknownValue::LengthDelimited(
-                        unknown_fields.write_to_bytes(),
-                    ));
-                }
-                _ => {}
-            },
+            model::ProtobufConstant::Message(..) => {}
         };
 
-        Err(match field_type {
-            TypeResolved::Message(..) | TypeResolved::Enum(..) | TypeResolved::Group(..) => {
-                ConvertError::ConstantsOfTypeMessageEnumGroupNotImplemented
-            }
-            _ => ConvertError::UnsupportedExtensionType(
-                option_name_for_diag.to_owned(),
-                format!("{:?}", field_type),
-                value.clone(),
-            ),
-        })
+        Err(ConvertError::UnsupportedExtensionType(
+            option_name_for_diag.to_owned(),
+            format!("{:?}", field_type),
+            value.clone(),
+        ))
     }
 
     fn file_options(
@@ -1154,5 +1658,7 @@ pub fn file_descriptor(
     output.options =
         Some(resolver.file_options(&input.options, &ProtobufRelativePath::empty())?).into();
 
+    output.source_code_info = Some(resolver.source_code_info()).into();
+
     Ok(output)
 }
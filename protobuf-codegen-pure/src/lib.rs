@@ -39,6 +39,7 @@ mod linked_hash_map;
 mod model;
 mod parser;
 mod path;
+mod source_code_info;
 
 use linked_hash_map::LinkedHashMap;
 use protobuf_codegen::amend_io_error;
@@ -46,6 +47,12 @@ pub use protobuf_codegen::Customize;
 
 #[cfg(test)]
 mod test_against_protobuf_protos;
+#[cfg(test)]
+mod test_custom_options;
+#[cfg(test)]
+mod test_eq_hash_ord;
+#[cfg(test)]
+mod test_source_code_info;
 
 /// Invoke pure rust codegen. See [crate docs](crate) for example.
 // TODO: merge with protoc-rust def
@@ -59,6 +66,10 @@ pub struct Codegen {
     inputs: Vec<PathBuf>,
     /// Customize code generation
     customize: Customize,
+    /// Customize code generation by element name
+    customize_by_name: Vec<(String, Customize)>,
+    /// Rust paths of types generated elsewhere
+    extern_paths: Vec<(String, String)>,
 }
 
 impl Codegen {
@@ -107,17 +118,61 @@ impl Codegen {
         self
     }
 
+    /// Customize code generation of files, messages, fields and enums
+    /// with given fully qualified protobuf name, e. g. `.my.pkg.Message.field`.
+    ///
+    /// A name also matches everything nested in the element, `.` matches everything.
+    /// These options take precedence over options specified in `.proto` files,
+    /// so attributes or derives can be added without editing `.proto` files:
+    ///
+    /// ```ignore
+    /// codegen.customize_by_name(".my.pkg.Message", Customize {
+    ///     extra_derives: Some("Hash, Eq".to_owned()),
+    ///     extra_type_attrs: Some("#[non_exhaustive]".to_owned()),
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub fn customize_by_name(&mut self, name: &str, customize: Customize) -> &mut Self {
+        self.customize_by_name.push((name.to_owned(), customize));
+        self
+    }
+
+    /// Use types of protobuf package or type with given fully qualified name
+    /// from given Rust path instead of generating them, e. g. types already generated
+    /// in a dependency crate:
+    ///
+    /// ```ignore
+    /// codegen.extern_path(".common.types", "::common_protos::types");
+    /// ```
+    ///
+    /// Generated code references `.common.types.Foo` as `::common_protos::types::Foo`,
+    /// nested packages and messages are nested modules, and files of the package
+    /// are not generated even if listed in inputs.
+    pub fn extern_path(&mut self, proto_path: &str, rust_path: &str) -> &mut Self {
+        self.extern_paths
+            .push((proto_path.to_owned(), rust_path.to_owned()));
+        self
+    }
+
     /// Like `protoc --rust_out=...` but without requiring `protoc` or `protoc-gen-rust`
     /// commands in `$PATH`.
     pub fn run(&self) -> io::Result<()> {
         let p = parse_and_typecheck(&self.includes, &self.inputs)?;
 
+        let mut customize = self.customize.clone();
+        customize
+            .customize_by_name
+            .extend(self.customize_by_name.iter().cloned());
+        customize
+            .extern_paths
+            .extend(self.extern_paths.iter().cloned());
+
         protobuf_codegen::gen_and_write(
             &p.file_descriptors,
             &format!("protobuf-codegen-pure={}", env!("CARGO_PKG_VERSION")),
             &p.relative_paths,
             &self.out_dir,
-            &self.customize,
+            &customize,
         )
     }
 }
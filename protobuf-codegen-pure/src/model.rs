@@ -12,9 +12,10 @@ use std::fmt::Write;
 
 use crate::convert::ConvertError;
 use crate::convert::ConvertResult;
-use crate::linked_hash_map::LinkedHashMap;
 pub use crate::parser::ParserError;
 pub use crate::parser::ParserErrorWithLocation;
+use protobuf::descriptor::SourceCodeInfo;
+use protobuf::reflect::MessageDescriptor;
 use protobuf::reflect::ReflectValueBox;
 use protobuf::reflect::RuntimeTypeBox;
 use protobuf_codegen::ProtobufIdent;
@@ -170,6 +171,8 @@ pub struct Field {
     pub name: String,
     /// Field `Rule`
     pub rule: Rule,
+    /// Field is declared with `optional` label in proto3 file, so it tracks presence
+    pub proto3_optional: bool,
     /// Field type
     pub typ: FieldType,
     /// Tag number
@@ -230,14 +233,6 @@ impl Message {
             .collect()
     }
 
-    /** Find a field by name. */
-    pub fn field_by_name(&self, name: &str) -> Option<&Field> {
-        self.regular_fields_including_in_oneofs()
-            .iter()
-            .find(|f| f.t.name == name)
-            .map(|f| &f.t)
-    }
-
     pub fn _nested_extensions(&self) -> Vec<&Group> {
         self.regular_fields_including_in_oneofs()
             .into_iter()
@@ -338,10 +333,13 @@ pub struct Service {
     pub options: Vec<ProtobufOption>,
 }
 
+/// Message constant in text format, e. g. `{ a: 1 b { c: "x" } [ext]: 2 }`
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct ProtobufConstantMessage {
-    pub fields: LinkedHashMap<String, ProtobufConstant>,
-    pub extensions: LinkedHashMap<String, ProtobufConstantMessage>,
+    /// Fields in order of appearance, repeated field is listed once for each value
+    pub fields: Vec<(String, ProtobufConstant)>,
+    /// Extensions in order of appearance
+    pub extensions: Vec<(String, ProtobufConstant)>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -359,13 +357,15 @@ impl ProtobufConstantMessage {
     pub fn format(&self) -> String {
         let mut s = String::new();
         write!(s, "{{").unwrap();
-        for (n, v) in &self.fields {
+        let extensions = self.extensions.iter().map(|(n, v)| (format!("[{}]", n), v));
+        let fields = self.fields.iter().map(|(n, v)| (n.clone(), v));
+        for (n, v) in fields.chain(extensions) {
             match v {
-                ProtobufConstant::Message(m) => write!(s, "{} {}", n, m.format()).unwrap(),
-                v => write!(s, "{}: {}", n, v.format()).unwrap(),
+                ProtobufConstant::Message(m) => write!(s, " {} {}", n, m.format()).unwrap(),
+                v => write!(s, " {}: {}", n, v.format()).unwrap(),
             }
         }
-        write!(s, "}}").unwrap();
+        write!(s, " }}").unwrap();
         s
     }
 }
@@ -466,6 +466,18 @@ pub struct ProtobufOption {
     pub value: ProtobufConstant,
 }
 
+/// Location of an option in `SourceCodeInfo`
+#[derive(Debug, Clone)]
+pub struct OptionLocation {
+    /// Index of the location in `SourceCodeInfo`, location path points to options
+    /// and is extended with field numbers of the option name when the option is resolved
+    pub location: usize,
+    /// Options message, e.g. `FieldOptions`
+    pub options: MessageDescriptor,
+    /// Option name
+    pub name: ProtobufOptionName,
+}
+
 /// Visibility of import statement
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ImportVis {
@@ -506,6 +518,10 @@ pub struct FileDescriptor {
     pub services: Vec<WithLoc<Service>>,
     /// Non-builtin options
     pub options: Vec<ProtobufOption>,
+    /// Locations and comments of declarations
+    pub source_code_info: SourceCodeInfo,
+    /// Locations of options which paths are resolved in `convert`
+    pub option_locations: Vec<OptionLocation>,
 }
 
 impl FileDescriptor {
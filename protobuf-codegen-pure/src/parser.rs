@@ -1,5 +1,17 @@
+use std::mem;
 use std::str;
 
+use protobuf::descriptor::source_code_info::Location;
+use protobuf::descriptor::EnumOptions;
+use protobuf::descriptor::EnumValueOptions;
+use protobuf::descriptor::FieldOptions;
+use protobuf::descriptor::FileOptions;
+use protobuf::descriptor::MessageOptions;
+use protobuf::descriptor::MethodOptions;
+use protobuf::descriptor::OneofOptions;
+use protobuf::descriptor::ServiceOptions;
+use protobuf::descriptor::SourceCodeInfo;
+use protobuf::text_format::lexer::Comments;
 use protobuf::text_format::lexer::LexerError;
 use protobuf::text_format::lexer::NumLit;
 use protobuf::text_format::lexer::ParserLanguage;
@@ -7,6 +19,11 @@ use protobuf::text_format::lexer::StrLitDecodeError;
 use protobuf::text_format::lexer::Token;
 
 use crate::model::*;
+use crate::source_code_info::field_number;
+use crate::source_code_info::FilePaths;
+use crate::source_code_info::MessagePaths;
+use crate::source_code_info::RepeatedPath;
+use crate::source_code_info::SpanIndex;
 use protobuf::text_format::lexer::int;
 use protobuf::text_format::lexer::Tokenizer;
 use protobuf::text_format::lexer::TokenizerError;
@@ -133,10 +150,16 @@ impl ToU8 for u32 {
     }
 }
 
-#[derive(Clone)]
 pub(crate) struct Parser<'a> {
     pub tokenizer: Tokenizer<'a>,
     syntax: Syntax,
+    span_index: SpanIndex<'a>,
+    locations: Vec<Location>,
+    option_locations: Vec<OptionLocation>,
+    /// Leading comment of the next declaration
+    upcoming_doc_comments: Option<String>,
+    /// Detached comments before the next declaration
+    upcoming_detached_comments: Vec<String>,
 }
 
 #[derive(Copy, Clone)]
@@ -158,7 +181,13 @@ impl MessageBodyParseMode {
                 | MessageBodyParseMode::ExtendProto3 => true,
                 MessageBodyParseMode::Oneof => false,
             },
-            Rule::Optional | Rule::Required => match *self {
+            Rule::Optional => match *self {
+                MessageBodyParseMode::MessageProto2
+                | MessageBodyParseMode::MessageProto3
+                | MessageBodyParseMode::ExtendProto2 => true,
+                MessageBodyParseMode::ExtendProto3 | MessageBodyParseMode::Oneof => false,
+            },
+            Rule::Required => match *self {
                 MessageBodyParseMode::MessageProto2 | MessageBodyParseMode::ExtendProto2 => true,
                 MessageBodyParseMode::MessageProto3
                 | MessageBodyParseMode::ExtendProto3
@@ -237,6 +266,126 @@ impl<'a> Parser<'a> {
         Parser {
             tokenizer: Tokenizer::new(input, ParserLanguage::Proto),
             syntax: Syntax::Proto2,
+            span_index: SpanIndex::new(input),
+            locations: Vec::new(),
+            option_locations: Vec::new(),
+            upcoming_doc_comments: None,
+            upcoming_detached_comments: Vec::new(),
+        }
+    }
+
+    // Source code info
+
+    /// Start a location at the next token
+    fn start_location(&mut self, path: Vec<i32>) -> ParserResult<usize> {
+        let pos = self.tokenizer.lookahead_pos()?;
+        let (line, col) = self.span_index.line_col(pos);
+        let mut location = Location::new();
+        location.path = path;
+        location.span = vec![line, col];
+        self.locations.push(location);
+        Ok(self.locations.len() - 1)
+    }
+
+    /// Start a location where another location starts
+    fn start_location_at(&mut self, path: Vec<i32>, start: usize) -> usize {
+        let mut location = Location::new();
+        location.path = path;
+        location.span = self.locations[start].span[..2].to_vec();
+        self.locations.push(location);
+        self.locations.len() - 1
+    }
+
+    /// End a location at the last consumed token
+    fn end_location(&mut self, location: usize) {
+        let end_pos = self.tokenizer.prev_token_end_pos();
+        self.end_location_at(location, end_pos);
+    }
+
+    fn end_location_at(&mut self, location: usize, end_pos: usize) {
+        let (line, col) = self.span_index.line_col(end_pos);
+        let span = &mut self.locations[location].span;
+        if line != span[0] {
+            span.push(line);
+        }
+        span.push(col);
+    }
+
+    /// Add a location of the input range
+    fn add_location(&mut self, path: Vec<i32>, pos: usize, end_pos: usize) {
+        let (line, col) = self.span_index.line_col(pos);
+        let mut location = Location::new();
+        location.path = path;
+        location.span = vec![line, col];
+        self.locations.push(location);
+        self.end_location_at(self.locations.len() - 1, end_pos);
+    }
+
+    /// Record a location of tokens consumed by `f`
+    fn with_location<R>(
+        &mut self,
+        path: Vec<i32>,
+        f: impl FnOnce(&mut Self) -> ParserResult<R>,
+    ) -> ParserResult<R> {
+        let location = self.start_location(path)?;
+        let r = f(self)?;
+        self.end_location(location);
+        Ok(r)
+    }
+
+    /// Path of the location extended with field number
+    fn sub_path(&self, location: usize, field_number: i32) -> Vec<i32> {
+        let mut path = self.locations[location].path.clone();
+        path.push(field_number);
+        path
+    }
+
+    /// Consume a symbol which ends a declaration and attach comments
+    /// around it to the location the same way `protoc` does
+    fn next_end_of_declaration_if_eq(
+        &mut self,
+        symbol: char,
+        location: Option<usize>,
+    ) -> ParserResult<bool> {
+        if !self.tokenizer.next_symbol_if_eq(symbol)? {
+            return Ok(false);
+        }
+
+        let Comments {
+            trailing,
+            detached,
+            leading,
+        } = self.tokenizer.next_comments()?;
+        let leading = mem::replace(&mut self.upcoming_doc_comments, leading);
+
+        match location {
+            Some(location) => {
+                let detached = mem::replace(&mut self.upcoming_detached_comments, detached);
+                let location = &mut self.locations[location];
+                if let Some(leading) = leading.filter(|c| !c.is_empty()) {
+                    location.set_leading_comments(leading);
+                }
+                if let Some(trailing) = trailing.filter(|c| !c.is_empty()) {
+                    location.set_trailing_comments(trailing);
+                }
+                location.leading_detached_comments = detached;
+            }
+            // Comments left at the end of the scope are dropped
+            None if symbol == '}' => self.upcoming_detached_comments = detached,
+            None => self.upcoming_detached_comments.extend(detached),
+        }
+        Ok(true)
+    }
+
+    fn next_end_of_declaration_expect_eq(
+        &mut self,
+        symbol: char,
+        location: Option<usize>,
+    ) -> ParserResult<()> {
+        if self.next_end_of_declaration_if_eq(symbol, location)? {
+            Ok(())
+        } else {
+            Err(TokenizerError::ExpectChar(symbol).into())
         }
     }
 
@@ -259,7 +408,7 @@ impl<'a> Parser<'a> {
 
     // emptyStatement = ";"
     fn next_empty_statement_opt(&mut self) -> ParserResult<Option<()>> {
-        if self.tokenizer.next_symbol_if_eq(';')? {
+        if self.next_end_of_declaration_if_eq(';', None)? {
             Ok(Some(()))
         } else {
             Ok(None)
@@ -286,12 +435,12 @@ impl<'a> Parser<'a> {
     // groupName = capitalLetter { letter | decimalDigit | "_" }
     fn next_group_name(&mut self) -> ParserResult<String> {
         // lexer cannot distinguish between group name and other ident
-        let mut clone = self.clone();
-        let ident = clone.tokenizer.next_ident()?;
+        let mut clone = self.tokenizer.clone();
+        let ident = clone.next_ident()?;
         if !ident.chars().next().unwrap().is_ascii_uppercase() {
             return Err(ParserError::GroupNameShouldStartWithUpperCase);
         }
-        *self = clone;
+        self.tokenizer = clone;
         Ok(ident)
     }
 
@@ -315,29 +464,66 @@ impl<'a> Parser<'a> {
             .next_token_check_map(|token| Ok(token.to_num_lit()?))
     }
 
+    // Message constant in text format, fields may be separated by `,` or `;`
     fn next_message_constant(&mut self) -> ParserResult<ProtobufConstantMessage> {
         let mut r = ProtobufConstantMessage::default();
-        self.tokenizer.next_symbol_expect_eq('{')?;
-        while !self.tokenizer.lookahead_is_symbol('}')? {
+        let end = if self.tokenizer.next_symbol_if_eq('<')? {
+            '>'
+        } else {
+            self.tokenizer.next_symbol_expect_eq('{')?;
+            '}'
+        };
+        while !self.tokenizer.lookahead_is_symbol(end)? {
             if self.tokenizer.next_symbol_if_eq('[')? {
                 let n = self.next_full_ident()?;
                 self.tokenizer.next_symbol_expect_eq(']')?;
-                let v = self.next_message_constant()?;
-                r.extensions.insert(n, v);
+                for v in self.next_message_constant_field_values()? {
+                    r.extensions.push((n.clone(), v));
+                }
             } else {
                 let n = self.tokenizer.next_ident()?;
-                let v = if self.tokenizer.next_symbol_if_eq(':')? {
-                    self.next_constant()?
-                } else {
-                    ProtobufConstant::Message(self.next_message_constant()?)
-                };
-                r.fields.insert(n, v);
+                for v in self.next_message_constant_field_values()? {
+                    r.fields.push((n.clone(), v));
+                }
+            }
+            if !self.tokenizer.next_symbol_if_eq(',')? {
+                self.tokenizer.next_symbol_if_eq(';')?;
             }
         }
-        self.tokenizer.next_symbol_expect_eq('}')?;
+        self.tokenizer.next_symbol_expect_eq(end)?;
         Ok(r)
     }
 
+    // Values of a field in message constant, list `[a, b]` is a shortcut for repeated field
+    fn next_message_constant_field_values(&mut self) -> ParserResult<Vec<ProtobufConstant>> {
+        let colon = self.tokenizer.next_symbol_if_eq(':')?;
+        if !self.tokenizer.next_symbol_if_eq('[')? {
+            return Ok(vec![self.next_message_constant_field_value(colon)?]);
+        }
+        let mut values = Vec::new();
+        if !self.tokenizer.next_symbol_if_eq(']')? {
+            loop {
+                values.push(self.next_message_constant_field_value(colon)?);
+                if self.tokenizer.next_symbol_if_eq(']')? {
+                    break;
+                }
+                self.tokenizer.next_symbol_expect_eq(',')?;
+            }
+        }
+        Ok(values)
+    }
+
+    // Colon is optional before message value
+    fn next_message_constant_field_value(&mut self, colon: bool) -> ParserResult<ProtobufConstant> {
+        if self.tokenizer.lookahead_is_symbol('{')? || self.tokenizer.lookahead_is_symbol('<')? {
+            Ok(ProtobufConstant::Message(self.next_message_constant()?))
+        } else if colon {
+            self.next_constant()
+        } else {
+            Err(ParserError::ExpectConstant)
+        }
+    }
+
     // constant = fullIdent | ( [ "-" | "+" ] intLit ) | ( [ "-" | "+" ] floatLit ) |
     //            strLit | boolLit
     fn next_constant(&mut self) -> ParserResult<ProtobufConstant> {
@@ -390,7 +576,9 @@ impl<'a> Parser<'a> {
     // syntax = "syntax" "=" quote "proto2" quote ";"
     // syntax = "syntax" "=" quote "proto3" quote ";"
     fn next_syntax(&mut self) -> ParserResult<Option<Syntax>> {
-        if self.tokenizer.next_ident_if_eq("syntax")? {
+        if self.tokenizer.lookahead_is_ident("syntax")? {
+            let location = self.start_location(vec![field_number::FILE_SYNTAX])?;
+            self.tokenizer.advance()?;
             self.tokenizer.next_symbol_expect_eq('=')?;
             let syntax_str = self.tokenizer.next_str_lit()?.decode_utf8()?;
             let syntax = if syntax_str == "proto2" {
@@ -400,7 +588,8 @@ impl<'a> Parser<'a> {
             } else {
                 return Err(ParserError::UnknownSyntax);
             };
-            self.tokenizer.next_symbol_expect_eq(';')?;
+            self.next_end_of_declaration_expect_eq(';', Some(location))?;
+            self.end_location(location);
             Ok(Some(syntax))
         } else {
             Ok(None)
@@ -410,17 +599,26 @@ impl<'a> Parser<'a> {
     // Import Statement
 
     // import = "import" [ "weak" | "public" ] strLit ";"
-    fn next_import_opt(&mut self) -> ParserResult<Option<Import>> {
-        if self.tokenizer.next_ident_if_eq("import")? {
-            let vis = if self.tokenizer.next_ident_if_eq("weak")? {
+    fn next_import_opt(&mut self, paths: &mut FilePaths) -> ParserResult<Option<Import>> {
+        if self.tokenizer.lookahead_is_ident("import")? {
+            let location = self.start_location(paths.dependencies.next())?;
+            self.tokenizer.advance()?;
+            let vis = if self.tokenizer.lookahead_is_ident("weak")? {
+                self.with_location(paths.weak_dependencies.next(), |p| {
+                    Ok(p.tokenizer.advance()?)
+                })?;
                 ImportVis::Weak
-            } else if self.tokenizer.next_ident_if_eq("public")? {
+            } else if self.tokenizer.lookahead_is_ident("public")? {
+                self.with_location(paths.public_dependencies.next(), |p| {
+                    Ok(p.tokenizer.advance()?)
+                })?;
                 ImportVis::Public
             } else {
                 ImportVis::Default
             };
             let path = self.tokenizer.next_str_lit()?.decode_utf8()?;
-            self.tokenizer.next_symbol_expect_eq(';')?;
+            self.next_end_of_declaration_expect_eq(';', Some(location))?;
+            self.end_location(location);
             Ok(Some(Import { path, vis }))
         } else {
             Ok(None)
@@ -431,9 +629,12 @@ impl<'a> Parser<'a> {
 
     // package = "package" fullIdent ";"
     fn next_package_opt(&mut self) -> ParserResult<Option<String>> {
-        if self.tokenizer.next_ident_if_eq("package")? {
+        if self.tokenizer.lookahead_is_ident("package")? {
+            let location = self.start_location(vec![field_number::FILE_PACKAGE])?;
+            self.tokenizer.advance()?;
             let package = self.next_full_ident()?;
-            self.tokenizer.next_symbol_expect_eq(';')?;
+            self.next_end_of_declaration_expect_eq(';', Some(location))?;
+            self.end_location(location);
             Ok(Some(package))
         } else {
             Ok(None)
@@ -468,13 +669,37 @@ impl<'a> Parser<'a> {
         Ok(ProtobufOptionName { components })
     }
 
+    /// Remember the option location to complete its path with option field numbers
+    /// when the option is resolved
+    fn add_option_location<M: protobuf::Message>(
+        &mut self,
+        location: usize,
+        name: &ProtobufOptionName,
+    ) {
+        self.option_locations.push(OptionLocation {
+            location,
+            options: M::descriptor_static(),
+            name: name.clone(),
+        });
+    }
+
     // option = "option" optionName  "=" constant ";"
-    fn next_option_opt(&mut self) -> ParserResult<Option<ProtobufOption>> {
-        if self.tokenizer.next_ident_if_eq("option")? {
+    fn next_option_opt<M: protobuf::Message>(
+        &mut self,
+        options_path: &[i32],
+    ) -> ParserResult<Option<ProtobufOption>> {
+        if self.tokenizer.lookahead_is_ident("option")? {
+            // `protoc` records both the statement and the option itself
+            let statement_location = self.start_location(options_path.to_vec())?;
+            let location = self.start_location(options_path.to_vec())?;
+            self.tokenizer.advance()?;
             let name = self.next_option_name()?;
             self.tokenizer.next_symbol_expect_eq('=')?;
             let value = self.next_constant()?;
-            self.tokenizer.next_symbol_expect_eq(';')?;
+            self.next_end_of_declaration_expect_eq(';', Some(location))?;
+            self.end_location(location);
+            self.end_location(statement_location);
+            self.add_option_location::<M>(location, &name);
             Ok(Some(ProtobufOption { name, value }))
         } else {
             Ok(None)
@@ -484,20 +709,23 @@ impl<'a> Parser<'a> {
     // Fields
 
     // label = "required" | "optional" | "repeated"
-    fn next_label(&mut self, mode: MessageBodyParseMode) -> ParserResult<Rule> {
+    fn next_label(&mut self, mode: MessageBodyParseMode, field: usize) -> ParserResult<Rule> {
         let map = &[
             ("optional", Rule::Optional),
             ("required", Rule::Required),
             ("repeated", Rule::Repeated),
         ];
         for &(name, value) in map {
-            let mut clone = self.clone();
-            if clone.tokenizer.next_ident_if_eq(name)? {
+            let mut clone = self.tokenizer.clone();
+            if clone.next_ident_if_eq(name)? {
                 if !mode.label_allowed(value) {
                     return Err(ParserError::LabelNotAllowed);
                 }
 
-                *self = clone;
+                let location =
+                    self.start_location(self.sub_path(field, field_number::FIELD_LABEL))?;
+                self.tokenizer = clone;
+                self.end_location(location);
                 return Ok(value);
             }
         }
@@ -550,21 +778,56 @@ impl<'a> Parser<'a> {
     }
 
     // fieldOption = optionName "=" constant
-    fn next_field_option(&mut self) -> ParserResult<ProtobufOption> {
+    fn next_field_option(&mut self, options_path: &[i32]) -> ParserResult<ProtobufOption> {
+        let location = self.start_location(options_path.to_vec())?;
         let name = self.next_option_name()?;
         self.tokenizer.next_symbol_expect_eq('=')?;
         let value = self.next_constant()?;
+        self.end_location(location);
+        self.add_option_location::<FieldOptions>(location, &name);
         Ok(ProtobufOption { name, value })
     }
 
+    // `default` and `json_name` are stored in the field itself,
+    // and their locations point to the field
+    fn next_field_pseudo_option(&mut self, field: usize) -> ParserResult<Option<ProtobufOption>> {
+        let name = if self.tokenizer.lookahead_is_ident("default")? {
+            self.tokenizer.advance()?;
+            self.tokenizer.next_symbol_expect_eq('=')?;
+            "default"
+        } else if self.tokenizer.lookahead_is_ident("json_name")? {
+            let path = self.sub_path(field, field_number::FIELD_JSON_NAME);
+            let value = self.with_location(path.clone(), |p| {
+                p.tokenizer.advance()?;
+                p.tokenizer.next_symbol_expect_eq('=')?;
+                p.with_location(path, |p| p.next_constant())
+            })?;
+            let name = ProtobufOptionName::simple("json_name");
+            return Ok(Some(ProtobufOption { name, value }));
+        } else {
+            return Ok(None);
+        };
+        let value = self.with_location(
+            self.sub_path(field, field_number::FIELD_DEFAULT_VALUE),
+            |p| p.next_constant(),
+        )?;
+        let name = ProtobufOptionName::simple(name);
+        Ok(Some(ProtobufOption { name, value }))
+    }
+
     // fieldOptions = fieldOption { ","  fieldOption }
-    fn next_field_options(&mut self) -> ParserResult<Vec<ProtobufOption>> {
+    fn next_field_options(&mut self, field: usize) -> ParserResult<Vec<ProtobufOption>> {
+        let options_path = self.sub_path(field, field_number::FIELD_OPTIONS);
         let mut options = Vec::new();
 
-        options.push(self.next_field_option()?);
-
-        while self.tokenizer.next_symbol_if_eq(',')? {
-            options.push(self.next_field_option()?);
+        loop {
+            options.push(match self.next_field_pseudo_option(field)? {
+                Some(option) => option,
+                None => self.next_field_option(&options_path)?,
+            });
+            if !self.tokenizer.next_symbol_if_eq(',')? {
+                break;
+            }
         }
 
         Ok(options)
@@ -572,27 +835,65 @@ impl<'a> Parser<'a> {
 
     // field = label type fieldName "=" fieldNumber [ "[" fieldOptions "]" ] ";"
     // group = label "group" groupName "=" fieldNumber messageBody
-    fn next_field(&mut self, mode: MessageBodyParseMode) -> ParserResult<WithLoc<Field>> {
+    fn next_field(
+        &mut self,
+        mode: MessageBodyParseMode,
+        location: usize,
+        nested_types: &mut RepeatedPath,
+    ) -> ParserResult<WithLoc<Field>> {
         let loc = self.tokenizer.lookahead_loc();
-        let rule = if self.clone().tokenizer.next_ident_if_eq("map")? {
+        let proto3_optional =
+            self.syntax == Syntax::Proto3 && self.tokenizer.lookahead_is_ident("optional")?;
+        let rule = if self.tokenizer.lookahead_is_ident("map")? {
             if !mode.map_allowed() {
                 return Err(ParserError::MapFieldNotAllowed);
             }
             Rule::Optional
         } else {
-            self.next_label(mode)?
+            self.next_label(mode, location)?
         };
+        // Path of the type location depends on the type
+        let type_location = self.start_location(self.locations[location].path.clone())?;
         if self.tokenizer.next_ident_if_eq("group")? {
-            let name = self.next_group_name()?.to_owned();
+            self.locations[type_location]
+                .path
+                .push(field_number::FIELD_TYPE);
+            self.end_location(type_location);
+
+            let name_pos = self.tokenizer.lookahead_pos()?;
+            let name = self
+                .with_location(self.sub_path(location, field_number::FIELD_NAME), |p| {
+                    p.next_group_name()
+                })?;
+            let name_end_pos = self.tokenizer.prev_token_end_pos();
             self.tokenizer.next_symbol_expect_eq('=')?;
-            let number = self.next_field_number()?;
+            let number = self
+                .with_location(self.sub_path(location, field_number::FIELD_NUMBER), |p| {
+                    p.next_field_number()
+                })?;
+
+            // Group declares both a nested message and a field
+            let group_location = self.start_location_at(nested_types.next(), location);
+            self.add_location(
+                self.sub_path(group_location, field_number::MESSAGE_NAME),
+                name_pos,
+                name_end_pos,
+            );
+            self.add_location(
+                self.sub_path(location, field_number::FIELD_TYPE_NAME),
+                name_pos,
+                name_end_pos,
+            );
 
             let mode = match self.syntax {
                 Syntax::Proto2 => MessageBodyParseMode::MessageProto2,
                 Syntax::Proto3 => MessageBodyParseMode::MessageProto3,
             };
 
-            let MessageBody { fields, .. } = self.next_message_body(mode)?;
+            let mut paths = MessagePaths::new(self.locations[group_location].path.clone());
+            let MessageBody { fields, .. } =
+                self.next_message_body(mode, group_location, &mut paths)?;
+            self.end_location(group_location);
 
             let fields = fields
                 .into_iter()
@@ -608,6 +909,7 @@ impl<'a> Parser<'a> {
                 // https://git.io/JvxAP
                 name: name.to_ascii_lowercase(),
                 rule,
+                proto3_optional,
                 typ: FieldType::Group(Group { name: name, fields }),
                 number,
                 options: Vec::new(),
@@ -615,22 +917,45 @@ impl<'a> Parser<'a> {
             Ok(WithLoc { t: field, loc })
         } else {
             let typ = self.next_field_type()?;
-            let name = self.tokenizer.next_ident()?.to_owned();
+            self.locations[type_location].path.push(match typ {
+                FieldType::MessageOrEnum(..) | FieldType::Map(..) => field_number::FIELD_TYPE_NAME,
+                _ => field_number::FIELD_TYPE,
+            });
+            self.end_location(type_location);
+
+            let name = self
+                .with_location(self.sub_path(location, field_number::FIELD_NAME), |p| {
+                    Ok(p.tokenizer.next_ident()?)
+                })?;
             self.tokenizer.next_symbol_expect_eq('=')?;
-            let number = self.next_field_number()?;
+            let number = self
+                .with_location(self.sub_path(location, field_number::FIELD_NUMBER), |p| {
+                    p.next_field_number()
+                })?;
 
             let mut options = Vec::new();
 
-            if self.tokenizer.next_symbol_if_eq('[')? {
-                for o in self.next_field_options()? {
+            if self.tokenizer.lookahead_is_symbol('[')? {
+                let options_location =
+                    self.start_location(self.sub_path(location, field_number::FIELD_OPTIONS))?;
+                self.tokenizer.advance()?;
+                for o in self.next_field_options(location)? {
                     options.push(o);
                 }
                 self.tokenizer.next_symbol_expect_eq(']')?;
+                self.end_location(options_location);
             }
-            self.tokenizer.next_symbol_expect_eq(';')?;
+            self.next_end_of_declaration_expect_eq(';', Some(location))?;
+
+            if let FieldType::Map(..) = typ {
+                // Map entry message
+                nested_types.next();
+            }
+
             let field = Field {
                 name,
                 rule,
+                proto3_optional,
                 typ,
                 number,
                 options,
@@ -641,12 +966,18 @@ impl<'a> Parser<'a> {
 
     // oneof = "oneof" oneofName "{" { oneofField | emptyStatement } "}"
     // oneofField = type fieldName "=" fieldNumber [ "[" fieldOptions "]" ] ";"
-    fn next_oneof_opt(&mut self) -> ParserResult<Option<OneOf>> {
-        if self.tokenizer.next_ident_if_eq("oneof")? {
-            let name = self.tokenizer.next_ident()?.to_owned();
+    fn next_oneof_opt(&mut self, paths: &mut MessagePaths) -> ParserResult<Option<OneOf>> {
+        if self.tokenizer.lookahead_is_ident("oneof")? {
+            let location = self.start_location(paths.oneofs.next())?;
+            self.tokenizer.advance()?;
+            let name = self
+                .with_location(self.sub_path(location, field_number::ONEOF_NAME), |p| {
+                    Ok(p.tokenizer.next_ident()?)
+                })?;
             let MessageBody {
                 fields, options, ..
-            } = self.next_message_body(MessageBodyParseMode::Oneof)?;
+            } = self.next_message_body(MessageBodyParseMode::Oneof, location, paths)?;
+            self.end_location(location);
             let fields = fields
                 .into_iter()
                 .map(|fo| match fo.t {
@@ -686,34 +1017,55 @@ impl<'a> Parser<'a> {
     // Extensions
 
     // range =  intLit [ "to" ( intLit | "max" ) ]
-    fn next_range(&mut self) -> ParserResult<FieldNumberRange> {
-        let from = self.next_field_number()?;
+    fn next_range(&mut self, path: Vec<i32>) -> ParserResult<FieldNumberRange> {
+        let location = self.start_location(path)?;
+        let from_pos = self.tokenizer.lookahead_pos()?;
+        let from = self.with_location(self.sub_path(location, field_number::RANGE_START), |p| {
+            p.next_field_number()
+        })?;
+        let from_end_pos = self.tokenizer.prev_token_end_pos();
         let to = if self.tokenizer.next_ident_if_eq("to")? {
-            if self.tokenizer.next_ident_if_eq("max")? {
-                0x20000000 - 1
-            } else {
-                self.next_field_number()?
-            }
+            self.with_location(self.sub_path(location, field_number::RANGE_END), |p| {
+                if p.tokenizer.next_ident_if_eq("max")? {
+                    Ok(0x20000000 - 1)
+                } else {
+                    p.next_field_number()
+                }
+            })?
         } else {
+            self.add_location(
+                self.sub_path(location, field_number::RANGE_END),
+                from_pos,
+                from_end_pos,
+            );
             from
         };
+        self.end_location(location);
         Ok(FieldNumberRange { from, to })
     }
 
     // ranges = range { "," range }
-    fn next_ranges(&mut self) -> ParserResult<Vec<FieldNumberRange>> {
+    fn next_ranges(&mut self, paths: &mut RepeatedPath) -> ParserResult<Vec<FieldNumberRange>> {
         let mut ranges = Vec::new();
-        ranges.push(self.next_range()?);
+        ranges.push(self.next_range(paths.next())?);
         while self.tokenizer.next_symbol_if_eq(',')? {
-            ranges.push(self.next_range()?);
+            ranges.push(self.next_range(paths.next())?);
         }
         Ok(ranges)
     }
 
     // extensions = "extensions" ranges ";"
-    fn next_extensions_opt(&mut self) -> ParserResult<Option<Vec<FieldNumberRange>>> {
-        if self.tokenizer.next_ident_if_eq("extensions")? {
-            Ok(Some(self.next_ranges()?))
+    fn next_extensions_opt(
+        &mut self,
+        paths: &mut RepeatedPath,
+    ) -> ParserResult<Option<Vec<FieldNumberRange>>> {
+        if self.tokenizer.lookahead_is_ident("extensions")? {
+            let location = self.start_location(paths.path.clone())?;
+            self.tokenizer.advance()?;
+            let ranges = self.next_ranges(paths)?;
+            self.next_end_of_declaration_expect_eq(';', Some(location))?;
+            self.end_location(location);
+            Ok(Some(ranges))
         } else {
             Ok(None)
         }
@@ -724,20 +1076,33 @@ impl<'a> Parser<'a> {
     // Grammar is incorrect: https://github.com/google/protobuf/issues/4558
     // reserved = "reserved" ( ranges | fieldNames ) ";"
     // fieldNames = fieldName { "," fieldName }
-    fn next_reserved_opt(&mut self) -> ParserResult<Option<(Vec<FieldNumberRange>, Vec<String>)>> {
-        if self.tokenizer.next_ident_if_eq("reserved")? {
+    fn next_reserved_opt(
+        &mut self,
+        paths: &mut MessagePaths,
+    ) -> ParserResult<Option<(Vec<FieldNumberRange>, Vec<String>)>> {
+        if self.tokenizer.lookahead_is_ident("reserved")? {
+            // Path depends on what is reserved
+            let location = self.start_location(Vec::new())?;
+            self.tokenizer.advance()?;
             let (ranges, names) = if let &Token::StrLit(..) = self.tokenizer.lookahead_some()? {
+                self.locations[location].path = paths.reserved_names.path.clone();
                 let mut names = Vec::new();
-                names.push(self.tokenizer.next_str_lit()?.decode_utf8()?);
-                while self.tokenizer.next_symbol_if_eq(',')? {
-                    names.push(self.tokenizer.next_str_lit()?.decode_utf8()?);
+                loop {
+                    names.push(self.with_location(paths.reserved_names.next(), |p| {
+                        Ok(p.tokenizer.next_str_lit()?.decode_utf8()?)
+                    })?);
+                    if !self.tokenizer.next_symbol_if_eq(',')? {
+                        break;
+                    }
                 }
                 (Vec::new(), names)
             } else {
-                (self.next_ranges()?, Vec::new())
+                self.locations[location].path = paths.reserved_ranges.path.clone();
+                (self.next_ranges(&mut paths.reserved_ranges)?, Vec::new())
             };
 
-            self.tokenizer.next_symbol_expect_eq(';')?;
+            self.next_end_of_declaration_expect_eq(';', Some(location))?;
+            self.end_location(location);
 
             Ok(Some((ranges, names)))
         } else {
@@ -750,10 +1115,13 @@ impl<'a> Parser<'a> {
     // Enum definition
 
     // enumValueOption = optionName "=" constant
-    fn next_enum_value_option(&mut self) -> ParserResult<ProtobufOption> {
+    fn next_enum_value_option(&mut self, options_path: &[i32]) -> ParserResult<ProtobufOption> {
+        let location = self.start_location(options_path.to_vec())?;
         let name = self.next_option_name()?;
         self.tokenizer.next_symbol_expect_eq('=')?;
         let value = self.next_constant()?;
+        self.end_location(location);
+        self.add_option_location::<EnumValueOptions>(location, &name);
         Ok(ProtobufOption { name, value })
     }
 
@@ -773,18 +1141,29 @@ impl<'a> Parser<'a> {
     }
 
     // enumField = ident "=" intLit [ "[" enumValueOption { ","  enumValueOption } "]" ]";"
-    fn next_enum_field(&mut self) -> ParserResult<EnumValue> {
-        let name = self.tokenizer.next_ident()?.to_owned();
+    fn next_enum_field(&mut self, location: usize) -> ParserResult<EnumValue> {
+        let name = self.with_location(
+            self.sub_path(location, field_number::ENUM_VALUE_NAME),
+            |p| Ok(p.tokenizer.next_ident()?),
+        )?;
         self.tokenizer.next_symbol_expect_eq('=')?;
-        let number = self.next_enum_value()?;
+        let number = self.with_location(
+            self.sub_path(location, field_number::ENUM_VALUE_NUMBER),
+            |p| p.next_enum_value(),
+        )?;
         let mut options = Vec::new();
-        if self.tokenizer.next_symbol_if_eq('[')? {
-            options.push(self.next_enum_value_option()?);
+        if self.tokenizer.lookahead_is_symbol('[')? {
+            let options_path = self.sub_path(location, field_number::ENUM_VALUE_OPTIONS);
+            let options_location = self.start_location(options_path.clone())?;
+            self.tokenizer.advance()?;
+            options.push(self.next_enum_value_option(&options_path)?);
             while self.tokenizer.next_symbol_if_eq(',')? {
-                options.push(self.next_enum_value_option()?);
+                options.push(self.next_enum_value_option(&options_path)?);
             }
             self.tokenizer.next_symbol_expect_eq(']')?;
+            self.end_location(options_location);
         }
+        self.next_end_of_declaration_expect_eq(';', Some(location))?;
 
         Ok(EnumValue {
             name,
@@ -795,28 +1174,39 @@ impl<'a> Parser<'a> {
 
     // enum = "enum" enumName enumBody
     // enumBody = "{" { option | enumField | emptyStatement } "}"
-    fn next_enum_opt(&mut self) -> ParserResult<Option<Enumeration>> {
-        if self.tokenizer.next_ident_if_eq("enum")? {
-            let name = self.tokenizer.next_ident()?.to_owned();
+    fn next_enum_opt(&mut self, enums: &mut RepeatedPath) -> ParserResult<Option<Enumeration>> {
+        if self.tokenizer.lookahead_is_ident("enum")? {
+            let location = self.start_location(enums.next())?;
+            self.tokenizer.advance()?;
+            let name = self
+                .with_location(self.sub_path(location, field_number::ENUM_NAME), |p| {
+                    Ok(p.tokenizer.next_ident()?)
+                })?;
 
             let mut values = Vec::new();
             let mut options = Vec::new();
 
-            self.tokenizer.next_symbol_expect_eq('{')?;
-            while self.tokenizer.lookahead_if_symbol()? != Some('}') {
+            let mut value_paths =
+                RepeatedPath::new(&self.locations[location].path, field_number::ENUM_VALUE);
+            let options_path = self.sub_path(location, field_number::ENUM_OPTIONS);
+
+            self.next_end_of_declaration_expect_eq('{', Some(location))?;
+            while !self.next_end_of_declaration_if_eq('}', None)? {
                 // emptyStatement
-                if self.tokenizer.next_symbol_if_eq(';')? {
+                if self.next_end_of_declaration_if_eq(';', None)? {
                     continue;
                 }
 
-                if let Some(o) = self.next_option_opt()? {
+                if let Some(o) = self.next_option_opt::<EnumOptions>(&options_path)? {
                     options.push(o);
                     continue;
                 }
 
-                values.push(self.next_enum_field()?);
+                let value_location = self.start_location(value_paths.next())?;
+                values.push(self.next_enum_field(value_location)?);
+                self.end_location(value_location);
             }
-            self.tokenizer.next_symbol_expect_eq('}')?;
+            self.end_location(location);
             Ok(Some(Enumeration {
                 name,
                 values,
@@ -831,48 +1221,66 @@ impl<'a> Parser<'a> {
 
     // messageBody = "{" { field | enum | message | extend | extensions | group |
     //               option | oneof | mapField | reserved | emptyStatement } "}"
-    fn next_message_body(&mut self, mode: MessageBodyParseMode) -> ParserResult<MessageBody> {
-        self.tokenizer.next_symbol_expect_eq('{')?;
+    fn next_message_body(
+        &mut self,
+        mode: MessageBodyParseMode,
+        location: usize,
+        paths: &mut MessagePaths,
+    ) -> ParserResult<MessageBody> {
+        let options_path = match mode {
+            MessageBodyParseMode::Oneof => self.sub_path(location, field_number::ONEOF_OPTIONS),
+            _ => {
+                let mut path = paths.message.clone();
+                path.push(field_number::MESSAGE_OPTIONS);
+                path
+            }
+        };
+
+        self.next_end_of_declaration_expect_eq('{', Some(location))?;
 
         let mut r = MessageBody::default();
 
-        while self.tokenizer.lookahead_if_symbol()? != Some('}') {
+        while !self.next_end_of_declaration_if_eq('}', None)? {
             let loc = self.tokenizer.lookahead_loc();
 
             // emptyStatement
-            if self.tokenizer.next_symbol_if_eq(';')? {
+            if self.next_end_of_declaration_if_eq(';', None)? {
                 continue;
             }
 
             if mode.is_most_non_fields_allowed() {
-                if let Some((field_nums, field_names)) = self.next_reserved_opt()? {
+                if let Some((field_nums, field_names)) = self.next_reserved_opt(paths)? {
                     r.reserved_nums.extend(field_nums);
                     r.reserved_names.extend(field_names);
                     continue;
                 }
 
-                if let Some(oneof) = self.next_oneof_opt()? {
+                if let Some(oneof) = self.next_oneof_opt(paths)? {
                     let one_of = FieldOrOneOf::OneOf(oneof);
                     r.fields.push(WithLoc { t: one_of, loc });
                     continue;
                 }
 
-                if let Some(extension_ranges) = self.next_extensions_opt()? {
+                if let Some(extension_ranges) =
+                    self.next_extensions_opt(&mut paths.extension_ranges)?
+                {
                     r.extension_ranges.extend(extension_ranges);
                     continue;
                 }
 
-                if let Some(extensions) = self.next_extend_opt()? {
+                if let Some(extensions) =
+                    self.next_extend_opt(&mut paths.extensions, &mut paths.nested_types)?
+                {
                     r.extensions.extend(extensions);
                     continue;
                 }
 
-                if let Some(nested_message) = self.next_message_opt()? {
+                if let Some(nested_message) = self.next_message_opt(&mut paths.nested_types)? {
                     r.messages.push(nested_message);
                     continue;
                 }
 
-                if let Some(nested_enum) = self.next_enum_opt()? {
+                if let Some(nested_enum) = self.next_enum_opt(&mut paths.enums)? {
                     r.enums.push(nested_enum);
                     continue;
                 }
@@ -886,7 +1294,13 @@ impl<'a> Parser<'a> {
             }
 
             if mode.is_option_allowed() {
-                if let Some(option) = self.next_option_opt()? {
+                let option = match mode {
+                    MessageBodyParseMode::Oneof => {
+                        self.next_option_opt::<OneofOptions>(&options_path)?
+                    }
+                    _ => self.next_option_opt::<MessageOptions>(&options_path)?,
+                };
+                if let Some(option) = option {
                     r.options.push(option);
                     continue;
                 }
@@ -894,27 +1308,39 @@ impl<'a> Parser<'a> {
                 self.tokenizer.next_ident_if_eq_error("option")?;
             }
 
-            let field = FieldOrOneOf::Field(self.next_field(mode)?);
-            r.fields.push(WithLoc { t: field, loc });
+            let field_location = self.start_location(paths.fields.next())?;
+            let field = self.next_field(mode, field_location, &mut paths.nested_types)?;
+            self.end_location(field_location);
+            r.fields.push(WithLoc {
+                t: FieldOrOneOf::Field(field),
+                loc,
+            });
         }
 
-        self.tokenizer.next_symbol_expect_eq('}')?;
-
         Ok(r)
     }
 
     // message = "message" messageName messageBody
-    fn next_message_opt(&mut self) -> ParserResult<Option<WithLoc<Message>>> {
+    fn next_message_opt(
+        &mut self,
+        messages: &mut RepeatedPath,
+    ) -> ParserResult<Option<WithLoc<Message>>> {
         let loc = self.tokenizer.lookahead_loc();
 
-        if self.tokenizer.next_ident_if_eq("message")? {
-            let name = self.tokenizer.next_ident()?.to_owned();
+        if self.tokenizer.lookahead_is_ident("message")? {
+            let location = self.start_location(messages.next())?;
+            self.tokenizer.advance()?;
+            let name = self
+                .with_location(self.sub_path(location, field_number::MESSAGE_NAME), |p| {
+                    Ok(p.tokenizer.next_ident()?)
+                })?;
 
             let mode = match self.syntax {
                 Syntax::Proto2 => MessageBodyParseMode::MessageProto2,
                 Syntax::Proto3 => MessageBodyParseMode::MessageProto3,
             };
 
+            let mut paths = MessagePaths::new(self.locations[location].path.clone());
             let MessageBody {
                 fields,
                 reserved_nums,
@@ -924,7 +1350,8 @@ impl<'a> Parser<'a> {
                 options,
                 extensions,
                 extension_ranges,
-            } = self.next_message_body(mode)?;
+            } = self.next_message_body(mode, location, &mut paths)?;
+            self.end_location(location);
 
             let message = Message {
                 name,
@@ -946,31 +1373,61 @@ impl<'a> Parser<'a> {
     // Extend
 
     // extend = "extend" messageType "{" {field | group | emptyStatement} "}"
-    fn next_extend_opt(&mut self) -> ParserResult<Option<Vec<WithLoc<Extension>>>> {
-        let mut clone = self.clone();
-        if clone.tokenizer.next_ident_if_eq("extend")? {
+    fn next_extend_opt(
+        &mut self,
+        extensions: &mut RepeatedPath,
+        nested_types: &mut RepeatedPath,
+    ) -> ParserResult<Option<Vec<WithLoc<Extension>>>> {
+        if self.tokenizer.lookahead_is_ident("extend")? {
             // According to spec `extend` is only for `proto2`, but it is used in `proto3`
             // https://github.com/google/protobuf/issues/4610
 
-            *self = clone;
+            let location = self.start_location(extensions.path.clone())?;
+            self.tokenizer.advance()?;
 
+            let extendee_pos = self.tokenizer.lookahead_pos()?;
             let extendee = self.next_message_or_enum_type()?;
+            let extendee_end_pos = self.tokenizer.prev_token_end_pos();
 
             let mode = match self.syntax {
                 Syntax::Proto2 => MessageBodyParseMode::ExtendProto2,
                 Syntax::Proto3 => MessageBodyParseMode::ExtendProto3,
             };
 
-            let MessageBody { fields, .. } = self.next_message_body(mode)?;
+            let mut fields = Vec::new();
 
-            // TODO: is oneof allowed in extend?
-            let fields: Vec<WithLoc<Field>> = fields
-                .into_iter()
-                .map(|fo| match fo.t {
-                    FieldOrOneOf::Field(f) => Ok(f),
-                    FieldOrOneOf::OneOf(_) => Err(ParserError::OneOfInExtend),
-                })
-                .collect::<Result<_, ParserError>>()?;
+            self.next_end_of_declaration_expect_eq('{', Some(location))?;
+            while !self.next_end_of_declaration_if_eq('}', None)? {
+                // emptyStatement
+                if self.next_end_of_declaration_if_eq(';', None)? {
+                    continue;
+                }
+
+                if self.tokenizer.lookahead_is_ident("oneof")? {
+                    return Err(ParserError::OneOfInExtend);
+                }
+                for keyword in &[
+                    "reserved",
+                    "extensions",
+                    "extend",
+                    "message",
+                    "enum",
+                    "option",
+                ] {
+                    self.tokenizer.next_ident_if_eq_error(keyword)?;
+                }
+
+                // Each extension field gets its own copy of extendee location
+                let field_location = self.start_location(extensions.next())?;
+                self.add_location(
+                    self.sub_path(field_location, field_number::FIELD_EXTENDEE),
+                    extendee_pos,
+                    extendee_end_pos,
+                );
+                fields.push(self.next_field(mode, field_location, nested_types)?);
+                self.end_location(field_location);
+            }
+            self.end_location(location);
 
             let extensions = fields
                 .into_iter()
@@ -990,11 +1447,12 @@ impl<'a> Parser<'a> {
 
     // Service definition
 
-    fn next_options_or_colon(&mut self) -> ParserResult<Vec<ProtobufOption>> {
+    fn next_options_or_colon(&mut self, method: usize) -> ParserResult<Vec<ProtobufOption>> {
         let mut options = Vec::new();
-        if self.tokenizer.next_symbol_if_eq('{')? {
-            while self.tokenizer.lookahead_if_symbol()? != Some('}') {
-                if let Some(option) = self.next_option_opt()? {
+        if self.next_end_of_declaration_if_eq('{', Some(method))? {
+            let options_path = self.sub_path(method, field_number::METHOD_OPTIONS);
+            while !self.next_end_of_declaration_if_eq('}', None)? {
+                if let Some(option) = self.next_option_opt::<MethodOptions>(&options_path)? {
                     options.push(option);
                     continue;
                 }
@@ -1005,9 +1463,8 @@ impl<'a> Parser<'a> {
 
                 return Err(ParserError::IncorrectInput);
             }
-            self.tokenizer.next_symbol_expect_eq('}')?;
         } else {
-            self.tokenizer.next_symbol_expect_eq(';')?;
+            self.next_end_of_declaration_expect_eq(';', Some(method))?;
         }
 
         Ok(options)
@@ -1015,16 +1472,28 @@ impl<'a> Parser<'a> {
 
     // stream = "stream" streamName "(" messageType "," messageType ")"
     //        (( "{" { option | emptyStatement } "}") | ";" )
-    fn next_stream_opt(&mut self) -> ParserResult<Option<Method>> {
+    fn next_stream_opt(&mut self, methods: &mut RepeatedPath) -> ParserResult<Option<Method>> {
         assert_eq!(Syntax::Proto2, self.syntax);
-        if self.tokenizer.next_ident_if_eq("stream")? {
-            let name = self.tokenizer.next_ident()?;
+        if self.tokenizer.lookahead_is_ident("stream")? {
+            let location = self.start_location(methods.next())?;
+            self.tokenizer.advance()?;
+            let name = self
+                .with_location(self.sub_path(location, field_number::METHOD_NAME), |p| {
+                    Ok(p.tokenizer.next_ident()?)
+                })?;
             self.tokenizer.next_symbol_expect_eq('(')?;
-            let input_type = self.tokenizer.next_ident()?;
+            let input_type = self.with_location(
+                self.sub_path(location, field_number::METHOD_INPUT_TYPE),
+                |p| Ok(p.tokenizer.next_ident()?),
+            )?;
             self.tokenizer.next_symbol_expect_eq(',')?;
-            let output_type = self.tokenizer.next_ident()?;
+            let output_type = self.with_location(
+                self.sub_path(location, field_number::METHOD_OUTPUT_TYPE),
+                |p| Ok(p.tokenizer.next_ident()?),
+            )?;
             self.tokenizer.next_symbol_expect_eq(')')?;
-            let options = self.next_options_or_colon()?;
+            let options = self.next_options_or_colon(location)?;
+            self.end_location(location);
             Ok(Some(Method {
                 name,
                 input_type,
@@ -1038,22 +1507,53 @@ impl<'a> Parser<'a> {
         }
     }
 
+    // [ "stream" ] messageType
+    fn next_method_type(
+        &mut self,
+        method: usize,
+        streaming_field_number: i32,
+        type_field_number: i32,
+    ) -> ParserResult<(bool, String)> {
+        let streaming = self.tokenizer.lookahead_is_ident("stream")?;
+        if streaming {
+            self.with_location(self.sub_path(method, streaming_field_number), |p| {
+                Ok(p.tokenizer.advance()?)
+            })?;
+        }
+        let message_type = self.with_location(self.sub_path(method, type_field_number), |p| {
+            p.next_message_or_enum_type()
+        })?;
+        Ok((streaming, message_type))
+    }
+
     // rpc = "rpc" rpcName "(" [ "stream" ] messageType ")"
     //     "returns" "(" [ "stream" ] messageType ")"
     //     (( "{" { option | emptyStatement } "}" ) | ";" )
-    fn next_rpc_opt(&mut self) -> ParserResult<Option<Method>> {
-        if self.tokenizer.next_ident_if_eq("rpc")? {
-            let name = self.tokenizer.next_ident()?;
+    fn next_rpc_opt(&mut self, methods: &mut RepeatedPath) -> ParserResult<Option<Method>> {
+        if self.tokenizer.lookahead_is_ident("rpc")? {
+            let location = self.start_location(methods.next())?;
+            self.tokenizer.advance()?;
+            let name = self
+                .with_location(self.sub_path(location, field_number::METHOD_NAME), |p| {
+                    Ok(p.tokenizer.next_ident()?)
+                })?;
             self.tokenizer.next_symbol_expect_eq('(')?;
-            let client_streaming = self.tokenizer.next_ident_if_eq("stream")?;
-            let input_type = self.next_message_or_enum_type()?;
+            let (client_streaming, input_type) = self.next_method_type(
+                location,
+                field_number::METHOD_CLIENT_STREAMING,
+                field_number::METHOD_INPUT_TYPE,
+            )?;
             self.tokenizer.next_symbol_expect_eq(')')?;
             self.tokenizer.next_ident_expect_eq("returns")?;
             self.tokenizer.next_symbol_expect_eq('(')?;
-            let server_streaming = self.tokenizer.next_ident_if_eq("stream")?;
-            let output_type = self.next_message_or_enum_type()?;
+            let (server_streaming, output_type) = self.next_method_type(
+                location,
+                field_number::METHOD_SERVER_STREAMING,
+                field_number::METHOD_OUTPUT_TYPE,
+            )?;
             self.tokenizer.next_symbol_expect_eq(')')?;
-            let options = self.next_options_or_colon()?;
+            let options = self.next_options_or_colon(location)?;
+            self.end_location(location);
             Ok(Some(Method {
                 name,
                 input_type,
@@ -1072,28 +1572,39 @@ impl<'a> Parser<'a> {
     //
     // proto3:
     // service = "service" serviceName "{" { option | rpc | emptyStatement } "}"
-    fn next_service_opt(&mut self) -> ParserResult<Option<WithLoc<Service>>> {
+    fn next_service_opt(
+        &mut self,
+        services: &mut RepeatedPath,
+    ) -> ParserResult<Option<WithLoc<Service>>> {
         let loc = self.tokenizer.lookahead_loc();
 
-        if self.tokenizer.next_ident_if_eq("service")? {
-            let name = self.tokenizer.next_ident()?;
+        if self.tokenizer.lookahead_is_ident("service")? {
+            let location = self.start_location(services.next())?;
+            self.tokenizer.advance()?;
+            let name = self
+                .with_location(self.sub_path(location, field_number::SERVICE_NAME), |p| {
+                    Ok(p.tokenizer.next_ident()?)
+                })?;
             let mut methods = Vec::new();
             let mut options = Vec::new();
-            self.tokenizer.next_symbol_expect_eq('{')?;
-            while self.tokenizer.lookahead_if_symbol()? != Some('}') {
-                if let Some(method) = self.next_rpc_opt()? {
+            let mut method_paths =
+                RepeatedPath::new(&self.locations[location].path, field_number::SERVICE_METHOD);
+            let options_path = self.sub_path(location, field_number::SERVICE_OPTIONS);
+            self.next_end_of_declaration_expect_eq('{', Some(location))?;
+            while !self.next_end_of_declaration_if_eq('}', None)? {
+                if let Some(method) = self.next_rpc_opt(&mut method_paths)? {
                     methods.push(method);
                     continue;
                 }
 
                 if self.syntax == Syntax::Proto2 {
-                    if let Some(method) = self.next_stream_opt()? {
+                    if let Some(method) = self.next_stream_opt(&mut method_paths)? {
                         methods.push(method);
                         continue;
                     }
                 }
 
-                if let Some(o) = self.next_option_opt()? {
+                if let Some(o) = self.next_option_opt::<ServiceOptions>(&options_path)? {
                     options.push(o);
                     continue;
                 }
@@ -1104,7 +1615,7 @@ impl<'a> Parser<'a> {
 
                 return Err(ParserError::IncorrectInput);
             }
-            self.tokenizer.next_symbol_expect_eq('}')?;
+            self.end_location(location);
             Ok(Some(WithLoc {
                 loc,
                 t: Service {
@@ -1123,6 +1634,14 @@ impl<'a> Parser<'a> {
     // proto = syntax { import | package | option | topLevelDef | emptyStatement }
     // topLevelDef = message | enum | extend | service
     pub fn next_proto(&mut self) -> ParserResult<FileDescriptor> {
+        let Comments {
+            detached, leading, ..
+        } = self.tokenizer.next_comments()?;
+        self.upcoming_detached_comments = detached;
+        self.upcoming_doc_comments = leading;
+
+        let root_location = self.start_location(Vec::new())?;
+
         let syntax = self.next_syntax()?.unwrap_or(Syntax::Proto2);
         self.syntax = syntax;
 
@@ -1134,8 +1653,11 @@ impl<'a> Parser<'a> {
         let mut options = Vec::new();
         let mut services = Vec::new();
 
+        let mut paths = FilePaths::new();
+        let options_path = [field_number::FILE_OPTIONS];
+
         while !self.tokenizer.syntax_eof()? {
-            if let Some(import) = self.next_import_opt()? {
+            if let Some(import) = self.next_import_opt(&mut paths)? {
                 imports.push(import);
                 continue;
             }
@@ -1145,38 +1667,45 @@ impl<'a> Parser<'a> {
                 continue;
             }
 
-            if let Some(option) = self.next_option_opt()? {
+            if let Some(option) = self.next_option_opt::<FileOptions>(&options_path)? {
                 options.push(option);
                 continue;
             }
 
-            if let Some(message) = self.next_message_opt()? {
+            if let Some(message) = self.next_message_opt(&mut paths.messages)? {
                 messages.push(message);
                 continue;
             }
 
-            if let Some(enumeration) = self.next_enum_opt()? {
+            if let Some(enumeration) = self.next_enum_opt(&mut paths.enums)? {
                 enums.push(enumeration);
                 continue;
             }
 
-            if let Some(more_extensions) = self.next_extend_opt()? {
+            if let Some(more_extensions) =
+                self.next_extend_opt(&mut paths.extensions, &mut paths.messages)?
+            {
                 extensions.extend(more_extensions);
                 continue;
             }
 
-            if let Some(service) = self.next_service_opt()? {
+            if let Some(service) = self.next_service_opt(&mut paths.services)? {
                 services.push(service);
                 continue;
             }
 
-            if self.tokenizer.next_symbol_if_eq(';')? {
+            if let Some(()) = self.next_empty_statement_opt()? {
                 continue;
             }
 
             return Err(ParserError::IncorrectInput);
         }
 
+        self.end_location(root_location);
+
+        let mut source_code_info = SourceCodeInfo::new();
+        source_code_info.location = mem::take(&mut self.locations);
+
         Ok(FileDescriptor {
             imports,
             package,
@@ -1186,6 +1715,8 @@ impl<'a> Parser<'a> {
             extensions,
             services,
             options,
+            source_code_info,
+            option_locations: mem::take(&mut self.option_locations),
         })
     }
 }
@@ -1209,6 +1740,12 @@ mod test {
         r
     }
 
+    fn next_field(p: &mut Parser, mode: MessageBodyParseMode) -> ParserResult<WithLoc<Field>> {
+        let location = p.start_location(Vec::new())?;
+        let mut messages = FilePaths::new().messages;
+        p.next_field(mode, location, &mut messages)
+    }
+
     fn parse_opt<P, R>(input: &str, parse_what: P) -> R
     where
         P: FnOnce(&mut Parser) -> ParserResult<Option<R>>,
@@ -1234,7 +1771,7 @@ mod test {
     #[test]
     fn test_field_default_value_int() {
         let msg = r#"  optional int64 f = 4 [default = 12];  "#;
-        let mess = parse(msg, |p| p.next_field(MessageBodyParseMode::MessageProto2));
+        let mess = parse(msg, |p| next_field(p, MessageBodyParseMode::MessageProto2));
         assert_eq!("f", mess.t.name);
         assert_eq!(
             ProtobufOptionName::simple("default"),
@@ -1246,7 +1783,7 @@ mod test {
     #[test]
     fn test_field_default_value_float() {
         let msg = r#"  optional float f = 2 [default = 10.0];  "#;
-        let mess = parse(msg, |p| p.next_field(MessageBodyParseMode::MessageProto2));
+        let mess = parse(msg, |p| next_field(p, MessageBodyParseMode::MessageProto2));
         assert_eq!("f", mess.t.name);
         assert_eq!(
             ProtobufOptionName::simple("default"),
@@ -1271,7 +1808,7 @@ mod test {
         repeated MaturityInfo maturitySet = 10;
     }"#;
 
-        let mess = parse_opt(msg, |p| p.next_message_opt());
+        let mess = parse_opt(msg, |p| p.next_message_opt(&mut FilePaths::new().messages));
         assert_eq!(10, mess.t.fields.len());
     }
 
@@ -1284,7 +1821,7 @@ mod test {
                 CANCELED          = 3;
     }"#;
 
-        let enumeration = parse_opt(msg, |p| p.next_enum_opt());
+        let enumeration = parse_opt(msg, |p| p.next_enum_opt(&mut FilePaths::new().enums));
         assert_eq!(4, enumeration.values.len());
     }
 
@@ -1292,7 +1829,9 @@ mod test {
     fn test_ignore() {
         let msg = r#"option optimize_for = SPEED;"#;
 
-        parse_opt(msg, |p| p.next_option_opt());
+        parse_opt(msg, |p| {
+            p.next_option_opt::<FileOptions>(&[field_number::FILE_OPTIONS])
+        });
     }
 
     #[test]
@@ -1350,7 +1889,7 @@ mod test {
         optional string b = 1;
     }"#;
 
-        let mess = parse_opt(msg, |p| p.next_message_opt());
+        let mess = parse_opt(msg, |p| p.next_message_opt(&mut FilePaths::new().messages));
         assert_eq!(1, mess.t.messages.len());
     }
 
@@ -1361,7 +1900,7 @@ mod test {
         optional map<string, int32> b = 1;
     }"#;
 
-        let mess = parse_opt(msg, |p| p.next_message_opt());
+        let mess = parse_opt(msg, |p| p.next_message_opt(&mut FilePaths::new().messages));
         assert_eq!(1, mess.t.fields.len());
         match mess.t.regular_fields_for_test()[0].typ {
             FieldType::Map(ref f) => match &**f {
@@ -1385,11 +1924,29 @@ mod test {
         repeated bool a5 = 5;
     }"#;
 
-        let mess = parse_opt(msg, |p| p.next_message_opt());
+        let mess = parse_opt(msg, |p| p.next_message_opt(&mut FilePaths::new().messages));
         assert_eq!(1, mess.t.oneofs_for_test().len());
         assert_eq!(3, mess.t.oneofs_for_test()[0].fields.len());
     }
 
+    #[test]
+    fn test_proto3_optional() {
+        let msg = r#"syntax = "proto3";
+
+    message A {
+        optional int32 a = 1;
+        int32 b = 2;
+    }
+    "#;
+
+        let desc = parse(msg, |p| p.next_proto());
+        let fields = desc.messages[0].t.regular_fields_for_test();
+        assert_eq!(Rule::Optional, fields[0].rule);
+        assert!(fields[0].proto3_optional);
+        assert_eq!(Rule::Optional, fields[1].rule);
+        assert!(!fields[1].proto3_optional);
+    }
+
     #[test]
     fn test_reserved() {
         let msg = r#"message Sample {
@@ -1399,7 +1956,7 @@ mod test {
        required bytes name =2;
     }"#;
 
-        let mess = parse_opt(msg, |p| p.next_message_opt());
+        let mess = parse_opt(msg, |p| p.next_message_opt(&mut FilePaths::new().messages));
         assert_eq!(
             vec![
                 FieldNumberRange { from: 4, to: 4 },
@@ -1422,7 +1979,7 @@ mod test {
             optional int32 x = 1 [default = 17];
         }"#;
 
-        let mess = parse_opt(msg, |p| p.next_message_opt());
+        let mess = parse_opt(msg, |p| p.next_message_opt(&mut FilePaths::new().messages));
         assert_eq!(
             ProtobufOptionName::simple("default"),
             mess.t.regular_fields_for_test()[0].options[0].name
@@ -1441,7 +1998,7 @@ mod test {
             optional string x = 1 [default = "ab\nc d\"g\'h\0\"z"];
         }"#;
 
-        let mess = parse_opt(msg, |p| p.next_message_opt());
+        let mess = parse_opt(msg, |p| p.next_message_opt(&mut FilePaths::new().messages));
         assert_eq!(
             r#""ab\nc d\"g\'h\0\"z""#,
             mess.t.regular_fields_for_test()[0].options[0]
@@ -1456,7 +2013,7 @@ mod test {
             optional bytes x = 1 [default = "ab\nc d\xfeE\"g\'h\0\"z"];
         }"#;
 
-        let mess = parse_opt(msg, |p| p.next_message_opt());
+        let mess = parse_opt(msg, |p| p.next_message_opt(&mut FilePaths::new().messages));
         assert_eq!(
             r#""ab\nc d\xfeE\"g\'h\0\"z""#,
             mess.t.regular_fields_for_test()[0].options[0]
@@ -1477,7 +2034,7 @@ mod test {
 
             required int bbb = 3;
         }"#;
-        let mess = parse_opt(msg, |p| p.next_message_opt());
+        let mess = parse_opt(msg, |p| p.next_message_opt(&mut FilePaths::new().messages));
 
         assert_eq!("identifier", mess.t.regular_fields_for_test()[1].name);
         if let FieldType::Group(Group { fields, .. }) = &mess.t.regular_fields_for_test()[1].typ {
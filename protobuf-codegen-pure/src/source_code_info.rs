@@ -0,0 +1,173 @@
+//! Helpers to build `SourceCodeInfo` the same way `protoc` does.
+
+/// Field numbers of `descriptor.proto` messages used in location paths.
+pub(crate) mod field_number {
+    pub const FILE_PACKAGE: i32 = 2;
+    pub const FILE_DEPENDENCY: i32 = 3;
+    pub const FILE_MESSAGE_TYPE: i32 = 4;
+    pub const FILE_ENUM_TYPE: i32 = 5;
+    pub const FILE_SERVICE: i32 = 6;
+    pub const FILE_EXTENSION: i32 = 7;
+    pub const FILE_OPTIONS: i32 = 8;
+    pub const FILE_PUBLIC_DEPENDENCY: i32 = 10;
+    pub const FILE_WEAK_DEPENDENCY: i32 = 11;
+    pub const FILE_SYNTAX: i32 = 12;
+
+    pub const MESSAGE_NAME: i32 = 1;
+    pub const MESSAGE_FIELD: i32 = 2;
+    pub const MESSAGE_NESTED_TYPE: i32 = 3;
+    pub const MESSAGE_ENUM_TYPE: i32 = 4;
+    pub const MESSAGE_EXTENSION_RANGE: i32 = 5;
+    pub const MESSAGE_EXTENSION: i32 = 6;
+    pub const MESSAGE_OPTIONS: i32 = 7;
+    pub const MESSAGE_ONEOF_DECL: i32 = 8;
+    pub const MESSAGE_RESERVED_RANGE: i32 = 9;
+    pub const MESSAGE_RESERVED_NAME: i32 = 10;
+
+    pub const RANGE_START: i32 = 1;
+    pub const RANGE_END: i32 = 2;
+
+    pub const FIELD_NAME: i32 = 1;
+    pub const FIELD_EXTENDEE: i32 = 2;
+    pub const FIELD_NUMBER: i32 = 3;
+    pub const FIELD_LABEL: i32 = 4;
+    pub const FIELD_TYPE: i32 = 5;
+    pub const FIELD_TYPE_NAME: i32 = 6;
+    pub const FIELD_DEFAULT_VALUE: i32 = 7;
+    pub const FIELD_OPTIONS: i32 = 8;
+    pub const FIELD_JSON_NAME: i32 = 10;
+
+    pub const ONEOF_NAME: i32 = 1;
+    pub const ONEOF_OPTIONS: i32 = 2;
+
+    pub const ENUM_NAME: i32 = 1;
+    pub const ENUM_VALUE: i32 = 2;
+    pub const ENUM_OPTIONS: i32 = 3;
+
+    pub const ENUM_VALUE_NAME: i32 = 1;
+    pub const ENUM_VALUE_NUMBER: i32 = 2;
+    pub const ENUM_VALUE_OPTIONS: i32 = 3;
+
+    pub const SERVICE_NAME: i32 = 1;
+    pub const SERVICE_METHOD: i32 = 2;
+    pub const SERVICE_OPTIONS: i32 = 3;
+
+    pub const METHOD_NAME: i32 = 1;
+    pub const METHOD_INPUT_TYPE: i32 = 2;
+    pub const METHOD_OUTPUT_TYPE: i32 = 3;
+    pub const METHOD_OPTIONS: i32 = 4;
+    pub const METHOD_CLIENT_STREAMING: i32 = 5;
+    pub const METHOD_SERVER_STREAMING: i32 = 6;
+}
+
+/// Path of a repeated descriptor field along with the number of elements added so far.
+pub(crate) struct RepeatedPath {
+    pub path: Vec<i32>,
+    len: i32,
+}
+
+impl RepeatedPath {
+    pub fn new(parent: &[i32], field_number: i32) -> RepeatedPath {
+        let mut path = parent.to_vec();
+        path.push(field_number);
+        RepeatedPath { path, len: 0 }
+    }
+
+    /// Path of the next element
+    pub fn next(&mut self) -> Vec<i32> {
+        let mut path = self.path.clone();
+        path.push(self.len);
+        self.len += 1;
+        path
+    }
+}
+
+/// Paths of top level declarations.
+pub(crate) struct FilePaths {
+    pub dependencies: RepeatedPath,
+    pub public_dependencies: RepeatedPath,
+    pub weak_dependencies: RepeatedPath,
+    pub messages: RepeatedPath,
+    pub enums: RepeatedPath,
+    pub services: RepeatedPath,
+    pub extensions: RepeatedPath,
+}
+
+impl FilePaths {
+    pub fn new() -> FilePaths {
+        FilePaths {
+            dependencies: RepeatedPath::new(&[], field_number::FILE_DEPENDENCY),
+            public_dependencies: RepeatedPath::new(&[], field_number::FILE_PUBLIC_DEPENDENCY),
+            weak_dependencies: RepeatedPath::new(&[], field_number::FILE_WEAK_DEPENDENCY),
+            messages: RepeatedPath::new(&[], field_number::FILE_MESSAGE_TYPE),
+            enums: RepeatedPath::new(&[], field_number::FILE_ENUM_TYPE),
+            services: RepeatedPath::new(&[], field_number::FILE_SERVICE),
+            extensions: RepeatedPath::new(&[], field_number::FILE_EXTENSION),
+        }
+    }
+}
+
+/// Paths of declarations in a message body.
+///
+/// Oneofs share fields and nested types with the containing message.
+pub(crate) struct MessagePaths {
+    pub message: Vec<i32>,
+    pub fields: RepeatedPath,
+    pub nested_types: RepeatedPath,
+    pub enums: RepeatedPath,
+    pub extensions: RepeatedPath,
+    pub oneofs: RepeatedPath,
+    pub extension_ranges: RepeatedPath,
+    pub reserved_ranges: RepeatedPath,
+    pub reserved_names: RepeatedPath,
+}
+
+impl MessagePaths {
+    pub fn new(message: Vec<i32>) -> MessagePaths {
+        MessagePaths {
+            fields: RepeatedPath::new(&message, field_number::MESSAGE_FIELD),
+            nested_types: RepeatedPath::new(&message, field_number::MESSAGE_NESTED_TYPE),
+            enums: RepeatedPath::new(&message, field_number::MESSAGE_ENUM_TYPE),
+            extensions: RepeatedPath::new(&message, field_number::MESSAGE_EXTENSION),
+            oneofs: RepeatedPath::new(&message, field_number::MESSAGE_ONEOF_DECL),
+            extension_ranges: RepeatedPath::new(&message, field_number::MESSAGE_EXTENSION_RANGE),
+            reserved_ranges: RepeatedPath::new(&message, field_number::MESSAGE_RESERVED_RANGE),
+            reserved_names: RepeatedPath::new(&message, field_number::MESSAGE_RESERVED_NAME),
+            message,
+        }
+    }
+}
+
+/// Converts byte offsets in the input into `protoc` span line and column.
+pub(crate) struct SpanIndex<'a> {
+    input: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SpanIndex<'a> {
+    pub fn new(input: &'a str) -> SpanIndex<'a> {
+        let line_starts = Some(0)
+            .into_iter()
+            .chain(input.match_indices('\n').map(|(pos, _)| pos + 1))
+            .collect();
+        SpanIndex { input, line_starts }
+    }
+
+    /// Zero-based line and column, column is counted in bytes with tab stops every 8 columns
+    pub fn line_col(&self, pos: usize) -> (i32, i32) {
+        let line = match self.line_starts.binary_search(&pos) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let col = self.input.as_bytes()[self.line_starts[line]..pos]
+            .iter()
+            .fold(0, |col, &b| {
+                if b == b'\t' {
+                    col + 8 - col % 8
+                } else {
+                    col + 1
+                }
+            });
+        (line as i32, col)
+    }
+}
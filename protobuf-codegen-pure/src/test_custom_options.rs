@@ -0,0 +1,133 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use protobuf::descriptor::FileDescriptorProto;
+use protobuf::descriptor::MessageOptions;
+use protobuf::CodedOutputStream;
+use protobuf::Message;
+
+use crate::parse_and_typecheck;
+
+fn write<F>(f: F) -> Vec<u8>
+where
+    F: FnOnce(&mut CodedOutputStream) -> protobuf::ProtobufResult<()>,
+{
+    let mut bytes = Vec::new();
+    {
+        let mut os = CodedOutputStream::vec(&mut bytes);
+        f(&mut os).unwrap();
+        os.flush().unwrap();
+    }
+    bytes
+}
+
+fn varint(number: u32, value: u64) -> Vec<u8> {
+    write(|os| os.write_uint64(number, value))
+}
+
+fn bytes(number: u32, value: &[u8]) -> Vec<u8> {
+    write(|os| os.write_bytes(number, value))
+}
+
+fn parse(include: &Path, name: &str) -> FileDescriptorProto {
+    let input = include.join(name);
+    let parsed = parse_and_typecheck(&[include.to_owned()], &[input]).expect("parse_and_typecheck");
+    parsed
+        .file_descriptors
+        .into_iter()
+        .find(|fd| fd.get_name() == name)
+        .expect("file descriptor")
+}
+
+fn unittest_custom_options() -> FileDescriptorProto {
+    parse(
+        Path::new("../protobuf-test/src"),
+        "google/protobuf/unittest_custom_options.proto",
+    )
+}
+
+fn message_options<'a>(fd: &'a FileDescriptorProto, name: &str) -> &'a MessageOptions {
+    fd.message_type
+        .iter()
+        .find(|m| m.get_name() == name)
+        .expect("message")
+        .options
+        .get_or_default()
+}
+
+fn length_delimited(options: &protobuf::UnknownFields, number: u32) -> Vec<Vec<u8>> {
+    options
+        .get(number)
+        .map(|values| values.length_delimited.clone())
+        .unwrap_or_default()
+}
+
+// Options are compared with `protoc` output in `protobuf-test`,
+// these tests only check what is not covered there
+
+#[test]
+fn group_option() {
+    let fd = unittest_custom_options();
+    let options = message_options(&fd, "VariousComplexOptions").get_unknown_fields();
+
+    let complexopt6 = 7595468;
+    let xyzzy = 7593951;
+    let groups = &options.get(complexopt6).expect("complexopt6").groups;
+    assert_eq!(1, groups.len());
+    assert_eq!(&[24], &groups[0].get(xyzzy).expect("xyzzy").varint[..]);
+}
+
+#[test]
+fn proto3() {
+    let dir = env::temp_dir().join("protobuf-codegen-pure-test-custom-options");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("proto3_options.proto"),
+        r#"
+            syntax = "proto3";
+            import "google/protobuf/descriptor.proto";
+
+            enum E { ZERO = 0; ONE = 1; }
+
+            message Opt {
+                int32 i = 1;
+                repeated int32 packed = 2;
+                repeated string strings = 3;
+                map<string, int32> map = 4;
+                optional int32 optional_i = 5;
+                E e = 6;
+            }
+
+            extend google.protobuf.MessageOptions {
+                Opt opt = 50000;
+            }
+
+            message M {
+                option (opt) = {
+                    e: ONE, i: 0, optional_i: 0;
+                    packed: [1, 2] packed: 3
+                    strings: ["a", ""]
+                    map < key: "k" value: 0 >
+                };
+            }
+        "#,
+    )
+    .unwrap();
+    let fd = parse(&dir, "proto3_options.proto");
+
+    // Fields without presence are skipped when zero, repeated scalars are packed
+    let expected = [
+        bytes(2, &[1, 2, 3]),
+        bytes(3, b"a"),
+        bytes(3, b""),
+        bytes(4, &bytes(1, b"k")),
+        varint(5, 0),
+        varint(6, 1),
+    ]
+    .concat();
+    assert_eq!(
+        vec![expected],
+        length_delimited(message_options(&fd, "M").get_unknown_fields(), 50000)
+    );
+}
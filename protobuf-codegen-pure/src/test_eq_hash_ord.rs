@@ -0,0 +1,68 @@
+use std::env;
+use std::fs;
+
+use protobuf_codegen::Customize;
+
+use crate::parse_and_typecheck;
+
+/// Generate test file, file name must be unique for the test since tests run in parallel.
+fn gen(file_name: &str, customize_by_name: &[(&str, bool)]) -> String {
+    let dir = env::temp_dir().join("protobuf-codegen-pure-test-eq-hash-ord");
+    fs::create_dir_all(&dir).unwrap();
+    let input = dir.join(file_name);
+    fs::write(
+        &input,
+        r#"
+            syntax = "proto3";
+            package pkg;
+            message WithFloat { float f = 1; }
+            message WithInt { int32 i = 1; }
+        "#,
+    )
+    .unwrap();
+    let parsed = parse_and_typecheck(&[dir], &[input]).expect("parse_and_typecheck");
+    let customize = Customize {
+        customize_by_name: customize_by_name
+            .iter()
+            .map(|(name, derive)| {
+                (
+                    name.to_string(),
+                    Customize {
+                        derive_eq_hash_ord: Some(*derive),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let results = protobuf_codegen::gen(
+        &parsed.file_descriptors,
+        "test",
+        &parsed.relative_paths,
+        &customize,
+    );
+    String::from_utf8(results[0].content.clone()).unwrap()
+}
+
+#[test]
+fn message_not_qualifying_in_package_is_skipped() {
+    let content = gen("package.proto", &[(".pkg", true)]);
+    assert!(content.contains("impl ::std::hash::Hash for WithInt"));
+    assert!(!content.contains("impl ::std::hash::Hash for WithFloat"));
+}
+
+#[test]
+#[should_panic(expected = "cannot implement `Eq`, `Hash` and `Ord` for message `pkg.WithFloat`")]
+fn message_not_qualifying_named_explicitly_is_error() {
+    gen("explicit.proto", &[(".pkg.WithFloat", true)]);
+}
+
+#[test]
+fn message_named_explicitly_disabled_later() {
+    let content = gen(
+        "disabled_later.proto",
+        &[(".pkg.WithFloat", true), (".pkg.WithFloat", false)],
+    );
+    assert!(!content.contains("impl ::std::hash::Hash for WithFloat"));
+}
@@ -0,0 +1,105 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use protobuf::descriptor;
+use protobuf::descriptor::FileDescriptorProto;
+use protobuf::plugin;
+use protobuf::well_known_types::*;
+use protobuf::Message;
+
+use crate::parse_and_typecheck;
+
+/// Descriptors bundled with `protobuf` crate, generated by `protoc`
+fn protoc_file_descriptors() -> Vec<FileDescriptorProto> {
+    vec![
+        descriptor::file_descriptor_proto().clone(),
+        plugin::file_descriptor().proto().clone(),
+        Any::descriptor_static().file_descriptor_proto().clone(),
+        Api::descriptor_static().file_descriptor_proto().clone(),
+        Duration::descriptor_static()
+            .file_descriptor_proto()
+            .clone(),
+        Empty::descriptor_static().file_descriptor_proto().clone(),
+        FieldMask::descriptor_static()
+            .file_descriptor_proto()
+            .clone(),
+        SourceContext::descriptor_static()
+            .file_descriptor_proto()
+            .clone(),
+        Struct::descriptor_static().file_descriptor_proto().clone(),
+        Timestamp::descriptor_static()
+            .file_descriptor_proto()
+            .clone(),
+        Type::descriptor_static().file_descriptor_proto().clone(),
+        DoubleValue::descriptor_static()
+            .file_descriptor_proto()
+            .clone(),
+    ]
+}
+
+#[test]
+fn source_code_info_same_as_protoc() {
+    let include = Path::new("../proto");
+    let expected_file_descriptors = protoc_file_descriptors();
+    let inputs: Vec<PathBuf> = expected_file_descriptors
+        .iter()
+        .map(|fd| include.join(fd.get_name()))
+        .collect();
+    let parsed = parse_and_typecheck(&[include.to_owned()], &inputs).expect("parse_and_typecheck");
+
+    for expected in &expected_file_descriptors {
+        let actual = parsed
+            .file_descriptors
+            .iter()
+            .find(|fd| fd.get_name() == expected.get_name())
+            .expect("file descriptor");
+        let expected_locations = &expected.source_code_info.get_or_default().location;
+        let actual_locations = &actual.source_code_info.get_or_default().location;
+        assert!(!expected_locations.is_empty());
+        for (expected_location, actual_location) in expected_locations.iter().zip(actual_locations)
+        {
+            assert_eq!(
+                expected_location,
+                actual_location,
+                "{}",
+                expected.get_name()
+            );
+        }
+        assert_eq!(
+            expected_locations.len(),
+            actual_locations.len(),
+            "{}",
+            expected.get_name()
+        );
+    }
+}
+
+#[test]
+fn comments_of_oneofs_and_enum_values_in_generated_code() {
+    let include = Path::new("../proto");
+    let input = include.join("google/protobuf/struct.proto");
+    let parsed = parse_and_typecheck(&[include.to_owned()], &[input]).expect("parse_and_typecheck");
+    let results = protobuf_codegen::gen(
+        &parsed.file_descriptors,
+        "test",
+        &parsed.relative_paths,
+        &Default::default(),
+    );
+    let content = String::from_utf8(results[0].content.clone()).unwrap();
+    let lines: Vec<&str> = content.lines().map(str::trim).collect();
+
+    // comment is written before attributes of the declaration
+    let assert_documented = |decl: &str, comment: &str| {
+        let mut pos = lines.iter().position(|l| l.starts_with(decl)).expect(decl);
+        pos -= 1;
+        while lines[pos].starts_with("#[") {
+            pos -= 1;
+        }
+        assert_eq!(format!("///  {}", comment), lines[pos], "{}", decl);
+    };
+    assert_documented("pub kind: ", "The kind of value.");
+    assert_documented("pub enum Kind", "The kind of value.");
+    assert_documented("null_value(", "Represents a null value.");
+    assert_documented("list_value(", "Represents a repeated `Value`.");
+    assert_documented("NULL_VALUE = 0,", "Null value.");
+}
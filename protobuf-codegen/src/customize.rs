@@ -1,8 +1,12 @@
+use protobuf::descriptor::EnumOptions;
 use protobuf::descriptor::FieldOptions;
 use protobuf::descriptor::FileOptions;
 use protobuf::descriptor::MessageOptions;
 use protobuf::rustproto;
 
+use crate::code_writer::CodeWriter;
+use crate::protobuf_abs_path::ProtobufAbsolutePath;
+
 /// Specifies style of generated code.
 /// Generated files can be customized using this proto
 /// or using `rustproto.proto` options.
@@ -11,30 +15,139 @@ pub struct Customize {
     /// Make oneof enum public.
     pub expose_oneof: Option<bool>,
     /// When true all fields are public, and accessors are not generated
+    ///
+    /// Fields stored as different Rust types (see `chrono_for_timestamp`, `time_for_timestamp`,
+    /// `wrappers_as_option` and `rust_type`) are always public and have no accessors:
+    /// for such fields the type option overrides `expose_fields`, `generate_accessors`
+    /// and `generate_getter`, wherever these are set.
     pub expose_fields: Option<bool>,
     /// When false, `get_`, `set_`, `mut_` etc. accessors are not generated
+    ///
+    /// Ignored for fields stored as different Rust types, see `expose_fields`.
     pub generate_accessors: Option<bool>,
     /// When false, `get_` is not generated even if `syntax = "proto2"`
+    ///
+    /// Ignored for fields stored as different Rust types, see `expose_fields`.
     pub generate_getter: Option<bool>,
     /// Use `bytes::Bytes` for `bytes` fields
     pub carllerche_bytes_for_bytes: Option<bool>,
     /// Use `bytes::Bytes` for `string` fields
     pub carllerche_bytes_for_string: Option<bool>,
+    /// Store singular `google.protobuf.Timestamp` fields as `chrono::DateTime<chrono::Utc>`,
+    /// requires `with-chrono` feature of protobuf crate.
+    /// Repeated and oneof fields are not affected.
+    ///
+    /// Generated code does not keep converted `Timestamp` around: a field value
+    /// is converted to `Timestamp` when computing size, when checking initialization
+    /// and when writing, so each serialization converts it three times.
+    pub chrono_for_timestamp: Option<bool>,
+    /// Store singular `google.protobuf.Timestamp` fields as `time::OffsetDateTime`,
+    /// requires `with-time` feature of protobuf crate.
+    /// Ignored if `chrono_for_timestamp` is set.
+    /// Has the same serialization cost as `chrono_for_timestamp`.
+    pub time_for_timestamp: Option<bool>,
+    /// Generate fields of wrapper types (`google.protobuf.Int32Value` etc.)
+    /// as plain Rust types: `Option<i32>` for singular fields, `Vec<i32>` for repeated fields
+    /// and `HashMap<K, i32>` for map values. Oneof fields are not affected.
+    ///
+    /// Values are wrapped into messages on every serialization
+    /// (several times per value, like with `chrono_for_timestamp`),
+    /// cloning `String` and `Vec<u8>` values each time.
+    pub wrappers_as_option: Option<bool>,
+    /// Rust type of a field used instead of the generated type, e. g. `::std::sync::Arc<str>`.
+    ///
+    /// Supported for singular and repeated `string` and `bytes` fields, the type must implement
+    /// `AsRef<str>` (or `AsRef<[u8]>`) and `TryFrom<String>` (or `TryFrom<Vec<u8>>`),
+    /// proto3 fields also require `Default`.
+    /// Also supported for singular and repeated message fields,
+    /// the type must be `Clone`, and the type and the message must implement
+    /// `TryFrom` of each other. Message field values are cloned and converted
+    /// on every serialization, with the cost described for `chrono_for_timestamp`.
+    ///
+    /// In all cases the type must also implement `PartialEq` and `Debug`
+    /// (generated messages derive `PartialEq`, repeated field accessors require `Debug`),
+    /// be `Send + Sync + 'static`, and implement `Serialize` and `Deserialize`
+    /// when `serde_derive` is set without `serde_json_mapping`.
+    ///
+    /// Conversion failure while parsing is reported as an error, as well as
+    /// conversion failure when the field is set from JSON or text format.
+    ///
+    /// The option applies to a single field only: it is read from `rustproto.rust_type_field`
+    /// option and from `customize_by_name` entries naming the field exactly,
+    /// and is ignored when set for a codegen invocation, a file or a message.
+    pub rust_type: Option<String>,
     /// Implement serde_derive for messages
     pub serde_derive: Option<bool>,
     /// When `serde_derive` is set, serde annotations will be guarded with `#[cfg(cfg, ...)]`.
     pub serde_derive_cfg: Option<String>,
+    /// When `serde_derive` is set, implement serde traits following protobuf JSON mapping
+    /// instead of deriving them, so serde output is the same as output of `protobuf::json`:
+    /// camelCase field names, enums as strings, 64-bit integers as strings,
+    /// oneofs flattened into the message object and special forms of well-known types.
+    /// Requires `with-serde` feature of protobuf crate, ignored with lite runtime.
+    pub serde_json_mapping: Option<bool>,
     /// Enable lite runtime
     pub lite_runtime: Option<bool>,
+    /// Additional comma-separated derives for messages, oneofs and enums, e. g. `Hash, Eq`.
+    /// Derives already generated are not duplicated.
+    pub extra_derives: Option<String>,
+    /// Attributes inserted verbatim before message structs, e. g. `#[non_exhaustive]`.
+    pub extra_type_attrs: Option<String>,
+    /// Attributes inserted verbatim before message struct fields, e. g. `#[deprecated]`.
+    /// Oneof fields are not affected.
+    pub extra_field_attrs: Option<String>,
+    /// Attributes inserted verbatim before enums.
+    pub extra_enum_attrs: Option<String>,
+    /// Generate server trait, client and dispatcher for each `service`,
+    /// see `protobuf::service` module. Enabled by default.
+    pub gen_services: Option<bool>,
+    /// Generate async server trait, client and dispatcher for each `service`
+    /// in addition to (or, when `gen_services` is false, instead of) sync ones.
+    pub gen_async_services: Option<bool>,
+    /// Generate a builder struct for each message, e. g. `FooBuilder` for message `Foo`,
+    /// which is created with `Foo::builder()`.
+    /// `_` is appended to builder name clashing with another message or enum,
+    /// e. g. `FooBuilder_` when there's also message `FooBuilder`.
+    pub gen_builders: Option<bool>,
+    /// Implement `Eq`, `Hash`, `PartialOrd` and `Ord` for messages without
+    /// floating point fields in the message and in all messages it references.
+    /// Referenced messages must have this option enabled and must not come from `extern_paths`,
+    /// fields with `rust_type` are not supported.
+    /// Map fields are hashed and compared in key order, messages are ordered
+    /// by fields in declaration order. When the option is set for a message by name,
+    /// codegen fails if the message does not qualify; when it is set for a file or a package,
+    /// messages which do not qualify are generated without these traits.
+    pub derive_eq_hash_ord: Option<bool>,
     /// Generate `mod.rs` in the output directory.
     ///
     /// This option allows inclusion of generated files from cargo output directory.
     ///
     /// This option will likely be on by default in rust-protobuf version 3.
     pub gen_mod_rs: Option<bool>,
+    /// Generate modules nested by protobuf package, e. g. `com::example::v1::Message`,
+    /// instead of one module per `.proto` file in the output directory.
+    ///
+    /// Generated file for `foo.proto` of package `com.example.v1` is written
+    /// to `com/example/v1/foo.rs`, and `mod.rs` is generated in each directory
+    /// declaring nested package modules and re-exporting contents of all files of the package.
+    pub mods_by_package: Option<bool>,
     /// Used internally to generate protos bundled in protobuf crate
     /// like `descriptor.proto`
     pub inside_protobuf: Option<bool>,
+    /// Paths of types generated elsewhere, e. g. in a dependency crate:
+    /// pairs of protobuf package or fully qualified type name and Rust path,
+    /// like `(".common.types", "::common_protos::types")`.
+    ///
+    /// References to types matching the longest protobuf path point to the Rust path,
+    /// with nested packages and nested messages being nested modules.
+    /// Files of matching packages and files whose top-level messages and enums all match
+    /// are not generated; a file defining both matching and other types is generated entirely.
+    pub extern_paths: Vec<(String, String)>,
+    /// Options applied to files, messages, fields and enums by fully qualified protobuf name,
+    /// e. g. `.my.pkg.Message.field`, in order, after options specified in `.proto` files.
+    ///
+    /// A name matches an element and everything nested in it, `.` matches everything.
+    pub customize_by_name: Vec<(String, Customize)>,
 
     // When adding more options please keep in sync with `parse_from_parameter` below.
     /// Make sure `Customize` is always used with `..Default::default()`
@@ -72,21 +185,66 @@ impl Customize {
         if let Some(v) = that.carllerche_bytes_for_string {
             self.carllerche_bytes_for_string = Some(v);
         }
+        if let Some(v) = that.chrono_for_timestamp {
+            self.chrono_for_timestamp = Some(v);
+        }
+        if let Some(v) = that.time_for_timestamp {
+            self.time_for_timestamp = Some(v);
+        }
+        if let Some(v) = that.wrappers_as_option {
+            self.wrappers_as_option = Some(v);
+        }
+        if let Some(ref v) = that.rust_type {
+            self.rust_type = Some(v.clone());
+        }
         if let Some(v) = that.serde_derive {
             self.serde_derive = Some(v);
         }
         if let Some(ref v) = that.serde_derive_cfg {
             self.serde_derive_cfg = Some(v.clone());
         }
+        if let Some(v) = that.serde_json_mapping {
+            self.serde_json_mapping = Some(v);
+        }
         if let Some(v) = that.lite_runtime {
             self.lite_runtime = Some(v);
         }
+        if let Some(ref v) = that.extra_derives {
+            self.extra_derives = Some(v.clone());
+        }
+        if let Some(ref v) = that.extra_type_attrs {
+            self.extra_type_attrs = Some(v.clone());
+        }
+        if let Some(ref v) = that.extra_field_attrs {
+            self.extra_field_attrs = Some(v.clone());
+        }
+        if let Some(ref v) = that.extra_enum_attrs {
+            self.extra_enum_attrs = Some(v.clone());
+        }
+        if let Some(v) = that.gen_services {
+            self.gen_services = Some(v);
+        }
+        if let Some(v) = that.gen_async_services {
+            self.gen_async_services = Some(v);
+        }
+        if let Some(v) = that.gen_builders {
+            self.gen_builders = Some(v);
+        }
+        if let Some(v) = that.derive_eq_hash_ord {
+            self.derive_eq_hash_ord = Some(v);
+        }
         if let Some(v) = that.gen_mod_rs {
             self.gen_mod_rs = Some(v);
         }
+        if let Some(v) = that.mods_by_package {
+            self.mods_by_package = Some(v);
+        }
         if let Some(v) = that.inside_protobuf {
             self.inside_protobuf = Some(v);
         }
+        self.extern_paths.extend(that.extern_paths.iter().cloned());
+        self.customize_by_name
+            .extend(that.customize_by_name.iter().cloned());
     }
 
     /// Update unset fields of self with fields from other customize
@@ -125,16 +283,52 @@ impl Customize {
                 r.carllerche_bytes_for_bytes = Some(parse_bool(v)?);
             } else if n == "carllerche_bytes_for_string" {
                 r.carllerche_bytes_for_string = Some(parse_bool(v)?);
+            } else if n == "chrono_for_timestamp" {
+                r.chrono_for_timestamp = Some(parse_bool(v)?);
+            } else if n == "time_for_timestamp" {
+                r.time_for_timestamp = Some(parse_bool(v)?);
+            } else if n == "wrappers_as_option" {
+                r.wrappers_as_option = Some(parse_bool(v)?);
+            } else if n == "rust_type" {
+                r.rust_type = Some(v.to_owned());
             } else if n == "serde_derive" {
                 r.serde_derive = Some(parse_bool(v)?);
             } else if n == "serde_derive_cfg" {
                 r.serde_derive_cfg = Some(v.to_owned());
+            } else if n == "serde_json_mapping" {
+                r.serde_json_mapping = Some(parse_bool(v)?);
             } else if n == "lite_runtime" {
                 r.lite_runtime = Some(parse_bool(v)?);
+            } else if n == "extra_derives" {
+                r.extra_derives = Some(v.to_owned());
+            } else if n == "extra_type_attrs" {
+                r.extra_type_attrs = Some(v.to_owned());
+            } else if n == "extra_field_attrs" {
+                r.extra_field_attrs = Some(v.to_owned());
+            } else if n == "extra_enum_attrs" {
+                r.extra_enum_attrs = Some(v.to_owned());
+            } else if n == "gen_services" {
+                r.gen_services = Some(parse_bool(v)?);
+            } else if n == "gen_async_services" {
+                r.gen_async_services = Some(parse_bool(v)?);
+            } else if n == "gen_builders" {
+                r.gen_builders = Some(parse_bool(v)?);
+            } else if n == "derive_eq_hash_ord" {
+                r.derive_eq_hash_ord = Some(parse_bool(v)?);
             } else if n == "gen_mod_rs" {
                 r.gen_mod_rs = Some(parse_bool(v)?);
+            } else if n == "mods_by_package" {
+                r.mods_by_package = Some(parse_bool(v)?);
             } else if n == "inside_protobuf" {
                 r.inside_protobuf = Some(parse_bool(v)?);
+            } else if n == "extern_path" {
+                // `extern_path=.common.types=::common_protos::types`
+                let eq = match v.find('=') {
+                    Some(eq) => eq,
+                    None => return Err(CustomizeParseParameterError::EqNotFound),
+                };
+                r.extern_paths
+                    .push((v[..eq].to_owned(), v[eq + 1..].to_owned()));
             } else {
                 return Err(CustomizeParseParameterError::UnknownOptionName(
                     n.to_owned(),
@@ -143,6 +337,67 @@ impl Customize {
         }
         Ok(r)
     }
+
+    /// Apply `customize_by_name` options matching element with given fully qualified name.
+    ///
+    /// `rust_type` is applied only when the name matches exactly.
+    pub(crate) fn update_with_by_name(&mut self, name: &ProtobufAbsolutePath) {
+        let matching: Vec<Customize> = self
+            .customize_by_name
+            .iter()
+            .filter_map(|(pattern, customize)| {
+                let pattern = match pattern.as_str() {
+                    "" | "." => ProtobufAbsolutePath::root(),
+                    pattern => ProtobufAbsolutePath::from_path_maybe_dot(pattern),
+                };
+                let rem = name.remove_prefix(&pattern)?;
+                let mut customize = customize.clone();
+                if !rem.is_empty() {
+                    customize.rust_type = None;
+                }
+                Some(customize)
+            })
+            .collect();
+        for customize in matching {
+            self.update_with(&customize);
+        }
+    }
+
+    /// `customize_by_name` options naming exactly the element with given fully qualified name,
+    /// as opposed to options of enclosing elements.
+    pub(crate) fn by_exact_name<'a>(
+        &'a self,
+        name: &'a ProtobufAbsolutePath,
+    ) -> impl Iterator<Item = &'a Customize> + 'a {
+        self.customize_by_name
+            .iter()
+            .filter(move |(pattern, _)| {
+                pattern != ""
+                    && pattern != "."
+                    && ProtobufAbsolutePath::from_path_maybe_dot(pattern) == *name
+            })
+            .map(|(_, customize)| customize)
+    }
+
+    /// Append `extra_derives` not already present in the list.
+    pub(crate) fn add_extra_derives<'a>(&'a self, derive: &mut Vec<&'a str>) {
+        if let Some(ref extra) = self.extra_derives {
+            for d in extra.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+                if !derive.contains(&d) {
+                    derive.push(d);
+                }
+            }
+        }
+    }
+
+    /// Write `extra_type_attrs`, `extra_field_attrs` or `extra_enum_attrs` value.
+    pub(crate) fn write_extra_attrs(w: &mut CodeWriter, attrs: &Option<String>) {
+        if let Some(ref attrs) = attrs {
+            for line in attrs.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                w.write_line(line);
+            }
+        }
+    }
 }
 
 pub fn customize_from_rustproto_for_message(source: &MessageOptions) -> Customize {
@@ -152,11 +407,27 @@ pub fn customize_from_rustproto_for_message(source: &MessageOptions) -> Customiz
     let generate_getter = rustproto::exts::generate_getter.get(source);
     let carllerche_bytes_for_bytes = rustproto::exts::carllerche_bytes_for_bytes.get(source);
     let carllerche_bytes_for_string = rustproto::exts::carllerche_bytes_for_string.get(source);
+    let chrono_for_timestamp = rustproto::exts::chrono_for_timestamp.get(source);
+    let time_for_timestamp = rustproto::exts::time_for_timestamp.get(source);
+    let wrappers_as_option = rustproto::exts::wrappers_as_option.get(source);
+    let rust_type = None;
     let serde_derive = rustproto::exts::serde_derive.get(source);
     let serde_derive_cfg = rustproto::exts::serde_derive_cfg.get(source);
+    let serde_json_mapping = rustproto::exts::serde_json_mapping.get(source);
     let lite_runtime = None;
+    let extra_derives = rustproto::exts::extra_derives.get(source);
+    let extra_type_attrs = rustproto::exts::extra_type_attrs.get(source);
+    let extra_field_attrs = rustproto::exts::extra_field_attrs.get(source);
+    let extra_enum_attrs = None;
+    let gen_services = None;
+    let gen_async_services = None;
+    let gen_builders = None;
+    let derive_eq_hash_ord = None;
     let gen_mod_rs = None;
+    let mods_by_package = None;
     let inside_protobuf = None;
+    let extern_paths = Vec::new();
+    let customize_by_name = Vec::new();
     Customize {
         expose_oneof,
         expose_fields,
@@ -164,11 +435,27 @@ pub fn customize_from_rustproto_for_message(source: &MessageOptions) -> Customiz
         generate_getter,
         carllerche_bytes_for_bytes,
         carllerche_bytes_for_string,
+        chrono_for_timestamp,
+        time_for_timestamp,
+        wrappers_as_option,
+        rust_type,
         serde_derive,
         serde_derive_cfg,
+        serde_json_mapping,
         lite_runtime,
+        extra_derives,
+        extra_type_attrs,
+        extra_field_attrs,
+        extra_enum_attrs,
+        gen_services,
+        gen_async_services,
+        gen_builders,
+        derive_eq_hash_ord,
         gen_mod_rs,
+        mods_by_package,
         inside_protobuf,
+        extern_paths,
+        customize_by_name,
         _future_options: (),
     }
 }
@@ -181,11 +468,87 @@ pub fn customize_from_rustproto_for_field(source: &FieldOptions) -> Customize {
     let carllerche_bytes_for_bytes = rustproto::exts::carllerche_bytes_for_bytes_field.get(source);
     let carllerche_bytes_for_string =
         rustproto::exts::carllerche_bytes_for_string_field.get(source);
+    let chrono_for_timestamp = rustproto::exts::chrono_for_timestamp_field.get(source);
+    let time_for_timestamp = rustproto::exts::time_for_timestamp_field.get(source);
+    let wrappers_as_option = rustproto::exts::wrappers_as_option_field.get(source);
+    let rust_type = rustproto::exts::rust_type_field.get(source);
+    let serde_derive = None;
+    let serde_derive_cfg = None;
+    let serde_json_mapping = None;
+    let lite_runtime = None;
+    let extra_derives = None;
+    let extra_type_attrs = None;
+    let extra_field_attrs = rustproto::exts::extra_field_attrs_field.get(source);
+    let extra_enum_attrs = None;
+    let gen_services = None;
+    let gen_async_services = None;
+    let gen_builders = None;
+    let derive_eq_hash_ord = None;
+    let gen_mod_rs = None;
+    let mods_by_package = None;
+    let inside_protobuf = None;
+    let extern_paths = Vec::new();
+    let customize_by_name = Vec::new();
+    Customize {
+        expose_oneof,
+        expose_fields,
+        generate_accessors,
+        generate_getter,
+        carllerche_bytes_for_bytes,
+        carllerche_bytes_for_string,
+        chrono_for_timestamp,
+        time_for_timestamp,
+        wrappers_as_option,
+        rust_type,
+        serde_derive,
+        serde_derive_cfg,
+        serde_json_mapping,
+        lite_runtime,
+        extra_derives,
+        extra_type_attrs,
+        extra_field_attrs,
+        extra_enum_attrs,
+        gen_services,
+        gen_async_services,
+        gen_builders,
+        derive_eq_hash_ord,
+        gen_mod_rs,
+        mods_by_package,
+        inside_protobuf,
+        extern_paths,
+        customize_by_name,
+        _future_options: (),
+    }
+}
+
+pub fn customize_from_rustproto_for_enum(source: &EnumOptions) -> Customize {
+    let expose_oneof = None;
+    let expose_fields = None;
+    let generate_accessors = None;
+    let generate_getter = None;
+    let carllerche_bytes_for_bytes = None;
+    let carllerche_bytes_for_string = None;
+    let chrono_for_timestamp = None;
+    let time_for_timestamp = None;
+    let wrappers_as_option = None;
+    let rust_type = None;
     let serde_derive = None;
     let serde_derive_cfg = None;
+    let serde_json_mapping = None;
     let lite_runtime = None;
+    let extra_derives = rustproto::exts::extra_derives_enum.get(source);
+    let extra_type_attrs = None;
+    let extra_field_attrs = None;
+    let extra_enum_attrs = rustproto::exts::extra_enum_attrs.get(source);
+    let gen_services = None;
+    let gen_async_services = None;
+    let gen_builders = None;
+    let derive_eq_hash_ord = None;
     let gen_mod_rs = None;
+    let mods_by_package = None;
     let inside_protobuf = None;
+    let extern_paths = Vec::new();
+    let customize_by_name = Vec::new();
     Customize {
         expose_oneof,
         expose_fields,
@@ -193,11 +556,27 @@ pub fn customize_from_rustproto_for_field(source: &FieldOptions) -> Customize {
         generate_getter,
         carllerche_bytes_for_bytes,
         carllerche_bytes_for_string,
+        chrono_for_timestamp,
+        time_for_timestamp,
+        wrappers_as_option,
+        rust_type,
         serde_derive,
         serde_derive_cfg,
+        serde_json_mapping,
         lite_runtime,
+        extra_derives,
+        extra_type_attrs,
+        extra_field_attrs,
+        extra_enum_attrs,
+        gen_services,
+        gen_async_services,
+        gen_builders,
+        derive_eq_hash_ord,
         gen_mod_rs,
+        mods_by_package,
         inside_protobuf,
+        extern_paths,
+        customize_by_name,
         _future_options: (),
     }
 }
@@ -209,11 +588,27 @@ pub fn customize_from_rustproto_for_file(source: &FileOptions) -> Customize {
     let generate_getter = rustproto::exts::generate_getter_all.get(source);
     let carllerche_bytes_for_bytes = rustproto::exts::carllerche_bytes_for_bytes_all.get(source);
     let carllerche_bytes_for_string = rustproto::exts::carllerche_bytes_for_string_all.get(source);
+    let chrono_for_timestamp = rustproto::exts::chrono_for_timestamp_all.get(source);
+    let time_for_timestamp = rustproto::exts::time_for_timestamp_all.get(source);
+    let wrappers_as_option = rustproto::exts::wrappers_as_option_all.get(source);
+    let rust_type = None;
     let serde_derive = rustproto::exts::serde_derive_all.get(source);
     let serde_derive_cfg = rustproto::exts::serde_derive_cfg_all.get(source);
+    let serde_json_mapping = rustproto::exts::serde_json_mapping_all.get(source);
     let lite_runtime = rustproto::exts::lite_runtime_all.get(source);
+    let extra_derives = rustproto::exts::extra_derives_all.get(source);
+    let extra_type_attrs = rustproto::exts::extra_type_attrs_all.get(source);
+    let extra_field_attrs = rustproto::exts::extra_field_attrs_all.get(source);
+    let extra_enum_attrs = rustproto::exts::extra_enum_attrs_all.get(source);
+    let gen_services = None;
+    let gen_async_services = None;
+    let gen_builders = None;
+    let derive_eq_hash_ord = None;
     let gen_mod_rs = None;
+    let mods_by_package = None;
     let inside_protobuf = None;
+    let extern_paths = Vec::new();
+    let customize_by_name = Vec::new();
     Customize {
         expose_oneof,
         expose_fields,
@@ -221,11 +616,98 @@ pub fn customize_from_rustproto_for_file(source: &FileOptions) -> Customize {
         generate_getter,
         carllerche_bytes_for_bytes,
         carllerche_bytes_for_string,
+        chrono_for_timestamp,
+        time_for_timestamp,
+        wrappers_as_option,
+        rust_type,
         serde_derive,
         serde_derive_cfg,
+        serde_json_mapping,
         lite_runtime,
+        extra_derives,
+        extra_type_attrs,
+        extra_field_attrs,
+        extra_enum_attrs,
+        gen_services,
+        gen_async_services,
+        gen_builders,
+        derive_eq_hash_ord,
         inside_protobuf,
         gen_mod_rs,
+        mods_by_package,
+        extern_paths,
+        customize_by_name,
         _future_options: (),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn by_name(name: &str) -> Option<String> {
+        let mut customize = Customize {
+            customize_by_name: vec![
+                (
+                    ".".to_owned(),
+                    Customize {
+                        extra_derives: Some("Hash".to_owned()),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "a.b.Message".to_owned(),
+                    Customize {
+                        extra_derives: Some("Eq".to_owned()),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    ".a.b.Message.field".to_owned(),
+                    Customize {
+                        extra_derives: Some("Ord".to_owned()),
+                        ..Default::default()
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+        customize.update_with_by_name(&ProtobufAbsolutePath::from(name));
+        customize.extra_derives
+    }
+
+    #[test]
+    fn update_with_by_name() {
+        assert_eq!(Some("Hash"), by_name(".a.b").as_deref());
+        assert_eq!(Some("Hash"), by_name(".a.b.MessageWithSuffix").as_deref());
+        assert_eq!(Some("Eq"), by_name(".a.b.Message").as_deref());
+        assert_eq!(Some("Eq"), by_name(".a.b.Message.Nested").as_deref());
+        assert_eq!(Some("Ord"), by_name(".a.b.Message.field").as_deref());
+    }
+
+    #[test]
+    fn parse_extern_path() {
+        let customize =
+            Customize::parse_from_parameter("extern_path=.a.b=::ab extern_path=.c.D=crate::c::D")
+                .unwrap();
+        assert_eq!(
+            vec![
+                (".a.b".to_owned(), "::ab".to_owned()),
+                (".c.D".to_owned(), "crate::c::D".to_owned()),
+            ],
+            customize.extern_paths
+        );
+        assert!(Customize::parse_from_parameter("extern_path=.a.b").is_err());
+    }
+
+    #[test]
+    fn add_extra_derives() {
+        let customize = Customize {
+            extra_derives: Some("Hash, Eq,PartialOrd".to_owned()),
+            ..Default::default()
+        };
+        let mut derive = vec!["Clone", "Eq"];
+        customize.add_extra_derives(&mut derive);
+        assert_eq!(vec!["Clone", "Eq", "Hash", "PartialOrd"], derive);
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use protobuf::descriptor::*;
 
 use super::code_writer::*;
+use super::customize::customize_from_rustproto_for_enum;
 use super::customize::Customize;
 use crate::inside::protobuf_crate_path;
 use crate::rust::EXPR_NONE;
@@ -68,6 +69,12 @@ impl<'a> EnumGen<'a> {
         path: &'a [i32],
         info: Option<&'a SourceCodeInfo>,
     ) -> EnumGen<'a> {
+        let mut customize = customize.clone();
+        customize.update_with(&customize_from_rustproto_for_enum(
+            enum_with_scope.en.get_proto().options.get_or_default(),
+        ));
+        customize.update_with_by_name(&enum_with_scope.name_absolute());
+
         let lite_runtime = customize.lite_runtime.unwrap_or_else(|| {
             enum_with_scope
                 .get_scope()
@@ -78,11 +85,16 @@ impl<'a> EnumGen<'a> {
                 == file_options::OptimizeMode::LITE_RUNTIME
         });
 
+        if lite_runtime {
+            // JSON mapping needs reflection which is not available in lite runtime
+            customize.serde_json_mapping = Some(false);
+        }
+
         EnumGen {
             enum_with_scope,
             type_name: enum_with_scope.rust_name().to_path(),
             lite_runtime,
-            customize: customize.clone(),
+            customize,
             path,
             info,
             file_index,
@@ -140,6 +152,7 @@ impl<'a> EnumGen<'a> {
         self.write_impl_value(w);
         w.write_line("");
         self.write_impl_self(w);
+        serde::write_serde_impls_for_enum(w, &self.customize, &format!("{}", self.type_name));
     }
 
     fn write_impl_self(&self, w: &mut CodeWriter) {
@@ -161,14 +174,29 @@ impl<'a> EnumGen<'a> {
         derive.push("Debug");
         if !self.allow_alias() {
             derive.push("Hash");
+            derive.push("PartialOrd");
+            derive.push("Ord");
         } else {
             w.comment("Note: you cannot use pattern matching for enums with allow_alias option");
         }
+        self.customize.add_extra_derives(&mut derive);
         w.derive(&derive);
         serde::write_serde_attr(w, &self.customize, "derive(Serialize, Deserialize)");
+        Customize::write_extra_attrs(w, &self.customize.extra_enum_attrs);
         let ref type_name = self.type_name;
+        static VALUE_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
+        let value_number = *VALUE_NUMBER.get(|| {
+            protobuf::reflect::MessageDescriptor::for_type::<EnumDescriptorProto>()
+                .get_field_by_name("value")
+                .expect("`value` must exist")
+                .get_proto()
+                .get_number()
+        });
         w.expr_block(&format!("pub enum {}", type_name), |w| {
-            for value in self.values_all() {
+            for (id, value) in self.values_all().into_iter().enumerate() {
+                let mut path = self.path.to_vec();
+                path.extend_from_slice(&[value_number, id as i32]);
+                w.all_documentation(self.info, &path);
                 if self.allow_alias() {
                     w.write_line(&format!(
                         "{}, // {}",
@@ -0,0 +1,189 @@
+//! Check if `Eq`, `Hash` and `Ord` can be implemented for generated message.
+
+use std::collections::HashSet;
+
+use protobuf::reflect::FieldDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::RuntimeFieldType;
+use protobuf::reflect::RuntimeTypeBox;
+
+use crate::customize::customize_from_rustproto_for_field;
+use crate::customize::customize_from_rustproto_for_file;
+use crate::customize::customize_from_rustproto_for_message;
+use crate::customize::Customize;
+use crate::extern_path::is_extern;
+use crate::protobuf_abs_path::ProtobufAbsolutePath;
+use crate::protobuf_ident::ProtobufIdent;
+
+/// Same limit as for deriving `PartialEq`, see `MessageGen::supports_derive_partial_eq`.
+const MAX_FIELDS: usize = 500;
+
+/// Find why `Eq`, `Hash` and `Ord` cannot be implemented for the message.
+///
+/// Message does not qualify if it or any message it references transitively
+/// has a floating point field, references a well-known type (these are defined
+/// in `protobuf` crate without these traits), an enum with aliases,
+/// or has too many fields.
+///
+/// Referenced messages must get the impls too: they must be generated in
+/// this codegen invocation with `derive_eq_hash_ord` option enabled,
+/// and must not have fields with `rust_type` option.
+pub(crate) fn eq_hash_ord_blocker(
+    message: &MessageDescriptor,
+    customize: &Customize,
+) -> Option<String> {
+    let mut visited = HashSet::new();
+    blocker(message, customize, true, &mut visited)
+}
+
+/// Options in effect for the message, computed the same way
+/// as when the message is generated: options passed to codegen,
+/// updated with options of the file, of enclosing messages and of the message itself.
+fn customize_for_message(message: &MessageDescriptor, customize: &Customize) -> Customize {
+    let file = message.file_descriptor();
+    let package = file.proto().get_package();
+
+    let mut customize = customize.clone();
+    customize.update_with(&customize_from_rustproto_for_file(
+        file.proto().options.get_or_default(),
+    ));
+    customize.update_with_by_name(&ProtobufAbsolutePath::from_package_path(match package {
+        "" => None,
+        package => Some(package),
+    }));
+
+    let name_to_package = match package {
+        "" => message.full_name(),
+        package => &message.full_name()[package.len() + 1..],
+    };
+    let mut path = String::new();
+    for component in name_to_package.split('.') {
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(component);
+        let m = file.message_by_package_relative_name(&path).unwrap();
+        customize.update_with(&customize_from_rustproto_for_message(
+            m.get_proto().options.get_or_default(),
+        ));
+        customize.update_with_by_name(&ProtobufAbsolutePath::from_path_without_dot(m.full_name()));
+    }
+    customize.rust_type = None;
+    customize
+}
+
+/// Options in effect for the field, see `customize_for_message`.
+fn customize_for_field(
+    message: &MessageDescriptor,
+    field: &FieldDescriptor,
+    message_customize: &Customize,
+) -> Customize {
+    let mut customize = message_customize.clone();
+    customize.update_with(&customize_from_rustproto_for_field(
+        field.get_proto().options.get_or_default(),
+    ));
+    let mut name = ProtobufAbsolutePath::from_path_without_dot(message.full_name());
+    name.push_simple(ProtobufIdent::from(field.get_name()));
+    customize.update_with_by_name(&name);
+    customize
+}
+
+fn blocker(
+    message: &MessageDescriptor,
+    root_customize: &Customize,
+    top: bool,
+    visited: &mut HashSet<String>,
+) -> Option<String> {
+    if !visited.insert(message.full_name().to_owned()) {
+        return None;
+    }
+
+    if !top {
+        let name = ProtobufAbsolutePath::from_path_without_dot(message.full_name());
+        if is_extern(&name, root_customize) {
+            return Some(format!(
+                "referenced message `{}` is generated elsewhere",
+                message.full_name()
+            ));
+        }
+    }
+
+    let customize = customize_for_message(message, root_customize);
+    if !top && !customize.derive_eq_hash_ord.unwrap_or(false) {
+        return Some(format!(
+            "referenced message `{}` does not have `derive_eq_hash_ord` option enabled",
+            message.full_name()
+        ));
+    }
+
+    if message.fields().len() > MAX_FIELDS {
+        return Some(format!(
+            "message `{}` has more than {} fields",
+            message.full_name(),
+            MAX_FIELDS
+        ));
+    }
+
+    for field in message.fields() {
+        if customize_for_field(message, &field, &customize)
+            .rust_type
+            .is_some()
+        {
+            return Some(format!(
+                "field `{}.{}` has `rust_type` option",
+                message.full_name(),
+                field.get_name()
+            ));
+        }
+
+        let types = match field.runtime_field_type() {
+            RuntimeFieldType::Singular(t) | RuntimeFieldType::Repeated(t) => vec![t],
+            RuntimeFieldType::Map(k, v) => vec![k, v],
+        };
+        for t in types {
+            match t {
+                RuntimeTypeBox::F32 | RuntimeTypeBox::F64 => {
+                    return Some(format!(
+                        "field `{}.{}` has floating point type",
+                        message.full_name(),
+                        field.get_name()
+                    ));
+                }
+                RuntimeTypeBox::Message(m) if m.full_name().starts_with("google.protobuf.") => {
+                    return Some(format!(
+                        "field `{}.{}` has well-known type `{}`",
+                        message.full_name(),
+                        field.get_name(),
+                        m.full_name()
+                    ));
+                }
+                RuntimeTypeBox::Enum(e) if e.full_name().starts_with("google.protobuf.") => {
+                    return Some(format!(
+                        "field `{}.{}` has well-known type `{}`",
+                        message.full_name(),
+                        field.get_name(),
+                        e.full_name()
+                    ));
+                }
+                RuntimeTypeBox::Enum(e)
+                    if e.get_proto().options.get_or_default().get_allow_alias() =>
+                {
+                    return Some(format!(
+                        "field `{}.{}` has enum type `{}` with `allow_alias` option",
+                        message.full_name(),
+                        field.get_name(),
+                        e.full_name()
+                    ));
+                }
+                RuntimeTypeBox::Message(m) => {
+                    if let Some(b) = blocker(&m, root_customize, false, visited) {
+                        return Some(b);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    None
+}
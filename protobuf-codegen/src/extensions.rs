@@ -25,6 +25,7 @@ impl<'a> ExtGen<'a> {
             &ProtobufAbsolutePath::from(self.field.get_extendee()),
             &FileAndMod {
                 file: self.file.proto().get_name().to_owned(),
+                package: self.file.proto().get_package().to_owned(),
                 relative_mod: RustRelativePath::from("exts"),
                 customize: self.customize.clone(),
             },
@@ -48,6 +49,7 @@ impl<'a> ExtGen<'a> {
                 &ProtobufAbsolutePath::from(self.field.get_type_name()),
                 &FileAndMod {
                     file: self.file.proto().get_name().to_owned(),
+                    package: self.file.proto().get_package().to_owned(),
                     relative_mod: RustRelativePath::from("exts"),
                     customize: self.customize.clone(),
                 },
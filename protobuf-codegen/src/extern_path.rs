@@ -0,0 +1,161 @@
+//! Types generated outside of current codegen invocation, see `Customize::extern_paths`.
+
+use protobuf::reflect::FileDescriptor;
+
+use crate::customize::Customize;
+use crate::file::proto_package_to_rust_mods;
+use crate::inside::protobuf_crate_path;
+use crate::protobuf_abs_path::ProtobufAbsolutePath;
+use crate::rust_name::RustIdentWithPath;
+use crate::rust_name::RustPath;
+use crate::scope::FileScope;
+use crate::scope::WithScope;
+use crate::ProtobufRelativePath;
+
+fn components(path: &ProtobufRelativePath) -> Vec<&str> {
+    if path.is_empty() {
+        Vec::new()
+    } else {
+        path.get().split('.').collect()
+    }
+}
+
+/// Longest extern path which is a prefix of given name.
+///
+/// Returns the rest of the name and the Rust path.
+fn find_extern_path<'a>(
+    customize: &'a Customize,
+    name: &ProtobufAbsolutePath,
+) -> Option<(ProtobufRelativePath, &'a str)> {
+    customize
+        .extern_paths
+        .iter()
+        .filter_map(|(proto_path, rust_path)| {
+            let proto_path = match proto_path.as_str() {
+                "" | "." => ProtobufAbsolutePath::root(),
+                proto_path => ProtobufAbsolutePath::from_path_maybe_dot(proto_path),
+            };
+            name.remove_prefix(&proto_path)
+                .map(|rem| (rem, rust_path.as_str()))
+        })
+        .min_by_key(|(rem, _)| rem.get().len())
+}
+
+/// Rust module of a package generated elsewhere.
+fn extern_package_rust_path(package: &str, customize: &Customize) -> Option<RustPath> {
+    let package = match package {
+        "" => ProtobufAbsolutePath::root(),
+        package => ProtobufAbsolutePath::from_path_without_dot(package),
+    };
+    let (rem, rust_path) = find_extern_path(customize, &package)?;
+    // nested packages are nested modules
+    let mut r = RustPath::from(rust_path);
+    for m in proto_package_to_rust_mods(rem.get()) {
+        r = r.append_ident(m);
+    }
+    Some(r)
+}
+
+/// Files of the package are generated elsewhere and must not be generated.
+fn is_extern_package(package: &str, customize: &Customize) -> bool {
+    extern_package_rust_path(package, customize).is_some()
+}
+
+/// File is generated elsewhere and must not be generated: its package is mapped,
+/// or all its top-level messages and enums are mapped.
+///
+/// File which defines both mapped and unmapped types is generated entirely.
+pub(crate) fn is_extern_file(file: &FileDescriptor, customize: &Customize) -> bool {
+    if is_extern_package(file.proto().get_package(), customize) {
+        return true;
+    }
+    let scope = FileScope {
+        file_descriptor: file,
+    }
+    .to_scope();
+    let messages = scope.get_messages();
+    let enums = scope.get_enums();
+    (!messages.is_empty() || !enums.is_empty())
+        && file.proto().extension.is_empty()
+        && messages
+            .iter()
+            .all(|m| is_extern(&m.name_absolute(), customize))
+        && enums
+            .iter()
+            .all(|e| is_extern(&e.name_absolute(), customize))
+}
+
+/// Message or enum (or its package) is generated elsewhere.
+pub(crate) fn is_extern(name: &ProtobufAbsolutePath, customize: &Customize) -> bool {
+    find_extern_path(customize, name).is_some()
+}
+
+/// Rust name of message or enum generated elsewhere.
+pub(crate) fn extern_rust_name(
+    message_or_enum: &dyn WithScope,
+    customize: &Customize,
+) -> Option<RustIdentWithPath> {
+    let (rem, rust_path) = find_extern_path(customize, &message_or_enum.name_absolute())?;
+    let rem = components(&rem);
+    let name_to_package = message_or_enum.protobuf_name_to_package();
+    let name_to_package = components(&name_to_package);
+    let rust_name_to_file = message_or_enum.rust_name_to_file();
+
+    if rem.len() >= name_to_package.len() {
+        // extern path is a package
+        let rem_package = rem[..rem.len() - name_to_package.len()].join(".");
+        let mut r = RustPath::from(rust_path);
+        for m in proto_package_to_rust_mods(&rem_package) {
+            r = r.append_ident(m);
+        }
+        Some(r.append_with_ident(rust_name_to_file))
+    } else if rem.is_empty() {
+        // extern path is this message or enum
+        Some(RustIdentWithPath::from(rust_path))
+    } else {
+        // extern path is an outer message, nested types are in nested modules
+        // next to the outer message
+        let depth = name_to_package.len() - rem.len();
+        let mut nested = rust_name_to_file.path;
+        for _ in 1..depth {
+            nested.remove_first();
+        }
+        Some(
+            RustIdentWithPath::from(rust_path)
+                .path
+                .append(nested)
+                .with_ident(rust_name_to_file.ident),
+        )
+    }
+}
+
+/// Expression for `FileDescriptor` of a file generated elsewhere.
+pub(crate) fn extern_file_descriptor_expr(
+    file: &FileDescriptor,
+    customize: &Customize,
+) -> Option<String> {
+    let protobuf_crate = protobuf_crate_path(customize);
+    let scope = FileScope {
+        file_descriptor: file,
+    }
+    .to_scope();
+    // type path is known precisely even if extern path is a message
+    for message in scope.get_messages() {
+        if let Some(rust_name) = extern_rust_name(&message, customize) {
+            return Some(format!(
+                "<{} as {}::Message>::descriptor_static().file_descriptor().clone()",
+                rust_name, protobuf_crate
+            ));
+        }
+    }
+    for enum_type in scope.get_enums() {
+        if let Some(rust_name) = extern_rust_name(&enum_type, customize) {
+            return Some(format!(
+                "<{} as {}::ProtobufEnum>::enum_descriptor_static().file_descriptor().clone()",
+                rust_name, protobuf_crate
+            ));
+        }
+    }
+    extern_package_rust_path(file.proto().get_package(), customize)
+        .map(|p| format!("{}()", p.append_ident("file_descriptor".into())))
+}
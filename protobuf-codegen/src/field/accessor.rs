@@ -10,6 +10,7 @@ use crate::field::SingularField;
 use crate::field::SingularFieldFlag;
 use crate::inside::protobuf_crate_path;
 use crate::oneof::OneofField;
+use crate::rust_types_values::rust_name;
 use crate::rust_types_values::RustType;
 use crate::scope::WithScope;
 use protobuf::descriptor::field_descriptor_proto;
@@ -69,27 +70,44 @@ impl FieldGen<'_> {
         ]
     }
 
-    fn make_accessor_fns_has_get_set(&self) -> Vec<String> {
+    fn make_accessor_fns_has_get_set_clear(&self) -> Vec<String> {
         let message = self.proto_field.message.rust_name();
         vec![
             format!("{}::has_{}", message, self.rust_name),
             format!("{}::get_{}", message, self.rust_name),
             format!("{}::set_{}", message, self.rust_name),
+            format!("{}::{}", message, self.clear_field_func()),
         ]
     }
 
-    fn make_accessor_fns_has_get_mut_set(&self) -> Vec<String> {
+    fn make_accessor_fns_has_get_mut_set_clear(&self) -> Vec<String> {
         let message = self.proto_field.message.rust_name();
         vec![
             format!("{}::has_{}", message, self.rust_name),
             format!("{}::get_{}", message, self.rust_name),
             format!("{}::mut_{}", message, self.rust_name),
             format!("{}::set_{}", message, self.rust_name),
+            format!("{}::{}", message, self.clear_field_func()),
         ]
     }
 
     fn accessor_fn_map(&self, map_field: &MapField) -> AccessorFn {
-        let MapField { .. } = map_field;
+        if let MapField {
+            value: FieldElem::Message(m),
+            value_converted: Some(..),
+            ..
+        } = map_field
+        {
+            return AccessorFn {
+                name: "make_map_converted_message_accessor".to_owned(),
+                type_params: vec![
+                    "_".to_owned(),
+                    format!("{}", m.rust_name_relative(&self.get_file_and_mod())),
+                    "_".to_owned(),
+                ],
+                callback_params: self.make_accessor_fns_lambda(),
+            };
+        }
         AccessorFn {
             name: "make_map_simpler_accessor".to_owned(),
             type_params: vec![format!("_"), format!("_")],
@@ -98,7 +116,33 @@ impl FieldGen<'_> {
     }
 
     fn accessor_fn_repeated(&self, repeated_field: &RepeatedField) -> AccessorFn {
-        let RepeatedField { .. } = repeated_field;
+        if let RepeatedField {
+            elem: FieldElem::Message(m),
+            converted: Some(..),
+            ..
+        } = repeated_field
+        {
+            return AccessorFn {
+                name: "make_vec_converted_message_accessor".to_owned(),
+                type_params: vec![
+                    format!("{}", m.rust_name_relative(&self.get_file_and_mod())),
+                    "_".to_owned(),
+                ],
+                callback_params: self.make_accessor_fns_lambda(),
+            };
+        }
+        if let RepeatedField {
+            elem: FieldElem::Primitive(t, ..),
+            converted: Some(..),
+            ..
+        } = repeated_field
+        {
+            return AccessorFn {
+                name: "make_vec_converted_accessor".to_owned(),
+                type_params: vec![rust_name(*t).to_code(&self.customize), "_".to_owned()],
+                callback_params: self.make_accessor_fns_lambda(),
+            };
+        }
         let name = match repeated_field.kind() {
             RepeatedFieldKind::Vec => "make_vec_simpler_accessor",
         };
@@ -163,9 +207,31 @@ impl FieldGen<'_> {
                 )],
                 callback_params: self.make_accessor_fns_lambda_default_value(),
             },
-            FieldElem::Group => {
-                unreachable!("no accessor for group field");
+        }
+    }
+
+    fn accessor_fn_singular_converted(&self, singular: &SingularField) -> AccessorFn {
+        match singular.elem {
+            FieldElem::Message(ref m) => AccessorFn {
+                name: "make_option_converted_message_accessor".to_owned(),
+                type_params: vec![
+                    format!("{}", m.rust_name_relative(&self.get_file_and_mod())),
+                    "_".to_owned(),
+                ],
+                callback_params: self.make_accessor_fns_lambda(),
+            },
+            FieldElem::Primitive(t, ..) => {
+                let name = match singular.flag {
+                    SingularFieldFlag::WithFlag { .. } => "make_option_converted_accessor",
+                    SingularFieldFlag::WithoutFlag => "make_simpler_field_converted_accessor",
+                };
+                AccessorFn {
+                    name: name.to_owned(),
+                    type_params: vec![rust_name(t).to_code(&self.customize), "_".to_owned()],
+                    callback_params: self.make_accessor_fns_lambda(),
+                }
             }
+            FieldElem::Enum(..) => unreachable!("enum fields cannot be converted"),
         }
     }
 
@@ -183,7 +249,7 @@ impl FieldGen<'_> {
             return AccessorFn {
                 name: "make_oneof_copy_has_get_set_simpler_accessors".to_owned(),
                 type_params: vec![format!("_")],
-                callback_params: self.make_accessor_fns_has_get_set(),
+                callback_params: self.make_accessor_fns_has_get_set_clear(),
             };
         }
 
@@ -191,7 +257,7 @@ impl FieldGen<'_> {
             return AccessorFn {
                 name: "make_oneof_copy_has_get_set_simpler_accessors".to_owned(),
                 type_params: vec![format!("_")],
-                callback_params: self.make_accessor_fns_has_get_set(),
+                callback_params: self.make_accessor_fns_has_get_set_clear(),
             };
         }
 
@@ -199,7 +265,7 @@ impl FieldGen<'_> {
             return AccessorFn {
                 name: "make_oneof_message_has_get_mut_set_accessor".to_owned(),
                 type_params: vec![format!("{}", name)],
-                callback_params: self.make_accessor_fns_has_get_mut_set(),
+                callback_params: self.make_accessor_fns_has_get_mut_set_clear(),
             };
         }
 
@@ -207,7 +273,7 @@ impl FieldGen<'_> {
         AccessorFn {
             name: "make_oneof_deref_has_get_set_simpler_accessor".to_owned(),
             type_params: vec![format!("_")],
-            callback_params: self.make_accessor_fns_has_get_set(),
+            callback_params: self.make_accessor_fns_has_get_set_clear(),
         }
     }
 
@@ -215,13 +281,21 @@ impl FieldGen<'_> {
         match self.kind {
             FieldKind::Repeated(ref repeated_field) => self.accessor_fn_repeated(repeated_field),
             FieldKind::Map(ref map_field) => self.accessor_fn_map(map_field),
+            FieldKind::Singular(
+                ref singular @ SingularField {
+                    converted: Some(..),
+                    ..
+                },
+            ) => self.accessor_fn_singular_converted(singular),
             FieldKind::Singular(SingularField {
                 ref elem,
                 flag: SingularFieldFlag::WithoutFlag,
+                ..
             }) => self.accessor_fn_singular_without_flag(elem),
             FieldKind::Singular(SingularField {
                 ref elem,
                 flag: SingularFieldFlag::WithFlag { option_kind, .. },
+                ..
             }) => self.accessor_fn_singular_with_flag(elem, option_kind),
             FieldKind::Oneof(ref oneof) => self.accessor_fn_oneof(oneof),
         }
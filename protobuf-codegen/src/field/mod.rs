@@ -1,4 +1,5 @@
 use crate::protobuf_abs_path::ProtobufAbsolutePath;
+use crate::protobuf_ident::ProtobufIdent;
 use crate::rust;
 use protobuf::descriptor::*;
 use protobuf::rt;
@@ -28,6 +29,7 @@ use crate::scope::RootScope;
 use crate::scope::WithScope;
 use crate::serde;
 use crate::syntax::Syntax;
+use crate::well_known_types::wrapper_value_rust_type;
 use protobuf::reflect::ReflectValueRef;
 use protobuf::wire_format::WireType;
 
@@ -36,12 +38,24 @@ mod accessor;
 fn type_is_copy(field_type: field_descriptor_proto::Type) -> bool {
     match field_type {
         field_descriptor_proto::Type::TYPE_MESSAGE
+        | field_descriptor_proto::Type::TYPE_GROUP
         | field_descriptor_proto::Type::TYPE_STRING
         | field_descriptor_proto::Type::TYPE_BYTES => false,
         _ => true,
     }
 }
 
+/// Group fields are stored and accessed like message fields,
+/// only wire encoding is different.
+pub(crate) fn type_is_message_or_group(field_type: field_descriptor_proto::Type) -> bool {
+    match field_type {
+        field_descriptor_proto::Type::TYPE_MESSAGE | field_descriptor_proto::Type::TYPE_GROUP => {
+            true
+        }
+        _ => false,
+    }
+}
+
 trait FieldDescriptorProtoTypeExt {
     fn read(&self, is: &str, primitive_type_variant: PrimitiveTypeVariant) -> String;
     fn is_s_varint(&self) -> bool;
@@ -94,7 +108,7 @@ fn field_type_wire_type(field_type: field_descriptor_proto::Type) -> WireType {
         Type::TYPE_STRING => WireType::WireTypeLengthDelimited,
         Type::TYPE_BYTES => WireType::WireTypeLengthDelimited,
         Type::TYPE_MESSAGE => WireType::WireTypeLengthDelimited,
-        Type::TYPE_GROUP => WireType::WireTypeLengthDelimited, // not true
+        Type::TYPE_GROUP => WireType::WireTypeStartGroup,
     }
 }
 
@@ -218,10 +232,20 @@ impl SingularFieldFlag {
 pub(crate) struct SingularField<'a> {
     pub flag: SingularFieldFlag,
     pub elem: FieldElem<'a>,
+    // message field is stored as `Option` of this type,
+    // and converted to and from message when reading and writing
+    pub converted: Option<RustType>,
 }
 
 impl<'a> SingularField<'a> {
     fn rust_storage_type(&self, reference: &FileAndMod) -> RustType {
+        if let Some(ref converted) = self.converted {
+            return match self.flag {
+                SingularFieldFlag::WithFlag { .. } => RustType::Option(Box::new(converted.clone())),
+                // proto3 `string` or `bytes` field with `rust_type` option
+                SingularFieldFlag::WithoutFlag => converted.clone(),
+            };
+        }
         match self.flag {
             SingularFieldFlag::WithFlag { option_kind, .. } => {
                 option_kind.wrap_element(self.elem.rust_storage_elem_type(reference))
@@ -266,6 +290,9 @@ impl RepeatedFieldKind {
 pub(crate) struct RepeatedField<'a> {
     pub elem: FieldElem<'a>,
     pub packed: bool,
+    // when set, elements are stored as this type instead of message type,
+    // and converted to and from message when reading and writing
+    pub converted: Option<RustType>,
 }
 
 impl<'a> RepeatedField<'a> {
@@ -274,8 +301,10 @@ impl<'a> RepeatedField<'a> {
     }
 
     fn rust_type(&self, reference: &FileAndMod) -> RustType {
-        self.kind()
-            .wrap_element(self.elem.rust_storage_elem_type(reference))
+        self.kind().wrap_element(match self.converted {
+            Some(ref converted) => converted.clone(),
+            None => self.elem.rust_storage_elem_type(reference),
+        })
     }
 
     fn default(&self) -> String {
@@ -288,6 +317,23 @@ pub struct MapField<'a> {
     message: MessageWithScope<'a>,
     key: FieldElem<'a>,
     value: FieldElem<'a>,
+    // when set, values are stored as this type instead of message type
+    value_converted: Option<RustType>,
+}
+
+impl<'a> MapField<'a> {
+    /// implementation of ProtobufType trait for map value
+    fn value_lib_protobuf_type(&self, reference: &FileAndMod) -> String {
+        match (&self.value, &self.value_converted) {
+            (FieldElem::Message(m), Some(converted)) => format!(
+                "{}::reflect::types::ProtobufTypeConvertedMessage<{}, {}>",
+                protobuf_crate_path(&reference.customize),
+                m.rust_name_relative(reference),
+                converted.to_code(&reference.customize),
+            ),
+            _ => self.value.lib_protobuf_type(reference),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -404,14 +450,12 @@ pub(crate) enum FieldElem<'a> {
     Primitive(field_descriptor_proto::Type, PrimitiveTypeVariant),
     Message(FieldElemMessage<'a>),
     Enum(FieldElemEnum<'a>),
-    Group,
 }
 
 impl<'a> FieldElem<'a> {
     fn proto_type(&self) -> field_descriptor_proto::Type {
         match *self {
             FieldElem::Primitive(t, ..) => t,
-            FieldElem::Group => field_descriptor_proto::Type::TYPE_GROUP,
             FieldElem::Message(..) => field_descriptor_proto::Type::TYPE_MESSAGE,
             FieldElem::Enum(..) => field_descriptor_proto::Type::TYPE_ENUM,
         }
@@ -433,7 +477,6 @@ impl<'a> FieldElem<'a> {
                 PrimitiveTypeVariant::Carllerche,
             ) => RustType::Bytes,
             FieldElem::Primitive(.., PrimitiveTypeVariant::Carllerche) => unreachable!(),
-            FieldElem::Group => RustType::Group,
             FieldElem::Message(ref m) => m.rust_type(reference),
             FieldElem::Enum(ref en) => en.enum_or_unknown_rust_type(reference),
         }
@@ -455,7 +498,6 @@ impl<'a> FieldElem<'a> {
             FieldElem::Enum(ref en) => {
                 ProtobufTypeGen::EnumOrUnknown(en.rust_name_relative(reference))
             }
-            FieldElem::Group => unreachable!(),
         }
     }
 
@@ -480,9 +522,7 @@ fn field_elem<'a>(
     customize: &Customize,
     current_file_path: &RustRelativePath,
 ) -> FieldElem<'a> {
-    if field.field.get_proto().get_field_type() == field_descriptor_proto::Type::TYPE_GROUP {
-        FieldElem::Group
-    } else if field.field.get_proto().has_type_name() {
+    if field.field.get_proto().has_type_name() {
         let message_or_enum = root_scope.find_message_or_enum(&ProtobufAbsolutePath::from(
             field.field.get_proto().get_type_name(),
         ));
@@ -490,6 +530,10 @@ fn field_elem<'a>(
             (
                 field_descriptor_proto::Type::TYPE_MESSAGE,
                 MessageOrEnumWithScope::Message(message),
+            )
+            | (
+                field_descriptor_proto::Type::TYPE_GROUP,
+                MessageOrEnumWithScope::Message(message),
             ) => {
                 let entry_key_value =
                     if let (true, Some((key, value))) = (parse_map, map_entry(&message)) {
@@ -550,6 +594,66 @@ fn field_elem<'a>(
     }
 }
 
+/// Type used instead of generated message type for singular message field.
+fn converted_message_type(field: &FieldDescriptorProto, customize: &Customize) -> Option<RustType> {
+    match field.get_type_name() {
+        ".google.protobuf.Timestamp" if customize.chrono_for_timestamp.unwrap_or(false) => Some(
+            RustType::External("::chrono::DateTime<::chrono::Utc>".to_owned()),
+        ),
+        ".google.protobuf.Timestamp" if customize.time_for_timestamp.unwrap_or(false) => {
+            Some(RustType::External("::time::OffsetDateTime".to_owned()))
+        }
+        name if customize.wrappers_as_option.unwrap_or(false) => {
+            wrapper_value_rust_type(&ProtobufAbsolutePath::from(name))
+        }
+        _ => None,
+    }
+}
+
+/// Type used instead of generated message type for repeated field element or map value.
+fn converted_wrapper_type(elem: &FieldElem, customize: &Customize) -> Option<RustType> {
+    match elem {
+        FieldElem::Message(FieldElemMessage {
+            message,
+            map_entry: None,
+        }) if customize.wrappers_as_option.unwrap_or(false) => {
+            wrapper_value_rust_type(&message.name_absolute())
+        }
+        _ => None,
+    }
+}
+
+/// Type set with `rust_type` option, used instead of generated field type.
+fn rust_type_override(
+    field: &FieldWithContext,
+    elem: &FieldElem,
+    customize: &Customize,
+) -> Option<RustType> {
+    let rust_type = customize.rust_type.as_ref()?;
+    let supported = match elem {
+        FieldElem::Message(FieldElemMessage {
+            map_entry: Some(..),
+            ..
+        }) => false,
+        FieldElem::Message(..) => {
+            field.field.get_proto().get_field_type() == field_descriptor_proto::Type::TYPE_MESSAGE
+        }
+        FieldElem::Primitive(field_descriptor_proto::Type::TYPE_STRING, ..)
+        | FieldElem::Primitive(field_descriptor_proto::Type::TYPE_BYTES, ..) => true,
+        FieldElem::Primitive(..) | FieldElem::Enum(..) => false,
+    };
+    if !supported || field.is_oneof() {
+        panic!(
+            "`rust_type` option is not supported for field `{}.{}`: \
+             only singular and repeated `string`, `bytes` and message fields \
+             can have custom type",
+            field.message.message.get_name(),
+            field.field.get_name()
+        );
+    }
+    Some(RustType::External(rust_type.clone()))
+}
+
 #[derive(Clone)]
 pub(crate) struct FieldGen<'a> {
     root_scope: &'a RootScope<'a>,
@@ -565,8 +669,8 @@ pub(crate) struct FieldGen<'a> {
     pub generate_accessors: bool,
     pub generate_getter: bool,
     customize: Customize,
-    path: Vec<i32>,
-    info: Option<&'a SourceCodeInfo>,
+    pub path: Vec<i32>,
+    pub info: Option<&'a SourceCodeInfo>,
 }
 
 impl<'a> FieldGen<'a> {
@@ -581,6 +685,9 @@ impl<'a> FieldGen<'a> {
         customize.update_with(&customize_from_rustproto_for_field(
             field.field.get_proto().options.get_or_default(),
         ));
+        let mut name = field.message.name_absolute();
+        name.push_simple(ProtobufIdent::from(field.name()));
+        customize.update_with_by_name(&name);
 
         let elem = field_elem(
             &field,
@@ -594,21 +701,48 @@ impl<'a> FieldGen<'a> {
 
         let field_may_have_custom_default_value = syntax == Syntax::PROTO2
             && field.field.get_proto().get_label() != field_descriptor_proto::Label::LABEL_REPEATED
-            && field.field.get_proto().get_field_type()
-                != field_descriptor_proto::Type::TYPE_MESSAGE;
+            && !type_is_message_or_group(field.field.get_proto().get_field_type());
+
+        let converted = if let Some(t) = rust_type_override(&field, &elem, &customize) {
+            Some(t)
+        } else if field.is_oneof() {
+            None
+        } else if field.field.get_proto().get_label()
+            == field_descriptor_proto::Label::LABEL_REPEATED
+        {
+            match elem {
+                FieldElem::Message(FieldElemMessage {
+                    map_entry: Some(ref key_value),
+                    ..
+                }) => converted_wrapper_type(&key_value.1, &customize),
+                ref elem => converted_wrapper_type(elem, &customize),
+            }
+        } else {
+            converted_message_type(field.field.get_proto(), &customize)
+        };
+
+        // proto3 `optional` fields of non-message types track presence,
+        // so they get `has_` and `clear_` accessors like proto2 optional fields
+        let proto3_optional_scalar = field.field.get_proto().get_proto3_optional()
+            && !type_is_message_or_group(field.field.get_proto().get_field_type());
 
-        let default_expose_field = !field_may_have_custom_default_value;
-        let expose_field = customize.expose_fields.unwrap_or(default_expose_field);
+        let default_expose_field = !field_may_have_custom_default_value && !proto3_optional_scalar;
+        // converted fields are always public and have no accessors,
+        // overriding `expose_fields`, `generate_accessors` and `generate_getter`
+        let expose_field =
+            customize.expose_fields.unwrap_or(default_expose_field) || converted.is_some();
 
         let default_generate_accessors = !expose_field;
-        let generate_accessors = customize
+        let generate_accessors = (customize
             .generate_accessors
             .unwrap_or(default_generate_accessors)
-            || field.is_oneof();
+            || field.is_oneof())
+            && converted.is_none();
 
         let default_generate_getter = generate_accessors || field_may_have_custom_default_value;
-        let generate_getter =
-            customize.generate_getter.unwrap_or(default_generate_getter) || field.is_oneof();
+        let generate_getter = (customize.generate_getter.unwrap_or(default_generate_getter)
+            || field.is_oneof())
+            && converted.is_none();
 
         let kind = if field.field.get_proto().get_label()
             == field_descriptor_proto::Label::LABEL_REPEATED
@@ -623,6 +757,7 @@ impl<'a> FieldGen<'a> {
                     message: message.clone(),
                     key: key_value.0.clone(),
                     value: key_value.1.clone(),
+                    value_converted: converted,
                 }),
                 // regular repeated field
                 elem => FieldKind::Repeated(RepeatedField {
@@ -633,6 +768,7 @@ impl<'a> FieldGen<'a> {
                         .options
                         .get_or_default()
                         .get_packed(),
+                    converted,
                 }),
             }
         } else if let Some(oneof) = field.oneof() {
@@ -641,22 +777,29 @@ impl<'a> FieldGen<'a> {
             let flag = if field.message.scope.file_scope.syntax() == Syntax::PROTO3
                 && field.field.get_proto().get_field_type()
                     != field_descriptor_proto::Type::TYPE_MESSAGE
+                && !field.field.get_proto().get_proto3_optional()
             {
                 SingularFieldFlag::WithoutFlag
             } else {
                 let required = field.field.get_proto().get_label()
                     == field_descriptor_proto::Label::LABEL_REQUIRED;
-                let option_kind = match field.field.get_proto().get_field_type() {
-                    field_descriptor_proto::Type::TYPE_MESSAGE => OptionKind::MessageField,
-                    _ => OptionKind::Option,
-                };
+                let option_kind =
+                    if type_is_message_or_group(field.field.get_proto().get_field_type()) {
+                        OptionKind::MessageField
+                    } else {
+                        OptionKind::Option
+                    };
 
                 SingularFieldFlag::WithFlag {
                     required,
                     option_kind,
                 }
             };
-            FieldKind::Singular(SingularField { elem, flag })
+            FieldKind::Singular(SingularField {
+                elem,
+                flag,
+                converted,
+            })
         };
 
         FieldGen {
@@ -739,10 +882,16 @@ impl<'a> FieldGen<'a> {
         match self.kind {
             FieldKind::Repeated(ref repeated) => repeated.rust_type(reference),
             FieldKind::Map(MapField {
-                ref key, ref value, ..
+                ref key,
+                ref value,
+                ref value_converted,
+                ..
             }) => RustType::HashMap(
                 Box::new(key.rust_storage_elem_type(reference)),
-                Box::new(value.rust_storage_elem_type(reference)),
+                Box::new(match value_converted {
+                    Some(converted) => converted.clone(),
+                    None => value.rust_storage_elem_type(reference),
+                }),
             ),
             FieldKind::Singular(ref singular) => singular.rust_storage_type(reference),
             FieldKind::Oneof(..) => unreachable!(),
@@ -778,6 +927,10 @@ impl<'a> FieldGen<'a> {
     // for field `foo`, type of param of `fn set_foo(..)`
     fn set_xxx_param_type(&self, reference: &FileAndMod) -> RustType {
         match self.kind {
+            FieldKind::Singular(SingularField {
+                converted: Some(ref converted),
+                ..
+            }) => converted.clone(),
             FieldKind::Singular(SingularField { ref elem, .. })
             | FieldKind::Oneof(OneofField { ref elem, .. }) => {
                 elem.rust_set_xxx_param_type(reference)
@@ -960,7 +1113,7 @@ impl<'a> FieldGen<'a> {
     pub fn reconstruct_def(&self) -> String {
         let prefix = match (self.proto_field.field.get_proto().get_label(), self.syntax) {
             (field_descriptor_proto::Label::LABEL_REPEATED, _) => "repeated ",
-            (_, Syntax::PROTO3) => "",
+            (_, Syntax::PROTO3) if !self.proto_field.field.get_proto().get_proto3_optional() => "",
             (field_descriptor_proto::Label::LABEL_OPTIONAL, _) => "optional ",
             (field_descriptor_proto::Label::LABEL_REQUIRED, _) => "required ",
         };
@@ -1003,7 +1156,8 @@ impl<'a> FieldGen<'a> {
         match field_type_size(self.proto_type) {
             Some(data_size) => format!("{}", data_size + self.tag_size()),
             None => match self.proto_type {
-                field_descriptor_proto::Type::TYPE_MESSAGE => panic!("not a single-liner"),
+                field_descriptor_proto::Type::TYPE_MESSAGE
+                | field_descriptor_proto::Type::TYPE_GROUP => panic!("not a single-liner"),
                 field_descriptor_proto::Type::TYPE_BYTES => format!(
                     "{}::rt::bytes_size({}, &{})",
                     protobuf_crate_path(&self.customize),
@@ -1062,7 +1216,8 @@ impl<'a> FieldGen<'a> {
         };
 
         match self.proto_type {
-            field_descriptor_proto::Type::TYPE_MESSAGE => {
+            field_descriptor_proto::Type::TYPE_MESSAGE
+            | field_descriptor_proto::Type::TYPE_GROUP => {
                 let param_type = RustType::Ref(Box::new(
                     self.elem().rust_storage_elem_type(
                         &self
@@ -1073,9 +1228,16 @@ impl<'a> FieldGen<'a> {
                     ),
                 ));
 
+                let write_fn = match self.proto_type {
+                    field_descriptor_proto::Type::TYPE_GROUP => {
+                        "write_group_field_with_cached_size"
+                    }
+                    _ => "write_message_field_with_cached_size",
+                };
                 w.write_line(&format!(
-                    "{}::rt::write_message_field_with_cached_size({}, {}, {})?;",
+                    "{}::rt::{}({}, {}, {})?;",
                     protobuf_crate_path(&self.customize),
+                    write_fn,
                     self.proto_field.number(),
                     v.into_type(param_type, &self.customize).value,
                     os
@@ -1168,27 +1330,24 @@ impl<'a> FieldGen<'a> {
     }
 
     pub fn write_struct_field(&self, w: &mut CodeWriter) {
-        if self.proto_type == field_descriptor_proto::Type::TYPE_GROUP {
-            w.comment(&format!("{}: <group>", &self.rust_name));
-        } else {
-            w.all_documentation(self.info, &self.path);
-
-            self.write_serde_attr(w);
-            let vis = self.visibility();
-            w.field_decl_vis(
-                vis,
-                self.rust_name.get(),
-                &self
-                    .full_storage_type(
-                        &self
-                            .proto_field
-                            .message
-                            .scope
-                            .get_file_and_mod(self.customize.clone()),
-                    )
-                    .to_code(&self.customize),
-            );
-        }
+        w.all_documentation(self.info, &self.path);
+
+        self.write_serde_attr(w);
+        Customize::write_extra_attrs(w, &self.customize.extra_field_attrs);
+        let vis = self.visibility();
+        w.field_decl_vis(
+            vis,
+            self.rust_name.get(),
+            &self
+                .full_storage_type(
+                    &self
+                        .proto_field
+                        .message
+                        .scope
+                        .get_file_and_mod(self.customize.clone()),
+                )
+                .to_code(&self.customize),
+        );
     }
 
     fn write_serde_attr(&self, w: &mut CodeWriter) {
@@ -1215,6 +1374,7 @@ impl<'a> FieldGen<'a> {
             SingularField {
                 flag: SingularFieldFlag::WithFlag { option_kind, .. },
                 ref elem,
+                ..
             } => {
                 let var = "v";
                 let ref_prefix = match elem
@@ -1246,6 +1406,7 @@ impl<'a> FieldGen<'a> {
             SingularField {
                 flag: SingularFieldFlag::WithoutFlag,
                 ref elem,
+                ..
             } => match *elem {
                 FieldElem::Primitive(field_descriptor_proto::Type::TYPE_STRING, ..)
                 | FieldElem::Primitive(field_descriptor_proto::Type::TYPE_BYTES, ..) => {
@@ -1346,8 +1507,12 @@ impl<'a> FieldGen<'a> {
     }
 
     fn write_self_field_assign(&self, w: &mut CodeWriter, value: &str) {
-        let self_field = self.self_field();
-        w.write_line(&format!("{} = {};", self_field, value));
+        self.write_field_assign(w, "self", value);
+    }
+
+    // assign field of a message referenced by `obj` expression
+    fn write_field_assign(&self, w: &mut CodeWriter, obj: &str, value: &str) {
+        w.write_line(&format!("{}.{} = {};", obj, self.rust_name, value));
     }
 
     fn write_self_field_assign_some(&self, w: &mut CodeWriter, s: &SingularField, value: &str) {
@@ -1367,34 +1532,41 @@ impl<'a> FieldGen<'a> {
         }
     }
 
-    fn write_self_field_assign_value_singular(
+    fn write_field_assign_value_singular(
         &self,
         w: &mut CodeWriter,
+        obj: &str,
         s: &SingularField,
         value: &RustValueTyped,
     ) {
-        let SingularField { ref elem, ref flag } = s;
-        let converted = value.into_type(
-            elem.rust_storage_elem_type(
+        let SingularField {
+            ref elem, ref flag, ..
+        } = s;
+        let elem_type = match s.converted {
+            Some(ref converted) => converted.clone(),
+            None => elem.rust_storage_elem_type(
                 &self
                     .proto_field
                     .message
                     .scope
                     .get_file_and_mod(self.customize.clone()),
-            )
-            .clone(),
-            &self.customize,
-        );
+            ),
+        };
+        let converted = value.into_type(elem_type, &self.customize);
         let wrapped = match flag {
             SingularFieldFlag::WithoutFlag => converted.value,
+            // converted values are stored in `Option`
+            SingularFieldFlag::WithFlag { .. } if s.converted.is_some() => {
+                OptionKind::Option.wrap_value(&converted.value, &self.customize)
+            }
             SingularFieldFlag::WithFlag { option_kind, .. } => {
                 option_kind.wrap_value(&converted.value, &self.customize)
             }
         };
-        self.write_self_field_assign(w, &wrapped);
+        self.write_field_assign(w, obj, &wrapped);
     }
 
-    fn write_self_field_assign_value(&self, w: &mut CodeWriter, value: &RustValueTyped) {
+    fn write_field_assign_value(&self, w: &mut CodeWriter, obj: &str, value: &RustValueTyped) {
         match self.kind {
             FieldKind::Repeated(..) | FieldKind::Map(..) => {
                 let converted = value.into_type(
@@ -1407,10 +1579,10 @@ impl<'a> FieldGen<'a> {
                     ),
                     &self.customize,
                 );
-                self.write_self_field_assign(w, &converted.value);
+                self.write_field_assign(w, obj, &converted.value);
             }
             FieldKind::Singular(ref s) => {
-                self.write_self_field_assign_value_singular(w, s, value);
+                self.write_field_assign_value_singular(w, obj, s, value);
             }
             FieldKind::Oneof(..) => unreachable!(),
         }
@@ -1560,6 +1732,77 @@ impl<'a> FieldGen<'a> {
         format!("clear_{}", self.rust_name)
     }
 
+    // groups are terminated by end tag with the same field number,
+    // so `rt` functions reading groups take field number as first argument
+    fn group_field_number_arg(&self) -> String {
+        match self.proto_type {
+            field_descriptor_proto::Type::TYPE_GROUP => {
+                format!("{}, ", self.proto_field.number())
+            }
+            _ => String::new(),
+        }
+    }
+
+    // expression checking value `v` of message field stored as a different Rust type
+    // can be converted to an initialized message, `None` for other fields
+    pub fn converted_message_is_initialized(&self, v: &str) -> Option<String> {
+        match self.kind {
+            FieldKind::Singular(SingularField {
+                elem: FieldElem::Message(ref m),
+                converted: Some(..),
+                ..
+            })
+            | FieldKind::Repeated(RepeatedField {
+                elem: FieldElem::Message(ref m),
+                converted: Some(..),
+                ..
+            }) => Some(format!(
+                "{}::rt::converted_message_is_initialized::<{}, _>({})",
+                protobuf_crate_path(&self.customize),
+                m.rust_name_relative(&self.get_file_and_mod()),
+                v,
+            )),
+            _ => None,
+        }
+    }
+
+    // `ProtobufType` used to read `string` or `bytes` field stored as a different Rust type
+    fn converted_protobuf_type(&self) -> String {
+        ProtobufTypeGen::Primitive(self.proto_type, PrimitiveTypeVariant::Default)
+            .rust_type(&self.customize)
+    }
+
+    // view `string` or `bytes` value stored as a different Rust type as `&str` or `&[u8]`
+    fn converted_as_ref(&self, v: &str) -> String {
+        let target = match self.proto_type {
+            field_descriptor_proto::Type::TYPE_STRING => "str",
+            field_descriptor_proto::Type::TYPE_BYTES => "[u8]",
+            t => unreachable!("converted field of type {:?}", t),
+        };
+        format!("::std::convert::AsRef::<{}>::as_ref({})", target, v)
+    }
+
+    // `if` for singular `string` or `bytes` field stored as a different Rust type,
+    // callback is called with `&str` or `&[u8]` expression
+    fn write_if_converted_is_set<F>(&self, s: &SingularField, w: &mut CodeWriter, cb: F)
+    where
+        F: Fn(&str, &mut CodeWriter),
+    {
+        match s.flag {
+            SingularFieldFlag::WithFlag { .. } => {
+                w.if_let_stmt("Some(v)", &format!("{}.as_ref()", self.self_field()), |w| {
+                    cb(&self.converted_as_ref("v"), w);
+                });
+            }
+            SingularFieldFlag::WithoutFlag => {
+                let v = self.converted_as_ref(&format!("&{}", self.self_field()));
+                w.if_stmt(&format!("!{}.is_empty()", v), |w| {
+                    cb(&v, w);
+                });
+            }
+        }
+    }
+
     fn write_merge_from_field_message_string_bytes_repeated(
         &self,
         r: &RepeatedField,
@@ -1578,11 +1821,12 @@ impl<'a> FieldGen<'a> {
             _ => "",
         };
         w.write_line(&format!(
-            "{}::rt::read_repeated_{}{}_into{}(wire_type, is, &mut self.{})?;",
+            "{}::rt::read_repeated_{}{}_into{}({}wire_type, is, &mut self.{})?;",
             protobuf_crate_path(&self.customize),
             carllerche,
             type_name_for_fn,
             into_what_suffix,
+            self.group_field_number_arg(),
             self.rust_name,
         ));
     }
@@ -1612,12 +1856,13 @@ impl<'a> FieldGen<'a> {
         };
         let type_name_for_fn = protobuf_name(self.proto_type);
         w.write_line(&format!(
-            "{}::rt::read_{}_{}{}_{}(wire_type, is, &mut self.{})?;",
+            "{}::rt::read_{}_{}{}_{}({}wire_type, is, &mut self.{})?;",
             protobuf_crate_path(&self.customize),
             singular_or_proto3,
             carllerche,
             type_name_for_fn,
             suffix,
+            self.group_field_number_arg(),
             self.rust_name,
         ));
     }
@@ -1662,11 +1907,14 @@ impl<'a> FieldGen<'a> {
     fn write_merge_from_oneof(&self, o: &OneofField, wire_type_var: &str, w: &mut CodeWriter) {
         self.write_assert_wire_type(wire_type_var, w);
 
+        let read = match self.proto_type {
+            field_descriptor_proto::Type::TYPE_GROUP => {
+                format!("is.read_group({})", self.proto_field.number())
+            }
+            _ => self.proto_type.read("is", o.elem.primitive_type_variant()),
+        };
         let typed = RustValueTyped {
-            value: format!(
-                "{}?",
-                self.proto_type.read("is", o.elem.primitive_type_variant())
-            ),
+            value: format!("{}?", read),
             rust_type: self.full_storage_iter_elem_type(
                 &self
                     .proto_field
@@ -1700,14 +1948,12 @@ impl<'a> FieldGen<'a> {
 
     // Write `merge_from` part for this map field
     fn write_merge_from_map(&self, w: &mut CodeWriter) {
-        let &MapField {
-            ref key, ref value, ..
-        } = self.map();
+        let map = self.map();
         w.write_line(&format!(
             "{}::rt::read_map_into::<{}, {}>(wire_type, is, &mut {})?;",
             protobuf_crate_path(&self.customize),
-            key.lib_protobuf_type(&self.get_file_and_mod()),
-            value.lib_protobuf_type(&self.get_file_and_mod()),
+            map.key.lib_protobuf_type(&self.get_file_and_mod()),
+            map.value_lib_protobuf_type(&self.get_file_and_mod()),
             self.self_field()
         ));
     }
@@ -1720,9 +1966,32 @@ impl<'a> FieldGen<'a> {
         w: &mut CodeWriter,
     ) {
         match s.elem {
+            FieldElem::Message(ref m) if s.converted.is_some() => {
+                w.write_line(&format!(
+                    "{}::rt::read_singular_converted_message_into::<{}, _>({}, is, &mut self.{})?;",
+                    protobuf_crate_path(&self.customize),
+                    m.rust_name_relative(&self.get_file_and_mod()),
+                    wire_type_var,
+                    self.rust_name,
+                ));
+            }
             FieldElem::Message(..) => {
                 self.write_merge_from_field_message_string_bytes(w);
             }
+            FieldElem::Primitive(..) if s.converted.is_some() => {
+                let singular_or_proto3 = match s.flag {
+                    SingularFieldFlag::WithFlag { .. } => "singular",
+                    SingularFieldFlag::WithoutFlag => "singular_proto3",
+                };
+                w.write_line(&format!(
+                    "{}::rt::read_{}_converted_into::<{}, _>({}, is, &mut self.{})?;",
+                    protobuf_crate_path(&self.customize),
+                    singular_or_proto3,
+                    self.converted_protobuf_type(),
+                    wire_type_var,
+                    self.rust_name,
+                ));
+            }
             _ => {
                 self.write_assert_wire_type(wire_type_var, w);
                 let read_proc = format!(
@@ -1742,6 +2011,24 @@ impl<'a> FieldGen<'a> {
         };
 
         match field.elem {
+            FieldElem::Message(ref m) if field.converted.is_some() => {
+                w.write_line(&format!(
+                    "{}::rt::read_repeated_converted_message_into::<{}, _>({}, is, &mut self.{})?;",
+                    protobuf_crate_path(&self.customize),
+                    m.rust_name_relative(&self.get_file_and_mod()),
+                    wire_type_var,
+                    self.rust_name,
+                ));
+            }
+            FieldElem::Primitive(..) if field.converted.is_some() => {
+                w.write_line(&format!(
+                    "{}::rt::read_repeated_converted_into::<{}, _>({}, is, &mut self.{})?;",
+                    protobuf_crate_path(&self.customize),
+                    self.converted_protobuf_type(),
+                    wire_type_var,
+                    self.rust_name,
+                ));
+            }
             FieldElem::Message(..)
             | FieldElem::Primitive(field_descriptor_proto::Type::TYPE_STRING, ..)
             | FieldElem::Primitive(field_descriptor_proto::Type::TYPE_BYTES, ..) => {
@@ -1813,6 +2100,14 @@ impl<'a> FieldGen<'a> {
                     protobuf_crate_path(&self.customize),
                 ));
             }
+            field_descriptor_proto::Type::TYPE_GROUP => {
+                // start and end tags
+                let tag_size = self.tag_size() * 2;
+                w.write_line(&format!(
+                    "{} += {} + {}.compute_size();",
+                    sum_var, tag_size, item_var,
+                ));
+            }
             _ => {
                 w.write_line(&format!(
                     "{} += {};",
@@ -1825,11 +2120,67 @@ impl<'a> FieldGen<'a> {
 
     pub fn write_message_write_field(&self, w: &mut CodeWriter) {
         match self.kind {
+            FieldKind::Singular(SingularField {
+                elem: FieldElem::Message(ref m),
+                converted: Some(..),
+                ..
+            }) => {
+                w.if_let_stmt("Some(v)", &format!("{}.as_ref()", self.self_field()), |w| {
+                    w.write_line(&format!(
+                        "{}::rt::write_converted_message::<{}, _>({}, v, os)?;",
+                        protobuf_crate_path(&self.customize),
+                        m.rust_name_relative(&self.get_file_and_mod()),
+                        self.proto_field.number(),
+                    ));
+                });
+            }
+            FieldKind::Singular(
+                ref s @ SingularField {
+                    converted: Some(..),
+                    ..
+                },
+            ) => {
+                self.write_if_converted_is_set(s, w, |v, w| {
+                    w.write_line(&format!(
+                        "os.write_{}({}, {})?;",
+                        self.os_write_fn_suffix(),
+                        self.proto_field.number(),
+                        v
+                    ));
+                });
+            }
             FieldKind::Singular(ref s) => {
                 self.write_if_let_self_field_is_some(s, w, |v, w| {
                     self.write_write_element(w, "os", &v);
                 });
             }
+            FieldKind::Repeated(RepeatedField {
+                elem: FieldElem::Message(ref m),
+                converted: Some(..),
+                ..
+            }) => {
+                w.for_stmt(&format!("&{}", self.self_field()), "v", |w| {
+                    w.write_line(&format!(
+                        "{}::rt::write_converted_message::<{}, _>({}, v, os)?;",
+                        protobuf_crate_path(&self.customize),
+                        m.rust_name_relative(&self.get_file_and_mod()),
+                        self.proto_field.number(),
+                    ));
+                });
+            }
+            FieldKind::Repeated(RepeatedField {
+                converted: Some(..),
+                ..
+            }) => {
+                w.for_stmt(&format!("&{}", self.self_field()), "v", |w| {
+                    w.write_line(&format!(
+                        "os.write_{}({}, {})?;",
+                        self.os_write_fn_suffix(),
+                        self.proto_field.number(),
+                        self.converted_as_ref("v")
+                    ));
+                });
+            }
             FieldKind::Repeated(RepeatedField { packed: false, .. }) => {
                 self.write_for_self_field(w, "v", |w, v_type| {
                     let v = RustValueTyped {
@@ -1862,14 +2213,12 @@ impl<'a> FieldGen<'a> {
                     });
                 });
             }
-            FieldKind::Map(MapField {
-                ref key, ref value, ..
-            }) => {
+            FieldKind::Map(ref map) => {
                 w.write_line(&format!(
                     "{}::rt::write_map_with_cached_sizes::<{}, {}>({}, &{}, os)?;",
                     protobuf_crate_path(&self.customize),
-                    key.lib_protobuf_type(&self.get_file_and_mod()),
-                    value.lib_protobuf_type(&self.get_file_and_mod()),
+                    map.key.lib_protobuf_type(&self.get_file_and_mod()),
+                    map.value_lib_protobuf_type(&self.get_file_and_mod()),
                     self.proto_field.number(),
                     self.self_field()
                 ));
@@ -1880,6 +2229,38 @@ impl<'a> FieldGen<'a> {
 
     pub fn write_message_compute_field_size(&self, sum_var: &str, w: &mut CodeWriter) {
         match self.kind {
+            FieldKind::Singular(SingularField {
+                elem: FieldElem::Message(ref m),
+                converted: Some(..),
+                ..
+            }) => {
+                w.if_let_stmt("Some(v)", &format!("{}.as_ref()", self.self_field()), |w| {
+                    w.write_line(&format!(
+                        "{} += {}::rt::converted_message_size::<{}, _>({}, v);",
+                        sum_var,
+                        protobuf_crate_path(&self.customize),
+                        m.rust_name_relative(&self.get_file_and_mod()),
+                        self.proto_field.number(),
+                    ));
+                });
+            }
+            FieldKind::Singular(
+                ref s @ SingularField {
+                    converted: Some(..),
+                    ..
+                },
+            ) => {
+                self.write_if_converted_is_set(s, w, |v, w| {
+                    w.write_line(&format!(
+                        "{} += {}::rt::{}_size({}, {});",
+                        sum_var,
+                        protobuf_crate_path(&self.customize),
+                        self.os_write_fn_suffix(),
+                        self.proto_field.number(),
+                        v
+                    ));
+                });
+            }
             FieldKind::Singular(ref s) => {
                 self.write_if_let_self_field_is_some(s, w, |v, w| {
                     match field_type_size(self.proto_type) {
@@ -1893,6 +2274,36 @@ impl<'a> FieldGen<'a> {
                     };
                 });
             }
+            FieldKind::Repeated(RepeatedField {
+                elem: FieldElem::Message(ref m),
+                converted: Some(..),
+                ..
+            }) => {
+                w.for_stmt(&format!("&{}", self.self_field()), "v", |w| {
+                    w.write_line(&format!(
+                        "{} += {}::rt::converted_message_size::<{}, _>({}, v);",
+                        sum_var,
+                        protobuf_crate_path(&self.customize),
+                        m.rust_name_relative(&self.get_file_and_mod()),
+                        self.proto_field.number(),
+                    ));
+                });
+            }
+            FieldKind::Repeated(RepeatedField {
+                converted: Some(..),
+                ..
+            }) => {
+                w.for_stmt(&format!("&{}", self.self_field()), "v", |w| {
+                    w.write_line(&format!(
+                        "{} += {}::rt::{}_size({}, {});",
+                        sum_var,
+                        protobuf_crate_path(&self.customize),
+                        self.os_write_fn_suffix(),
+                        self.proto_field.number(),
+                        self.converted_as_ref("v")
+                    ));
+                });
+            }
             FieldKind::Repeated(RepeatedField { packed: false, .. }) => {
                 match field_type_size(self.proto_type) {
                     Some(s) => {
@@ -1912,15 +2323,13 @@ impl<'a> FieldGen<'a> {
                     }
                 };
             }
-            FieldKind::Map(MapField {
-                ref key, ref value, ..
-            }) => {
+            FieldKind::Map(ref map) => {
                 w.write_line(&format!(
                     "{} += {}::rt::compute_map_size::<{}, {}>({}, &{});",
                     sum_var,
                     protobuf_crate_path(&self.customize),
-                    key.lib_protobuf_type(&self.get_file_and_mod()),
-                    value.lib_protobuf_type(&self.get_file_and_mod()),
+                    map.key.lib_protobuf_type(&self.get_file_and_mod()),
+                    map.value_lib_protobuf_type(&self.get_file_and_mod()),
                     self.proto_field.number(),
                     self.self_field()
                 ));
@@ -2199,42 +2608,138 @@ impl<'a> FieldGen<'a> {
                 set_xxx_param_type.to_code(&self.customize)
             ),
             |w| {
-                let value_typed = RustValueTyped {
-                    value: "v".to_owned(),
-                    rust_type: set_xxx_param_type.clone(),
-                };
-                match self.kind {
-                    FieldKind::Oneof(ref oneof) => {
-                        let v = set_xxx_param_type.into_target(
-                            &oneof.rust_type(
-                                &self
-                                    .proto_field
-                                    .message
-                                    .scope
-                                    .get_file_and_mod(self.customize.clone()),
-                            ),
-                            "v",
-                            &self.customize,
-                        );
-                        w.write_line(&format!(
-                            "self.{} = ::std::option::Option::Some({}({}))",
-                            oneof.oneof_field_name,
-                            oneof.variant_path(
-                                &self
-                                    .proto_field
-                                    .message
-                                    .scope
-                                    .rust_path_to_file()
-                                    .clone()
-                                    .into_path()
-                            ),
-                            v
-                        ));
-                    }
-                    _ => {
-                        self.write_self_field_assign_value(w, &value_typed);
-                    }
-                }
+                self.write_field_set(w, "self", &set_xxx_param_type);
+            },
+        );
+    }
+
+    // assign `v` of type `set_xxx_param_type` to the field of a message referenced by `obj`
+    fn write_field_set(&self, w: &mut CodeWriter, obj: &str, set_xxx_param_type: &RustType) {
+        let value_typed = RustValueTyped {
+            value: "v".to_owned(),
+            rust_type: set_xxx_param_type.clone(),
+        };
+        match self.kind {
+            FieldKind::Oneof(ref oneof) => {
+                let v = set_xxx_param_type.into_target(
+                    &oneof.rust_type(
+                        &self
+                            .proto_field
+                            .message
+                            .scope
+                            .get_file_and_mod(self.customize.clone()),
+                    ),
+                    "v",
+                    &self.customize,
+                );
+                w.write_line(&format!(
+                    "{}.{} = ::std::option::Option::Some({}({}));",
+                    obj,
+                    oneof.oneof_field_name,
+                    oneof.variant_path(
+                        &self
+                            .proto_field
+                            .message
+                            .scope
+                            .rust_path_to_file()
+                            .clone()
+                            .into_path()
+                    ),
+                    v
+                ));
+            }
+            _ => {
+                self.write_field_assign_value(w, obj, &value_typed);
+            }
+        }
+    }
+
+    // for singular or oneof message field, type of the message
+    pub(crate) fn builder_message_type(&self) -> Option<RustType> {
+        match self.kind {
+            FieldKind::Singular(..) | FieldKind::Oneof(..) => {}
+            FieldKind::Repeated(..) | FieldKind::Map(..) => return None,
+        }
+        match self.set_xxx_param_type(
+            &self
+                .proto_field
+                .message
+                .scope
+                .get_file_and_mod(self.customize.clone()),
+        ) {
+            t @ RustType::Message(..) => Some(t),
+            _ => None,
+        }
+    }
+
+    // method `fn foo(mut self, v: ..) -> Self` of message builder
+    pub(crate) fn write_builder_method(&self, w: &mut CodeWriter, name: &RustIdent) {
+        let set_xxx_param_type = self.set_xxx_param_type(
+            &self
+                .proto_field
+                .message
+                .scope
+                .get_file_and_mod(self.customize.clone()),
+        );
+        let param_type = set_xxx_param_type.to_code(&self.customize);
+        if set_xxx_param_type.is_copy() {
+            w.pub_fn(
+                &format!("{}(mut self, v: {}) -> Self", name, param_type),
+                |w| {
+                    self.write_field_set(w, "self.message", &set_xxx_param_type);
+                    w.write_line("self");
+                },
+            );
+        } else {
+            // Accept `&str` for strings, `vec![..]` for repeated fields,
+            // builders for messages etc.
+            w.pub_fn(
+                &format!(
+                    "{}<V: ::std::convert::Into<{}>>(mut self, v: V) -> Self",
+                    name, param_type
+                ),
+                |w| {
+                    w.write_line(&format!("let v: {} = v.into();", param_type));
+                    self.write_field_set(w, "self.message", &set_xxx_param_type);
+                    w.write_line("self");
+                },
+            );
+        }
+    }
+
+    // method `fn foo_with(self, f: impl FnOnce(Builder) -> Builder) -> Self`
+    // of message builder for message field, delegating to method `setter`
+    pub(crate) fn write_builder_closure_method(
+        &self,
+        w: &mut CodeWriter,
+        name: &str,
+        setter: &RustIdent,
+    ) {
+        let message_type = self
+            .builder_message_type()
+            .expect("message field")
+            .to_code(&self.customize);
+        let buildable = format!(
+            "<{} as {}::Buildable>::Builder",
+            message_type,
+            protobuf_crate_path(&self.customize)
+        );
+        // Sub-message may be generated without a builder. The bound is higher-ranked,
+        // so it is checked when the method is called, not when it is defined.
+        w.pub_fn(
+            &format!(
+                "{}<F>(self, f: F) -> Self where for<'a> {}: {}::Buildable, F: ::std::ops::FnOnce({}) -> {}",
+                name,
+                message_type,
+                protobuf_crate_path(&self.customize),
+                buildable,
+                buildable,
+            ),
+            |w| {
+                w.write_line(&format!(
+                    "self.{}(f(::std::default::Default::default()))",
+                    setter
+                ));
             },
         );
     }
@@ -2440,6 +2945,7 @@ impl<'a> FieldGen<'a> {
             SingularField {
                 ref elem,
                 flag: SingularFieldFlag::WithFlag { option_kind, .. },
+                ..
             } => {
                 if !elem.is_copy() {
                     w.write_line(
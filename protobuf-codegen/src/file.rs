@@ -1,3 +1,7 @@
+use protobuf::descriptor::FileDescriptorProto;
+use protobuf::reflect::FileDescriptor;
+
+use crate::extern_path::extern_file_descriptor_expr;
 use crate::inside::protobuf_crate_path;
 use crate::rust;
 use crate::rust_name::RustIdent;
@@ -50,12 +54,69 @@ pub fn proto_name_to_rs(proto_file_path: &str) -> String {
     format!("{}.rs", proto_path_to_rust_mod(proto_file_path))
 }
 
-pub(crate) fn proto_path_to_fn_file_descriptor(
-    proto_path: &str,
+/// Modules for protobuf package when `mods_by_package` option is set,
+/// e. g. `com`, `example`, `v1` for `com.example.v1`.
+pub(crate) fn proto_package_to_rust_mods(package: &str) -> Vec<RustIdent> {
+    package
+        .split('.')
+        .filter(|c| !c.is_empty())
+        .map(|c| {
+            if rust::is_rust_keyword(c) {
+                RustIdent::from(format!("{}_pb", c))
+            } else {
+                RustIdent::from(c)
+            }
+        })
+        .collect()
+}
+
+/// Path of generated file relative to output directory.
+pub(crate) fn proto_file_to_rs(file: &FileDescriptorProto, customize: &Customize) -> String {
+    let mut r = String::new();
+    if customize.mods_by_package.unwrap_or(false) {
+        for m in proto_package_to_rust_mods(file.get_package()) {
+            r.push_str(m.get());
+            r.push('/');
+        }
+    }
+    r.push_str(&proto_name_to_rs(file.get_name()));
+    r
+}
+
+/// Path from module generated for a file of `current_package`
+/// to module generated for `target` file.
+pub(crate) fn proto_file_to_rust_mod_path(
+    current_package: &str,
+    target: &FileDescriptorProto,
     customize: &Customize,
 ) -> RustPath {
+    let mut r = RustPath::super_path();
+    if customize.mods_by_package.unwrap_or(false) {
+        let current = proto_package_to_rust_mods(current_package);
+        let target = proto_package_to_rust_mods(target.get_package());
+        let common = current
+            .iter()
+            .zip(&target)
+            .take_while(|(a, b)| a == b)
+            .count();
+        for _ in common..current.len() {
+            r = r.append_ident(RustIdent::super_ident());
+        }
+        for m in &target[common..] {
+            r = r.append_ident(m.clone());
+        }
+    }
+    r.append_ident(proto_path_to_rust_mod(target.get_name()))
+}
+
+/// Expression for `FileDescriptor` of dependency `dep` of a file of `current_package`.
+pub(crate) fn dep_file_descriptor_expr(
+    current_package: &str,
+    dep: &FileDescriptor,
+    customize: &Customize,
+) -> String {
     let protobuf_crate = protobuf_crate_path(customize);
-    match proto_path {
+    let fn_file_descriptor = match dep.proto().get_name() {
         "rustproto.proto" => protobuf_crate.append("rustproto::file_descriptor".into()),
         "google/protobuf/descriptor.proto" => {
             protobuf_crate.append("descriptor::file_descriptor".into())
@@ -63,17 +124,35 @@ pub(crate) fn proto_path_to_fn_file_descriptor(
         s if WELL_KNOWN_TYPES_PROTO_FILE_FULL_NAMES.contains(&s) => protobuf_crate
             .append("well_known_types::file_descriptors".into())
             .append_ident(proto_path_to_rust_mod(s)),
-        s => RustPath::super_path()
-            .append_ident(proto_path_to_rust_mod(s))
-            .append_ident("file_descriptor".into()),
-    }
+        _ => {
+            if let Some(expr) = extern_file_descriptor_expr(dep, customize) {
+                return expr;
+            }
+            proto_file_to_rust_mod_path(current_package, dep.proto(), customize)
+                .append_ident("file_descriptor".into())
+        }
+    };
+    format!("{}()", fn_file_descriptor)
 }
 
 #[cfg(test)]
 mod test {
 
+    use protobuf::descriptor::FileDescriptorProto;
+
+    use super::proto_file_to_rs;
+    use super::proto_file_to_rust_mod_path;
     use super::proto_path_to_rust_mod;
     use crate::rust_name::RustIdent;
+    use crate::rust_name::RustPath;
+    use crate::Customize;
+
+    fn file(name: &str, package: &str) -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.set_name(name.to_owned());
+        file.set_package(package.to_owned());
+        file
+    }
 
     #[test]
     fn test_mod_path_proto_ext() {
@@ -112,4 +191,45 @@ mod test {
             proto_path_to_rust_mod("foo\\bar\\baz.proto"),
         )
     }
+
+    #[test]
+    fn test_mod_path_between_files() {
+        let customize = Customize::default();
+        assert_eq!(
+            RustPath::from("super::bar"),
+            proto_file_to_rust_mod_path("a.b", &file("x/bar.proto", "c.d"), &customize),
+        );
+        assert_eq!(
+            "bar.rs",
+            proto_file_to_rs(&file("x/bar.proto", "c.d"), &customize)
+        );
+    }
+
+    #[test]
+    fn test_mod_path_between_files_by_package() {
+        let customize = Customize {
+            mods_by_package: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            RustPath::from("super::bar"),
+            proto_file_to_rust_mod_path("a.b", &file("bar.proto", "a.b"), &customize),
+        );
+        assert_eq!(
+            RustPath::from("super::super::super::c::type_pb::bar"),
+            proto_file_to_rust_mod_path("a.b", &file("bar.proto", "c.type"), &customize),
+        );
+        assert_eq!(
+            RustPath::from("super::super::bar"),
+            proto_file_to_rust_mod_path("a.b", &file("bar.proto", "a"), &customize),
+        );
+        assert_eq!(
+            RustPath::from("super::a::b::bar"),
+            proto_file_to_rust_mod_path("", &file("bar.proto", "a.b"), &customize),
+        );
+        assert_eq!(
+            "c/type_pb/bar.rs",
+            proto_file_to_rs(&file("x/bar.proto", "c.type"), &customize)
+        );
+    }
 }
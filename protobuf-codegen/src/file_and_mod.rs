@@ -3,6 +3,7 @@ use crate::rust_name::RustRelativePath;
 
 pub(crate) struct FileAndMod {
     pub file: String,
+    pub package: String,
     pub relative_mod: RustRelativePath,
     pub customize: Customize,
 }
@@ -4,7 +4,10 @@ extern crate proc_macro;
 extern crate protobuf;
 
 use std::collections::hash_map::HashMap;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fmt::Write as FmtWrite;
+use std::fs;
 use std::fs::File;
 use std::io;
 use std::io::Write;
@@ -18,7 +21,9 @@ pub mod case_convert;
 mod compiler_plugin;
 mod customize;
 mod enums;
+mod eq_hash_ord;
 mod extensions;
+mod extern_path;
 mod field;
 mod file;
 pub(crate) mod file_and_mod;
@@ -32,6 +37,7 @@ mod protobuf_rel_path;
 mod rust_name;
 mod rust_types_values;
 mod serde;
+mod service;
 mod well_known_types;
 
 pub(crate) mod rust;
@@ -48,17 +54,21 @@ use self::code_writer::CodeWriter;
 use self::enums::*;
 use self::extensions::*;
 use self::message::*;
+use self::service::write_services;
 #[doc(hidden)]
 pub use amend_io_error_util::amend_io_error;
 use scope::FileScope;
 use scope::RootScope;
 
-use crate::file::{proto_path_to_fn_file_descriptor, proto_path_to_rust_mod};
+use crate::file::{
+    dep_file_descriptor_expr, proto_file_to_rs, proto_package_to_rust_mods, proto_path_to_rust_mod,
+};
 use inside::protobuf_crate_path;
 pub use protobuf_abs_path::ProtobufAbsolutePath;
 pub use protobuf_ident::ProtobufIdent;
 pub use protobuf_rel_path::ProtobufRelativePath;
 
+use crate::extern_path::is_extern_file;
 use crate::rust::EXPR_VEC_NEW;
 use crate::scope::WithScope;
 use crate::well_known_types::gen_well_known_types_mod;
@@ -87,6 +97,7 @@ fn escape_byte(s: &mut String, b: u8) {
 
 fn write_file_descriptor(
     file_descriptor: &FileDescriptor,
+    files_map: &HashMap<&Path, &FileDescriptor>,
     customize: &Customize,
     w: &mut CodeWriter,
 ) {
@@ -111,9 +122,16 @@ fn write_file_descriptor(
                 |w| {
                     w.write_line(&format!("let mut deps = {};", EXPR_VEC_NEW));
                     for f in &file_descriptor.proto().dependency {
+                        let dep = files_map
+                            .get(Path::new(f))
+                            .unwrap_or_else(|| panic!("dependency not found: {}", f));
                         w.write_line(&format!(
-                            "deps.push({}());",
-                            proto_path_to_fn_file_descriptor(f, customize)
+                            "deps.push({});",
+                            dep_file_descriptor_expr(
+                                file_descriptor.proto().get_package(),
+                                dep,
+                                customize
+                            )
                         ));
                     }
 
@@ -159,7 +177,12 @@ fn write_file_descriptor(
     );
 }
 
-fn write_file_descriptor_data(file: &FileDescriptor, customize: &Customize, w: &mut CodeWriter) {
+fn write_file_descriptor_data(
+    file: &FileDescriptor,
+    files_map: &HashMap<&Path, &FileDescriptor>,
+    customize: &Customize,
+    w: &mut CodeWriter,
+) {
     let fdp_bytes = file.proto().write_to_bytes().unwrap();
     w.write_line("static file_descriptor_proto_data: &'static [u8] = b\"\\");
     w.indented(|w| {
@@ -214,7 +237,7 @@ fn write_file_descriptor_data(file: &FileDescriptor, customize: &Customize, w: &
         },
     );
     w.write_line("");
-    write_file_descriptor(file, &customize, w);
+    write_file_descriptor(file, files_map, &customize, w);
 }
 
 pub(crate) struct FileIndex {
@@ -246,16 +269,16 @@ impl FileIndex {
 struct GenFileResult {
     compiler_plugin_result: compiler_plugin::GenResult,
     mod_name: String,
+    package: String,
 }
 
 fn gen_file(
     file_descriptor: &FileDescriptor,
-    _files_map: &HashMap<&Path, &FileDescriptor>,
+    files_map: &HashMap<&Path, &FileDescriptor>,
     root_scope: &RootScope,
     customize: &Customize,
     parser: &str,
 ) -> GenFileResult {
-    // TODO: use it
     let mut customize = customize.clone();
     // options specified in invocation have precedence over options specified in file
     customize.update_with(&customize_from_rustproto_for_file(
@@ -264,6 +287,7 @@ fn gen_file(
 
     let file_scope = FileScope { file_descriptor };
     let scope = file_scope.to_scope();
+    customize.update_with_by_name(&scope.protobuf_absolute_path());
     let lite_runtime = customize.lite_runtime.unwrap_or_else(|| {
         file_descriptor
             .proto()
@@ -354,18 +378,21 @@ fn gen_file(
 
         write_extensions(file_descriptor, &root_scope, &mut w, &customize);
 
+        write_services(file_descriptor, &root_scope, &mut w, &customize);
+
         if !lite_runtime {
             w.write_line("");
-            write_file_descriptor_data(file_descriptor, &customize, &mut w);
+            write_file_descriptor_data(file_descriptor, files_map, &customize, &mut w);
         }
     }
 
     GenFileResult {
         compiler_plugin_result: compiler_plugin::GenResult {
-            name: proto_name_to_rs(file_descriptor.proto().get_name()),
+            name: proto_file_to_rs(file_descriptor.proto(), &customize),
             content: v,
         },
         mod_name: proto_path_to_rust_mod(file_descriptor.proto().get_name()).into_string(),
+        package: file_descriptor.proto().get_package().to_owned(),
     }
 }
 
@@ -384,6 +411,67 @@ fn gen_mod_rs(mods: &[String]) -> compiler_plugin::GenResult {
     }
 }
 
+/// `mod.rs` files for `mods_by_package` option, one in each package directory.
+///
+/// Each `mod.rs` declares modules of nested packages and modules of files of the package,
+/// and re-exports contents of the files, so the package module contains all their types.
+fn gen_mods_by_package(files: &[(String, String)]) -> Vec<compiler_plugin::GenResult> {
+    // package modules -> (nested package modules, file modules)
+    let mut dirs: BTreeMap<Vec<String>, (BTreeSet<String>, Vec<String>)> = BTreeMap::new();
+    dirs.insert(Vec::new(), Default::default());
+    for (package, mod_name) in files {
+        let package: Vec<String> = proto_package_to_rust_mods(package)
+            .into_iter()
+            .map(|m| m.into_string())
+            .collect();
+        for i in 0..package.len() {
+            dirs.entry(package[..i].to_vec())
+                .or_default()
+                .0
+                .insert(package[i].clone());
+        }
+        dirs.entry(package).or_default().1.push(mod_name.clone());
+    }
+
+    dirs.into_iter()
+        .map(|(dir, (packages, files))| {
+            if let Some(m) = files.iter().find(|m| packages.contains(*m)) {
+                panic!(
+                    "module `{}` generated for file conflicts with nested package module in `{}`",
+                    m,
+                    dir.join("::")
+                );
+            }
+
+            let mut v = Vec::new();
+            let mut w = CodeWriter::new(&mut v);
+            w.comment("@generated");
+            w.write_line("");
+            for m in packages.iter().chain(&files) {
+                w.write_line(&format!("pub mod {};", m));
+            }
+            if !files.is_empty() {
+                w.write_line("");
+                w.comment("names defined in several files (like `file_descriptor`) are ambiguous,");
+                w.comment("these are accessible only with file modules");
+                for m in &files {
+                    w.write_line("#[allow(unknown_lints, ambiguous_glob_reexports)]");
+                    w.write_line(&format!("pub use self::{}::*;", m));
+                }
+            }
+            drop(w);
+
+            let mut name = String::new();
+            for d in &dir {
+                name.push_str(d);
+                name.push('/');
+            }
+            name.push_str("mod.rs");
+            compiler_plugin::GenResult { name, content: v }
+        })
+        .collect()
+}
+
 // This function is also used externally by cargo plugin
 // https://github.com/plietar/rust-protobuf-build
 // So be careful changing its signature.
@@ -397,6 +485,7 @@ pub fn gen(
 
     let root_scope = RootScope {
         file_descriptors: &file_descriptors,
+        customize,
     };
 
     let mut results: Vec<compiler_plugin::GenResult> = Vec::new();
@@ -413,16 +502,22 @@ pub fn gen(
             file_name,
             files_map.keys()
         ));
+        if is_extern_file(file, customize) {
+            continue;
+        }
         let gen_file_result = gen_file(file, &files_map, &root_scope, customize, parser);
         results.push(gen_file_result.compiler_plugin_result);
-        mods.push(gen_file_result.mod_name);
+        mods.push((gen_file_result.package, gen_file_result.mod_name));
     }
 
     if customize.inside_protobuf.unwrap_or(false) {
         results.push(gen_well_known_types_mod(&file_descriptors));
     }
 
-    if customize.gen_mod_rs.unwrap_or(false) {
+    if customize.mods_by_package.unwrap_or(false) {
+        results.extend(gen_mods_by_package(&mods));
+    } else if customize.gen_mod_rs.unwrap_or(false) {
+        let mods: Vec<String> = mods.into_iter().map(|(_, m)| m).collect();
         results.push(gen_mod_rs(&mods));
     }
 
@@ -458,6 +553,10 @@ pub fn gen_and_write(
     for r in &results {
         let mut file_path = out_dir.to_owned();
         file_path.push(&r.name);
+        if let Some(dir) = file_path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| amend_io_error(e, format!("failed to create {:?}", dir)))?;
+        }
         let mut file_writer = File::create(&file_path)
             .map_err(|e| amend_io_error(e, format!("failed to create {:?}", file_path)))?;
         file_writer
@@ -7,6 +7,7 @@ use super::enums::*;
 use super::field::*;
 use super::rust_types_values::*;
 use crate::case_convert::snake_case;
+use crate::eq_hash_ord::eq_hash_ord_blocker;
 use crate::file_and_mod::FileAndMod;
 use crate::inside::protobuf_crate_path;
 use crate::oneof::OneofGen;
@@ -25,6 +26,9 @@ use protobuf::reflect::FileDescriptor;
 use protobuf::reflect::MessageDescriptor;
 use std::fmt;
 
+/// Methods of generated message builder, fields with these names get `field_` prefix.
+const BUILDER_RESERVED_NAMES: &[&str] = &["build", "try_build"];
+
 /// Protobuf message Rust type name
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct RustTypeMessage(pub RustIdentWithPath);
@@ -62,6 +66,8 @@ pub(crate) struct MessageGen<'a> {
     type_name: RustIdentWithPath,
     pub fields: Vec<FieldGen<'a>>,
     pub lite_runtime: bool,
+    /// Implement `Eq`, `Hash` and `Ord`
+    pub eq_hash_ord: bool,
     customize: Customize,
     path: &'a [i32],
     info: Option<&'a SourceCodeInfo>,
@@ -85,6 +91,22 @@ impl<'a> MessageGen<'a> {
         customize.update_with(&customize_from_rustproto_for_message(
             message.message.get_proto().options.get_or_default(),
         ));
+        customize.update_with_by_name(&message.name_absolute());
+        // `rust_type` is a field option, it is not inherited by fields
+        customize.rust_type = None;
+
+        let lite_runtime = customize.lite_runtime.unwrap_or_else(|| {
+            message
+                .get_file_descriptor()
+                .options
+                .get_or_default()
+                .get_optimize_for()
+                == file_options::OptimizeMode::LITE_RUNTIME
+        });
+        if lite_runtime {
+            // JSON mapping needs reflection which is not available in lite runtime
+            customize.serde_json_mapping = Some(false);
+        }
 
         static FIELD_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
         let field_number = *FIELD_NUMBER.get(|| {
@@ -105,14 +127,29 @@ impl<'a> MessageGen<'a> {
                 FieldGen::parse(field, root_scope, &customize, path, info)
             })
             .collect();
-        let lite_runtime = customize.lite_runtime.unwrap_or_else(|| {
-            message
-                .get_file_descriptor()
-                .options
-                .get_or_default()
-                .get_optimize_for()
-                == file_options::OptimizeMode::LITE_RUNTIME
-        });
+
+        let eq_hash_ord = customize.derive_eq_hash_ord.unwrap_or(false)
+            && match eq_hash_ord_blocker(&message_descriptor, root_scope.customize) {
+                None => true,
+                // Option set for this message by name is an error,
+                // option set for the whole file or package is skipped silently.
+                Some(reason) => {
+                    let explicit = customize
+                        .by_exact_name(&message.name_absolute())
+                        .filter_map(|c| c.derive_eq_hash_ord)
+                        .last()
+                        == Some(true);
+                    if explicit {
+                        panic!(
+                            "cannot implement `Eq`, `Hash` and `Ord` for message `{}`: {}",
+                            message_descriptor.full_name(),
+                            reason
+                        );
+                    }
+                    false
+                }
+            };
+
         MessageGen {
             message_descriptor,
             file_descriptor,
@@ -122,6 +159,7 @@ impl<'a> MessageGen<'a> {
             type_name: message.rust_name().to_path(),
             fields,
             lite_runtime,
+            eq_hash_ord,
             customize,
             path,
             info,
@@ -140,11 +178,46 @@ impl<'a> MessageGen<'a> {
         self.customize.expose_oneof.unwrap_or(true)
     }
 
+    fn gen_builders(&self) -> bool {
+        self.customize.gen_builders.unwrap_or(false)
+    }
+
+    // `_` is appended while the name clashes with a message or enum in the same scope,
+    // e. g. builder of `Foo` is `FooBuilder_` when there's also message `FooBuilder`
+    fn builder_name(&self) -> String {
+        let taken: Vec<String> = self
+            .message
+            .scope
+            .get_messages_and_enums()
+            .iter()
+            .map(|m| m.rust_name().to_string())
+            .collect();
+        let mut name = format!("{}Builder", self.message.rust_name());
+        while taken.contains(&name) {
+            name.push('_');
+        }
+        name
+    }
+
     fn oneofs(&'a self) -> Vec<OneofGen<'a>> {
+        static ONEOF_DECL_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
+        let oneof_decl_number = *ONEOF_DECL_NUMBER.get(|| {
+            protobuf::reflect::MessageDescriptor::for_type::<DescriptorProto>()
+                .get_field_by_name("oneof_decl")
+                .expect("`oneof_decl` must exist")
+                .get_proto()
+                .get_number()
+        });
+
         self.message
             .oneofs()
             .into_iter()
-            .map(|oneof| OneofGen::parse(self, oneof, &self.customize))
+            .enumerate()
+            .map(|(id, oneof)| {
+                let mut path = self.path.to_vec();
+                path.extend_from_slice(&[oneof_decl_number, id as i32]);
+                OneofGen::parse(self, oneof, &self.customize, path, self.info)
+            })
             .collect()
     }
 
@@ -161,7 +234,7 @@ impl<'a> MessageGen<'a> {
     fn message_fields(&'a self) -> Vec<&'a FieldGen> {
         self.fields
             .iter()
-            .filter(|f| f.proto_type == field_descriptor_proto::Type::TYPE_MESSAGE)
+            .filter(|f| type_is_message_or_group(f.proto_type))
             .collect()
     }
 
@@ -175,29 +248,12 @@ impl<'a> MessageGen<'a> {
             .collect()
     }
 
-    fn fields_except_group(&'a self) -> Vec<&'a FieldGen> {
-        self.fields
-            .iter()
-            .filter(|f| f.proto_type != field_descriptor_proto::Type::TYPE_GROUP)
-            .collect()
-    }
-
-    fn fields_except_oneof_and_group(&'a self) -> Vec<&'a FieldGen> {
-        self.fields
-            .iter()
-            .filter(|f| match f.kind {
-                FieldKind::Oneof(..) => false,
-                _ => f.proto_type != field_descriptor_proto::Type::TYPE_GROUP,
-            })
-            .collect()
-    }
-
     fn write_match_each_oneof_variant<F>(&self, w: &mut CodeWriter, cb: F)
     where
         F: Fn(&mut CodeWriter, &OneofVariantGen, &str, &RustType),
     {
         for oneof in self.oneofs() {
-            let variants = oneof.variants_except_group();
+            let variants = oneof.variants();
             if variants.is_empty() {
                 // Special case because
                 // https://github.com/rust-lang/rust/issues/50642
@@ -240,7 +296,7 @@ impl<'a> MessageGen<'a> {
         );
         w.def_fn(&sig, |w| {
             // To have access to its methods but not polute the name space.
-            for f in self.fields_except_oneof_and_group() {
+            for f in self.fields_except_oneof() {
                 f.write_message_write_field(w);
             }
             self.write_match_each_oneof_variant(w, |w, variant, v, v_type| {
@@ -274,7 +330,7 @@ impl<'a> MessageGen<'a> {
         w.stmt_block(
             &format!("static instance: {} = {}", self.type_name, self.type_name),
             |w| {
-                for f in &self.fields_except_oneof_and_group() {
+                for f in &self.fields_except_oneof() {
                     w.field_entry(
                         f.rust_name.get(),
                         &f.kind
@@ -307,11 +363,17 @@ impl<'a> MessageGen<'a> {
         w.def_fn(
             &format!("default_instance() -> &'static {}", self.type_name),
             |w| {
-                let has_map_field = self.fields.iter().any(|f| match f.kind {
+                // default values of these fields are not constant expressions
+                let has_non_const_field = self.fields.iter().any(|f| match f.kind {
                     FieldKind::Map(..) => true,
+                    FieldKind::Singular(SingularField {
+                        converted: Some(..),
+                        flag: SingularFieldFlag::WithoutFlag,
+                        ..
+                    }) => true,
                     _ => false,
                 });
-                if has_map_field {
+                if has_non_const_field {
                     self.write_default_instance_lazy(w)
                 } else {
                     self.write_default_instance_static(w)
@@ -330,7 +392,7 @@ impl<'a> MessageGen<'a> {
         w.def_fn("compute_size(&self) -> u32", |w| {
             // To have access to its methods but not polute the name space.
             w.write_line("let mut my_size = 0;");
-            for field in self.fields_except_oneof_and_group() {
+            for field in self.fields_except_oneof() {
                 field.write_message_compute_field_size("my_size", w);
             }
             self.write_match_each_oneof_variant(w, |w, variant, v, vtype| {
@@ -346,7 +408,7 @@ impl<'a> MessageGen<'a> {
     }
 
     fn write_field_accessors(&self, w: &mut CodeWriter) {
-        for f in self.fields_except_group() {
+        for f in &self.fields {
             f.write_message_single_field_accessors(w);
         }
     }
@@ -358,6 +420,13 @@ impl<'a> MessageGen<'a> {
                 w.write_line("::std::default::Default::default()");
             });
 
+            if self.gen_builders() {
+                w.write_line("");
+                w.pub_fn(&format!("builder() -> {}", self.builder_name()), |w| {
+                    w.write_line("::std::default::Default::default()");
+                });
+            }
+
             self.write_field_accessors(w);
             w.write_line("");
             self.write_generated_message_descriptor_data(w);
@@ -392,14 +461,14 @@ impl<'a> MessageGen<'a> {
             w.while_block("!is.eof()?", |w| {
                 w.write_line(&format!("let (field_number, wire_type) = is.read_tag_unpack()?;"));
                 w.match_block("field_number", |w| {
-                    for f in &self.fields_except_group() {
+                    for f in &self.fields {
                         let number = f.proto_field.number();
                         w.case_block(number.to_string(), |w| {
                             f.write_merge_from_field("wire_type", w);
                         });
                     }
                     w.case_block("_", |w| {
-                        w.write_line(&format!("{}::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;", protobuf_crate_path(&self.customize)));
+                        w.write_line(&format!("{}::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;", protobuf_crate_path(&self.customize)));
                     });
                 });
             });
@@ -435,7 +504,7 @@ impl<'a> MessageGen<'a> {
             Visibility::Path(self.message.get_scope().rust_path_to_file().to_reverse()),
             &sig,
             |w| {
-                let fields = self.fields_except_group();
+                let fields = &self.fields;
                 w.write_line(&format!("let mut fields = {};", EXPR_VEC_NEW));
                 for field in fields {
                     field.write_descriptor_field("fields", w);
@@ -471,6 +540,17 @@ impl<'a> MessageGen<'a> {
                     continue;
                 }
 
+                // value which cannot be converted to message makes message not initialized,
+                // so serialization fails before computing size
+                if let Some(cond) = f.converted_message_is_initialized("v") {
+                    f.write_for_self_field(w, "v", |w, _t| {
+                        w.if_stmt(&format!("!{}", cond), |w| {
+                            w.write_line("return false;");
+                        });
+                    });
+                    continue;
+                }
+
                 // TODO:
                 // if message is declared in this file and has no message fields,
                 // we could skip the check here
@@ -550,7 +630,7 @@ impl<'a> MessageGen<'a> {
             &format!("{}", self.type_name),
             |w| {
                 w.def_fn("clear(&mut self)", |w| {
-                    for f in self.fields_except_group() {
+                    for f in &self.fields {
                         f.write_clear(w);
                     }
                     w.write_line("self.unknown_fields.clear();");
@@ -570,12 +650,17 @@ impl<'a> MessageGen<'a> {
         if self.supports_derive_partial_eq() {
             derive.push("PartialEq");
         }
+        if self.eq_hash_ord {
+            derive.push("Eq");
+        }
         derive.extend(&["Clone", "Default"]);
         if self.lite_runtime {
             derive.push("Debug");
         }
+        self.customize.add_extra_derives(&mut derive);
         w.derive(&derive);
         serde::write_serde_attr(w, &self.customize, "derive(Serialize, Deserialize)");
+        Customize::write_extra_attrs(w, &self.customize.extra_type_attrs);
         w.pub_struct(&format!("{}", self.type_name), |w| {
             if !self.fields_except_oneof().is_empty() {
                 w.comment("message fields");
@@ -586,6 +671,7 @@ impl<'a> MessageGen<'a> {
             if !self.oneofs().is_empty() {
                 w.comment("message oneof groups");
                 for oneof in self.oneofs() {
+                    oneof.write_documentation(w);
                     let vis = match self.expose_oneof() {
                         true => Visibility::Public,
                         false => Visibility::Default,
@@ -642,6 +728,173 @@ impl<'a> MessageGen<'a> {
         );
     }
 
+    fn write_impl_hash(&self, w: &mut CodeWriter) {
+        w.impl_for_block("::std::hash::Hash", &format!("{}", self.type_name), |w| {
+            w.def_fn("hash<H: ::std::hash::Hasher>(&self, state: &mut H)", |w| {
+                for field in self.fields_except_oneof() {
+                    match field.kind {
+                        FieldKind::Map(..) => w.write_line(&format!(
+                            "{}::rt::hash_map(&self.{}, state);",
+                            protobuf_crate_path(&self.customize),
+                            field.rust_name
+                        )),
+                        _ => w.write_line(&format!(
+                            "::std::hash::Hash::hash(&self.{}, state);",
+                            field.rust_name
+                        )),
+                    }
+                }
+                for oneof in self.oneofs() {
+                    w.write_line(&format!(
+                        "::std::hash::Hash::hash(&self.{}, state);",
+                        oneof.oneof.field_name()
+                    ));
+                }
+                w.write_line("::std::hash::Hash::hash(&self.unknown_fields, state);");
+            });
+        });
+    }
+
+    fn write_impl_partial_ord(&self, w: &mut CodeWriter) {
+        w.impl_for_block(
+            "::std::cmp::PartialOrd",
+            &format!("{}", self.type_name),
+            |w| {
+                w.def_fn(
+                    "partial_cmp(&self, other: &Self) -> ::std::option::Option<::std::cmp::Ordering>",
+                    |w| {
+                        w.write_line("::std::option::Option::Some(::std::cmp::Ord::cmp(self, other))");
+                    },
+                );
+            },
+        );
+    }
+
+    fn write_impl_ord(&self, w: &mut CodeWriter) {
+        let mut comparisons = Vec::new();
+        for field in self.fields_except_oneof() {
+            comparisons.push(match field.kind {
+                FieldKind::Map(..) => format!(
+                    "{}::rt::cmp_map(&self.{}, &other.{})",
+                    protobuf_crate_path(&self.customize),
+                    field.rust_name,
+                    field.rust_name
+                ),
+                _ => format!(
+                    "::std::cmp::Ord::cmp(&self.{}, &other.{})",
+                    field.rust_name, field.rust_name
+                ),
+            });
+        }
+        for oneof in self.oneofs() {
+            comparisons.push(format!(
+                "::std::cmp::Ord::cmp(&self.{}, &other.{})",
+                oneof.oneof.field_name(),
+                oneof.oneof.field_name()
+            ));
+        }
+        comparisons
+            .push("::std::cmp::Ord::cmp(&self.unknown_fields, &other.unknown_fields)".to_owned());
+
+        w.impl_for_block("::std::cmp::Ord", &format!("{}", self.type_name), |w| {
+            w.def_fn("cmp(&self, other: &Self) -> ::std::cmp::Ordering", |w| {
+                // fields are compared in declaration order, unknown fields last
+                w.write_line(&comparisons[0]);
+                w.indented(|w| {
+                    for c in &comparisons[1..] {
+                        w.write_line(&format!(".then_with(|| {})", c));
+                    }
+                });
+            });
+        });
+    }
+
+    // builder method names of fields, `field_` prefix is added to names clashing with
+    // builder own methods
+    fn builder_method_names(&self) -> Vec<RustIdent> {
+        self.fields
+            .iter()
+            .map(|f| {
+                if BUILDER_RESERVED_NAMES.contains(&f.rust_name.get()) {
+                    RustIdent::new(&format!("field_{}", f.rust_name))
+                } else {
+                    f.rust_name.clone()
+                }
+            })
+            .collect()
+    }
+
+    fn write_builder(&self, w: &mut CodeWriter) {
+        let builder_name = self.builder_name();
+        let names = self.builder_method_names();
+
+        w.write_line(&format!(
+            "/// Builder of [`{}`] message, created with `{}::builder()`.",
+            self.type_name, self.type_name
+        ));
+        w.derive(&["Clone", "Default", "Debug"]);
+        w.pub_struct(&builder_name, |w| {
+            w.field_decl("message", &format!("{}", self.type_name));
+        });
+        w.write_line("");
+        w.impl_self_block(&builder_name, |w| {
+            for (field, name) in self.fields.iter().zip(&names) {
+                field.write_builder_method(w, name);
+                w.write_line("");
+
+                if field.builder_message_type().is_some() {
+                    let closure_name = format!("{}_with", name);
+                    let clash = BUILDER_RESERVED_NAMES.contains(&closure_name.as_str())
+                        || names.iter().any(|n| n.get() == closure_name);
+                    if !clash {
+                        field.write_builder_closure_method(w, &closure_name, name);
+                        w.write_line("");
+                    }
+                }
+            }
+            w.write_line("/// Construct the message, required fields are not checked.");
+            w.pub_fn(&format!("build(self) -> {}", self.type_name), |w| {
+                w.write_line("self.message");
+            });
+            w.write_line("");
+            w.write_line(
+                "/// Construct the message, return an error if required fields are not set.",
+            );
+            w.pub_fn(
+                &format!(
+                    "try_build(self) -> {}::ProtobufResult<{}>",
+                    protobuf_crate_path(&self.customize),
+                    self.type_name
+                ),
+                |w| {
+                    w.write_line(&format!(
+                        "{}::Message::check_initialized(&self.message)?;",
+                        protobuf_crate_path(&self.customize)
+                    ));
+                    w.write_line("::std::result::Result::Ok(self.message)");
+                },
+            );
+        });
+        w.write_line("");
+        w.impl_for_block(
+            &format!("::std::convert::From<{}>", builder_name),
+            &format!("{}", self.type_name),
+            |w| {
+                w.def_fn(&format!("from(builder: {}) -> Self", builder_name), |w| {
+                    w.write_line("builder.message");
+                });
+            },
+        );
+        w.write_line("");
+        w.impl_for_block(
+            &format!("{}::Buildable", protobuf_crate_path(&self.customize)),
+            &format!("{}", self.type_name),
+            |w| {
+                w.write_line(&format!("type Builder = {};", builder_name));
+            },
+        );
+    }
+
     pub fn write(&self, w: &mut CodeWriter) {
         w.all_documentation(self.info, self.path);
         self.write_struct(w);
@@ -654,6 +907,15 @@ impl<'a> MessageGen<'a> {
             self.write_dummy_impl_partial_eq(w);
         }
 
+        if self.eq_hash_ord {
+            w.write_line("");
+            self.write_impl_hash(w);
+            w.write_line("");
+            self.write_impl_partial_ord(w);
+            w.write_line("");
+            self.write_impl_ord(w);
+        }
+
         w.write_line("");
         self.write_impl_self(w);
         w.write_line("");
@@ -666,6 +928,11 @@ impl<'a> MessageGen<'a> {
         }
         w.write_line("");
         self.write_impl_value(w);
+        serde::write_serde_impls_for_message(w, &self.customize, &format!("{}", self.type_name));
+        if self.gen_builders() {
+            w.write_line("");
+            self.write_builder(w);
+        }
 
         let mod_name = message_name_to_nested_mod_name(&self.message.message.get_name());
 
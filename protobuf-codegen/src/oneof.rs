@@ -2,6 +2,7 @@
 
 use crate::code_writer::CodeWriter;
 use crate::customize::Customize;
+use crate::field::type_is_message_or_group;
 use crate::field::FieldElem;
 use crate::field::FieldGen;
 use crate::file_and_mod::FileAndMod;
@@ -14,7 +15,7 @@ use crate::scope::WithScope;
 use crate::scope::{FieldWithContext, OneofVariantWithContext};
 use crate::scope::{OneofWithContext, RootScope};
 use crate::{serde, ProtobufAbsolutePath};
-use protobuf::descriptor::field_descriptor_proto;
+use protobuf::descriptor::SourceCodeInfo;
 use std::collections::HashSet;
 
 // oneof one { ... }
@@ -39,9 +40,7 @@ impl<'a> OneofField<'a> {
         let mut visited_messages = HashSet::new();
         let mut fields = vec![field.clone()];
         while let Some(field) = fields.pop() {
-            if field.field.get_proto().get_field_type()
-                == field_descriptor_proto::Type::TYPE_MESSAGE
-            {
+            if type_is_message_or_group(field.field.get_proto().get_field_type()) {
                 let message_name =
                     ProtobufAbsolutePath::from(field.field.get_proto().get_type_name());
                 if !visited_messages.insert(message_name.clone()) {
@@ -158,6 +157,8 @@ pub(crate) struct OneofGen<'a> {
     pub oneof: OneofWithContext<'a>,
     lite_runtime: bool,
     customize: Customize,
+    path: Vec<i32>,
+    info: Option<&'a SourceCodeInfo>,
 }
 
 impl<'a> OneofGen<'a> {
@@ -165,24 +166,33 @@ impl<'a> OneofGen<'a> {
         message: &'a MessageGen,
         oneof: OneofWithContext<'a>,
         customize: &Customize,
+        path: Vec<i32>,
+        info: Option<&'a SourceCodeInfo>,
     ) -> OneofGen<'a> {
         OneofGen {
             message,
             oneof,
             lite_runtime: message.lite_runtime,
             customize: customize.clone(),
+            path,
+            info,
         }
     }
 
+    // comments of `oneof` declaration
+    pub fn write_documentation(&self, w: &mut CodeWriter) {
+        w.all_documentation(self.info, &self.path);
+    }
+
     pub fn type_name_relative(&self, source: &RustPath) -> RustIdentWithPath {
         make_path(source, &self.oneof.rust_name())
     }
 
-    pub fn variants_except_group(&'a self) -> Vec<OneofVariantGen<'a>> {
+    pub fn variants(&'a self) -> Vec<OneofVariantGen<'a>> {
         self.oneof
             .variants()
             .into_iter()
-            .filter_map(|v| {
+            .map(|v| {
                 let field = self
                     .message
                     .fields
@@ -190,15 +200,7 @@ impl<'a> OneofGen<'a> {
                     .filter(|f| f.proto_field.name() == v.field.get_name())
                     .next()
                     .expect(&format!("field not found by name: {}", v.field.get_name()));
-                match field.proto_type {
-                    field_descriptor_proto::Type::TYPE_GROUP => None,
-                    _ => Some(OneofVariantGen::parse(
-                        self,
-                        v,
-                        field,
-                        self.message.root_scope,
-                    )),
-                }
+                OneofVariantGen::parse(self, v, field, self.message.root_scope)
             })
             .collect()
     }
@@ -231,11 +233,17 @@ impl<'a> OneofGen<'a> {
     }
 
     fn write_enum(&self, w: &mut CodeWriter) {
-        let derive = vec!["Clone", "PartialEq", "Debug"];
+        self.write_documentation(w);
+        let mut derive = vec!["Clone", "PartialEq", "Debug"];
+        if self.message.eq_hash_ord {
+            derive.extend(&["Eq", "Hash", "PartialOrd", "Ord"]);
+        }
+        self.customize.add_extra_derives(&mut derive);
         w.derive(&derive);
         serde::write_serde_attr(w, &self.customize, "derive(Serialize, Deserialize)");
         w.pub_enum(&self.oneof.rust_name().ident.to_string(), |w| {
-            for variant in self.variants_except_group() {
+            for variant in self.variants() {
+                w.all_documentation(variant.field.info, &variant.field.path);
                 w.write_line(&format!(
                     "{}({}),",
                     variant.field.rust_name,
@@ -1,6 +1,8 @@
 use std::cmp;
 
 use crate::customize::Customize;
+use crate::extern_path::extern_rust_name;
+use crate::file::proto_file_to_rust_mod_path;
 use crate::file_and_mod::FileAndMod;
 use crate::inside::protobuf_crate_path;
 use crate::message::RustTypeMessage;
@@ -49,8 +51,8 @@ pub(crate) enum RustType {
     Bytes,
     // chars::Chars
     Chars,
-    // group
-    Group,
+    // type not defined by protobuf, e.g. `::chrono::DateTime<::chrono::Utc>`
+    External(String),
 }
 
 impl RustType {
@@ -87,9 +89,9 @@ impl RustType {
                 protobuf_crate_path(customize),
                 name
             ),
-            RustType::Group => format!("<group>"),
             RustType::Bytes => format!("::bytes::Bytes"),
             RustType::Chars => format!("{}::Chars", protobuf_crate_path(customize)),
+            RustType::External(ref name) => format!("{}", name),
         }
     }
 }
@@ -224,6 +226,9 @@ impl RustType {
                 name,
                 default
             ),
+            RustType::External(..) if !const_expr => {
+                "::std::default::Default::default()".to_string()
+            }
             _ => panic!("cannot create default value for: {:?}", self),
         }
     }
@@ -253,7 +258,8 @@ impl RustType {
             | RustType::Float(..)
             | RustType::Int(..)
             | RustType::Enum(..)
-            | RustType::EnumOrUnknown(..) => {
+            | RustType::EnumOrUnknown(..)
+            | RustType::External(..) => {
                 format!("{} = {}", v, self.default_value(customize, false))
             }
             ref ty => panic!("cannot clear type: {:?}", ty),
@@ -537,13 +543,20 @@ pub(crate) fn message_or_enum_to_rust_relative(
             protobuf_crate_path(&current.customize),
             message_or_enum.rust_name_to_file()
         ))
+    } else if let Some(rust_name) = extern_rust_name(message_or_enum, &current.customize) {
+        // Type generated elsewhere, e. g. in a dependency crate
+        rust_name
     } else {
         current
             .relative_mod
             .to_reverse()
             .into_path()
-            .append_ident(RustIdent::super_ident())
-            .append_with_ident(message_or_enum.rust_name_with_file())
+            .append(proto_file_to_rust_mod_path(
+                &current.package,
+                message_or_enum.get_file_descriptor(),
+                &current.customize,
+            ))
+            .append_with_ident(message_or_enum.rust_name_to_file())
     }
 }
 
@@ -1,6 +1,5 @@
 use crate::customize::Customize;
 use crate::field::rust_field_name_for_protobuf_field_name;
-use crate::file::proto_path_to_rust_mod;
 use crate::file_and_mod::FileAndMod;
 use crate::map::map_entry;
 use crate::message::message_name_to_nested_mod_name;
@@ -24,6 +23,8 @@ use protobuf::reflect::OneofDescriptor;
 
 pub(crate) struct RootScope<'a> {
     pub file_descriptors: &'a [FileDescriptor],
+    /// Options passed to codegen invocation.
+    pub customize: &'a Customize,
 }
 
 impl<'a> RootScope<'a> {
@@ -283,6 +284,12 @@ impl<'a> Scope<'a> {
                 .proto()
                 .get_name()
                 .to_owned(),
+            package: self
+                .file_scope
+                .file_descriptor
+                .proto()
+                .get_package()
+                .to_owned(),
             relative_mod: self.rust_path_to_file(),
             customize,
         }
@@ -336,15 +343,6 @@ pub(crate) trait WithScope<'a> {
             .into_path()
             .with_ident(self.rust_name())
     }
-
-    // fully-qualified name of this type
-    fn rust_name_with_file(&self) -> RustIdentWithPath {
-        let mut r = self.rust_name_to_file();
-        r.prepend_ident(proto_path_to_rust_mod(
-            self.get_scope().get_file_descriptor().get_name(),
-        ));
-        r
-    }
 }
 
 #[derive(Clone, Debug)]
@@ -523,8 +521,9 @@ pub(crate) struct FieldWithContext<'a> {
 }
 
 impl<'a> FieldWithContext<'a> {
+    /// Field is a member of a oneof (synthetic oneofs of proto3 `optional` fields are ignored).
     pub fn is_oneof(&self) -> bool {
-        self.field.get_proto().has_oneof_index()
+        self.field.containing_oneof().is_some()
     }
 
     pub fn oneof(&self) -> Option<OneofWithContext<'a>> {
@@ -1,9 +1,23 @@
 use crate::code_writer::CodeWriter;
 use crate::customize::Customize;
+use crate::inside::protobuf_crate_path;
+
+/// Serde traits are implemented following protobuf JSON mapping rather than derived.
+fn json_mapping(customize: &Customize) -> bool {
+    customize.serde_derive.unwrap_or(false) && customize.serde_json_mapping.unwrap_or(false)
+}
+
+fn write_cfg(w: &mut CodeWriter, customize: &Customize) {
+    if let Some(ref cfg) = customize.serde_derive_cfg {
+        w.write_line(&format!("#[cfg({})]", cfg));
+    }
+}
 
 /// Write serde attr according to specified codegen option.
+///
+/// Nothing is written when serde traits are implemented following JSON mapping.
 pub fn write_serde_attr(w: &mut CodeWriter, customize: &Customize, attr: &str) {
-    if customize.serde_derive.unwrap_or(false) {
+    if customize.serde_derive.unwrap_or(false) && !json_mapping(customize) {
         if let Some(ref cfg) = customize.serde_derive_cfg {
             w.write_line(&format!("#[cfg_attr({}, {})]", cfg, attr));
         } else {
@@ -11,3 +25,64 @@ pub fn write_serde_attr(w: &mut CodeWriter, customize: &Customize, attr: &str) {
         }
     }
 }
+
+/// Write `Serialize` and `Deserialize` implementations delegating
+/// to given functions of `protobuf::json::serde` module
+/// when `serde_json_mapping` option is enabled.
+fn write_serde_impls(
+    w: &mut CodeWriter,
+    customize: &Customize,
+    type_name: &str,
+    serialize_fn: &str,
+    deserialize_fn: &str,
+) {
+    if !json_mapping(customize) {
+        return;
+    }
+
+    let protobuf_crate = protobuf_crate_path(customize);
+
+    w.write_line("");
+    write_cfg(w, customize);
+    w.impl_for_block("::serde::Serialize", type_name, |w| {
+        w.def_fn(
+            "serialize<S: ::serde::Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>",
+            |w| {
+                w.write_line(&format!(
+                    "{}::json::serde::{}(self, serializer)",
+                    protobuf_crate, serialize_fn
+                ));
+            },
+        );
+    });
+
+    w.write_line("");
+    write_cfg(w, customize);
+    w.impl_args_for_block(&["'de"], "::serde::Deserialize<'de>", type_name, |w| {
+        w.def_fn(
+            "deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error>",
+            |w| {
+                w.write_line(&format!(
+                    "{}::json::serde::{}(deserializer)",
+                    protobuf_crate, deserialize_fn
+                ));
+            },
+        );
+    });
+}
+
+/// Write serde implementations for a message following JSON mapping if requested.
+pub fn write_serde_impls_for_message(w: &mut CodeWriter, customize: &Customize, type_name: &str) {
+    write_serde_impls(w, customize, type_name, "serialize", "deserialize");
+}
+
+/// Write serde implementations for an enum following JSON mapping if requested.
+pub fn write_serde_impls_for_enum(w: &mut CodeWriter, customize: &Customize, type_name: &str) {
+    write_serde_impls(
+        w,
+        customize,
+        type_name,
+        "serialize_enum",
+        "deserialize_enum",
+    );
+}
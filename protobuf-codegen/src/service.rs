@@ -0,0 +1,415 @@
+use protobuf::descriptor::*;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::Message;
+
+use crate::case_convert::snake_case;
+use crate::code_writer::CodeWriter;
+use crate::customize::Customize;
+use crate::file_and_mod::FileAndMod;
+use crate::inside::protobuf_crate_path;
+use crate::protobuf_abs_path::ProtobufAbsolutePath;
+use crate::rust;
+use crate::rust_name::RustIdent;
+use crate::rust_name::RustIdentWithPath;
+use crate::rust_name::RustRelativePath;
+use crate::rust_types_values::type_name_to_rust_relative;
+use crate::scope::FileScope;
+use crate::scope::RootScope;
+use crate::scope::WithScope;
+
+/// Names of client methods which are not RPCs.
+const CLIENT_RESERVED_NAMES: &[&str] = &["new", "transport"];
+
+fn field_number<M: Message>(name: &str) -> i32 {
+    MessageDescriptor::for_type::<M>()
+        .get_field_by_name(name)
+        .expect("field must exist")
+        .get_proto()
+        .get_number()
+}
+
+struct MethodGen<'a> {
+    proto: &'a MethodDescriptorProto,
+    rust_name: RustIdent,
+    service_full_name: &'a str,
+    file: &'a FileDescriptor,
+    root_scope: &'a RootScope<'a>,
+    customize: &'a Customize,
+}
+
+impl<'a> MethodGen<'a> {
+    fn full_name(&self) -> String {
+        format!("{}.{}", self.service_full_name, self.proto.get_name())
+    }
+
+    fn message_type(&self, type_name: &str) -> RustIdentWithPath {
+        type_name_to_rust_relative(
+            &ProtobufAbsolutePath::from(type_name),
+            &FileAndMod {
+                file: self.file.proto().get_name().to_owned(),
+                package: self.file.proto().get_package().to_owned(),
+                relative_mod: RustRelativePath::default(),
+                customize: self.customize.clone(),
+            },
+            self.root_scope,
+        )
+    }
+
+    fn input_type(&self) -> RustIdentWithPath {
+        self.message_type(self.proto.get_input_type())
+    }
+
+    fn output_type(&self) -> RustIdentWithPath {
+        self.message_type(self.proto.get_output_type())
+    }
+}
+
+fn is_unary(proto: &MethodDescriptorProto) -> bool {
+    !proto.get_client_streaming() && !proto.get_server_streaming()
+}
+
+/// Rust name of a method before de-duplication.
+fn method_rust_name(proto: &MethodDescriptorProto) -> String {
+    let name = snake_case(proto.get_name());
+    if rust::is_rust_keyword(&name) || CLIENT_RESERVED_NAMES.contains(&name.as_str()) {
+        format!("method_{}", name)
+    } else {
+        name
+    }
+}
+
+struct ServiceGen<'a> {
+    proto: &'a ServiceDescriptorProto,
+    full_name: String,
+    // Rust names of top-level messages and enums, and of other services of the file
+    taken_names: &'a [String],
+    file: &'a FileDescriptor,
+    root_scope: &'a RootScope<'a>,
+    customize: &'a Customize,
+    path: Vec<i32>,
+}
+
+impl<'a> ServiceGen<'a> {
+    /// Name of generated item, `_` is appended while it clashes
+    /// with a message, an enum or another service.
+    fn item_name(&self, suffix: &str) -> String {
+        let mut name = format!("{}{}", self.proto.get_name(), suffix);
+        while self.taken_names.contains(&name) {
+            name.push('_');
+        }
+        name
+    }
+
+    fn trait_name(&self, asynchronous: bool) -> String {
+        self.item_name(if asynchronous { "Async" } else { "" })
+    }
+
+    fn methods(&self) -> Vec<(usize, MethodGen<'_>)> {
+        let mut taken: Vec<String> = Vec::new();
+        self.proto
+            .method
+            .iter()
+            .enumerate()
+            .filter(|(_, proto)| is_unary(proto))
+            .map(|(i, proto)| {
+                // e. g. `GetFoo` and `get_foo`
+                let mut name = method_rust_name(proto);
+                while taken.contains(&name) {
+                    name.push('_');
+                }
+                taken.push(name.clone());
+                (
+                    i,
+                    MethodGen {
+                        proto,
+                        rust_name: RustIdent::new(&name),
+                        service_full_name: &self.full_name,
+                        file: self.file,
+                        root_scope: self.root_scope,
+                        customize: self.customize,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn method_path(&self, index: usize) -> Vec<i32> {
+        static METHOD_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
+        let method_number = *METHOD_NUMBER.get(|| field_number::<ServiceDescriptorProto>("method"));
+
+        let mut path = self.path.clone();
+        path.push(method_number);
+        path.push(index as i32);
+        path
+    }
+
+    fn source_code_info(&self) -> Option<&SourceCodeInfo> {
+        self.file.proto().source_code_info.as_ref()
+    }
+
+    fn service_path(&self) -> String {
+        format!("{}::service", protobuf_crate_path(self.customize))
+    }
+
+    fn write_trait(&self, w: &mut CodeWriter, asynchronous: bool) {
+        let service = self.service_path();
+        w.all_documentation(self.source_code_info(), &self.path);
+        w.pub_trait_extend(
+            &self.trait_name(asynchronous),
+            "::std::marker::Send + ::std::marker::Sync",
+            |w| {
+                for (i, (index, method)) in self.methods().into_iter().enumerate() {
+                    if i != 0 {
+                        w.write_line("");
+                    }
+                    w.all_documentation(self.source_code_info(), &self.method_path(index));
+                    if asynchronous {
+                        w.fn_def(&format!(
+                            "{}<'a>(&'a self, request: {}) -> {}::ServiceFuture<'a, {}>",
+                            method.rust_name,
+                            method.input_type(),
+                            service,
+                            method.output_type(),
+                        ));
+                    } else {
+                        w.fn_def(&format!(
+                            "{}(&self, request: {}) -> {}::ServiceResult<{}>",
+                            method.rust_name,
+                            method.input_type(),
+                            service,
+                            method.output_type(),
+                        ));
+                    }
+                }
+            },
+        );
+    }
+
+    fn write_client(&self, w: &mut CodeWriter, asynchronous: bool) {
+        let service = self.service_path();
+        let (name, transport) = if asynchronous {
+            (self.item_name("AsyncClient"), "AsyncServiceTransport")
+        } else {
+            (self.item_name("Client"), "ServiceTransport")
+        };
+
+        w.write_line(&format!("/// Client of `{}` service.", self.full_name));
+        w.derive(&["Debug", "Clone"]);
+        w.pub_struct(&format!("{}<T>", name), |w| {
+            w.field_decl("transport", "T");
+        });
+        w.write_line("");
+        w.expr_block(
+            &format!("impl<T: {}::{}> {}<T>", service, transport, name),
+            |w| {
+                w.write_line("/// Create a client which invokes methods using given transport.");
+                w.pub_fn(&format!("new(transport: T) -> {}<T>", name), |w| {
+                    w.write_line(&format!("{} {{ transport }}", name));
+                });
+                w.write_line("");
+                w.write_line("/// Transport used by this client.");
+                w.pub_fn("transport(&self) -> &T", |w| {
+                    w.write_line("&self.transport");
+                });
+                for (index, method) in self.methods() {
+                    w.write_line("");
+                    w.all_documentation(self.source_code_info(), &self.method_path(index));
+                    if asynchronous {
+                        w.pub_fn(
+                            &format!(
+                                "{}<'a>(&'a self, request: &{}) -> {}::ServiceFuture<'a, {}>",
+                                method.rust_name,
+                                method.input_type(),
+                                service,
+                                method.output_type(),
+                            ),
+                            |w| {
+                                w.write_line(&format!(
+                                    "{}::call_unary_async(&self.transport, \"{}\", request)",
+                                    service,
+                                    method.full_name(),
+                                ));
+                            },
+                        );
+                    } else {
+                        w.pub_fn(
+                            &format!(
+                                "{}(&self, request: &{}) -> {}::ServiceResult<{}>",
+                                method.rust_name,
+                                method.input_type(),
+                                service,
+                                method.output_type(),
+                            ),
+                            |w| {
+                                w.write_line(&format!(
+                                    "{}::call_unary(&self.transport, \"{}\", request)",
+                                    service,
+                                    method.full_name(),
+                                ));
+                            },
+                        );
+                    }
+                }
+            },
+        );
+    }
+
+    fn write_dispatcher(&self, w: &mut CodeWriter, asynchronous: bool) {
+        let service = self.service_path();
+        let trait_name = self.trait_name(asynchronous);
+        let (name, dispatcher) = if asynchronous {
+            (self.item_name("AsyncDispatcher"), "AsyncServiceDispatcher")
+        } else {
+            (self.item_name("Dispatcher"), "ServiceDispatcher")
+        };
+        let methods = self.methods();
+        let request = if methods.is_empty() {
+            "_request"
+        } else {
+            "request"
+        };
+
+        w.write_line(&format!(
+            "/// Routes `{}` requests to `{}` implementation.",
+            self.full_name, trait_name
+        ));
+        w.derive(&["Debug", "Clone"]);
+        w.pub_struct(&format!("{}<S>", name), |w| {
+            w.field_decl("service", "S");
+        });
+        w.write_line("");
+        w.expr_block(&format!("impl<S: {}> {}<S>", trait_name, name), |w| {
+            w.write_line("/// Create a dispatcher for given service implementation.");
+            w.pub_fn(&format!("new(service: S) -> {}<S>", name), |w| {
+                w.write_line(&format!("{} {{ service }}", name));
+            });
+            w.write_line("");
+            w.write_line("/// Service implementation.");
+            w.pub_fn("service(&self) -> &S", |w| {
+                w.write_line("&self.service");
+            });
+        });
+        w.write_line("");
+        w.impl_args_for_block(
+            &[&format!("S: {}", trait_name)],
+            &format!("{}::{}", service, dispatcher),
+            &format!("{}<S>", name),
+            |w| {
+                w.def_fn("service_name(&self) -> &str", |w| {
+                    w.write_line(&format!("\"{}\"", self.full_name));
+                });
+                w.write_line("");
+                let sig = if asynchronous {
+                    format!(
+                        "dispatch<'a>(&'a self, method: &'a str, {}: &'a [u8]) -> {}::ServiceFuture<'a, ::std::vec::Vec<u8>>",
+                        request, service,
+                    )
+                } else {
+                    format!(
+                        "dispatch(&self, method: &str, {}: &[u8]) -> {}::ServiceResult<::std::vec::Vec<u8>>",
+                        request, service,
+                    )
+                };
+                w.def_fn(&sig, |w| {
+                    w.match_expr("method", |w| {
+                        for (_, method) in &methods {
+                            if asynchronous {
+                                w.case_expr(
+                                    &format!("\"{}\"", method.full_name()),
+                                    &format!(
+                                        "{}::dispatch_unary_async(request, move |r| self.service.{}(r))",
+                                        service,
+                                        method.rust_name,
+                                    ),
+                                );
+                            } else {
+                                w.case_expr(
+                                    &format!("\"{}\"", method.full_name()),
+                                    &format!(
+                                        "{}::dispatch_unary(request, |r| self.service.{}(r))",
+                                        service,
+                                        method.rust_name,
+                                    ),
+                                );
+                            }
+                        }
+                        let unknown = format!(
+                            "Err({}::ServiceError::UnknownMethod(method.to_owned()))",
+                            service
+                        );
+                        if asynchronous {
+                            w.case_expr("_", &format!("{}::ready({})", service, unknown));
+                        } else {
+                            w.case_expr("_", &unknown);
+                        }
+                    });
+                });
+            },
+        );
+    }
+
+    fn write(&self, w: &mut CodeWriter) {
+        let mut variants = Vec::new();
+        if self.customize.gen_services.unwrap_or(true) {
+            variants.push(false);
+        }
+        if self.customize.gen_async_services.unwrap_or(false) {
+            variants.push(true);
+        }
+
+        for asynchronous in variants {
+            w.write_line("");
+            self.write_trait(w, asynchronous);
+            w.write_line("");
+            self.write_client(w, asynchronous);
+            w.write_line("");
+            self.write_dispatcher(w, asynchronous);
+        }
+    }
+}
+
+pub(crate) fn write_services(
+    file: &FileDescriptor,
+    root_scope: &RootScope,
+    w: &mut CodeWriter,
+    customize: &Customize,
+) {
+    static SERVICE_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
+    let service_number = *SERVICE_NUMBER.get(|| field_number::<FileDescriptorProto>("service"));
+
+    let file_scope = FileScope {
+        file_descriptor: file,
+    };
+    let messages_and_enums: Vec<String> = file_scope
+        .to_scope()
+        .get_messages_and_enums()
+        .iter()
+        .map(|m| m.rust_name().to_string())
+        .collect();
+
+    for (index, service) in file.proto().service.iter().enumerate() {
+        let taken_names: Vec<String> = file
+            .proto()
+            .service
+            .iter()
+            .filter(|s| s.get_name() != service.get_name())
+            .map(|s| s.get_name().to_owned())
+            .chain(messages_and_enums.iter().cloned())
+            .collect();
+        let full_name = match file.proto().get_package() {
+            "" => service.get_name().to_owned(),
+            package => format!("{}.{}", package, service.get_name()),
+        };
+        ServiceGen {
+            proto: service,
+            full_name,
+            taken_names: &taken_names,
+            file,
+            root_scope,
+            customize,
+            path: vec![service_number, index as i32],
+        }
+        .write(w);
+    }
+}
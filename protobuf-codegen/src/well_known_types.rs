@@ -3,6 +3,7 @@ use crate::compiler_plugin;
 use crate::file::proto_path_to_rust_mod;
 use crate::protobuf_abs_path::ProtobufAbsolutePath;
 use crate::protobuf_rel_path::ProtobufRelativePath;
+use crate::rust_types_values::RustType;
 use crate::scope::FileScope;
 use crate::scope::WithScope;
 use protobuf::reflect::FileDescriptor;
@@ -167,6 +168,23 @@ pub(crate) fn gen_well_known_types_mod(
     }
 }
 
+/// Rust type of `value` field of a wrapper message like `google.protobuf.Int32Value`,
+/// `None` if given message is not a wrapper.
+pub(crate) fn wrapper_value_rust_type(name: &ProtobufAbsolutePath) -> Option<RustType> {
+    Some(match &name.path[..] {
+        ".google.protobuf.DoubleValue" => RustType::Float(64),
+        ".google.protobuf.FloatValue" => RustType::Float(32),
+        ".google.protobuf.Int64Value" => RustType::Int(true, 64),
+        ".google.protobuf.UInt64Value" => RustType::Int(false, 64),
+        ".google.protobuf.Int32Value" => RustType::Int(true, 32),
+        ".google.protobuf.UInt32Value" => RustType::Int(false, 32),
+        ".google.protobuf.BoolValue" => RustType::Bool,
+        ".google.protobuf.StringValue" => RustType::String,
+        ".google.protobuf.BytesValue" => RustType::Vec(Box::new(RustType::Int(false, 8))),
+        _ => return None,
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -182,4 +200,16 @@ mod test {
             is_well_known_type_full(&ProtobufAbsolutePath::from(".google.protobuf.Fgfg"))
         );
     }
+
+    #[test]
+    fn test_wrapper_value_rust_type() {
+        assert_eq!(
+            Some(RustType::Int(false, 32)),
+            wrapper_value_rust_type(&ProtobufAbsolutePath::from(".google.protobuf.UInt32Value"))
+        );
+        assert_eq!(
+            None,
+            wrapper_value_rust_type(&ProtobufAbsolutePath::from(".google.protobuf.Timestamp"))
+        );
+    }
 }
@@ -0,0 +1,32 @@
+// Executor for futures which complete without waiting, used by service tests
+
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr;
+use std::task::Context;
+use std::task::Poll;
+use std::task::RawWaker;
+use std::task::RawWakerVTable;
+use std::task::Waker;
+
+fn noop_raw_waker() -> RawWaker {
+    fn clone(_: *const ()) -> RawWaker {
+        noop_raw_waker()
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    RawWaker::new(ptr::null(), &VTABLE)
+}
+
+/// Poll the future once, panic if it is not ready.
+///
+/// Futures of in-memory transport complete without waiting.
+pub fn block_on<F: Future>(mut future: F) -> F::Output {
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = unsafe { Pin::new_unchecked(&mut future) };
+    match future.as_mut().poll(&mut context) {
+        Poll::Ready(r) => r,
+        Poll::Pending => panic!("future is not ready"),
+    }
+}
@@ -135,6 +135,12 @@ pub fn gen_mod_rs_in_dir(dir: &str) {
         if mod_name.contains("carllerche") {
             writeln!(mod_rs, r#"#[cfg(feature = "with-bytes")]"#).expect("write carllerche");
         }
+        if mod_name.contains("chrono_for_timestamp") {
+            writeln!(mod_rs, r#"#[cfg(feature = "with-chrono")]"#).expect("write chrono");
+        }
+        if mod_name.contains("time_for_timestamp") {
+            writeln!(mod_rs, r#"#[cfg(feature = "with-time")]"#).expect("write time");
+        }
         writeln!(mod_rs, "mod {};", mod_name).expect("write");
     }
 
@@ -226,6 +232,52 @@ where
 
     let customize = Customize {
         serde_derive_cfg: Some("serde".to_owned()),
+        gen_async_services: Some(true),
+        gen_builders: Some(true),
+        customize_by_name: vec![
+            (
+                ".test_extra_attrs.ByName".to_owned(),
+                Customize {
+                    extra_derives: Some("Hash, Eq".to_owned()),
+                    ..Default::default()
+                },
+            ),
+            (
+                ".test_eq_hash_ord".to_owned(),
+                Customize {
+                    derive_eq_hash_ord: Some(true),
+                    ..Default::default()
+                },
+            ),
+            (
+                ".test_eq_hash_ord.Disabled".to_owned(),
+                Customize {
+                    derive_eq_hash_ord: Some(false),
+                    ..Default::default()
+                },
+            ),
+            (
+                ".test_eq_hash_ord.WithRustType.name".to_owned(),
+                Customize {
+                    rust_type: Some("::std::boxed::Box<str>".to_owned()),
+                    ..Default::default()
+                },
+            ),
+            (
+                ".test_rust_type.MessageLevelRustType".to_owned(),
+                Customize {
+                    rust_type: Some("::std::boxed::Box<str>".to_owned()),
+                    ..Default::default()
+                },
+            ),
+            (
+                ".test_rust_type.WithRustType.by_name".to_owned(),
+                Customize {
+                    rust_type: Some("::std::boxed::Box<str>".to_owned()),
+                    ..Default::default()
+                },
+            ),
+        ],
         ..Default::default()
     };
 
@@ -7,6 +7,7 @@ extern crate protobuf_codegen;
 extern crate log;
 extern crate tempfile;
 
+pub mod block_on;
 pub mod build;
 pub mod hex;
 
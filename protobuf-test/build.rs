@@ -89,11 +89,71 @@ fn generate_include_generated() {
         .unwrap();
 }
 
+fn generate_mods_by_package() {
+    let dir = format!("{}/mods_by_package", env::var("OUT_DIR").unwrap());
+    if Path::new(&dir).exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir(&dir).unwrap();
+    protoc_rust::Codegen::new()
+        .out_dir(dir)
+        .input("src/mods_by_package/com/example/common.proto")
+        .input("src/mods_by_package/com/example/msg.proto")
+        .input("src/mods_by_package/org/common.proto")
+        .input("src/mods_by_package/root.proto")
+        .customize(Customize {
+            mods_by_package: Some(true),
+            ..Default::default()
+        })
+        .include("src/mods_by_package")
+        .run()
+        .unwrap();
+}
+
+fn generate_extern_path() {
+    let dir = format!("{}/extern_path", env::var("OUT_DIR").unwrap());
+    if Path::new(&dir).exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir_all(format!("{}/common", dir)).unwrap();
+    fs::create_dir_all(format!("{}/service", dir)).unwrap();
+    protoc_rust::Codegen::new()
+        .out_dir(format!("{}/common", dir))
+        .input("src/extern_path/common.proto")
+        .input("src/extern_path/other.proto")
+        .customize(Customize {
+            gen_mod_rs: Some(true),
+            ..Default::default()
+        })
+        .include("src/extern_path")
+        .run()
+        .unwrap();
+    protoc_rust::Codegen::new()
+        .out_dir(format!("{}/service", dir))
+        .input("src/extern_path/common.proto")
+        .input("src/extern_path/other.proto")
+        .input("src/extern_path/service.proto")
+        .customize(Customize {
+            gen_mod_rs: Some(true),
+            ..Default::default()
+        })
+        .extern_path(".ext.common", "crate::extern_path::common_protos::common")
+        .extern_path(
+            ".ext.other.Thing",
+            "crate::extern_path::common_protos::other::Thing",
+        )
+        .include("src/extern_path")
+        .run()
+        .unwrap();
+}
+
 fn generate_pb_rs() {
     generate_in_common();
     generate_in_v2_v3();
     generate_interop();
     generate_include_generated();
+    generate_mods_by_package();
+    generate_extern_path();
 }
 
 fn main() {
@@ -0,0 +1,105 @@
+use std::convert::TryFrom;
+
+use chrono::DateTime;
+use chrono::Utc;
+
+use protobuf::json;
+use protobuf::reflect::ReflectValueBox;
+use protobuf::well_known_types::Timestamp;
+use protobuf::Message;
+
+use super::test_chrono_for_timestamp_pb::*;
+
+use protobuf_test_common::hex::decode_hex;
+use protobuf_test_common::*;
+
+fn time(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+}
+
+// representable by `chrono` but not by `Timestamp`
+fn out_of_range() -> DateTime<Utc> {
+    DateTime::from_timestamp(253402300800, 0).unwrap()
+}
+
+#[test]
+fn test_field_types() {
+    let mut m = WithTimestamp::new();
+    m.time = Some(time("2021-02-03T04:05:06.007Z"));
+    m.times.push(Timestamp::new());
+    m.raw = Some(Timestamp::new()).into();
+}
+
+#[test]
+fn test_binary() {
+    let mut m = WithTimestamp::new();
+    m.time = Some(time("1970-01-01T00:00:01.5Z"));
+    test_serialize_deserialize("0a 08 08 01 10 80 ca b5 ee 01", &m);
+
+    let mut raw = WithTimestamp::new();
+    raw.raw = Some(Timestamp::try_from(time("1970-01-01T00:00:01.5Z")).unwrap()).into();
+    test_serialize_deserialize("1a 08 08 01 10 80 ca b5 ee 01", &raw);
+}
+
+#[test]
+fn test_merge() {
+    let mut m = WithTimestamp::new();
+    m.time = Some(time("1970-01-01T00:00:01Z"));
+    // nanos = 500000000
+    m.merge_from_bytes(&decode_hex("0a 06 10 80 ca b5 ee 01"))
+        .unwrap();
+    assert_eq!(Some(time("1970-01-01T00:00:01.5Z")), m.time);
+}
+
+#[test]
+fn test_out_of_range() {
+    let mut m = WithTimestamp::new();
+    m.time = Some(out_of_range());
+    assert!(!m.is_initialized());
+    assert!(m.write_to_bytes().is_err());
+
+    // existing value is kept if it cannot be merged with
+    assert!(m
+        .merge_from_bytes(&decode_hex("0a 06 10 80 ca b5 ee 01"))
+        .is_err());
+    assert_eq!(Some(out_of_range()), m.time);
+
+    // seconds = 253402300800, year 10000
+    assert!(WithTimestamp::parse_from_bytes(&decode_hex("0a 07 08 80 83 d1 ff af 07")).is_err());
+}
+
+#[test]
+fn test_json() {
+    let mut m = WithTimestamp::new();
+    m.time = Some(time("2021-02-03T04:05:06.007Z"));
+    test_json_print_parse_message("{\"time\": \"2021-02-03T04:05:06.007Z\"}", &m);
+
+    assert!(
+        json::parse_from_str::<WithTimestamp>("{\"time\": \"10000-01-01T00:00:00Z\"}").is_err()
+    );
+}
+
+#[test]
+fn test_reflect() {
+    let mut m = WithTimestamp::new();
+    let field = WithTimestamp::descriptor_static()
+        .get_field_by_name("time")
+        .unwrap();
+
+    let timestamp = Timestamp::try_from(time("2021-02-03T04:05:06.007Z")).unwrap();
+    field.set_singular_field(
+        &mut m,
+        ReflectValueBox::Message(Box::new(timestamp.clone())),
+    );
+    assert_eq!(Some(time("2021-02-03T04:05:06.007Z")), m.time);
+    let value = field.get_singular_field_or_default(&m);
+    let value = value.to_message().unwrap();
+    assert_eq!(Some(&timestamp), value.downcast_ref::<Timestamp>());
+
+    let mut invalid = Timestamp::new();
+    invalid.nanos = -1;
+    assert!(field
+        .try_set_singular_field(&mut m, ReflectValueBox::Message(Box::new(invalid)))
+        .is_err());
+    assert_eq!(Some(time("2021-02-03T04:05:06.007Z")), m.time);
+}
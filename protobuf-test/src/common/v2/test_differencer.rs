@@ -0,0 +1,168 @@
+use protobuf::reflect::FieldDifferenceKind;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDifferencer;
+use protobuf::reflect::RepeatedFieldComparison;
+use protobuf::text_format;
+use protobuf::Message;
+use protobuf::MessageDyn;
+
+use super::test_differencer_pb::Point;
+use super::test_differencer_pb::Shape;
+
+fn shape(text: &str) -> Shape {
+    text_format::parse_from_str(text).unwrap()
+}
+
+fn field(
+    message: &protobuf::reflect::MessageDescriptor,
+    name: &str,
+) -> protobuf::reflect::FieldDescriptor {
+    message.get_field_by_name(name).unwrap()
+}
+
+#[test]
+fn equal() {
+    let a = shape("name: 'a' points { id: 1 } tags: 1 counts { key: 'x' value: 1 }");
+    let b = a.clone();
+    assert!((&a as &dyn MessageDyn).diff_dyn(&b).is_empty());
+}
+
+#[test]
+fn added_removed_modified() {
+    let a = shape(
+        "name: 'a' \
+         points { id: 1 name: 'p' } \
+         tags: 1 tags: 2 \
+         counts { key: 'x' value: 1 } counts { key: 'y' value: 2 }",
+    );
+    let b = shape(
+        "name: 'b' \
+         points { id: 1 name: 'q' } points { id: 2 } \
+         tags: 1 \
+         counts { key: 'x' value: 3 } counts { key: 'z' value: 4 }",
+    );
+    let diff = (&a as &dyn MessageDyn).diff_dyn(&b);
+    assert_eq!(
+        "\
+modified: name: \"a\" -> \"b\"
+modified: points[0].name: \"p\" -> \"q\"
+added: points[1]: {id: 2}
+removed: tags[1]: 2
+modified: counts[\"x\"]: 1 -> 3
+removed: counts[\"y\"]: 2
+added: counts[\"z\"]: 4
+",
+        diff.to_string()
+    );
+
+    let kinds: Vec<FieldDifferenceKind> = diff.differences().iter().map(|d| d.kind).collect();
+    assert_eq!(
+        vec![
+            FieldDifferenceKind::Modified,
+            FieldDifferenceKind::Modified,
+            FieldDifferenceKind::Added,
+            FieldDifferenceKind::Removed,
+            FieldDifferenceKind::Modified,
+            FieldDifferenceKind::Removed,
+            FieldDifferenceKind::Added,
+        ],
+        kinds
+    );
+    assert_eq!("points[0].name", diff.differences()[1].path.to_string());
+    assert_eq!(None, diff.differences()[2].left);
+}
+
+#[test]
+fn ignore_field() {
+    let a = shape("name: 'a' points { id: 1 name: 'p' }");
+    let b = shape("name: 'b' points { id: 1 name: 'q' }");
+    let diff = MessageDifferencer::new()
+        .ignore_field(field(&Shape::descriptor_static(), "name"))
+        .ignore_field(field(&Point::descriptor_static(), "name"))
+        .compare(&a, &b);
+    assert!(diff.is_empty(), "{}", diff);
+}
+
+#[test]
+fn repeated_as_set() {
+    let a = shape("tags: 1 tags: 2 tags: 3 tags: 3");
+    let b = shape("tags: 3 tags: 1 tags: 3");
+    let diff = MessageDifferencer::new()
+        .default_repeated_field_comparison(RepeatedFieldComparison::AsSet)
+        .compare(&a, &b);
+    assert_eq!("removed: tags[1]: 2\n", diff.to_string());
+}
+
+#[test]
+fn repeated_keyed() {
+    let a = shape("points { id: 1 name: 'p' } points { id: 2 name: 'r' }");
+    let b = shape("points { id: 2 name: 'r' } points { id: 1 name: 'q' } points { id: 3 }");
+    let diff = MessageDifferencer::new()
+        .repeated_field_comparison(
+            field(&Shape::descriptor_static(), "points"),
+            RepeatedFieldComparison::Keyed(field(&Point::descriptor_static(), "id")),
+        )
+        .compare(&a, &b);
+    assert_eq!(
+        "\
+modified: points[1].name: \"p\" -> \"q\"
+added: points[2]: {id: 3}
+",
+        diff.to_string()
+    );
+}
+
+#[test]
+fn float_tolerance() {
+    let a = shape("ratio: 1.0");
+    let b = shape("ratio: 1.05");
+    assert!(!MessageDifferencer::new().compare(&a, &b).is_empty());
+    assert!(MessageDifferencer::new()
+        .float_tolerance(0.1, 0.0)
+        .compare(&a, &b)
+        .is_empty());
+    assert!(MessageDifferencer::new()
+        .float_tolerance(0.0, 0.1)
+        .compare(&a, &b)
+        .is_empty());
+}
+
+#[test]
+fn unset_as_default() {
+    let a = shape("name: 'a'");
+    let b = shape("name: 'a' center {}");
+    assert_eq!(
+        "added: center: {}\n",
+        MessageDifferencer::new().compare(&a, &b).to_string()
+    );
+    assert!(MessageDifferencer::new()
+        .treat_unset_as_default(true)
+        .compare(&a, &b)
+        .is_empty());
+}
+
+#[test]
+fn generated_and_dynamic() {
+    let a = shape("name: 'a' points { id: 1 }");
+    let b = shape("name: 'a' points { id: 2 }");
+
+    let file_descriptor = FileDescriptor::new_dynamic(
+        super::test_differencer_pb::file_descriptor()
+            .proto()
+            .clone(),
+        Vec::new(),
+    );
+    let mut d = file_descriptor
+        .message_by_package_relative_name("Shape")
+        .unwrap()
+        .new_instance();
+    d.merge_from_message_dyn(&b);
+
+    assert_eq!(
+        "modified: points[0].id: 1 -> 2\n",
+        MessageDifferencer::new()
+            .ignore_field(field(&d.descriptor_dyn(), "name"))
+            .compare(&a, &*d)
+            .to_string()
+    );
+}
@@ -0,0 +1,116 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::marker;
+
+use super::test_eq_hash_ord_pb::*;
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn assert_eq_hash_ord<T: Eq + Hash + Ord>() {}
+
+// Inherent method is preferred over trait method when `T: Hash`
+struct Probe<T>(marker::PhantomData<T>);
+
+impl<T: Hash> Probe<T> {
+    fn is_hash(&self) -> bool {
+        true
+    }
+}
+
+trait NotHash {
+    fn is_hash(&self) -> bool {
+        false
+    }
+}
+
+impl<T> NotHash for Probe<T> {}
+
+fn card(rank: i32) -> Card {
+    let mut card = Card::new();
+    card.set_rank(rank);
+    card.set_suit(Suit::SPADES);
+    card.set_name(format!("card {}", rank));
+    card
+}
+
+#[test]
+fn test_traits() {
+    assert_eq_hash_ord::<Card>();
+    assert_eq_hash_ord::<Inner>();
+    assert_eq_hash_ord::<card::Choice>();
+    assert_eq_hash_ord::<Suit>();
+
+    assert!(Probe::<Card>(marker::PhantomData).is_hash());
+    assert!(!Probe::<WithFloat>(marker::PhantomData).is_hash());
+    assert!(!Probe::<ReferencesFloat>(marker::PhantomData).is_hash());
+    assert!(!Probe::<Disabled>(marker::PhantomData).is_hash());
+    assert!(!Probe::<ReferencesDisabled>(marker::PhantomData).is_hash());
+    assert!(!Probe::<WithRustType>(marker::PhantomData).is_hash());
+    assert!(!Probe::<ReferencesRustType>(marker::PhantomData).is_hash());
+}
+
+#[test]
+fn test_hash_set() {
+    let mut set = HashSet::new();
+    set.insert(card(1));
+    set.insert(card(2));
+    set.insert(card(1));
+    assert_eq!(2, set.len());
+    assert!(set.contains(&card(2)));
+    assert!(!set.contains(&card(3)));
+}
+
+#[test]
+fn test_btree_set() {
+    let mut with_inner = card(1);
+    with_inner.inner.mut_or_default().set_id(10);
+
+    let set: BTreeSet<Card> = vec![card(3), with_inner.clone(), card(1), card(2), card(1)]
+        .into_iter()
+        .collect();
+    let ranks: Vec<i32> = set.iter().map(|c| c.get_rank()).collect();
+    assert_eq!(vec![1, 1, 2, 3], ranks);
+    // fields are compared in declaration order
+    assert!(card(1) < with_inner);
+    assert!(with_inner < card(2));
+}
+
+#[test]
+fn test_map_order_independent() {
+    let mut a = card(1);
+    let mut b = card(1);
+    for i in 0..20 {
+        a.mut_counts().insert(format!("k{}", i), i);
+    }
+    for i in (0..20).rev() {
+        b.mut_counts().insert(format!("k{}", i), i);
+    }
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+    assert_eq!(std::cmp::Ordering::Equal, a.cmp(&b));
+
+    b.mut_counts().insert("k5".to_owned(), 100);
+    assert!(a < b);
+}
+
+#[test]
+fn test_oneof() {
+    let mut a = card(1);
+    a.set_number(5);
+    let mut b = card(1);
+    b.set_text("five".to_owned());
+    assert_ne!(hash(&a), hash(&b));
+    assert!(a != b);
+    assert!(a < b);
+
+    let mut c = card(1);
+    c.mut_inner_choice().set_id(1);
+    assert!(b < c);
+}
@@ -0,0 +1,40 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use super::test_extra_attrs_pb::*;
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn assert_eq_hash<T: Eq + Hash>() {}
+
+#[test]
+fn test_extra_derives() {
+    let mut a = WithExtraDerives::new();
+    a.set_a(1);
+    a.set_d("d".to_owned());
+    a.nested.mut_or_default().set_x(2);
+    let b = a.clone();
+    assert_eq!(hash(&a), hash(&b));
+
+    // derives propagate to oneof and nested messages
+    assert_eq_hash::<with_extra_derives::One>();
+    assert_eq_hash::<with_extra_derives::Nested>();
+}
+
+#[test]
+fn test_extra_derives_enum() {
+    assert!(Color::RED < Color::GREEN);
+    assert_eq!(Some(&Color::GREEN), [Color::GREEN, Color::RED].iter().max());
+}
+
+#[test]
+fn test_customize_by_name() {
+    let mut m = ByName::new();
+    m.set_a(3);
+    assert_eq!(hash(&m), hash(&m.clone()));
+}
@@ -0,0 +1,110 @@
+use protobuf::reflect::FieldPath;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::ReflectEqMode;
+use protobuf::reflect::ReflectValueRef;
+use protobuf::text_format;
+use protobuf::well_known_types::FieldMask;
+use protobuf::Message;
+
+use super::test_field_mask_pb::file_descriptor;
+use super::test_field_mask_pb::Outer;
+
+fn parse(text: &str) -> Outer {
+    text_format::parse_from_str(text).unwrap()
+}
+
+fn dynamic_outer_descriptor() -> MessageDescriptor {
+    FileDescriptor::new_dynamic(file_descriptor().proto().clone(), Vec::new())
+        .message_by_package_relative_name("Outer")
+        .unwrap()
+}
+
+#[test]
+fn is_valid_for() {
+    let d = Outer::descriptor_static();
+    assert!(FieldMask::from_paths(&["id", "inner.a", "items", "counts"]).is_valid_for(&d));
+    assert!(FieldMask::new().is_valid_for(&d));
+    assert!(!FieldMask::from_paths(&["unknown"]).is_valid_for(&d));
+    assert!(!FieldMask::from_paths(&["inner.x"]).is_valid_for(&d));
+    assert!(!FieldMask::from_paths(&["id.a"]).is_valid_for(&d));
+    assert!(!FieldMask::from_paths(&["items.a"]).is_valid_for(&d));
+    assert!(!FieldMask::from_paths(&[""]).is_valid_for(&d));
+}
+
+#[test]
+fn trim() {
+    let mut m = parse(
+        "id: 1 name: 'n' inner { a: 2 b: 'b' c: 3 } items { a: 4 } counts { key: 'k' value: 5 }",
+    );
+    FieldMask::from_paths(&["name", "inner.b", "counts"]).trim(&mut m);
+    assert_eq!(
+        parse("name: 'n' inner { b: 'b' } counts { key: 'k' value: 5 }"),
+        m
+    );
+}
+
+#[test]
+fn trim_dynamic() {
+    let d = dynamic_outer_descriptor();
+    let mut m = d.new_instance();
+    text_format::merge_from_str(&mut *m, "id: 1 inner { a: 2 b: 'b' }").unwrap();
+    FieldMask::from_paths(&["inner.a"]).trim(&mut *m);
+
+    let path = |p| FieldPath::parse(&d, p).unwrap();
+    assert_eq!(None, path("id").get(&*m));
+    assert_eq!(Some(ReflectValueRef::I32(2)), path("inner.a").get(&*m));
+    assert_eq!(None, path("inner.b").get(&*m));
+}
+
+#[test]
+fn merge_message() {
+    let source = parse("id: 1 inner { a: 2 c: 3 } items { a: 4 } counts { key: 'k' value: 5 }");
+    let mut destination =
+        parse("name: 'n' inner { b: 'b' c: 6 } items { a: 7 } counts { key: 'j' value: 8 }");
+    let mask = FieldMask::from_paths(&["id", "name", "inner.c", "items", "counts"]);
+    mask.merge_message(&source, &mut destination, false);
+    assert_eq!(
+        parse(
+            "id: 1 inner { b: 'b' c: 6 c: 3 } items { a: 7 } items { a: 4 } \
+             counts { key: 'j' value: 8 } counts { key: 'k' value: 5 }"
+        ),
+        destination
+    );
+}
+
+#[test]
+fn merge_message_replacing_repeated() {
+    let source = parse("inner { c: 3 } items { a: 4 }");
+    let mut destination = parse("inner { a: 1 c: 6 } items { a: 7 }");
+    let mask = FieldMask::from_paths(&["inner.c", "items"]);
+    mask.merge_message(&source, &mut destination, true);
+    assert_eq!(parse("inner { a: 1 c: 3 } items { a: 4 }"), destination);
+}
+
+#[test]
+fn merge_message_unset_message_field() {
+    let source = parse("id: 1");
+    let mut destination = parse("inner { a: 1 }");
+    FieldMask::from_paths(&["inner"]).merge_message(&source, &mut destination, false);
+    assert_eq!(parse("inner { a: 1 }"), destination);
+}
+
+#[test]
+fn merge_message_into_dynamic() {
+    let d = dynamic_outer_descriptor();
+    let source = parse("id: 1 inner { a: 2 b: 'b' } items { a: 3 }");
+    let mut destination = d.new_instance();
+    FieldMask::from_paths(&["inner.b", "items"]).merge_message(&source, &mut *destination, false);
+    assert!(destination.reflect_eq_dyn(
+        &parse("inner { b: 'b' } items { a: 3 }"),
+        &ReflectEqMode::default()
+    ));
+}
+
+#[test]
+fn json() {
+    let mask = FieldMask::from_paths(&["inner.a", "name"]);
+    assert_eq!(Some("inner.a,name".to_owned()), mask.to_json_string());
+    assert_eq!(mask, FieldMask::from_json_string("inner.a,name"));
+}
@@ -0,0 +1,260 @@
+use protobuf::reflect::walk_message;
+use protobuf::reflect::FieldPath;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::MessageVisitor;
+use protobuf::reflect::ReflectValueBox;
+use protobuf::reflect::ReflectValueRef;
+
+use protobuf::MessageDyn;
+
+use super::test_field_path_pb;
+
+fn dynamic_file_descriptor() -> FileDescriptor {
+    FileDescriptor::new_dynamic(
+        test_field_path_pb::file_descriptor().proto().clone(),
+        Vec::new(),
+    )
+}
+
+fn node_descriptor(file_descriptor: &FileDescriptor) -> MessageDescriptor {
+    file_descriptor
+        .message_by_package_relative_name("Node")
+        .unwrap()
+}
+
+fn do_test_display(file_descriptor: &FileDescriptor) {
+    let node = node_descriptor(file_descriptor);
+    for path in &[
+        "leaf.name",
+        "leaves[2].numbers[0]",
+        "leaf_by_name[\"a b\"].name",
+        "names_by_id[-3]",
+        "child.child.leaves",
+    ] {
+        assert_eq!(*path, FieldPath::parse(&node, path).unwrap().to_string());
+    }
+    assert_eq!(
+        "leaf_by_name[\"x\"]",
+        FieldPath::parse(&node, "leafByName[ \"x\" ]")
+            .unwrap()
+            .to_string()
+    );
+}
+
+#[test]
+fn generated_display() {
+    do_test_display(&test_field_path_pb::file_descriptor());
+}
+
+#[test]
+fn dynamic_display() {
+    do_test_display(&dynamic_file_descriptor());
+}
+
+fn do_test_parse_error(file_descriptor: &FileDescriptor) {
+    let node = node_descriptor(file_descriptor);
+    for path in &[
+        "",
+        "leaf.nme",
+        "leaf.name.x",
+        "leaves.name",
+        "leaf[0]",
+        "leaves[\"a\"]",
+        "leaf_by_name[1]",
+        "names_by_id[\"1\"]",
+        "names_by_id[3000000000]",
+        "leaf.",
+        "leaves[1",
+    ] {
+        assert!(FieldPath::parse(&node, path).is_err(), "{}", path);
+    }
+}
+
+#[test]
+fn generated_parse_error() {
+    do_test_parse_error(&test_field_path_pb::file_descriptor());
+}
+
+#[test]
+fn dynamic_parse_error() {
+    do_test_parse_error(&dynamic_file_descriptor());
+}
+
+fn do_test_get_set(file_descriptor: &FileDescriptor) {
+    let node = node_descriptor(file_descriptor);
+    let mut m = node.new_instance();
+    let m = &mut *m;
+
+    let leaf_name = FieldPath::parse(&node, "child.leaf.name").unwrap();
+    assert_eq!(None, leaf_name.get(m));
+    leaf_name
+        .set(m, ReflectValueBox::String("n".to_owned()))
+        .unwrap();
+    assert_eq!(Some(ReflectValueRef::String("n")), leaf_name.get(m));
+
+    let number = FieldPath::parse(&node, "leaves[0].numbers[1]").unwrap();
+    assert_eq!(None, number.get(m));
+    // `leaves[0]` is created, but `numbers[1]` is past the end
+    assert!(number.set(m, ReflectValueBox::I32(1)).is_err());
+    let first = FieldPath::parse(&node, "leaves[0].numbers[0]").unwrap();
+    first.set(m, ReflectValueBox::I32(10)).unwrap();
+    number.set(m, ReflectValueBox::I32(11)).unwrap();
+    number.set(m, ReflectValueBox::I32(12)).unwrap();
+    assert_eq!(Some(ReflectValueRef::I32(10)), first.get(m));
+    assert_eq!(Some(ReflectValueRef::I32(12)), number.get(m));
+
+    let by_name = FieldPath::parse(&node, "leaf_by_name[\"k\"].name").unwrap();
+    assert_eq!(None, by_name.get(m));
+    by_name
+        .set(m, ReflectValueBox::String("v".to_owned()))
+        .unwrap();
+    assert_eq!(Some(ReflectValueRef::String("v")), by_name.get(m));
+
+    let by_id = FieldPath::parse(&node, "names_by_id[-1]").unwrap();
+    by_id
+        .set(m, ReflectValueBox::String("minus one".to_owned()))
+        .unwrap();
+    assert_eq!(Some(ReflectValueRef::String("minus one")), by_id.get(m));
+
+    let leaves = node.get_field_by_name("leaves").unwrap();
+    assert_eq!(1, leaves.get_repeated(m).len());
+}
+
+#[test]
+fn generated_get_set() {
+    do_test_get_set(&test_field_path_pb::file_descriptor());
+}
+
+#[test]
+fn dynamic_get_set() {
+    do_test_get_set(&dynamic_file_descriptor());
+}
+
+fn do_test_clear(file_descriptor: &FileDescriptor) {
+    let node = node_descriptor(file_descriptor);
+    let mut m = node.new_instance();
+    let m = &mut *m;
+
+    // Clear does not create intermediate messages
+    FieldPath::parse(&node, "child.leaf.name")
+        .unwrap()
+        .clear(m)
+        .unwrap();
+    FieldPath::parse(&node, "leaves[3].name")
+        .unwrap()
+        .clear(m)
+        .unwrap();
+    assert!(!node.get_field_by_name("child").unwrap().has_field(m));
+    assert_eq!(
+        0,
+        node.get_field_by_name("leaves")
+            .unwrap()
+            .get_repeated(m)
+            .len()
+    );
+
+    let name = FieldPath::parse(&node, "leaves[0].name").unwrap();
+    name.set(m, ReflectValueBox::String("a".to_owned()))
+        .unwrap();
+    name.clear(m).unwrap();
+    assert_eq!(None, name.get(m));
+
+    let by_name = FieldPath::parse(&node, "leaf_by_name[\"k\"]").unwrap();
+    by_name.mut_message(m).unwrap();
+    assert!(by_name.get(m).is_some());
+    by_name.clear(m).unwrap();
+    assert_eq!(None, by_name.get(m));
+
+    let leaves = FieldPath::parse(&node, "leaves").unwrap();
+    leaves.clear(m).unwrap();
+    assert_eq!(
+        0,
+        node.get_field_by_name("leaves")
+            .unwrap()
+            .get_repeated(m)
+            .len()
+    );
+}
+
+#[test]
+fn generated_clear() {
+    do_test_clear(&test_field_path_pb::file_descriptor());
+}
+
+#[test]
+fn dynamic_clear() {
+    do_test_clear(&dynamic_file_descriptor());
+}
+
+fn do_test_mut_message(file_descriptor: &FileDescriptor) {
+    let node = node_descriptor(file_descriptor);
+    let mut m = node.new_instance();
+    let m = &mut *m;
+
+    let leaf = FieldPath::parse(&node, "child.leaves[0]").unwrap();
+    let leaf_message = leaf.mut_message(m).unwrap();
+    leaf_message
+        .descriptor_dyn()
+        .get_field_by_name("name")
+        .unwrap()
+        .set_singular_field(leaf_message, ReflectValueBox::String("x".to_owned()));
+
+    let name = FieldPath::parse(&node, "child.leaves[0].name").unwrap();
+    assert_eq!(Some(ReflectValueRef::String("x")), name.get(m));
+}
+
+#[test]
+fn generated_mut_message() {
+    do_test_mut_message(&test_field_path_pb::file_descriptor());
+}
+
+#[test]
+fn dynamic_mut_message() {
+    do_test_mut_message(&dynamic_file_descriptor());
+}
+
+struct RootPath(Option<FieldPath>);
+
+impl MessageVisitor for RootPath {
+    fn enter_message(&mut self, path: &FieldPath, _message: &dyn MessageDyn) {
+        if path.is_empty() {
+            self.0 = Some(path.clone());
+        }
+    }
+}
+
+fn do_test_empty_path(file_descriptor: &FileDescriptor) {
+    let node = node_descriptor(file_descriptor);
+    let mut m = node.new_instance();
+    let m = &mut *m;
+    let name = FieldPath::parse(&node, "leaf.name").unwrap();
+    name.set(m, ReflectValueBox::String("x".to_owned()))
+        .unwrap();
+
+    let mut root = RootPath(None);
+    walk_message(m, &mut root);
+    let root = root.0.unwrap();
+
+    match root.get(m) {
+        Some(ReflectValueRef::Message(r)) => assert_eq!(node, r.descriptor_dyn()),
+        v => panic!("not a message: {:?}", v),
+    }
+    assert!(root
+        .set(m, ReflectValueBox::String("x".to_owned()))
+        .is_err());
+    assert_eq!(node, root.mut_message(m).unwrap().descriptor_dyn());
+
+    root.clear(m).unwrap();
+    assert_eq!(None, name.get(m));
+}
+
+#[test]
+fn generated_empty_path() {
+    do_test_empty_path(&test_field_path_pb::file_descriptor());
+}
+
+#[test]
+fn dynamic_empty_path() {
+    do_test_empty_path(&dynamic_file_descriptor());
+}
@@ -1,5 +1,7 @@
 use protobuf_test_common::*;
 
+use protobuf::reflect::FileDescriptor;
+
 use super::test_oneof_pb::*;
 
 #[test]
@@ -61,3 +63,51 @@ fn test_types() {
     t(|o| o.set_enum_field(EnumForOneof::A));
     t(|o| o.mut_message_field().set_f(22));
 }
+
+fn dynamic_file_descriptor() -> FileDescriptor {
+    FileDescriptor::new_dynamic(
+        file_descriptor().proto().clone(),
+        vec![protobuf::rustproto::file_descriptor()],
+    )
+}
+
+fn do_test_reflect_which_field_and_clear(file_descriptor: &FileDescriptor) {
+    let descriptor = file_descriptor
+        .message_by_package_relative_name("TestOneof")
+        .unwrap();
+    let oneof = descriptor.oneofs().next().unwrap();
+    assert_eq!("one", oneof.get_name());
+
+    let int32_field = descriptor.get_field_by_name("int32_field").unwrap();
+    let string_field = descriptor.get_field_by_name("string_field").unwrap();
+
+    let mut m = descriptor.new_instance();
+    let m = &mut *m;
+    assert!(oneof.get_field(m).is_none());
+
+    int32_field.set_singular_field(m, 10i32.into());
+    assert!(oneof.get_field(m) == Some(int32_field.clone()));
+
+    // Setting another member resets the previous one
+    string_field.set_singular_field(m, "aa".to_owned().into());
+    assert!(oneof.get_field(m) == Some(string_field.clone()));
+    assert!(!int32_field.has_field(m));
+
+    // Clearing a member which is not set does not affect the oneof
+    int32_field.clear_field(m);
+    assert!(oneof.get_field(m) == Some(string_field.clone()));
+
+    oneof.clear(m);
+    assert!(oneof.get_field(m).is_none());
+    assert!(!string_field.has_field(m));
+}
+
+#[test]
+fn generated_reflect_which_field_and_clear() {
+    do_test_reflect_which_field_and_clear(&file_descriptor());
+}
+
+#[test]
+fn dynamic_reflect_which_field_and_clear() {
+    do_test_reflect_which_field_and_clear(&dynamic_file_descriptor());
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "with-serde")]
+
+use protobuf::json::serde::SerdeMessage;
+use protobuf::json::serde::SerdeMessageDyn;
+use protobuf::json::serde::SerdeMessageSeed;
+use protobuf::well_known_types::Duration;
+use protobuf::well_known_types::Value;
+use protobuf::Message;
+use serde::de::DeserializeSeed;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+use serde_json;
+
+use super::test_serde_json_mapping_pb::*;
+
+fn test_outer() -> Outer {
+    let mut m = Outer::new();
+    m.set_small_number(10);
+    m.set_big_number(1 << 60);
+    m.set_ratio(0.5);
+    m.set_color(Color::GREEN);
+    m.set_data(b"abc".to_vec());
+    let mut inner = Inner::new();
+    inner.set_name("x\"y".to_owned());
+    m.inners.push(inner);
+    m.counts.insert("a".to_owned(), 1);
+    m.timeout = Some(Duration::from(std::time::Duration::from_secs(3))).into();
+    let mut value = Value::new();
+    value.set_number_value(1.5);
+    m.value = Some(value).into();
+    m
+}
+
+#[test]
+fn test_same_as_json_print() {
+    let m = test_outer();
+    let serialized = serde_json::to_value(SerdeMessage(m.clone())).unwrap();
+    let printed: serde_json::Value =
+        serde_json::from_str(&protobuf::json::print_to_string(&m).unwrap()).unwrap();
+    assert_eq!(printed, serialized);
+    assert_eq!("1152921504606846976", serialized["bigNumber"]);
+    assert_eq!("GREEN", serialized["color"]);
+    assert_eq!("3s", serialized["timeout"]);
+}
+
+#[test]
+fn test_round_trip() {
+    let m = test_outer();
+    let serialized = serde_json::to_string(&SerdeMessage(m.clone())).unwrap();
+    let deserialized: SerdeMessage<Outer> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(m, deserialized.0);
+}
+
+#[test]
+fn test_dynamic() {
+    let m = test_outer();
+    let serialized = serde_json::to_string(&SerdeMessageDyn(&m)).unwrap();
+    let seed = SerdeMessageSeed(Outer::descriptor_static());
+    let deserialized = seed
+        .deserialize(&mut serde_json::Deserializer::from_str(&serialized))
+        .unwrap();
+    assert!(Outer::descriptor_static().eq(&m, &*deserialized));
+}
+
+#[test]
+fn test_embedded_with() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Document {
+        title: String,
+        #[serde(with = "protobuf::json::serde")]
+        outer: Outer,
+    }
+
+    let document = Document {
+        title: "doc".to_owned(),
+        outer: test_outer(),
+    };
+    let serialized = serde_json::to_string(&document).unwrap();
+    let deserialized: Document = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(document, deserialized);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(serde_json::from_str::<SerdeMessage<Outer>>(r#"{"unknownField": 1}"#).is_err());
+    assert!(serde_json::from_str::<SerdeMessage<Outer>>(r#"{"color": "BLUE"}"#).is_err());
+}
+
+#[test]
+fn test_parse_same_as_json_parse() {
+    for json in &[
+        r#"{"smallNumber": "10", "bigNumber": 7, "ratio": "-Infinity", "color": 1}"#,
+        r#"{"small_number": 1, "inners": null, "counts": {"a": "2"}}"#,
+        r#"{"value": {"a": [1, "b", null, true, {}]}}"#,
+    ] {
+        let deserialized = serde_json::from_str::<SerdeMessage<Outer>>(json).unwrap();
+        let parsed: Outer = protobuf::json::parse_from_str(json).unwrap();
+        assert!(
+            Outer::descriptor_static().eq(&parsed, &deserialized.0),
+            "{}",
+            json
+        );
+    }
+}
+
+#[test]
+fn test_parse_number_errors() {
+    assert!(serde_json::from_str::<SerdeMessage<Outer>>(r#"{"smallNumber": 1.5}"#).is_err());
+    assert!(serde_json::from_str::<SerdeMessage<Outer>>(r#"{"smallNumber": 3000000000}"#).is_err());
+    assert!(serde_json::from_str::<SerdeMessage<Outer>>(r#"{"smallNumber": true}"#).is_err());
+}
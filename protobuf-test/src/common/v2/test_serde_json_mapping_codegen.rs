@@ -0,0 +1,90 @@
+#![cfg(feature = "with-serde")]
+
+use protobuf::well_known_types::Int64Value;
+use protobuf::well_known_types::Struct;
+use protobuf::well_known_types::Timestamp;
+use protobuf::well_known_types::Value;
+use protobuf::Message;
+use serde_json;
+
+use super::test_serde_json_mapping_codegen_pb::*;
+
+fn test_drawing() -> Drawing {
+    let mut point = Point::new();
+    point.set_x_coord(-3);
+    point.set_y_coord(1 << 40);
+
+    let mut revision = Int64Value::new();
+    revision.value = 17;
+
+    let mut metadata = Struct::new();
+    let mut author = Value::new();
+    author.set_string_value("me".to_owned());
+    metadata.fields.insert("author".to_owned(), author);
+
+    let mut drawing = Drawing::new();
+    drawing.set_title("sketch".to_owned());
+    drawing.set_id(u64::MAX);
+    drawing.set_shape(Shape::SQUARE);
+    drawing.points.push(point.clone());
+    drawing.named_points.insert("origin".to_owned(), point);
+    drawing.set_fill_color("red".to_owned());
+    drawing.created = Some(Timestamp {
+        seconds: 1_600_000_000,
+        nanos: 500_000_000,
+        ..Default::default()
+    })
+    .into();
+    drawing.revision = Some(revision).into();
+    drawing.metadata = Some(metadata).into();
+    drawing
+}
+
+#[test]
+fn test_enum() {
+    let serialized = serde_json::to_string(&Shape::SQUARE).unwrap();
+    assert_eq!(r#""SQUARE""#, serialized);
+    assert_eq!(Shape::SQUARE, serde_json::from_str(&serialized).unwrap());
+    assert_eq!(Shape::SQUARE, serde_json::from_str("1").unwrap());
+    assert!(serde_json::from_str::<Shape>(r#""TRIANGLE""#).is_err());
+}
+
+#[test]
+fn test_same_as_json_print() {
+    let drawing = test_drawing();
+    let serialized = serde_json::to_string(&drawing).unwrap();
+    // same output modulo whitespace which `serde_json` does not emit
+    let printed = protobuf::json::print_to_string(&drawing).unwrap();
+    assert_eq!(printed.replace(", ", ",").replace(": ", ":"), serialized);
+
+    let value: serde_json::Value = serde_json::from_str(&serialized).unwrap();
+    assert_eq!("18446744073709551615", value["id"]);
+    assert_eq!("-3", value["points"][0]["xCoord"]);
+    assert_eq!("red", value["fillColor"]);
+    assert_eq!("2020-09-13T12:26:40.500Z", value["created"]);
+    assert_eq!("17", value["revision"]);
+    assert_eq!("me", value["metadata"]["author"]);
+}
+
+#[test]
+fn test_round_trip() {
+    let drawing = test_drawing();
+    let serialized = serde_json::to_string(&drawing).unwrap();
+    let deserialized: Drawing = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(drawing, deserialized);
+
+    let parsed: Drawing = serde_json::from_str(r#"{"fillOpacity": 0.5, "shape": 1}"#).unwrap();
+    assert_eq!(0.5, parsed.get_fill_opacity());
+    assert_eq!(Shape::SQUARE, parsed.get_shape());
+}
+
+#[test]
+fn test_default() {
+    let drawing = Drawing::new();
+    assert_eq!("{}", serde_json::to_string(&drawing).unwrap());
+    assert_eq!(drawing, serde_json::from_str("{}").unwrap());
+    assert_eq!(
+        Drawing::descriptor_static().full_name(),
+        "test_serde_json_mapping_codegen.Drawing"
+    );
+}
@@ -1,10 +1,157 @@
+use protobuf::service::ready;
+use protobuf::service::AsyncInMemoryTransport;
+use protobuf::service::InMemoryTransport;
+use protobuf::service::ServiceDispatcher as _;
+use protobuf::service::ServiceError;
+use protobuf::service::ServiceFuture;
+use protobuf::service::ServiceResult;
+use protobuf::service::ServiceTransport;
+use protobuf_test_common::block_on::block_on;
+
+use super::test_basic_pb::Test1;
+use super::test_basic_pb::Test2;
 use super::test_service_pb::*;
 
+struct ServiceImpl;
+
+impl Service for ServiceImpl {
+    fn short_form(&self, _request: Request) -> ServiceResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn long_form(&self, _request: Request) -> ServiceResult<Response> {
+        Err(ServiceError::other("long form is not implemented"))
+    }
+
+    fn dotty_short_form(&self, request: Test1) -> ServiceResult<Test2> {
+        let mut response = Test2::new();
+        response.set_b(format!("short {}", request.get_a()));
+        Ok(response)
+    }
+
+    fn dotty_long_form(&self, request: Test1) -> ServiceResult<Test2> {
+        let mut response = Test2::new();
+        response.set_b(format!("long {}", request.get_a()));
+        Ok(response)
+    }
+}
+
+impl ServiceAsync for ServiceImpl {
+    fn short_form<'a>(&'a self, request: Request) -> ServiceFuture<'a, Response> {
+        ready(Service::short_form(self, request))
+    }
+
+    fn long_form<'a>(&'a self, request: Request) -> ServiceFuture<'a, Response> {
+        ready(Service::long_form(self, request))
+    }
+
+    fn dotty_short_form<'a>(&'a self, request: Test1) -> ServiceFuture<'a, Test2> {
+        ready(Service::dotty_short_form(self, request))
+    }
+
+    fn dotty_long_form<'a>(&'a self, request: Test1) -> ServiceFuture<'a, Test2> {
+        ready(Service::dotty_long_form(self, request))
+    }
+}
+
+fn test1(a: i32) -> Test1 {
+    let mut request = Test1::new();
+    request.set_a(a);
+    request
+}
+
 #[test]
 fn test_service() {
-    // The request/response types should still
-    // get generated, even though we ignore the
-    // service definition in the same file.
-    let _ = Request::new();
-    let _ = Response::new();
+    let mut transport = InMemoryTransport::new();
+    transport.add_service(ServiceDispatcher::new(ServiceImpl));
+    let client = ServiceClient::new(transport);
+
+    assert_eq!(Response::new(), client.short_form(&Request::new()).unwrap());
+    assert_eq!(
+        "short 10",
+        client.dotty_short_form(&test1(10)).unwrap().get_b()
+    );
+    assert_eq!(
+        "long 20",
+        client.dotty_long_form(&test1(20)).unwrap().get_b()
+    );
+    match client.long_form(&Request::new()) {
+        Err(ServiceError::Other(e)) => assert_eq!("long form is not implemented", e.to_string()),
+        r => panic!("{:?}", r),
+    }
+}
+
+#[test]
+fn test_dispatcher() {
+    let dispatcher = ServiceDispatcher::new(ServiceImpl);
+    assert_eq!("service.Service", dispatcher.service_name());
+
+    match dispatcher.dispatch("service.Service.NoSuchMethod", &[]) {
+        Err(ServiceError::UnknownMethod(m)) => assert_eq!("service.Service.NoSuchMethod", m),
+        r => panic!("{:?}", r),
+    }
+}
+
+#[test]
+fn test_unknown_service() {
+    let client = ServiceClient::new(InMemoryTransport::new());
+    match client.short_form(&Request::new()) {
+        Err(ServiceError::UnknownMethod(m)) => assert_eq!("service.Service.ShortForm", m),
+        r => panic!("{:?}", r),
+    }
+    assert!(client
+        .transport()
+        .call("service.Service.ShortForm", Vec::new())
+        .is_err());
+}
+
+#[test]
+fn test_async_service() {
+    let mut transport = AsyncInMemoryTransport::new();
+    transport.add_service(ServiceAsyncDispatcher::new(ServiceImpl));
+    let client = ServiceAsyncClient::new(transport);
+
+    assert_eq!(
+        Response::new(),
+        block_on(client.short_form(&Request::new())).unwrap()
+    );
+    assert_eq!(
+        "short 10",
+        block_on(client.dotty_short_form(&test1(10)))
+            .unwrap()
+            .get_b()
+    );
+    match block_on(client.long_form(&Request::new())) {
+        Err(ServiceError::Other(e)) => assert_eq!("long form is not implemented", e.to_string()),
+        r => panic!("{:?}", r),
+    }
+}
+
+struct ClashImpl;
+
+impl Clash for ClashImpl {
+    fn get_foo(&self, _request: Request) -> ServiceResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn get_foo_(&self, _request: Request) -> ServiceResult<Response> {
+        Err(ServiceError::other("get_foo"))
+    }
+}
+
+#[test]
+fn test_generated_names_clash() {
+    let _: ClashClient = ClashClient::new();
+    let _: ClashAsync = ClashAsync::new();
+    let _: ClashDispatcher = ClashDispatcher::new();
+
+    let mut transport = InMemoryTransport::new();
+    transport.add_service(ClashDispatcher_::new(ClashImpl));
+    let client = ClashClient_::new(transport);
+
+    assert_eq!(Response::new(), client.get_foo(&Request::new()).unwrap());
+    match client.get_foo_(&Request::new()) {
+        Err(ServiceError::Other(e)) => assert_eq!("get_foo", e.to_string()),
+        r => panic!("{:?}", r),
+    }
 }
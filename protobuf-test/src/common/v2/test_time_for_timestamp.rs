@@ -0,0 +1,87 @@
+use std::convert::TryFrom;
+
+use time::OffsetDateTime;
+
+use protobuf::json;
+use protobuf::reflect::ReflectValueBox;
+use protobuf::well_known_types::Timestamp;
+use protobuf::Message;
+
+use super::test_time_for_timestamp_pb::*;
+
+use protobuf_test_common::hex::decode_hex;
+use protobuf_test_common::*;
+
+fn time(nanos: i128) -> OffsetDateTime {
+    OffsetDateTime::from_unix_timestamp_nanos(nanos).unwrap()
+}
+
+#[test]
+fn test_field_types() {
+    let mut m = WithTimestamp::new();
+    m.time = Some(time(0));
+    m.times.push(Timestamp::new());
+    m.raw = Some(Timestamp::new()).into();
+}
+
+#[test]
+fn test_binary() {
+    let mut m = WithTimestamp::new();
+    m.time = Some(time(1_500_000_000));
+    test_serialize_deserialize("0a 08 08 01 10 80 ca b5 ee 01", &m);
+
+    let mut merged = WithTimestamp::new();
+    merged.time = Some(time(1_000_000_000));
+    merged
+        .merge_from_bytes(&decode_hex("0a 06 10 80 ca b5 ee 01"))
+        .unwrap();
+    assert_eq!(Some(time(1_500_000_000)), merged.time);
+}
+
+#[test]
+fn test_out_of_range() {
+    let out_of_range = OffsetDateTime::from_unix_timestamp(Timestamp::MIN_SECONDS - 1).unwrap();
+    let mut m = WithTimestamp::new();
+    m.time = Some(out_of_range);
+    assert!(!m.is_initialized());
+    assert!(m.write_to_bytes().is_err());
+
+    assert!(m
+        .merge_from_bytes(&decode_hex("0a 06 10 80 ca b5 ee 01"))
+        .is_err());
+    assert_eq!(Some(out_of_range), m.time);
+}
+
+#[test]
+fn test_json() {
+    let mut m = WithTimestamp::new();
+    m.time = Some(time(1_612_325_106_007_000_000));
+    test_json_print_parse_message("{\"time\": \"2021-02-03T04:05:06.007Z\"}", &m);
+
+    assert!(json::parse_from_str::<WithTimestamp>("{\"time\": 1}").is_err());
+}
+
+#[test]
+fn test_reflect() {
+    let mut m = WithTimestamp::new();
+    let field = WithTimestamp::descriptor_static()
+        .get_field_by_name("time")
+        .unwrap();
+
+    let timestamp = Timestamp::try_from(time(1_500_000_000)).unwrap();
+    field.set_singular_field(
+        &mut m,
+        ReflectValueBox::Message(Box::new(timestamp.clone())),
+    );
+    assert_eq!(Some(time(1_500_000_000)), m.time);
+    let value = field.get_singular_field_or_default(&m);
+    let value = value.to_message().unwrap();
+    assert_eq!(Some(&timestamp), value.downcast_ref::<Timestamp>());
+
+    let mut invalid = Timestamp::new();
+    invalid.nanos = -1;
+    assert!(field
+        .try_set_singular_field(&mut m, ReflectValueBox::Message(Box::new(invalid)))
+        .is_err());
+    assert_eq!(Some(time(1_500_000_000)), m.time);
+}
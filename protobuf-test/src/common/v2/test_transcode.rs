@@ -0,0 +1,111 @@
+use protobuf::reflect::FieldPath;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::ReflectEqMode;
+use protobuf::reflect::ReflectValueRef;
+use protobuf::text_format;
+use protobuf::Message;
+use protobuf::MessageDyn;
+
+use super::test_transcode_pb::file_descriptor;
+use super::test_transcode_pb::Container;
+use super::test_transcode_pb::Item;
+
+fn dynamic_file_descriptor() -> FileDescriptor {
+    FileDescriptor::new_dynamic(file_descriptor().proto().clone(), Vec::new())
+}
+
+fn new_dynamic_container() -> Box<dyn MessageDyn> {
+    dynamic_file_descriptor()
+        .message_by_package_relative_name("Container")
+        .unwrap()
+        .new_instance()
+}
+
+fn sample_container() -> Container {
+    let mut c: Container = text_format::parse_from_str(
+        "id: 10 \
+         item { name: 'a' color: GREEN } \
+         items { name: 'b' color: RED } \
+         items { name: 'c' color: GREEN } \
+         colors: GREEN \
+         item_by_name { key: 'd' value { name: 'd' color: GREEN } } \
+         other { name: 'e' }",
+    )
+    .unwrap();
+    c.mut_unknown_fields().add_varint(100, 17);
+    c
+}
+
+#[test]
+fn generated_to_dynamic_and_back() {
+    let c = sample_container();
+
+    let mut d = new_dynamic_container();
+    d.merge_from_message_dyn(&c);
+    assert!(d.reflect_eq_dyn(&c, &ReflectEqMode::default()));
+    assert!((&c as &dyn MessageDyn).reflect_eq_dyn(&*d, &ReflectEqMode::default()));
+
+    let t: Container = d.transcode();
+    assert_eq!(c, t);
+}
+
+#[test]
+fn reflect_eq_across_representations_detects_difference() {
+    let c = sample_container();
+    let mut d = new_dynamic_container();
+    d.merge_from_message_dyn(&c);
+
+    let mut c2 = c.clone();
+    c2.items[1] = text_format::parse_from_str("name: 'c'").unwrap();
+    assert!(!d.reflect_eq_dyn(&c2, &ReflectEqMode::default()));
+}
+
+#[test]
+fn descriptor_eq_dynamic() {
+    let c = sample_container();
+    let mut a = new_dynamic_container();
+    let mut b = new_dynamic_container();
+    a.merge_from_message_dyn(&c);
+    b.merge_from_message_dyn(&c);
+
+    let descriptor = a.descriptor_dyn();
+    assert!(descriptor.eq(&*a, &*b));
+    assert!(descriptor.eq(&*a, &c));
+    assert!(Container::descriptor_static().eq(&c, &*a));
+
+    b.mut_unknown_fields_dyn().add_varint(101, 1);
+    assert!(!descriptor.eq(&*a, &*b));
+}
+
+#[test]
+fn merge_semantics() {
+    let mut target = sample_container();
+    let mut source = new_dynamic_container();
+    text_format::merge_from_str(
+        &mut *source,
+        "id: 20 text: 't' items { name: 'f' color: GREEN }",
+    )
+    .unwrap();
+
+    (&mut target as &mut dyn MessageDyn).merge_from_message_dyn(&*source);
+
+    let get = |path: &str| {
+        FieldPath::parse(&Container::descriptor_static(), path)
+            .unwrap()
+            .get(&target)
+    };
+    assert_eq!(Some(ReflectValueRef::I32(20)), get("id"));
+    assert_eq!(Some(ReflectValueRef::String("a")), get("item.name"));
+    assert_eq!(3, target.items.len());
+    assert_eq!(Some(ReflectValueRef::String("f")), get("items[2].name"));
+    // oneof field is replaced
+    assert!(!target.has_other());
+    assert_eq!("t", target.get_text());
+}
+
+#[test]
+#[should_panic]
+fn merge_different_types() {
+    let mut c = Container::new();
+    (&mut c as &mut dyn MessageDyn).merge_from_message_dyn(&Item::new());
+}
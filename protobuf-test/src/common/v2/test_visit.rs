@@ -0,0 +1,236 @@
+use protobuf::reflect::walk_message;
+use protobuf::reflect::walk_message_mut;
+use protobuf::reflect::FieldPath;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::MessageVisitor;
+use protobuf::reflect::MessageVisitorMut;
+use protobuf::reflect::ReflectValueBox;
+use protobuf::reflect::ReflectValueRef;
+use protobuf::MessageDyn;
+use protobuf::UnknownValueRef;
+
+use super::test_visit_pb;
+
+fn dynamic_file_descriptor() -> FileDescriptor {
+    FileDescriptor::new_dynamic(test_visit_pb::file_descriptor().proto().clone(), Vec::new())
+}
+
+fn outer_descriptor(file_descriptor: &FileDescriptor) -> MessageDescriptor {
+    file_descriptor
+        .message_by_package_relative_name("Outer")
+        .unwrap()
+}
+
+fn new_outer(file_descriptor: &FileDescriptor) -> Box<dyn MessageDyn> {
+    let d = outer_descriptor(file_descriptor);
+    let mut m = d.new_instance();
+    for (path, value) in vec![
+        ("secret", ReflectValueBox::String("s0".to_owned())),
+        ("inner.secret", ReflectValueBox::String("s1".to_owned())),
+        ("inner.numbers[0]", ReflectValueBox::I32(1)),
+        ("inner.numbers[1]", ReflectValueBox::I32(2)),
+        ("inners[0].secret", ReflectValueBox::String("s2".to_owned())),
+        ("labels[\"k\"]", ReflectValueBox::String("v".to_owned())),
+        (
+            "inner_by_id[7].secret",
+            ReflectValueBox::String("s3".to_owned()),
+        ),
+    ] {
+        FieldPath::parse(&d, path)
+            .unwrap()
+            .set(&mut *m, value)
+            .unwrap();
+    }
+    m.mut_unknown_fields_dyn().add_varint(100, 33);
+    m
+}
+
+#[derive(Default)]
+struct RecordingVisitor {
+    events: Vec<String>,
+}
+
+impl MessageVisitor for RecordingVisitor {
+    fn enter_message(&mut self, path: &FieldPath, _message: &dyn MessageDyn) {
+        // field is not set for the root message
+        let field = path.field().map_or("-", |f| f.get_name());
+        self.events.push(format!("enter {} {}", path, field));
+    }
+
+    fn leave_message(&mut self, path: &FieldPath, _message: &dyn MessageDyn) {
+        self.events.push(format!("leave {}", path));
+    }
+
+    fn visit_singular(&mut self, path: &FieldPath, value: ReflectValueRef) {
+        self.events.push(format!("singular {} {:?}", path, value));
+    }
+
+    fn visit_repeated_element(&mut self, path: &FieldPath, index: usize, value: ReflectValueRef) {
+        self.events
+            .push(format!("element {} {} {:?}", path, index, value));
+    }
+
+    fn visit_map_entry(&mut self, path: &FieldPath, key: ReflectValueRef, value: ReflectValueRef) {
+        self.events
+            .push(format!("entry {} {:?} {:?}", path, key, value));
+    }
+
+    fn visit_unknown_field(&mut self, path: &FieldPath, number: u32, value: UnknownValueRef) {
+        match value {
+            UnknownValueRef::Varint(v) => self
+                .events
+                .push(format!("unknown {} {} {}", path, number, v)),
+            _ => panic!(),
+        }
+    }
+}
+
+fn do_test_walk(file_descriptor: &FileDescriptor) {
+    let m = new_outer(file_descriptor);
+    let mut visitor = RecordingVisitor::default();
+    walk_message(&*m, &mut visitor);
+    assert_eq!(
+        vec![
+            "enter  -",
+            "singular secret String(\"s0\")",
+            "enter inner inner",
+            "singular inner.secret String(\"s1\")",
+            "element inner.numbers[0] 0 I32(1)",
+            "element inner.numbers[1] 1 I32(2)",
+            "leave inner",
+            "enter inners[0] inners",
+            "singular inners[0].secret String(\"s2\")",
+            "leave inners[0]",
+            "entry labels[\"k\"] String(\"k\") String(\"v\")",
+            "enter inner_by_id[7] inner_by_id",
+            "singular inner_by_id[7].secret String(\"s3\")",
+            "leave inner_by_id[7]",
+            "unknown  100 33",
+            "leave ",
+        ],
+        visitor.events
+    );
+}
+
+#[test]
+fn generated_walk() {
+    do_test_walk(&test_visit_pb::file_descriptor());
+}
+
+#[test]
+fn dynamic_walk() {
+    do_test_walk(&dynamic_file_descriptor());
+}
+
+/// Replace all fields named `secret` with a placeholder.
+struct Redact;
+
+impl MessageVisitorMut for Redact {
+    fn visit_singular(
+        &mut self,
+        path: &FieldPath,
+        _value: ReflectValueRef,
+    ) -> Option<ReflectValueBox> {
+        match path.field().map(|f| f.get_name()) {
+            Some("secret") => Some(ReflectValueBox::String("***".to_owned())),
+            _ => None,
+        }
+    }
+
+    fn visit_repeated_element(
+        &mut self,
+        _path: &FieldPath,
+        _index: usize,
+        value: ReflectValueRef,
+    ) -> Option<ReflectValueBox> {
+        match value {
+            ReflectValueRef::I32(v) => Some(ReflectValueBox::I32(v * 10)),
+            _ => None,
+        }
+    }
+
+    fn visit_map_entry(
+        &mut self,
+        _path: &FieldPath,
+        _key: ReflectValueRef,
+        _value: ReflectValueRef,
+    ) -> Option<ReflectValueBox> {
+        Some(ReflectValueBox::String("x".to_owned()))
+    }
+}
+
+fn do_test_walk_mut(file_descriptor: &FileDescriptor) {
+    let d = outer_descriptor(file_descriptor);
+    let mut m = new_outer(file_descriptor);
+    walk_message_mut(&mut *m, &mut Redact);
+
+    let get = |path: &str| FieldPath::parse(&d, path).unwrap().get(&*m);
+    assert_eq!(Some(ReflectValueRef::String("***")), get("secret"));
+    assert_eq!(Some(ReflectValueRef::String("***")), get("inner.secret"));
+    assert_eq!(Some(ReflectValueRef::I32(20)), get("inner.numbers[1]"));
+    assert_eq!(
+        Some(ReflectValueRef::String("***")),
+        get("inners[0].secret")
+    );
+    assert_eq!(Some(ReflectValueRef::String("x")), get("labels[\"k\"]"));
+    assert_eq!(
+        Some(ReflectValueRef::String("***")),
+        get("inner_by_id[7].secret")
+    );
+}
+
+#[test]
+fn generated_walk_mut() {
+    do_test_walk_mut(&test_visit_pb::file_descriptor());
+}
+
+#[test]
+fn dynamic_walk_mut() {
+    do_test_walk_mut(&dynamic_file_descriptor());
+}
+
+/// Clear nested messages before they are visited.
+struct ClearInners;
+
+impl MessageVisitorMut for ClearInners {
+    fn enter_message(&mut self, path: &FieldPath, message: &mut dyn MessageDyn) {
+        if path.is_empty() {
+            FieldPath::parse(&message.descriptor_dyn(), "inners")
+                .unwrap()
+                .clear(message);
+        }
+    }
+
+    fn visit_singular(
+        &mut self,
+        path: &FieldPath,
+        _value: ReflectValueRef,
+    ) -> Option<ReflectValueBox> {
+        assert!(!path.to_string().starts_with("inners"));
+        None
+    }
+}
+
+fn do_test_walk_mut_modify_on_enter(file_descriptor: &FileDescriptor) {
+    let d = outer_descriptor(file_descriptor);
+    let mut m = new_outer(file_descriptor);
+    walk_message_mut(&mut *m, &mut ClearInners);
+    assert_eq!(
+        0,
+        d.get_field_by_name("inners")
+            .unwrap()
+            .get_repeated(&*m)
+            .len()
+    );
+}
+
+#[test]
+fn generated_walk_mut_modify_on_enter() {
+    do_test_walk_mut_modify_on_enter(&test_visit_pb::file_descriptor());
+}
+
+#[test]
+fn dynamic_walk_mut_modify_on_enter() {
+    do_test_walk_mut_modify_on_enter(&dynamic_file_descriptor());
+}
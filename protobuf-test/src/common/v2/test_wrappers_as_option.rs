@@ -0,0 +1,115 @@
+use protobuf::reflect::ReflectValueBox;
+use protobuf::reflect::ReflectValueRef;
+use protobuf::well_known_types::FloatValue;
+use protobuf::well_known_types::Int32Value;
+use protobuf::well_known_types::Int64Value;
+use protobuf::well_known_types::UInt32Value;
+use protobuf::Message;
+
+use super::test_wrappers_as_option_pb::*;
+
+use protobuf_test_common::*;
+
+fn int32_value(value: i32) -> Int32Value {
+    let mut m = Int32Value::new();
+    m.value = value;
+    m
+}
+
+#[test]
+fn test_field_types() {
+    let mut m = WithWrappers::new();
+    m.int32_value = Some(1);
+    m.string_value = Some("s".to_owned());
+    m.bytes_value = Some(b"b".to_vec());
+    m.bool_value = Some(true);
+    m.double_value = Some(1.5);
+    m.int64_values = vec![1, 2];
+    m.uint32_by_name.insert("a".to_owned(), 3);
+    m.one = Some(with_wrappers::One::float_value(FloatValue::new()));
+    m.raw = Some(int32_value(4)).into();
+}
+
+#[test]
+fn test_binary() {
+    let mut m = WithWrappers::new();
+    m.int32_value = Some(5);
+    test_serialize_deserialize("0a 02 08 05", &m);
+
+    // zero is written as an empty message, so presence is preserved
+    let mut m = WithWrappers::new();
+    m.int32_value = Some(0);
+    test_serialize_deserialize("0a 00", &m);
+
+    let mut m = WithWrappers::new();
+    m.string_value = Some("ab".to_owned());
+    m.int64_values = vec![1, 0];
+    test_serialize_deserialize("12 04 0a 02 61 62 52 02 08 01 52 00", &m);
+
+    let mut m = WithWrappers::new();
+    m.uint32_by_name.insert("a".to_owned(), 3);
+    test_serialize_deserialize("a2 01 07 0a 01 61 12 02 08 03", &m);
+
+    let mut m = WithWrappers::new();
+    m.raw = Some(int32_value(5)).into();
+    test_serialize_deserialize("c2 02 02 08 05", &m);
+}
+
+#[test]
+fn test_json() {
+    let mut m = WithWrappers::new();
+    m.int32_value = Some(5);
+    m.bool_value = Some(false);
+    m.int64_values = vec![1, 2];
+    m.uint32_by_name.insert("a".to_owned(), 3);
+    test_json_print_parse_message(
+        "{\"int32Value\": 5, \"boolValue\": false, \"int64Values\": [\"1\", \"2\"], \"uint32ByName\": {\"a\": 3}}",
+        &m,
+    );
+}
+
+#[test]
+fn test_reflect() {
+    let d = WithWrappers::descriptor_static();
+    let mut m = WithWrappers::new();
+
+    let field = d.get_field_by_name("int32_value").unwrap();
+    assert!(!field.has_field(&m));
+    field.set_singular_field(&mut m, ReflectValueBox::Message(Box::new(int32_value(7))));
+    assert_eq!(Some(7), m.int32_value);
+    let value = field.get_singular_field_or_default(&m);
+    let value = value.to_message().unwrap();
+    assert_eq!(Some(&int32_value(7)), value.downcast_ref::<Int32Value>());
+    field.clear_field(&mut m);
+    assert_eq!(None, m.int32_value);
+
+    let field = d.get_field_by_name("int64_values").unwrap();
+    let mut value = Int64Value::new();
+    value.value = 8;
+    field
+        .mut_repeated(&mut m)
+        .push(ReflectValueBox::Message(Box::new(value.clone())));
+    assert_eq!(vec![8], m.int64_values);
+    let repeated = field.get_repeated(&m);
+    assert_eq!(1, repeated.len());
+    match repeated.get(0) {
+        ReflectValueRef::Message(v) => assert_eq!(Some(&value), v.downcast_ref::<Int64Value>()),
+        v => panic!("unexpected value: {:?}", v),
+    }
+
+    let field = d.get_field_by_name("uint32_by_name").unwrap();
+    let mut value = UInt32Value::new();
+    value.value = 9;
+    field.mut_map(&mut m).insert(
+        ReflectValueBox::String("b".to_owned()),
+        ReflectValueBox::Message(Box::new(value.clone())),
+    );
+    assert_eq!(Some(&9), m.uint32_by_name.get("b"));
+    let map = field.get_map(&m);
+    match map.get(ReflectValueRef::String("b")) {
+        Some(ReflectValueRef::Message(v)) => {
+            assert_eq!(Some(&value), v.downcast_ref::<UInt32Value>())
+        }
+        v => panic!("unexpected value: {:?}", v),
+    }
+}
@@ -0,0 +1,56 @@
+// Types of `common.proto` and `other.proto` generated separately, like in a dependency crate
+pub mod common_protos {
+    include!(concat!(env!("OUT_DIR"), "/extern_path/common/mod.rs"));
+}
+
+// `service.proto` generated with extern paths pointing to `common_protos`
+mod service_protos {
+    include!(concat!(env!("OUT_DIR"), "/extern_path/service/mod.rs"));
+}
+
+use std::path::Path;
+
+use protobuf::reflect::RuntimeTypeBox;
+use protobuf::Message;
+use protobuf::MessageField;
+
+use self::common_protos::common::shared::Nested;
+use self::common_protos::common::Color;
+use self::common_protos::common::Shared;
+use self::common_protos::other::thing::Inner;
+use self::common_protos::other::Thing;
+use self::service_protos::service::Request;
+
+#[test]
+fn test_types_are_shared() {
+    let mut request = Request::new();
+    request.shared = MessageField::some(Shared::new());
+    request.nested = MessageField::some(Nested::new());
+    request.color = Color::GREEN.into();
+    request.thing = MessageField::some(Thing::new());
+    request.inner = MessageField::some(Inner::new());
+
+    let bytes = request.write_to_bytes().unwrap();
+    assert_eq!(request, Request::parse_from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_descriptors_are_shared() {
+    // file descriptor of `service.proto` depends on descriptors of `common_protos`
+    let shared_field = Request::descriptor_static()
+        .get_field_by_name("shared")
+        .unwrap();
+    match shared_field.singular_runtime_type() {
+        RuntimeTypeBox::Message(m) => assert!(Shared::descriptor_static() == m),
+        _ => panic!("message type expected"),
+    }
+}
+
+#[test]
+fn test_extern_files_are_not_generated() {
+    let dir = concat!(env!("OUT_DIR"), "/extern_path/service");
+    assert!(Path::new(dir).join("service.rs").exists());
+    assert!(!Path::new(dir).join("common.rs").exists());
+    // all types of `other.proto` are mapped
+    assert!(!Path::new(dir).join("other.rs").exists());
+}
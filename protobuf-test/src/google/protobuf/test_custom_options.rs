@@ -0,0 +1,83 @@
+//! Check options parsed by `protobuf-codegen-pure` are the same as parsed by `protoc`.
+
+use std::path::Path;
+
+use protobuf::descriptor::DescriptorProto;
+use protobuf::descriptor::EnumDescriptorProto;
+use protobuf::descriptor::FieldDescriptorProto;
+use protobuf::descriptor::FileDescriptorProto;
+
+use super::unittest_custom_options;
+
+fn parse_pure(name: &str) -> FileDescriptorProto {
+    let include = Path::new("src");
+    let parsed = protobuf_codegen_pure::parse_and_typecheck(
+        &[Path::new("../proto").to_owned(), include.to_owned()],
+        &[include.join(name)],
+    )
+    .expect("parse_and_typecheck");
+    parsed
+        .file_descriptors
+        .into_iter()
+        .find(|fd| fd.get_name() == name)
+        .expect("file descriptor")
+}
+
+fn check_fields(expected: &[FieldDescriptorProto], actual: &[FieldDescriptorProto], scope: &str) {
+    assert_eq!(expected.len(), actual.len(), "{}", scope);
+    for (e, a) in expected.iter().zip(actual) {
+        assert_eq!(e.get_name(), a.get_name(), "{}", scope);
+        assert_eq!(e.options, a.options, "{}.{}", scope, e.get_name());
+    }
+}
+
+fn check_enums(expected: &[EnumDescriptorProto], actual: &[EnumDescriptorProto], scope: &str) {
+    assert_eq!(expected.len(), actual.len(), "{}", scope);
+    for (e, a) in expected.iter().zip(actual) {
+        let scope = format!("{}.{}", scope, e.get_name());
+        assert_eq!(e.get_name(), a.get_name(), "{}", scope);
+        assert_eq!(e.options, a.options, "{}", scope);
+        assert_eq!(e.value.len(), a.value.len(), "{}", scope);
+        for (ev, av) in e.value.iter().zip(&a.value) {
+            assert_eq!(ev.get_name(), av.get_name(), "{}", scope);
+            assert_eq!(ev.options, av.options, "{}.{}", scope, ev.get_name());
+        }
+    }
+}
+
+fn check_messages(expected: &[DescriptorProto], actual: &[DescriptorProto], scope: &str) {
+    assert_eq!(expected.len(), actual.len(), "{}", scope);
+    for (e, a) in expected.iter().zip(actual) {
+        let scope = format!("{}.{}", scope, e.get_name());
+        assert_eq!(e.get_name(), a.get_name(), "{}", scope);
+        assert_eq!(e.options, a.options, "{}", scope);
+        check_fields(&e.field, &a.field, &scope);
+        check_fields(&e.extension, &a.extension, &scope);
+        assert_eq!(e.oneof_decl.len(), a.oneof_decl.len(), "{}", scope);
+        for (eo, ao) in e.oneof_decl.iter().zip(&a.oneof_decl) {
+            assert_eq!(eo.options, ao.options, "{}.{}", scope, eo.get_name());
+        }
+        check_enums(&e.enum_type, &a.enum_type, &scope);
+        check_messages(&e.nested_type, &a.nested_type, &scope);
+    }
+}
+
+#[test]
+fn options_same_as_protoc() {
+    let expected = unittest_custom_options::file_descriptor_proto();
+    let actual = parse_pure(expected.get_name());
+
+    assert_eq!(expected.options, actual.options);
+    let scope = format!(".{}", expected.get_package());
+    check_messages(&expected.message_type, &actual.message_type, &scope);
+    check_enums(&expected.enum_type, &actual.enum_type, &scope);
+    check_fields(&expected.extension, &actual.extension, &scope);
+    assert_eq!(expected.service.len(), actual.service.len());
+    for (e, a) in expected.service.iter().zip(&actual.service) {
+        assert_eq!(e.options, a.options, "{}", e.get_name());
+        assert_eq!(e.method.len(), a.method.len(), "{}", e.get_name());
+        for (em, am) in e.method.iter().zip(&a.method) {
+            assert_eq!(em.options, am.options, "{}.{}", e.get_name(), em.get_name());
+        }
+    }
+}
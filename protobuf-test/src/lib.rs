@@ -7,6 +7,12 @@ extern crate protobuf_test_common;
 #[cfg(feature = "with-bytes")]
 extern crate bytes;
 
+#[cfg(feature = "with-chrono")]
+extern crate chrono;
+
+#[cfg(feature = "with-time")]
+extern crate time;
+
 #[cfg(feature = "with-serde")]
 extern crate serde;
 #[cfg(feature = "with-serde")]
@@ -30,3 +36,7 @@ mod google;
 mod interop;
 
 mod include_generated;
+
+mod mods_by_package;
+
+mod extern_path;
@@ -0,0 +1,36 @@
+// Include `mod.rs` generated with `mods_by_package` option,
+// which references `com` and `org` package modules and `root` file module
+include!(concat!(env!("OUT_DIR"), "/mods_by_package/mod.rs"));
+
+use protobuf::Message;
+use protobuf::MessageField;
+
+use com::example::v1::Common;
+use com::example::v1::Kind;
+use com::example::v1::Msg;
+
+#[test]
+fn test() {
+    let mut msg = Msg::new();
+    msg.common = MessageField::some(Common::new());
+    msg.kind = Kind::SPECIAL.into();
+    msg.other = MessageField::some(org::type_pb::Item::new());
+    msg.nested = MessageField::some(org::type_pb::item::Nested::new());
+
+    let mut root = Root::new();
+    root.msg = MessageField::some(msg);
+
+    let bytes = root.write_to_bytes().unwrap();
+    assert_eq!(root, Root::parse_from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_file_descriptor() {
+    let file_descriptor = root::file_descriptor();
+    assert_eq!("root.proto", file_descriptor.proto().get_name());
+    assert_eq!("com.example.v1.Msg", Msg::descriptor_static().full_name());
+    assert_eq!(
+        "org.type.Item.Nested",
+        org::type_pb::item::Nested::descriptor_static().full_name()
+    );
+}
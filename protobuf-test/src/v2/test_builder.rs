@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use protobuf::Buildable;
+use protobuf::Message;
+
+use super::test_builder_pb::*;
+
+#[test]
+fn test_builder() {
+    let mut by_name = HashMap::new();
+    by_name.insert("x".to_owned(), Inner::builder().id(5).build());
+
+    let outer = Outer::builder()
+        .name("n")
+        .items(vec![1, 2])
+        .inner_with(|b| b.id(3).label("three"))
+        .inners(vec![Inner::builder().id(4).build()])
+        .by_name(by_name.clone())
+        .color(Color::GREEN)
+        .data(&b"ab"[..])
+        .ratio(0.5)
+        .build();
+
+    let mut expected = Outer::new();
+    expected.set_name("n".to_owned());
+    expected.items = vec![1, 2];
+    expected.inner.mut_or_default().set_id(3);
+    expected
+        .inner
+        .mut_or_default()
+        .set_label("three".to_owned());
+    expected.inners.push({
+        let mut inner = Inner::new();
+        inner.set_id(4);
+        inner
+    });
+    expected.by_name = by_name;
+    expected.set_color(Color::GREEN);
+    expected.set_data(b"ab".to_vec());
+    expected.set_ratio(0.5);
+
+    assert_eq!(expected, outer);
+}
+
+#[test]
+fn test_builder_message_field() {
+    let outer = Outer::builder().inner(Inner::builder().id(7)).build();
+    assert_eq!(7, outer.inner.get_or_default().get_id());
+
+    let outer = Outer::builder().inner(outer.inner.unwrap()).build();
+    assert_eq!(7, outer.inner.get_or_default().get_id());
+}
+
+#[test]
+fn test_builder_oneof() {
+    let outer = Outer::builder().number(10).text("t").build();
+    assert!(!outer.has_number());
+    assert_eq!("t", outer.get_text());
+
+    let outer = Outer::builder().message_with(|b| b.id(1)).build();
+    assert_eq!(1, outer.get_message().get_id());
+}
+
+#[test]
+fn test_builder_reserved_names() {
+    let outer = Outer::builder().field_build(true).name_with(2).build();
+    assert!(outer.get_build());
+    assert_eq!(2, outer.get_name_with());
+}
+
+#[test]
+fn test_try_build() {
+    assert!(Inner::builder().label("l").try_build().is_err());
+    assert_eq!(1, Inner::builder().id(1).try_build().unwrap().get_id());
+
+    assert!(Outer::builder()
+        .inner(Inner::builder())
+        .try_build()
+        .is_err());
+    let outer = Outer::builder()
+        .inner(Inner::builder().id(2))
+        .try_build()
+        .unwrap();
+    assert!(outer.is_initialized());
+}
+
+#[test]
+fn test_buildable() {
+    fn build_default<M: Buildable>() -> M {
+        M::builder().into()
+    }
+    assert_eq!(Inner::new(), build_default::<Inner>());
+}
+
+#[test]
+fn test_builder_name_clash() {
+    let builder: ClashBuilder_ = Clash::builder().id(1);
+    assert_eq!(1, builder.build().get_id());
+    let message: ClashBuilder = ClashBuilder::builder().name("n").build();
+    assert_eq!("n", message.get_name());
+}
@@ -0,0 +1,107 @@
+use protobuf::text_format;
+use protobuf::Message;
+use protobuf_test_common::hex::decode_hex;
+use protobuf_test_common::*;
+
+use super::test_group_pb::*;
+
+fn identifier(iii: i32, sss: &str) -> message_with_group::Identifier {
+    let mut identifier = message_with_group::Identifier::new();
+    identifier.set_iii(iii);
+    identifier.set_sss(sss.to_owned());
+    identifier
+}
+
+#[test]
+fn test_repeated_group() {
+    let mut m = MessageWithGroup::new();
+    m.identifier.push(identifier(1, "a"));
+    m.identifier.push(identifier(2, ""));
+    test_serialize_deserialize(
+        "93 01 98 01 01 a2 01 01 61 94 01 93 01 98 01 02 a2 01 00 94 01",
+        &m,
+    );
+}
+
+#[test]
+fn test_singular_group() {
+    let mut nested = message_with_group::single::Nested::new();
+    nested.set_y("b".to_owned());
+    let mut single = message_with_group::Single::new();
+    single.set_x(3);
+    single.nested.push(nested);
+    let mut m = MessageWithGroup::new();
+    m.set_aaa("z".to_owned());
+    m.single = Some(single).into();
+    test_serialize_deserialize("0a 01 7a f3 01 f8 01 03 83 02 8a 02 01 62 84 02 f4 01", &m);
+}
+
+#[test]
+fn test_unknown_group_is_preserved() {
+    let mut m = MessageWithGroup::new();
+    m.set_aaa("z".to_owned());
+    m.identifier.push(identifier(1, "a"));
+    m.identifier.push(identifier(2, "b"));
+    m.single = Some(message_with_group::Single::new()).into();
+    m.single.as_mut().unwrap().nested.push(Default::default());
+
+    let parsed = MessageWithoutGroup::parse_from_bytes(&m.write_to_bytes().unwrap()).unwrap();
+    assert_eq!("z", parsed.get_aaa());
+    let unknown_fields = parsed.get_unknown_fields();
+    assert_eq!(2, unknown_fields.get(18).unwrap().groups.len());
+    let single = &unknown_fields.get(30).unwrap().groups[0];
+    assert_eq!(1, single.get(32).unwrap().groups.len());
+
+    let round_trip = MessageWithGroup::parse_from_bytes(&parsed.write_to_bytes().unwrap()).unwrap();
+    assert_eq!(m, round_trip);
+}
+
+#[test]
+fn test_unknown_group_text_format() {
+    let mut m = MessageWithGroup::new();
+    m.identifier.push(identifier(1, "a"));
+    let parsed = MessageWithoutGroup::parse_from_bytes(&m.write_to_bytes().unwrap()).unwrap();
+    assert_eq!(
+        "18 {19: 1 20: \"a\"}",
+        text_format::print_to_string(&parsed)
+    );
+}
+
+#[test]
+fn test_mismatched_end_group() {
+    // start group 18, end group 30
+    let bytes = decode_hex("93 01 f4 01");
+    assert!(MessageWithGroup::parse_from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn test_unterminated_group() {
+    let bytes = decode_hex("93 01 98 01 01");
+    assert!(MessageWithGroup::parse_from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn test_text_format() {
+    let mut m = MessageWithGroup::new();
+    m.identifier.push(identifier(1, "a"));
+    m.single = Some(message_with_group::Single::new()).into();
+
+    let text = text_format::print_to_string(&m);
+    assert_eq!("Identifier {iii: 1 sss: \"a\"} Single {}", text);
+
+    let mut parsed = MessageWithGroup::new();
+    text_format::merge_from_str(&mut parsed, &text).unwrap();
+    assert_eq!(m, parsed);
+
+    // lowercase field names are accepted too
+    let mut parsed = MessageWithGroup::new();
+    text_format::merge_from_str(&mut parsed, "identifier { iii: 1 sss: \"a\" } single {}").unwrap();
+    assert_eq!(m, parsed);
+}
+
+#[test]
+fn test_json() {
+    let mut m = MessageWithGroup::new();
+    m.identifier.push(identifier(1, "a"));
+    test_json_print_parse_message("{\"identifier\": [{\"iii\": 1, \"sss\": \"a\"}]}", &m);
+}
@@ -1,6 +1,26 @@
-use super::test_oneof_group_pb::A;
+use protobuf::Message;
+use protobuf_test_common::*;
+
+use super::test_oneof_group_pb::*;
 
 #[test]
 fn test() {
     A::new();
 }
+
+#[test]
+fn test_group_in_oneof() {
+    let mut c = a::C::new();
+    c.set_d(5);
+    let mut m = A::new();
+    m.set_c(c);
+    test_serialize_deserialize("0b 10 05 0c", &m);
+}
+
+#[test]
+fn test_empty_group() {
+    let mut m = A::new();
+    m.set_c(a::C::new());
+    test_serialize_deserialize("0b 0c", &m);
+    assert!(A::parse_from_bytes(&[0x0b, 0x0c]).unwrap().has_c());
+}
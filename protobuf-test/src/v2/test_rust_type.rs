@@ -0,0 +1,238 @@
+use std::convert::TryFrom;
+
+use protobuf::json;
+use protobuf::reflect::walk_message_mut;
+use protobuf::reflect::FieldPath;
+use protobuf::reflect::MessageVisitorMut;
+use protobuf::reflect::ReflectValueBox;
+use protobuf::reflect::ReflectValueRef;
+use protobuf::text_format;
+use protobuf::well_known_types::FieldMask;
+use protobuf::Message;
+use protobuf::MessageField;
+use protobuf_test_common::hex::decode_hex;
+use protobuf_test_common::*;
+
+use super::test_rust_type_pb::*;
+
+/// Domain type stored in generated message instead of `UserIdProto`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserId(pub u64);
+
+impl TryFrom<UserIdProto> for UserId {
+    type Error = String;
+
+    fn try_from(p: UserIdProto) -> Result<UserId, String> {
+        match p.get_id() {
+            0 => Err("user id must be set".to_owned()),
+            id => Ok(UserId(id)),
+        }
+    }
+}
+
+impl From<UserId> for UserIdProto {
+    fn from(u: UserId) -> UserIdProto {
+        let mut p = UserIdProto::new();
+        p.set_id(u.0);
+        p
+    }
+}
+
+#[test]
+fn test_field_types() {
+    let m = WithRustType::new();
+    let _: &Option<Box<str>> = &m.name;
+    let _: &Option<Box<[u8]>> = &m.data;
+    let _: &Vec<::std::sync::Arc<str>> = &m.tags;
+    let _: &Vec<Box<[u8]>> = &m.chunks;
+    let _: &Option<UserId> = &m.user;
+    let _: &Vec<UserId> = &m.users;
+    let _: &Option<Box<str>> = &m.by_name;
+}
+
+#[test]
+fn test_write_read() {
+    let mut m = WithRustType::new();
+    test_serialize_deserialize("", &m);
+
+    m.name = Some("abc".into());
+    test_serialize_deserialize("0a 03 61 62 63", &m);
+
+    m.data = Some(vec![1, 2].into_boxed_slice());
+    m.tags = vec!["x".into(), "yz".into()];
+    m.chunks = vec![Box::new([]), vec![3].into_boxed_slice()];
+    test_serialize_deserialize(
+        "0a 03 61 62 63 12 02 01 02 1a 01 78 1a 02 79 7a 22 00 22 01 03",
+        &m,
+    );
+
+    m.user = Some(UserId(10));
+    m.users = vec![UserId(11), UserId(12)];
+    m.by_name = Some("d".into());
+    test_serialize_deserialize(
+        "0a 03 61 62 63 12 02 01 02 1a 01 78 1a 02 79 7a 22 00 22 01 03 \
+         2a 02 08 0a 32 02 08 0b 32 02 08 0c 3a 01 64",
+        &m,
+    );
+}
+
+#[test]
+fn test_conversion_failure() {
+    // `user` with zero id
+    let bytes = decode_hex("2a 02 08 00");
+    assert!(WithRustType::parse_from_bytes(&bytes).is_err());
+
+    // `users` with empty message
+    let bytes = decode_hex("32 00");
+    assert!(WithRustType::parse_from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn test_builder() {
+    let m = WithRustType::builder()
+        .name("abc")
+        .tags(vec!["x".into()])
+        .user(UserId(10))
+        .build();
+    assert_eq!(Some("abc"), m.name.as_deref());
+    assert_eq!(vec![::std::sync::Arc::<str>::from("x")], m.tags);
+    assert_eq!(Some(UserId(10)), m.user);
+}
+
+#[test]
+fn test_reflect() {
+    let mut m = WithRustType::new();
+    let d = WithRustType::descriptor_static();
+
+    let name = d.get_field_by_name("name").unwrap();
+    assert!(!name.has_field(&m));
+    assert_eq!("", name.get_singular_field_or_default(&m).to_str().unwrap());
+    name.set_singular_field(&mut m, ReflectValueBox::String("abc".to_owned()));
+    assert_eq!(Some("abc"), m.name.as_deref());
+    assert_eq!("abc", name.get_singular(&m).unwrap().to_str().unwrap());
+    name.clear_field(&mut m);
+    assert_eq!(None, m.name);
+
+    let data = d.get_field_by_name("data").unwrap();
+    data.set_singular_field(&mut m, ReflectValueBox::Bytes(vec![1, 2]));
+    assert_eq!(Some(&[1, 2][..]), m.data.as_deref());
+    assert_eq!(&[1, 2], data.get_singular(&m).unwrap().to_bytes().unwrap());
+
+    let tags = d.get_field_by_name("tags").unwrap();
+    tags.mut_repeated(&mut m)
+        .push(ReflectValueBox::String("x".to_owned()));
+    assert_eq!(vec![::std::sync::Arc::<str>::from("x")], m.tags);
+    let repeated = tags.get_repeated(&m);
+    assert_eq!(1, repeated.len());
+    assert_eq!("x", repeated.get(0).to_str().unwrap());
+
+    let user = d.get_field_by_name("user").unwrap();
+    let mut p = UserIdProto::new();
+    p.set_id(10);
+    user.set_singular_field(&mut m, ReflectValueBox::Message(Box::new(p)));
+    assert_eq!(Some(UserId(10)), m.user);
+
+    let by_name = d.get_field_by_name("by_name").unwrap();
+    by_name.set_singular_field(&mut m, ReflectValueBox::String("d".to_owned()));
+    assert_eq!(Some("d"), m.by_name.as_deref());
+}
+
+#[test]
+fn test_reflect_conversion_failure() {
+    let mut m = WithRustType::new();
+    let d = WithRustType::descriptor_static();
+
+    let user = d.get_field_by_name("user").unwrap();
+    let empty = ReflectValueBox::Message(Box::new(UserIdProto::new()));
+    assert!(user.try_set_singular_field(&mut m, empty).is_err());
+    assert_eq!(None, m.user);
+
+    let users = d.get_field_by_name("users").unwrap();
+    let empty = ReflectValueBox::Message(Box::new(UserIdProto::new()));
+    assert!(users.mut_repeated(&mut m).try_push(empty).is_err());
+    assert!(m.users.is_empty());
+}
+
+#[test]
+fn test_json_conversion_failure() {
+    let m: WithRustType = json::parse_from_str("{\"user\": {\"id\": 10}}").unwrap();
+    assert_eq!(Some(UserId(10)), m.user);
+
+    assert!(json::parse_from_str::<WithRustType>("{\"user\": {}}").is_err());
+    assert!(json::parse_from_str::<WithRustType>("{\"users\": [{}]}").is_err());
+}
+
+#[test]
+fn test_text_format_conversion_failure() {
+    let m: WithRustType = text_format::parse_from_str("user { id: 10 }").unwrap();
+    assert_eq!(Some(UserId(10)), m.user);
+
+    assert!(text_format::parse_from_str::<WithRustType>("user {}").is_err());
+    assert!(text_format::parse_from_str::<WithRustType>("users {}").is_err());
+}
+
+#[test]
+fn test_field_mask() {
+    let mut m = WithRustType::new();
+    m.name = Some("abc".into());
+    m.user = Some(UserId(10));
+
+    FieldMask::from_paths(&["user.id"]).trim(&mut m);
+    assert_eq!(None, m.name);
+    assert_eq!(Some(UserId(10)), m.user);
+
+    let mut source = WithRustType::new();
+    source.user = Some(UserId(11));
+    FieldMask::from_paths(&["user.id"]).merge_message(&source, &mut m, false);
+    assert_eq!(Some(UserId(11)), m.user);
+}
+
+#[test]
+fn test_field_path() {
+    let mut m = WithRustType::new();
+    let d = WithRustType::descriptor_static();
+
+    let user_id = FieldPath::parse(&d, "user.id").unwrap();
+    user_id.set(&mut m, ReflectValueBox::U64(10)).unwrap();
+    assert_eq!(Some(UserId(10)), m.user);
+
+    // Converted message cannot be mutated in place
+    let user = FieldPath::parse(&d, "user").unwrap();
+    assert!(user.mut_message(&mut m).is_err());
+
+    // Cleared id cannot be converted back to `UserId`
+    assert!(user_id.clear(&mut m).is_err());
+    assert_eq!(Some(UserId(10)), m.user);
+}
+
+#[test]
+fn test_walk_message_mut() {
+    struct IncrementIds;
+
+    impl MessageVisitorMut for IncrementIds {
+        fn visit_singular(
+            &mut self,
+            _path: &FieldPath,
+            value: ReflectValueRef,
+        ) -> Option<ReflectValueBox> {
+            value.to_u64().map(|id| ReflectValueBox::U64(id + 1))
+        }
+    }
+
+    let mut m = WithRustType::new();
+    m.user = Some(UserId(10));
+    m.users = vec![UserId(20), UserId(30)];
+    walk_message_mut(&mut m, &mut IncrementIds);
+    assert_eq!(Some(UserId(11)), m.user);
+    assert_eq!(vec![UserId(21), UserId(31)], m.users);
+}
+
+#[test]
+fn test_message_level_rust_type_is_ignored() {
+    let mut m = MessageLevelRustType::new();
+    m.set_id(1);
+    m.set_kind(Kind::KIND_USER);
+    m.set_name("n".to_owned());
+    m.user = MessageField::some(UserIdProto::new());
+    test_serialize_deserialize_no_hex(&m);
+}
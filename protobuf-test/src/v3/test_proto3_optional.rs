@@ -0,0 +1,117 @@
+use protobuf::Message;
+use protobuf_test_common::*;
+
+use super::test_proto3_optional_pb::*;
+
+#[test]
+fn test_accessors() {
+    let mut m = TestProto3Optional::new();
+    assert!(!m.has_iii());
+    assert_eq!(0, m.get_iii());
+
+    m.set_iii(0);
+    assert!(m.has_iii());
+    assert_eq!(0, m.get_iii());
+
+    m.clear_iii();
+    assert!(!m.has_iii());
+
+    m.set_sss(String::new());
+    assert!(m.has_sss());
+    m.set_eee(TestProto3OptionalEnum::UNKNOWN);
+    assert!(m.has_eee());
+}
+
+#[test]
+fn test_default_values_are_written() {
+    let mut m = TestProto3Optional::new();
+    test_serialize_deserialize("", &m);
+
+    m.set_iii(0);
+    test_serialize_deserialize("08 00", &m);
+
+    m.set_sss(String::new());
+    m.set_eee(TestProto3OptionalEnum::UNKNOWN);
+    m.set__iii(false);
+    test_serialize_deserialize("08 00 12 00 18 00 40 00", &m);
+
+    // plain proto3 field without presence
+    m.plain = 0;
+    test_serialize_deserialize("08 00 12 00 18 00 40 00", &m);
+}
+
+#[test]
+fn test_synthetic_oneofs_in_descriptor() {
+    let descriptor = TestProto3Optional::descriptor_static();
+    let oneof_names: Vec<&str> = descriptor
+        .get_proto()
+        .oneof_decl
+        .iter()
+        .map(|o| o.get_name())
+        .collect();
+    assert_eq!(
+        vec!["regular", "X_iii", "_sss", "_eee", "_mmm", "XX_iii"],
+        oneof_names
+    );
+
+    let iii = descriptor.get_field_by_name("iii").unwrap();
+    assert!(iii.get_proto().get_proto3_optional());
+    assert_eq!(1, iii.get_proto().get_oneof_index());
+    assert!(!descriptor
+        .get_field_by_name("plain")
+        .unwrap()
+        .get_proto()
+        .get_proto3_optional());
+}
+
+#[test]
+fn test_reflect_hides_synthetic_oneofs() {
+    let descriptor = TestProto3Optional::descriptor_static();
+
+    let oneofs: Vec<String> = descriptor
+        .oneofs()
+        .map(|o| o.get_name().to_owned())
+        .collect();
+    assert_eq!(vec!["regular".to_owned()], oneofs);
+
+    for name in &["iii", "sss", "eee", "mmm", "_iii", "plain"] {
+        let field = descriptor.get_field_by_name(name).unwrap();
+        assert!(field.containing_oneof().is_none(), "{}", name);
+    }
+    let one = descriptor.get_field_by_name("one").unwrap();
+    assert_eq!("regular", one.containing_oneof().unwrap().get_name());
+}
+
+#[test]
+fn test_reflect_has_field() {
+    let descriptor = TestProto3Optional::descriptor_static();
+    let iii = descriptor.get_field_by_name("iii").unwrap();
+    let plain = descriptor.get_field_by_name("plain").unwrap();
+
+    let mut m = TestProto3Optional::new();
+    assert!(!iii.has_field(&m));
+
+    m.set_iii(0);
+    m.plain = 0;
+    assert!(iii.has_field(&m));
+    assert!(!plain.has_field(&m));
+
+    iii.clear_field(&mut m);
+    assert!(!m.has_iii());
+}
+
+#[test]
+fn test_text_format_and_json() {
+    let mut m = TestProto3Optional::new();
+    m.set_iii(0);
+    m.set_sss(String::new());
+
+    assert_eq!(
+        "iii: 0 sss: \"\"",
+        protobuf::text_format::print_to_string(&m)
+    );
+    test_json_print_parse_message("{\"iii\": 0, \"sss\": \"\"}", &m);
+
+    let parsed: TestProto3Optional = protobuf::json::parse_from_str("{\"iii\": 0}").unwrap();
+    assert!(parsed.has_iii());
+}
@@ -0,0 +1,51 @@
+use protobuf::reflect::ReflectValueBox;
+use protobuf::Message;
+use protobuf_test_common::*;
+
+use super::test_rust_type_proto3_pb::*;
+
+#[test]
+fn test_field_types() {
+    let m = WithRustType::new();
+    let _: &Box<str> = &m.name;
+    let _: &Box<[u8]> = &m.data;
+    let _: &Option<Box<str>> = &m.opt_name;
+}
+
+#[test]
+fn test_write_read() {
+    let mut m = WithRustType::new();
+    test_serialize_deserialize("", &m);
+
+    m.opt_name = Some("".into());
+    test_serialize_deserialize("1a 00", &m);
+
+    m.name = "abc".into();
+    m.data = vec![1].into_boxed_slice();
+    test_serialize_deserialize("0a 03 61 62 63 12 01 01 1a 00", &m);
+}
+
+#[test]
+fn test_reflect() {
+    let mut m = WithRustType::new();
+    let d = WithRustType::descriptor_static();
+
+    let name = d.get_field_by_name("name").unwrap();
+    assert!(!name.has_field(&m));
+    assert_eq!(None, name.get_singular(&m));
+    name.set_singular_field(&mut m, ReflectValueBox::String("abc".to_owned()));
+    assert_eq!("abc", &*m.name);
+    assert!(name.has_field(&m));
+    assert_eq!("abc", name.get_singular(&m).unwrap().to_str().unwrap());
+    name.clear_field(&mut m);
+    assert_eq!("", &*m.name);
+
+    let data = d.get_field_by_name("data").unwrap();
+    data.set_singular_field(&mut m, ReflectValueBox::Bytes(vec![1, 2]));
+    assert_eq!(&[1, 2], &*m.data);
+
+    let opt_name = d.get_field_by_name("opt_name").unwrap();
+    opt_name.set_singular_field(&mut m, ReflectValueBox::String("".to_owned()));
+    assert!(opt_name.has_field(&m));
+    assert_eq!(Some(""), m.opt_name.as_deref());
+}
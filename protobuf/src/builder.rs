@@ -0,0 +1,15 @@
+use crate::Message;
+
+/// Trait implemented by messages generated with `gen_builders` codegen option.
+///
+/// Generated builders use it to accept a closure which configures
+/// a builder of a nested message, e. g. `.inner_with(|b| b.id(3))`.
+pub trait Buildable: Message {
+    /// Builder type generated for this message.
+    type Builder: Default + Into<Self>;
+
+    /// Create a builder with all fields unset.
+    fn builder() -> Self::Builder {
+        Self::Builder::default()
+    }
+}
@@ -1,3 +1,4 @@
+use std::cmp;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::sync::atomic::AtomicUsize;
@@ -54,3 +55,16 @@ impl Hash for CachedSize {
         // ignore cached size in cache computation
     }
 }
+
+impl PartialOrd for CachedSize {
+    fn partial_cmp(&self, other: &CachedSize) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CachedSize {
+    fn cmp(&self, _other: &CachedSize) -> cmp::Ordering {
+        // all cached sizes are equal
+        cmp::Ordering::Equal
+    }
+}
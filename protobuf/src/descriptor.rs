@@ -79,7 +79,7 @@ impl crate::Message for FileDescriptorSet {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.file)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -466,7 +466,7 @@ impl crate::Message for FileDescriptorProto {
                     self.syntax = ::std::option::Option::Some(is.read_string()?);
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -849,7 +849,7 @@ impl crate::Message for DescriptorProto {
                     crate::rt::read_repeated_string_into(wire_type, is, &mut self.reserved_name)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -1125,7 +1125,7 @@ pub mod descriptor_proto {
                         crate::rt::read_singular_message_into_field(wire_type, is, &mut self.options)?;
                     },
                     _ => {
-                        crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                        crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                     },
                 };
             }
@@ -1325,7 +1325,7 @@ pub mod descriptor_proto {
                         self.end = ::std::option::Option::Some(is.read_int32()?);
                     },
                     _ => {
-                        crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                        crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                     },
                 };
             }
@@ -1465,7 +1465,7 @@ impl crate::Message for ExtensionRangeOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -2050,7 +2050,7 @@ impl crate::Message for FieldDescriptorProto {
                     self.proto3_optional = ::std::option::Option::Some(is.read_bool()?);
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -2465,7 +2465,7 @@ impl crate::Message for OneofDescriptorProto {
                     crate::rt::read_singular_message_into_field(wire_type, is, &mut self.options)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -2696,7 +2696,7 @@ impl crate::Message for EnumDescriptorProto {
                     crate::rt::read_repeated_string_into(wire_type, is, &mut self.reserved_name)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -2919,7 +2919,7 @@ pub mod enum_descriptor_proto {
                         self.end = ::std::option::Option::Some(is.read_int32()?);
                     },
                     _ => {
-                        crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                        crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                     },
                 };
             }
@@ -3140,7 +3140,7 @@ impl crate::Message for EnumValueDescriptorProto {
                     crate::rt::read_singular_message_into_field(wire_type, is, &mut self.options)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -3351,7 +3351,7 @@ impl crate::Message for ServiceDescriptorProto {
                     crate::rt::read_singular_message_into_field(wire_type, is, &mut self.options)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -3715,7 +3715,7 @@ impl crate::Message for MethodDescriptorProto {
                     self.server_streaming = ::std::option::Option::Some(is.read_bool()?);
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -4762,7 +4762,7 @@ impl crate::Message for FileOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -5246,7 +5246,7 @@ impl crate::Message for MessageOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -5661,7 +5661,7 @@ impl crate::Message for FieldOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -5948,7 +5948,7 @@ impl crate::Message for OneofOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -6150,7 +6150,7 @@ impl crate::Message for EnumOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -6334,7 +6334,7 @@ impl crate::Message for EnumValueOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -6510,7 +6510,7 @@ impl crate::Message for ServiceOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -6721,7 +6721,7 @@ impl crate::Message for MethodOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -7177,7 +7177,7 @@ impl crate::Message for UninterpretedOption {
                     self.aggregate_value = ::std::option::Option::Some(is.read_string()?);
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -7436,7 +7436,7 @@ pub mod uninterpreted_option {
                         self.is_extension = ::std::option::Option::Some(is.read_bool()?);
                     },
                     _ => {
-                        crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                        crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                     },
                 };
             }
@@ -7577,7 +7577,7 @@ impl crate::Message for SourceCodeInfo {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.location)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -7826,7 +7826,7 @@ pub mod source_code_info {
                         crate::rt::read_repeated_string_into(wire_type, is, &mut self.leading_detached_comments)?;
                     },
                     _ => {
-                        crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                        crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                     },
                 };
             }
@@ -8004,7 +8004,7 @@ impl crate::Message for GeneratedCodeInfo {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.annotation)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -8253,7 +8253,7 @@ pub mod generated_code_info {
                         self.end = ::std::option::Option::Some(is.read_int32()?);
                     },
                     _ => {
-                        crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                        crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                     },
                 };
             }
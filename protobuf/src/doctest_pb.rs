@@ -62,7 +62,7 @@ impl crate::Message for MyMessage {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -1,4 +1,7 @@
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::marker;
 
 use crate::reflect::runtime_types::RuntimeTypeEnumOrUnknown;
@@ -37,7 +40,7 @@ pub trait ProtobufEnum: Eq + Sized + Copy + 'static + ProtobufValue + fmt::Debug
 }
 
 /// Protobuf enums with possibly unknown values are preserved in this struct.
-#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+#[derive(Eq, PartialEq, Copy, Clone)]
 #[repr(transparent)]
 // TODO: specify <E: ProtobufEnum> when it no longer prevents using const fns
 pub struct ProtobufEnumOrUnknown<E> {
@@ -98,6 +101,26 @@ impl<E: ProtobufEnum> ProtobufEnumOrUnknown<E> {
     }
 }
 
+// Implemented manually to not require `E: Hash` or `E: Ord`:
+// values are compared and hashed by number.
+impl<E> Hash for ProtobufEnumOrUnknown<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<E: PartialEq> PartialOrd for ProtobufEnumOrUnknown<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.value.cmp(&other.value))
+    }
+}
+
+impl<E: Eq> Ord for ProtobufEnumOrUnknown<E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
 impl<E: ProtobufEnum> From<E> for ProtobufEnumOrUnknown<E> {
     fn from(e: E) -> Self {
         ProtobufEnumOrUnknown::new(e)
@@ -22,6 +22,7 @@ pub enum WireError {
     InvalidEnumValue(i32),
     OverRecursionLimit,
     TruncatedMessage,
+    ValueOutOfRange,
     Other,
 }
 
@@ -37,6 +38,7 @@ impl fmt::Display for WireError {
             WireError::UnexpectedEof => write!(f, "unexpected EOF"),
             WireError::OverRecursionLimit => write!(f, "over recursion limit"),
             WireError::TruncatedMessage => write!(f, "truncated message"),
+            WireError::ValueOutOfRange => write!(f, "value out of range"),
             WireError::Other => write!(f, "other error"),
         }
     }
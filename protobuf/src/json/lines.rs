@@ -0,0 +1,531 @@
+//! [JSON Lines](https://jsonlines.org/) (newline-delimited JSON) reader and writer:
+//! one protobuf message per line.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+use std::str;
+use std::str::Utf8Error;
+
+use crate::json::parse::ParseError;
+use crate::json::parse_dynamic_from_str_with_options;
+use crate::json::parse_from_str_with_options;
+use crate::json::print::PrintError;
+use crate::json::print_to_string_with_options;
+use crate::json::ParseOptions;
+use crate::json::PrintOptions;
+use crate::message::Message;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::MessageDescriptor;
+
+/// What to do with a line which cannot be parsed or a message which cannot be printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonLinesErrorPolicy {
+    /// Return an error to the caller. Iterators stop after the first error.
+    FailFast,
+    /// Skip the line or the message and continue.
+    /// Skipped errors are passed to the handler set with `set_skipped_error_handler`
+    /// and counted by `skipped_error_count`.
+    Skip,
+}
+
+impl Default for JsonLinesErrorPolicy {
+    fn default() -> Self {
+        JsonLinesErrorPolicy::FailFast
+    }
+}
+
+/// Error reading or writing JSON lines.
+#[derive(Debug)]
+pub enum JsonLinesError {
+    /// I/O error, reported regardless of error policy.
+    Io(io::Error),
+    /// Line is not valid UTF-8, line numbers start with 1.
+    Utf8 {
+        /// Line number
+        line: u64,
+        /// UTF-8 decoding error within the line
+        error: Utf8Error,
+    },
+    /// Line cannot be parsed, line numbers start with 1.
+    Parse {
+        /// Line number
+        line: u64,
+        /// Parse error within the line
+        error: ParseError,
+    },
+    /// Message cannot be printed, line numbers start with 1.
+    Print {
+        /// Number of the line the message would be written to
+        line: u64,
+        /// Print error
+        error: PrintError,
+    },
+}
+
+impl fmt::Display for JsonLinesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonLinesError::Io(e) => write!(f, "{}", e),
+            JsonLinesError::Utf8 { line, error } => write!(f, "line {}: {}", line, error),
+            JsonLinesError::Parse { line, error } => write!(f, "line {}: {}", line, error),
+            JsonLinesError::Print { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl Error for JsonLinesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            JsonLinesError::Io(e) => Some(e),
+            JsonLinesError::Utf8 { error, .. } => Some(error),
+            JsonLinesError::Parse { error, .. } => Some(error),
+            JsonLinesError::Print { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for JsonLinesError {
+    fn from(e: io::Error) -> Self {
+        JsonLinesError::Io(e)
+    }
+}
+
+/// Errors skipped with [`JsonLinesErrorPolicy::Skip`] policy.
+#[derive(Default)]
+struct SkippedErrors {
+    count: u64,
+    handler: Option<Box<dyn FnMut(JsonLinesError)>>,
+}
+
+impl SkippedErrors {
+    fn skip(&mut self, error: JsonLinesError) {
+        self.count += 1;
+        if let Some(handler) = &mut self.handler {
+            handler(error);
+        }
+    }
+}
+
+/// Read messages from JSON lines, one message per line.
+///
+/// Empty lines are ignored.
+///
+/// ```
+/// # use protobuf::json::JsonLinesReader;
+/// # use protobuf::well_known_types::Duration;
+/// let input = "\"1s\"\n\"2.500s\"\n";
+/// let mut reader = JsonLinesReader::new(input.as_bytes());
+/// let mut durations = Vec::new();
+/// while let Some(duration) = reader.read_message::<Duration>().unwrap() {
+///     durations.push(duration);
+/// }
+/// assert_eq!(2, durations.len());
+/// ```
+pub struct JsonLinesReader<R: BufRead> {
+    read: R,
+    parse_options: ParseOptions,
+    error_policy: JsonLinesErrorPolicy,
+    line_number: u64,
+    skipped_errors: SkippedErrors,
+}
+
+impl<R: BufRead> JsonLinesReader<R> {
+    /// Create a reader with default parse options and fail-fast error policy.
+    pub fn new(read: R) -> JsonLinesReader<R> {
+        JsonLinesReader {
+            read,
+            parse_options: ParseOptions::default(),
+            error_policy: JsonLinesErrorPolicy::default(),
+            line_number: 0,
+            skipped_errors: SkippedErrors::default(),
+        }
+    }
+
+    /// Set options used to parse each line.
+    pub fn set_parse_options(&mut self, parse_options: ParseOptions) {
+        self.parse_options = parse_options;
+    }
+
+    /// Set what to do with lines which cannot be parsed.
+    pub fn set_error_policy(&mut self, error_policy: JsonLinesErrorPolicy) {
+        self.error_policy = error_policy;
+    }
+
+    /// Number of the last line read, zero before the first line.
+    pub fn line_number(&self) -> u64 {
+        self.line_number
+    }
+
+    /// Call `handler` for each line skipped with [`JsonLinesErrorPolicy::Skip`] policy.
+    ///
+    /// Skipped errors are dropped when no handler is set.
+    pub fn set_skipped_error_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(JsonLinesError) + 'static,
+    {
+        self.skipped_errors.handler = Some(Box::new(handler));
+    }
+
+    /// Number of lines skipped with [`JsonLinesErrorPolicy::Skip`] policy.
+    pub fn skipped_error_count(&self) -> u64 {
+        self.skipped_errors.count
+    }
+
+    fn read_with<T, F>(&mut self, mut parse: F) -> Result<Option<T>, JsonLinesError>
+    where
+        F: FnMut(&str, &ParseOptions) -> Result<T, ParseError>,
+    {
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            if self.read.read_until(b'\n', &mut line)? == 0 {
+                return Ok(None);
+            }
+            self.line_number += 1;
+
+            let error = match str::from_utf8(&line) {
+                Ok(line) => {
+                    let json = line.trim();
+                    if json.is_empty() {
+                        continue;
+                    }
+                    match parse(json, &self.parse_options) {
+                        Ok(m) => return Ok(Some(m)),
+                        Err(error) => JsonLinesError::Parse {
+                            line: self.line_number,
+                            error,
+                        },
+                    }
+                }
+                Err(error) => JsonLinesError::Utf8 {
+                    line: self.line_number,
+                    error,
+                },
+            };
+            match self.error_policy {
+                JsonLinesErrorPolicy::FailFast => return Err(error),
+                JsonLinesErrorPolicy::Skip => self.skipped_errors.skip(error),
+            }
+        }
+    }
+
+    /// Read next message, `None` on end of input.
+    pub fn read_message<M: Message>(&mut self) -> Result<Option<M>, JsonLinesError> {
+        self.read_with(parse_from_str_with_options)
+    }
+
+    /// Read next message of type specified by descriptor, `None` on end of input.
+    pub fn read_dynamic(
+        &mut self,
+        descriptor: &MessageDescriptor,
+    ) -> Result<Option<Box<dyn MessageDyn>>, JsonLinesError> {
+        self.read_with(|json, parse_options| {
+            parse_dynamic_from_str_with_options(descriptor, json, parse_options)
+        })
+    }
+
+    fn into_iter_with<T, F>(
+        mut self,
+        mut read: F,
+    ) -> impl Iterator<Item = Result<T, JsonLinesError>>
+    where
+        F: FnMut(&mut Self) -> Result<Option<T>, JsonLinesError>,
+    {
+        let mut done = false;
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            match read(&mut self) {
+                Ok(Some(m)) => Some(Ok(m)),
+                Ok(None) => {
+                    done = true;
+                    None
+                }
+                Err(e) => {
+                    done = true;
+                    Some(Err(e))
+                }
+            }
+        })
+    }
+
+    /// Iterate over all messages of the input.
+    ///
+    /// With [`JsonLinesErrorPolicy::Skip`] policy invalid lines are passed to
+    /// the [skipped error handler](Self::set_skipped_error_handler).
+    pub fn messages<M: Message>(self) -> impl Iterator<Item = Result<M, JsonLinesError>> {
+        self.into_iter_with(|r| r.read_message())
+    }
+
+    /// Iterate over all messages of the input of type specified by descriptor.
+    ///
+    /// Invalid lines are handled like in [`messages`](Self::messages).
+    pub fn dynamic_messages(
+        self,
+        descriptor: MessageDescriptor,
+    ) -> impl Iterator<Item = Result<Box<dyn MessageDyn>, JsonLinesError>> {
+        self.into_iter_with(move |r| r.read_dynamic(&descriptor))
+    }
+}
+
+/// Write messages as JSON lines, one message per line.
+///
+/// ```
+/// # use protobuf::json::JsonLinesWriter;
+/// # use protobuf::well_known_types::Duration;
+/// let mut writer = JsonLinesWriter::new(Vec::new());
+/// writer.write_message(&Duration::from(std::time::Duration::from_secs(1))).unwrap();
+/// assert_eq!(b"\"1s\"\n", &writer.into_inner()[..]);
+/// ```
+pub struct JsonLinesWriter<W: Write> {
+    write: W,
+    print_options: PrintOptions,
+    error_policy: JsonLinesErrorPolicy,
+    line_number: u64,
+    skipped_errors: SkippedErrors,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    /// Create a writer with default print options and fail-fast error policy.
+    pub fn new(write: W) -> JsonLinesWriter<W> {
+        JsonLinesWriter {
+            write,
+            print_options: PrintOptions::default(),
+            error_policy: JsonLinesErrorPolicy::default(),
+            line_number: 0,
+            skipped_errors: SkippedErrors::default(),
+        }
+    }
+
+    /// Set options used to print each message.
+    pub fn set_print_options(&mut self, print_options: PrintOptions) {
+        self.print_options = print_options;
+    }
+
+    /// Set what to do with messages which cannot be printed.
+    pub fn set_error_policy(&mut self, error_policy: JsonLinesErrorPolicy) {
+        self.error_policy = error_policy;
+    }
+
+    /// Number of lines written.
+    pub fn line_number(&self) -> u64 {
+        self.line_number
+    }
+
+    /// Call `handler` for each message skipped with [`JsonLinesErrorPolicy::Skip`] policy.
+    ///
+    /// Skipped errors are dropped when no handler is set.
+    pub fn set_skipped_error_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(JsonLinesError) + 'static,
+    {
+        self.skipped_errors.handler = Some(Box::new(handler));
+    }
+
+    /// Number of messages skipped with [`JsonLinesErrorPolicy::Skip`] policy.
+    pub fn skipped_error_count(&self) -> u64 {
+        self.skipped_errors.count
+    }
+
+    /// Write a message as a single line.
+    pub fn write_message(&mut self, message: &dyn MessageDyn) -> Result<(), JsonLinesError> {
+        let json = match print_to_string_with_options(message, &self.print_options) {
+            Ok(json) => json,
+            Err(error) => {
+                let error = JsonLinesError::Print {
+                    line: self.line_number + 1,
+                    error,
+                };
+                return match self.error_policy {
+                    JsonLinesErrorPolicy::FailFast => Err(error),
+                    JsonLinesErrorPolicy::Skip => {
+                        self.skipped_errors.skip(error);
+                        Ok(())
+                    }
+                };
+            }
+        };
+        // Printer escapes line breaks in strings, so the message is a single line
+        debug_assert!(!json.contains('\n'));
+        self.write.write_all(json.as_bytes())?;
+        self.write.write_all(b"\n")?;
+        self.line_number += 1;
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> Result<(), JsonLinesError> {
+        self.write.flush()?;
+        Ok(())
+    }
+
+    /// Get the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.write
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::well_known_types::Duration;
+    use crate::well_known_types::FieldMask;
+    use crate::well_known_types::SourceContext;
+
+    fn duration(seconds: i64) -> Duration {
+        let mut d = Duration::new();
+        d.seconds = seconds;
+        d
+    }
+
+    #[test]
+    fn write_read() {
+        let mut writer = JsonLinesWriter::new(Vec::new());
+        writer.write_message(&duration(1)).unwrap();
+        writer.write_message(&duration(2)).unwrap();
+        assert_eq!(2, writer.line_number());
+        let output = writer.into_inner();
+        assert_eq!(
+            "\"1s\"\n\"2s\"\n",
+            String::from_utf8(output.clone()).unwrap()
+        );
+
+        let reader = JsonLinesReader::new(&output[..]);
+        let read: Vec<Duration> = reader.messages().collect::<Result<_, _>>().unwrap();
+        assert_eq!(vec![duration(1), duration(2)], read);
+    }
+
+    #[test]
+    fn read_dynamic() {
+        let input = "\"1s\"\n\r\n  \n\"3s\"\r\n";
+        let mut reader = JsonLinesReader::new(input.as_bytes());
+        let descriptor = Duration::descriptor_static();
+        let first = reader.read_dynamic(&descriptor).unwrap().unwrap();
+        assert!(descriptor.eq(&duration(1), &*first));
+        let second = reader.read_dynamic(&descriptor).unwrap().unwrap();
+        assert!(descriptor.eq(&duration(3), &*second));
+        assert_eq!(4, reader.line_number());
+        assert!(reader.read_dynamic(&descriptor).unwrap().is_none());
+    }
+
+    #[test]
+    fn fail_fast() {
+        let input = "\"1s\"\n\"x\"\n\"3s\"\n";
+        let mut reader = JsonLinesReader::new(input.as_bytes());
+        assert_eq!(Some(duration(1)), reader.read_message().unwrap());
+        match reader.read_message::<Duration>() {
+            Err(JsonLinesError::Parse { line: 2, .. }) => {}
+            r => panic!("unexpected: {:?}", r),
+        }
+        // reading may continue after an error
+        assert_eq!(Some(duration(3)), reader.read_message().unwrap());
+
+        let reader = JsonLinesReader::new(input.as_bytes());
+        let read: Vec<_> = reader.messages::<Duration>().collect();
+        assert_eq!(2, read.len());
+        assert!(read[1].is_err());
+        assert!(format!("{}", read[1].as_ref().unwrap_err()).starts_with("line 2: "));
+    }
+
+    fn skipped_lines(reader: &mut JsonLinesReader<&[u8]>) -> Rc<RefCell<Vec<u64>>> {
+        let lines = Rc::new(RefCell::new(Vec::new()));
+        let lines_copy = lines.clone();
+        reader.set_skipped_error_handler(move |e| {
+            lines_copy.borrow_mut().push(match e {
+                JsonLinesError::Parse { line, .. } | JsonLinesError::Utf8 { line, .. } => line,
+                e => panic!("unexpected: {:?}", e),
+            })
+        });
+        lines
+    }
+
+    #[test]
+    fn skip() {
+        let input = "\"1s\"\n\"x\"\n\"3s\"\n{\n";
+        let mut reader = JsonLinesReader::new(input.as_bytes());
+        reader.set_error_policy(JsonLinesErrorPolicy::Skip);
+        let lines = skipped_lines(&mut reader);
+        assert_eq!(Some(duration(1)), reader.read_message().unwrap());
+        assert_eq!(Some(duration(3)), reader.read_message().unwrap());
+        assert_eq!(None, reader.read_message::<Duration>().unwrap());
+        assert_eq!(2, reader.skipped_error_count());
+        assert_eq!(vec![2, 4], *lines.borrow());
+    }
+
+    #[test]
+    fn skip_in_iterator() {
+        let input = "\"x\"\n\"1s\"\n\"y\"\n";
+        let mut reader = JsonLinesReader::new(input.as_bytes());
+        reader.set_error_policy(JsonLinesErrorPolicy::Skip);
+        let lines = skipped_lines(&mut reader);
+        let read: Vec<Duration> = reader.messages().collect::<Result<_, _>>().unwrap();
+        assert_eq!(vec![duration(1)], read);
+        assert_eq!(vec![1, 3], *lines.borrow());
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let input: &[u8] = b"\"1s\"\n\"\xff\"\n\"x\"\n\"4s\"\n";
+        let mut reader = JsonLinesReader::new(input);
+        assert_eq!(Some(duration(1)), reader.read_message().unwrap());
+        match reader.read_message::<Duration>() {
+            Err(JsonLinesError::Utf8 { line: 2, .. }) => {}
+            r => panic!("unexpected: {:?}", r),
+        }
+        match reader.read_message::<Duration>() {
+            Err(JsonLinesError::Parse { line: 3, .. }) => {}
+            r => panic!("unexpected: {:?}", r),
+        }
+        assert_eq!(Some(duration(4)), reader.read_message().unwrap());
+
+        let mut reader = JsonLinesReader::new(input);
+        reader.set_error_policy(JsonLinesErrorPolicy::Skip);
+        let lines = skipped_lines(&mut reader);
+        let read: Vec<Duration> = reader.messages().collect::<Result<_, _>>().unwrap();
+        assert_eq!(vec![duration(1), duration(4)], read);
+        assert_eq!(vec![2, 3], *lines.borrow());
+    }
+
+    #[test]
+    fn parse_options() {
+        let input = "{\"fileName\": \"a.proto\", \"unknown\": 2}\n";
+        assert!(JsonLinesReader::new(input.as_bytes())
+            .read_message::<SourceContext>()
+            .is_err());
+
+        let mut reader = JsonLinesReader::new(input.as_bytes());
+        reader.set_parse_options(ParseOptions {
+            ignore_unknown_fields: true,
+            ..Default::default()
+        });
+        let read: SourceContext = reader.read_message().unwrap().unwrap();
+        assert_eq!("a.proto", read.file_name);
+    }
+
+    #[test]
+    fn write_errors() {
+        let mut invalid = FieldMask::new();
+        invalid.paths.push("fooBar".to_owned());
+        let mut valid = FieldMask::new();
+        valid.paths.push("foo_bar".to_owned());
+
+        let mut writer = JsonLinesWriter::new(Vec::new());
+        match writer.write_message(&invalid) {
+            Err(JsonLinesError::Print { line: 1, .. }) => {}
+            r => panic!("unexpected: {:?}", r),
+        }
+
+        writer.set_error_policy(JsonLinesErrorPolicy::Skip);
+        writer.write_message(&invalid).unwrap();
+        writer.write_message(&valid).unwrap();
+        assert_eq!(1, writer.skipped_error_count());
+        assert_eq!(b"\"fooBar\"\n", &writer.into_inner()[..]);
+    }
+}
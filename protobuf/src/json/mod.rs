@@ -4,13 +4,22 @@
 mod base64;
 mod float;
 mod json_name;
+mod lines;
 mod parse;
 mod print;
-mod rfc_3339;
+pub(crate) mod rfc_3339;
+#[cfg(feature = "with-serde")]
+pub mod serde;
+#[cfg(feature = "with-serde-json")]
+mod serde_json_value;
 mod well_known_wrapper;
 
 #[doc(hidden)]
 pub use self::json_name::json_name;
+pub use self::lines::JsonLinesError;
+pub use self::lines::JsonLinesErrorPolicy;
+pub use self::lines::JsonLinesReader;
+pub use self::lines::JsonLinesWriter;
 pub use self::parse::merge_from_str;
 pub use self::parse::merge_from_str_with_options;
 pub use self::parse::parse_dynamic_from_str;
@@ -23,3 +32,5 @@ pub use self::print::print_to_string;
 pub use self::print::print_to_string_with_options;
 pub use self::print::PrintError;
 pub use self::print::PrintOptions;
+#[cfg(feature = "with-serde-json")]
+pub use self::serde_json_value::SerdeJsonValueError;
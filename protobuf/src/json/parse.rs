@@ -1,5 +1,7 @@
+use std::convert::TryFrom;
 use std::num::ParseFloatError;
 use std::num::ParseIntError;
+use std::num::TryFromIntError;
 
 use std::f32;
 use std::f64;
@@ -10,6 +12,7 @@ use super::base64;
 use crate::enums::ProtobufEnum;
 use crate::json::base64::FromBase64Error;
 use crate::message::Message;
+use crate::reflect::ConversionError;
 use crate::reflect::EnumDescriptor;
 use crate::reflect::EnumValueDescriptor;
 use crate::reflect::FieldDescriptor;
@@ -51,7 +54,9 @@ use crate::well_known_types::UInt64Value;
 use crate::well_known_types::Value;
 
 #[derive(Debug)]
-enum ParseErrorWithoutLocInner {
+// some errors are only produced by the serde adapter
+#[cfg_attr(not(feature = "with-serde"), allow(dead_code))]
+pub(crate) enum ParseErrorWithoutLocInner {
     TokenizerError(TokenizerError),
     UnknownFieldName(String),
     UnknownEnumVariantName(String),
@@ -62,17 +67,20 @@ enum ParseErrorWithoutLocInner {
     Rfc3339(rfc_3339::Rfc3339ParseError),
     ParseIntError(ParseIntError),
     ParseFloatError(ParseFloatError),
+    TryFromIntError(TryFromIntError),
     ExpectingBool,
+    ExpectingInteger,
     ExpectingStrOrInt,
     ExpectingNumber,
     UnexpectedToken,
     AnyParsingIsNotImplemented,
     MessageNotInitialized,
+    ConversionError(ConversionError),
 }
 
 /// JSON parse error.
 #[derive(Debug)]
-struct ParseErrorWithoutLoc(ParseErrorWithoutLocInner);
+pub(crate) struct ParseErrorWithoutLoc(pub(crate) ParseErrorWithoutLocInner);
 
 impl fmt::Display for ParseErrorWithoutLoc {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -93,7 +101,9 @@ impl fmt::Display for ParseErrorWithoutLoc {
             ParseErrorWithoutLocInner::Rfc3339(e) => write!(f, "RFC3339 parse error: {}", e),
             ParseErrorWithoutLocInner::ParseIntError(e) => write!(f, "{}", e),
             ParseErrorWithoutLocInner::ParseFloatError(e) => write!(f, "{}", e),
+            ParseErrorWithoutLocInner::TryFromIntError(e) => write!(f, "{}", e),
             ParseErrorWithoutLocInner::ExpectingBool => write!(f, "expecting bool"),
+            ParseErrorWithoutLocInner::ExpectingInteger => write!(f, "expecting integer"),
             ParseErrorWithoutLocInner::ExpectingStrOrInt => {
                 write!(f, "expecting string or integer")
             }
@@ -105,6 +115,7 @@ impl fmt::Display for ParseErrorWithoutLoc {
             ParseErrorWithoutLocInner::MessageNotInitialized => {
                 write!(f, "Message not initialized")
             }
+            ParseErrorWithoutLocInner::ConversionError(e) => write!(f, "{}", e),
         }
     }
 }
@@ -117,6 +128,12 @@ impl From<TokenizerError> for ParseErrorWithoutLoc {
     }
 }
 
+impl From<ConversionError> for ParseErrorWithoutLoc {
+    fn from(e: ConversionError) -> Self {
+        ParseErrorWithoutLoc(ParseErrorWithoutLocInner::ConversionError(e))
+    }
+}
+
 impl From<FromBase64Error> for ParseErrorWithoutLoc {
     fn from(e: FromBase64Error) -> Self {
         ParseErrorWithoutLoc(ParseErrorWithoutLocInner::FromBase64Error(e))
@@ -135,6 +152,12 @@ impl From<ParseFloatError> for ParseErrorWithoutLoc {
     }
 }
 
+impl From<TryFromIntError> for ParseErrorWithoutLoc {
+    fn from(e: TryFromIntError) -> Self {
+        ParseErrorWithoutLoc(ParseErrorWithoutLocInner::TryFromIntError(e))
+    }
+}
+
 impl From<rfc_3339::Rfc3339ParseError> for ParseErrorWithoutLoc {
     fn from(e: rfc_3339::Rfc3339ParseError) -> Self {
         ParseErrorWithoutLoc(ParseErrorWithoutLocInner::Rfc3339(e))
@@ -156,7 +179,7 @@ impl fmt::Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
-type ParseResultWithoutLoc<A> = Result<A, ParseErrorWithoutLoc>;
+pub(crate) type ParseResultWithoutLoc<A> = Result<A, ParseErrorWithoutLoc>;
 type ParseResult<A> = Result<A, ParseError>;
 
 #[derive(Clone)]
@@ -165,10 +188,15 @@ struct Parser<'a> {
     parse_options: ParseOptions,
 }
 
-trait FromJsonNumber: PartialEq + Sized {
+#[cfg_attr(not(feature = "with-serde"), allow(dead_code))]
+pub(crate) trait FromJsonNumber: PartialEq + Sized {
     fn from_f64(v: f64) -> Self;
     fn to_f64(&self) -> f64;
     fn from_string(v: &str) -> ParseResultWithoutLoc<Self>;
+    /// Integer already parsed by a serde deserializer.
+    fn from_integer(v: i128) -> ParseResultWithoutLoc<Self>;
+    /// Fractional number already parsed by a serde deserializer.
+    fn from_fractional(v: f64) -> ParseResultWithoutLoc<Self>;
 }
 
 impl FromJsonNumber for u32 {
@@ -183,6 +211,16 @@ impl FromJsonNumber for u32 {
     fn from_string(v: &str) -> Result<Self, ParseErrorWithoutLoc> {
         Ok(v.parse()?)
     }
+
+    fn from_integer(v: i128) -> ParseResultWithoutLoc<Self> {
+        Ok(u32::try_from(v)?)
+    }
+
+    fn from_fractional(_v: f64) -> ParseResultWithoutLoc<Self> {
+        Err(ParseErrorWithoutLoc(
+            ParseErrorWithoutLocInner::ExpectingInteger,
+        ))
+    }
 }
 
 impl FromJsonNumber for u64 {
@@ -197,6 +235,16 @@ impl FromJsonNumber for u64 {
     fn from_string(v: &str) -> Result<Self, ParseErrorWithoutLoc> {
         Ok(v.parse()?)
     }
+
+    fn from_integer(v: i128) -> ParseResultWithoutLoc<Self> {
+        Ok(u64::try_from(v)?)
+    }
+
+    fn from_fractional(_v: f64) -> ParseResultWithoutLoc<Self> {
+        Err(ParseErrorWithoutLoc(
+            ParseErrorWithoutLocInner::ExpectingInteger,
+        ))
+    }
 }
 
 impl FromJsonNumber for i32 {
@@ -211,6 +259,16 @@ impl FromJsonNumber for i32 {
     fn from_string(v: &str) -> Result<Self, ParseErrorWithoutLoc> {
         Ok(v.parse()?)
     }
+
+    fn from_integer(v: i128) -> ParseResultWithoutLoc<Self> {
+        Ok(i32::try_from(v)?)
+    }
+
+    fn from_fractional(_v: f64) -> ParseResultWithoutLoc<Self> {
+        Err(ParseErrorWithoutLoc(
+            ParseErrorWithoutLocInner::ExpectingInteger,
+        ))
+    }
 }
 
 impl FromJsonNumber for i64 {
@@ -225,6 +283,16 @@ impl FromJsonNumber for i64 {
     fn from_string(v: &str) -> Result<Self, ParseErrorWithoutLoc> {
         Ok(v.parse()?)
     }
+
+    fn from_integer(v: i128) -> ParseResultWithoutLoc<Self> {
+        Ok(i64::try_from(v)?)
+    }
+
+    fn from_fractional(_v: f64) -> ParseResultWithoutLoc<Self> {
+        Err(ParseErrorWithoutLoc(
+            ParseErrorWithoutLocInner::ExpectingInteger,
+        ))
+    }
 }
 
 impl FromJsonNumber for f32 {
@@ -247,6 +315,14 @@ impl FromJsonNumber for f32 {
             Ok(v.parse()?)
         }
     }
+
+    fn from_integer(v: i128) -> ParseResultWithoutLoc<Self> {
+        Ok(v as f32)
+    }
+
+    fn from_fractional(v: f64) -> ParseResultWithoutLoc<Self> {
+        Ok(v as f32)
+    }
 }
 
 impl FromJsonNumber for f64 {
@@ -269,6 +345,98 @@ impl FromJsonNumber for f64 {
             Ok(v.parse()?)
         }
     }
+
+    fn from_integer(v: i128) -> ParseResultWithoutLoc<Self> {
+        Ok(v as f64)
+    }
+
+    fn from_fractional(v: f64) -> ParseResultWithoutLoc<Self> {
+        Ok(v)
+    }
+}
+
+// Rules for values read from strings, shared with the serde adapter.
+
+pub(crate) fn parse_bool(s: &str) -> ParseResultWithoutLoc<bool> {
+    if s == "true" {
+        Ok(true)
+    } else if s == "false" {
+        Ok(false)
+    } else {
+        Err(ParseErrorWithoutLoc(
+            ParseErrorWithoutLocInner::ExpectingBool,
+        ))
+    }
+}
+
+pub(crate) fn parse_number<V: FromJsonNumber>(s: &str) -> ParseResultWithoutLoc<V> {
+    V::from_string(s)
+}
+
+pub(crate) fn parse_bytes(s: &str) -> ParseResultWithoutLoc<Vec<u8>> {
+    Ok(base64::decode(s)?)
+}
+
+pub(crate) fn parse_enum(
+    name: String,
+    descriptor: &EnumDescriptor,
+) -> ParseResultWithoutLoc<EnumValueDescriptor> {
+    // TODO: can map key be int
+    match descriptor.get_value_by_name(&name) {
+        Some(v) => Ok(v),
+        None => Err(ParseErrorWithoutLoc(
+            ParseErrorWithoutLocInner::UnknownEnumVariantName(name),
+        )),
+    }
+}
+
+pub(crate) fn enum_value_by_number(
+    number: i32,
+    descriptor: &EnumDescriptor,
+) -> ParseResultWithoutLoc<EnumValueDescriptor> {
+    match descriptor.get_value_by_number(number) {
+        Some(v) => Ok(v),
+        // TODO: EnumValueOrUnknown
+        None => Err(ParseErrorWithoutLoc(
+            ParseErrorWithoutLocInner::UnknownEnumVariantNumber(number),
+        )),
+    }
+}
+
+pub(crate) fn parse_key(key: String, t: &RuntimeTypeBox) -> ParseResultWithoutLoc<ReflectValueBox> {
+    match t {
+        RuntimeTypeBox::I32 => parse_number::<i32>(&key).map(ReflectValueBox::I32),
+        RuntimeTypeBox::I64 => parse_number::<i64>(&key).map(ReflectValueBox::I64),
+        RuntimeTypeBox::U32 => parse_number::<u32>(&key).map(ReflectValueBox::U32),
+        RuntimeTypeBox::U64 => parse_number::<u64>(&key).map(ReflectValueBox::U64),
+        RuntimeTypeBox::Bool => parse_bool(&key).map(ReflectValueBox::Bool),
+        RuntimeTypeBox::String => Ok(ReflectValueBox::String(key)),
+        t @ RuntimeTypeBox::F32
+        | t @ RuntimeTypeBox::F64
+        | t @ RuntimeTypeBox::VecU8
+        | t @ RuntimeTypeBox::Enum(..) => panic!("{} cannot be a map key", t),
+        RuntimeTypeBox::Message(_) => panic!("message cannot be a map key"),
+    }
+}
+
+pub(crate) fn merge_duration_str(duration: &mut Duration, s: &str) -> ParseResultWithoutLoc<()> {
+    let parsed: Duration = s
+        .parse()
+        .map_err(|_| ParseErrorWithoutLoc(ParseErrorWithoutLocInner::IncorrectDuration))?;
+    duration.seconds = parsed.seconds;
+    duration.nanos = parsed.nanos;
+    Ok(())
+}
+
+pub(crate) fn merge_timestamp_str(timestamp: &mut Timestamp, s: &str) -> ParseResultWithoutLoc<()> {
+    let (seconds, nanos) = rfc_3339::TmUtc::parse_rfc_3339(s)?;
+    timestamp.seconds = seconds;
+    timestamp.nanos = nanos as i32;
+    Ok(())
+}
+
+pub(crate) fn merge_field_mask_str(field_mask: &mut FieldMask, s: &str) {
+    field_mask.paths = FieldMask::from_json_string(s).paths;
 }
 
 impl<'a> Parser<'a> {
@@ -284,18 +452,6 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_bool(&self, s: &str) -> ParseResultWithoutLoc<bool> {
-        if s == "true" {
-            Ok(true)
-        } else if s == "false" {
-            Ok(false)
-        } else {
-            Err(ParseErrorWithoutLoc(
-                ParseErrorWithoutLocInner::ExpectingBool,
-            ))
-        }
-    }
-
     fn read_json_number_opt(&mut self) -> ParseResultWithoutLoc<Option<JsonNumberLit>> {
         Ok(self.tokenizer.next_token_if_map(|t| match t {
             Token::JsonNumber(v) => Some(v.clone()),
@@ -308,7 +464,7 @@ impl<'a> Parser<'a> {
             V::from_string(&v.0)
         } else if self.tokenizer.lookahead_is_str_lit()? {
             let v = self.read_string()?;
-            self.parse_number(&v)
+            parse_number(&v)
         } else {
             Err(ParseErrorWithoutLoc(
                 ParseErrorWithoutLocInner::ExpectingNumber,
@@ -316,10 +472,6 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_number<V: FromJsonNumber>(&self, s: &str) -> ParseResultWithoutLoc<V> {
-        V::from_string(s)
-    }
-
     fn merge_wrapper<W>(&mut self, w: &mut W) -> ParseResultWithoutLoc<()>
     where
         W: WellKnownWrapper,
@@ -386,11 +538,7 @@ impl<'a> Parser<'a> {
 
     fn read_bytes(&mut self) -> ParseResultWithoutLoc<Vec<u8>> {
         let s = self.read_string()?;
-        self.parse_bytes(&s)
-    }
-
-    fn parse_bytes(&self, s: &str) -> ParseResultWithoutLoc<Vec<u8>> {
-        Ok(base64::decode(s)?)
+        parse_bytes(&s)
     }
 
     fn read_enum(
@@ -403,16 +551,10 @@ impl<'a> Parser<'a> {
 
         if self.tokenizer.lookahead_is_str_lit()? {
             let name = self.read_string()?;
-            self.parse_enum(name, descriptor)
+            parse_enum(name, descriptor)
         } else if self.tokenizer.lookahead_is_json_number()? {
             let number = self.read_i32()?;
-            match descriptor.get_value_by_number(number) {
-                Some(v) => Ok(v),
-                // TODO: EnumValueOrUnknown
-                None => Err(ParseErrorWithoutLoc(
-                    ParseErrorWithoutLocInner::UnknownEnumVariantNumber(number),
-                )),
-            }
+            enum_value_by_number(number, descriptor)
         } else {
             Err(ParseErrorWithoutLoc(
                 ParseErrorWithoutLocInner::ExpectingStrOrInt,
@@ -420,20 +562,6 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_enum(
-        &self,
-        name: String,
-        descriptor: &EnumDescriptor,
-    ) -> ParseResultWithoutLoc<EnumValueDescriptor> {
-        // TODO: can map key be int
-        match descriptor.get_value_by_name(&name) {
-            Some(v) => Ok(v),
-            None => Err(ParseErrorWithoutLoc(
-                ParseErrorWithoutLocInner::UnknownEnumVariantName(name),
-            )),
-        }
-    }
-
     fn read_wk_null_value(&mut self) -> ParseResultWithoutLoc<NullValue> {
         self.tokenizer.next_ident_expect_eq("null")?;
         Ok(NullValue::NULL_VALUE)
@@ -470,7 +598,7 @@ impl<'a> Parser<'a> {
         field: &FieldDescriptor,
         t: &RuntimeTypeBox,
     ) -> ParseResultWithoutLoc<()> {
-        field.set_singular_field(message, self.read_value(t)?);
+        field.try_set_singular_field(message, self.read_value(t)?)?;
         Ok(())
     }
 
@@ -507,7 +635,7 @@ impl<'a> Parser<'a> {
         repeated.clear();
 
         self.read_list(|s| {
-            repeated.push(s.read_value(t)?);
+            repeated.try_push(s.read_value(t)?)?;
             Ok(())
         })
     }
@@ -552,22 +680,6 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
-    fn parse_key(&self, key: String, t: &RuntimeTypeBox) -> ParseResultWithoutLoc<ReflectValueBox> {
-        match t {
-            RuntimeTypeBox::I32 => self.parse_number::<i32>(&key).map(ReflectValueBox::I32),
-            RuntimeTypeBox::I64 => self.parse_number::<i64>(&key).map(ReflectValueBox::I64),
-            RuntimeTypeBox::U32 => self.parse_number::<u32>(&key).map(ReflectValueBox::U32),
-            RuntimeTypeBox::U64 => self.parse_number::<u64>(&key).map(ReflectValueBox::U64),
-            RuntimeTypeBox::Bool => self.parse_bool(&key).map(ReflectValueBox::Bool),
-            RuntimeTypeBox::String => Ok(ReflectValueBox::String(key)),
-            t @ RuntimeTypeBox::F32
-            | t @ RuntimeTypeBox::F64
-            | t @ RuntimeTypeBox::VecU8
-            | t @ RuntimeTypeBox::Enum(..) => panic!("{} cannot be a map key", t),
-            RuntimeTypeBox::Message(_) => panic!("message cannot be a map key"),
-        }
-    }
-
     fn merge_map_field(
         &mut self,
         message: &mut dyn MessageDyn,
@@ -579,10 +691,10 @@ impl<'a> Parser<'a> {
         map.clear();
 
         self.read_map(
-            |ss, s| ss.parse_key(s, kt),
+            |_, s| parse_key(s, kt),
             |s, k| {
                 let v = s.read_value(vt)?;
-                map.insert(k, v);
+                map.try_insert(k, v)?;
                 Ok(())
             },
         )
@@ -734,91 +846,17 @@ impl<'a> Parser<'a> {
 
     fn merge_wk_duration(&mut self, duration: &mut Duration) -> ParseResultWithoutLoc<()> {
         let s = self.read_string()?;
-        let mut lexer = Lexer::new(&s, ParserLanguage::Json);
-
-        fn next_dec(lexer: &mut Lexer) -> ParseResultWithoutLoc<(u64, u32)> {
-            let s = lexer.take_while(|c| c >= '0' && c <= '9');
-
-            if s.len() == 0 {
-                Ok((0, 0))
-            } else {
-                match s.parse() {
-                    Ok(n) => Ok((n, s.len() as u32)),
-                    Err(_) => Err(ParseErrorWithoutLoc(
-                        ParseErrorWithoutLocInner::IncorrectDuration,
-                    )),
-                }
-            }
-        }
-
-        let minus = lexer.next_char_if_eq('-');
-        let seconds = match next_dec(&mut lexer)? {
-            (_, 0) => {
-                return Err(ParseErrorWithoutLoc(
-                    ParseErrorWithoutLocInner::IncorrectDuration,
-                ))
-            }
-            (s, _) => s,
-        };
-        let nanos = if lexer.next_char_if_eq('.') {
-            let (mut a, mut b) = next_dec(&mut lexer)?;
-            if b > 9 {
-                return Err(ParseErrorWithoutLoc(
-                    ParseErrorWithoutLocInner::IncorrectDuration,
-                ));
-            }
-            while b != 9 {
-                b += 1;
-                a *= 10;
-            }
-
-            if a > 999_999_999 {
-                return Err(ParseErrorWithoutLoc(
-                    ParseErrorWithoutLocInner::IncorrectDuration,
-                ));
-            }
-
-            a
-        } else {
-            0
-        };
-
-        // The suffix "s" is required
-        if !lexer.next_char_if_eq('s') {
-            return Err(ParseErrorWithoutLoc(
-                ParseErrorWithoutLocInner::IncorrectDuration,
-            ));
-        }
-
-        if !lexer.eof() {
-            return Err(ParseErrorWithoutLoc(
-                ParseErrorWithoutLocInner::IncorrectDuration,
-            ));
-        }
-
-        if minus {
-            duration.seconds = -(seconds as i64);
-            duration.nanos = -(nanos as i32);
-        } else {
-            duration.seconds = seconds as i64;
-            duration.nanos = nanos as i32;
-        }
-        Ok(())
+        merge_duration_str(duration, &s)
     }
 
     fn merge_wk_timestamp(&mut self, timestamp: &mut Timestamp) -> ParseResultWithoutLoc<()> {
         let s = self.read_string()?;
-        let (seconds, nanos) = rfc_3339::TmUtc::parse_rfc_3339(&s)?;
-        timestamp.seconds = seconds;
-        timestamp.nanos = nanos as i32;
-        Ok(())
+        merge_timestamp_str(timestamp, &s)
     }
 
     fn merge_wk_field_mask(&mut self, field_mask: &mut FieldMask) -> ParseResultWithoutLoc<()> {
         let s = self.read_string()?;
-        if !s.is_empty() {
-            field_mask.paths = s.split(',').map(|s| s.to_owned()).collect();
-        }
+        merge_field_mask_str(field_mask, &s);
         Ok(())
     }
 
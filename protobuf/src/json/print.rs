@@ -32,22 +32,37 @@ use crate::well_known_types::Value;
 
 use crate::json::well_known_wrapper::WellKnownWrapper;
 
-use crate::json::rfc_3339::TmUtc;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::EnumValueDescriptor;
 use crate::reflect::RuntimeFieldType;
 use crate::reflect::RuntimeTypeBox;
 
 #[derive(Debug)]
-enum PrintErrorInner {
+pub(crate) enum PrintErrorInner {
     Fmt(fmt::Error),
     AnyPrintingIsNotImplemented,
     TimestampNegativeNanos,
+    FieldMaskInvalidPath,
 }
 
 /// Print to JSON error.
 #[derive(Debug)]
-pub struct PrintError(PrintErrorInner);
+pub struct PrintError(pub(crate) PrintErrorInner);
+
+impl fmt::Display for PrintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            PrintErrorInner::Fmt(e) => write!(f, "{}", e),
+            PrintErrorInner::AnyPrintingIsNotImplemented => {
+                write!(f, "Any printing is not implemented")
+            }
+            PrintErrorInner::TimestampNegativeNanos => write!(f, "timestamp with negative nanos"),
+            PrintErrorInner::FieldMaskInvalidPath => write!(f, "invalid field mask path"),
+        }
+    }
+}
+
+impl std::error::Error for PrintError {}
 
 impl From<fmt::Error> for PrintError {
     fn from(e: fmt::Error) -> Self {
@@ -66,35 +81,34 @@ trait PrintableToJson {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()>;
 }
 
+/// NaN and infinities are printed as strings.
+pub(crate) fn float_special_name(v: f64) -> Option<&'static str> {
+    if v.is_nan() {
+        Some(float::PROTOBUF_JSON_NAN)
+    } else if v.is_infinite() && v > 0.0 {
+        Some(float::PROTOBUF_JSON_INF)
+    } else if v.is_infinite() {
+        Some(float::PROTOBUF_JSON_MINUS_INF)
+    } else {
+        None
+    }
+}
+
 trait JsonFloat: fmt::Display + fmt::Debug + PrintableToJson {
-    fn is_nan(&self) -> bool;
-    fn is_pos_infinity(&self) -> bool;
-    fn is_neg_infinity(&self) -> bool;
+    fn to_f64(&self) -> f64;
 
     fn print_to_json_impl(&self, w: &mut String) -> PrintResult<()> {
-        Ok(if self.is_nan() {
-            write!(w, "\"{}\"", float::PROTOBUF_JSON_NAN)?
-        } else if self.is_pos_infinity() {
-            write!(w, "\"{}\"", float::PROTOBUF_JSON_INF)?
-        } else if self.is_neg_infinity() {
-            write!(w, "\"{}\"", float::PROTOBUF_JSON_MINUS_INF)?
-        } else {
-            write!(w, "{:?}", self)?
-        })
+        match float_special_name(self.to_f64()) {
+            Some(name) => write!(w, "\"{}\"", name)?,
+            None => write!(w, "{:?}", self)?,
+        }
+        Ok(())
     }
 }
 
 impl JsonFloat for f32 {
-    fn is_nan(&self) -> bool {
-        f32::is_nan(*self)
-    }
-
-    fn is_pos_infinity(&self) -> bool {
-        f32::is_infinite(*self) && self > &0.0
-    }
-
-    fn is_neg_infinity(&self) -> bool {
-        f32::is_infinite(*self) && self < &0.0
+    fn to_f64(&self) -> f64 {
+        *self as f64
     }
 }
 
@@ -105,16 +119,8 @@ impl PrintableToJson for f32 {
 }
 
 impl JsonFloat for f64 {
-    fn is_nan(&self) -> bool {
-        f64::is_nan(*self)
-    }
-
-    fn is_pos_infinity(&self) -> bool {
-        f64::is_infinite(*self) && self > &0.0
-    }
-
-    fn is_neg_infinity(&self) -> bool {
-        f64::is_infinite(*self) && self < &0.0
+    fn to_f64(&self) -> f64 {
+        *self
     }
 }
 
@@ -214,30 +220,33 @@ impl<'a> PrintableToJson for ReflectValueRef<'a> {
 
 impl PrintableToJson for Duration {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        let sign = if self.seconds >= 0 { "" } else { "-" };
-        Ok(write!(
-            w.buf,
-            "\"{}{}.{:09}s\"",
-            sign,
-            self.seconds.abs(),
-            self.nanos.abs()
-        )?)
+        w.print_printable(&self.to_string())
+    }
+}
+
+pub(crate) fn timestamp_to_json_string(timestamp: &Timestamp) -> PrintResult<String> {
+    if timestamp.nanos < 0 {
+        return Err(PrintError(PrintErrorInner::TimestampNegativeNanos));
+    }
+    Ok(timestamp.to_string())
+}
+
+pub(crate) fn field_mask_to_json_string(field_mask: &FieldMask) -> PrintResult<String> {
+    match field_mask.to_json_string() {
+        Some(s) => Ok(s),
+        None => Err(PrintError(PrintErrorInner::FieldMaskInvalidPath)),
     }
 }
 
 impl PrintableToJson for Timestamp {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        if self.nanos < 0 {
-            return Err(PrintError(PrintErrorInner::TimestampNegativeNanos));
-        }
-        let tm_utc = TmUtc::from_protobuf_timestamp(self.seconds, self.nanos as u32);
-        w.print_printable(&tm_utc.to_string())
+        w.print_printable(&timestamp_to_json_string(self)?)
     }
 }
 
 impl PrintableToJson for FieldMask {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        w.print_printable(&self.paths.join(","))
+        w.print_printable(&field_mask_to_json_string(self)?)
     }
 }
 
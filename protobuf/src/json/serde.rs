@@ -0,0 +1,892 @@
+//! [Serde](https://serde.rs) adapter for messages following protobuf JSON mapping.
+//!
+//! Messages are serialized exactly as [`print_to_string`](crate::json::print_to_string)
+//! prints them and deserialized exactly as [`parse_from_str`](crate::json::parse_from_str)
+//! parses them: with camelCase field names, enums as strings, 64-bit integers as strings
+//! and special forms of well-known types. So messages can be embedded in larger
+//! serde documents, or serialized to other self-describing formats like YAML or CBOR.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "with-serde")] {
+//! use protobuf::well_known_types::Duration;
+//!
+//! #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+//! struct Config {
+//!     #[serde(with = "protobuf::json::serde")]
+//!     timeout: Duration,
+//! }
+//! # }
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de;
+use serde::de::DeserializeSeed;
+use serde::ser;
+use serde::ser::SerializeMap;
+use serde::ser::SerializeSeq;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::json::base64;
+use crate::json::parse;
+use crate::json::parse::FromJsonNumber;
+use crate::json::parse::ParseErrorWithoutLoc;
+use crate::json::parse::ParseErrorWithoutLocInner;
+use crate::json::print;
+use crate::json::print::PrintError;
+use crate::json::print::PrintErrorInner;
+use crate::json::well_known_wrapper::WellKnownWrapper;
+use crate::message::Message;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::EnumDescriptor;
+use crate::reflect::EnumValueDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectMapMut;
+use crate::reflect::ReflectMapRef;
+use crate::reflect::ReflectRepeatedMut;
+use crate::reflect::ReflectRepeatedRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::well_known_types::value;
+use crate::well_known_types::Any;
+use crate::well_known_types::BoolValue;
+use crate::well_known_types::BytesValue;
+use crate::well_known_types::DoubleValue;
+use crate::well_known_types::Duration;
+use crate::well_known_types::FieldMask;
+use crate::well_known_types::FloatValue;
+use crate::well_known_types::Int32Value;
+use crate::well_known_types::Int64Value;
+use crate::well_known_types::ListValue;
+use crate::well_known_types::NullValue;
+use crate::well_known_types::StringValue;
+use crate::well_known_types::Struct;
+use crate::well_known_types::Timestamp;
+use crate::well_known_types::UInt32Value;
+use crate::well_known_types::UInt64Value;
+use crate::well_known_types::Value;
+use crate::ProtobufEnum;
+
+// Serialization walks the message with reflection,
+// emitting the same values as `print_to_string` with default options.
+
+fn serialize_f64<S: Serializer>(v: f64, serializer: S) -> Result<S::Ok, S::Error> {
+    match print::float_special_name(v) {
+        Some(name) => serializer.serialize_str(name),
+        None => serializer.serialize_f64(v),
+    }
+}
+
+fn serialize_f32<S: Serializer>(v: f32, serializer: S) -> Result<S::Ok, S::Error> {
+    match print::float_special_name(v as f64) {
+        Some(name) => serializer.serialize_str(name),
+        None => serializer.serialize_f32(v),
+    }
+}
+
+struct ValueSer<'a>(ReflectValueRef<'a>);
+
+impl<'a> Serialize for ValueSer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.0 {
+            ReflectValueRef::U32(v) => serializer.serialize_u32(*v),
+            // 64-bit integers are quoted
+            ReflectValueRef::U64(v) => serializer.collect_str(v),
+            ReflectValueRef::I32(v) => serializer.serialize_i32(*v),
+            ReflectValueRef::I64(v) => serializer.collect_str(v),
+            ReflectValueRef::F32(v) => serialize_f32(*v, serializer),
+            ReflectValueRef::F64(v) => serialize_f64(*v, serializer),
+            ReflectValueRef::Bool(v) => serializer.serialize_bool(*v),
+            ReflectValueRef::String(v) => serializer.serialize_str(v),
+            ReflectValueRef::Bytes(v) => serializer.serialize_str(&base64::encode(v)),
+            ReflectValueRef::Enum(d, v) => {
+                if d.is::<NullValue>() {
+                    return serializer.serialize_unit();
+                }
+                match d.get_value_by_number(*v) {
+                    Some(value) => serializer.serialize_str(value.get_name()),
+                    None => serializer.serialize_i32(*v),
+                }
+            }
+            ReflectValueRef::Message(m) => MessageSer(&**m).serialize(serializer),
+        }
+    }
+}
+
+/// Map keys are always strings.
+struct MapKeySer<'a>(ReflectValueRef<'a>);
+
+impl<'a> Serialize for MapKeySer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.0 {
+            ReflectValueRef::U32(v) => serializer.collect_str(v),
+            ReflectValueRef::U64(v) => serializer.collect_str(v),
+            ReflectValueRef::I32(v) => serializer.collect_str(v),
+            ReflectValueRef::I64(v) => serializer.collect_str(v),
+            ReflectValueRef::Bool(v) => serializer.collect_str(v),
+            ReflectValueRef::String(v) => serializer.serialize_str(v),
+            ReflectValueRef::Bytes(v) => serializer.serialize_str(&base64::encode(v)),
+            ReflectValueRef::Enum(..)
+            | ReflectValueRef::F32(_)
+            | ReflectValueRef::F64(_)
+            | ReflectValueRef::Message(_) => panic!("cannot be object key"),
+        }
+    }
+}
+
+struct RepeatedSer<'a>(ReflectRepeatedRef<'a>);
+
+impl<'a> Serialize for RepeatedSer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for v in &self.0 {
+            seq.serialize_element(&ValueSer(v))?;
+        }
+        seq.end()
+    }
+}
+
+struct MapSer<'a>(ReflectMapRef<'a>);
+
+impl<'a> Serialize for MapSer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (k, v) in &self.0 {
+            map.serialize_entry(&MapKeySer(k), &ValueSer(v))?;
+        }
+        map.end()
+    }
+}
+
+struct MessageSer<'a>(&'a dyn MessageDyn);
+
+impl<'a> MessageSer<'a> {
+    fn serialize_wk_value<S: Serializer>(value: &Value, serializer: S) -> Result<S::Ok, S::Error> {
+        match &value.kind {
+            None | Some(value::Kind::null_value(_)) => serializer.serialize_unit(),
+            Some(value::Kind::bool_value(b)) => serializer.serialize_bool(*b),
+            Some(value::Kind::number_value(n)) => serialize_f64(*n, serializer),
+            Some(value::Kind::string_value(s)) => serializer.serialize_str(s),
+            Some(value::Kind::struct_value(s)) => MessageSer(s).serialize(serializer),
+            Some(value::Kind::list_value(l)) => MessageSer(l).serialize(serializer),
+        }
+    }
+
+    fn serialize_regular<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let message = self.0;
+        let descriptor = message.descriptor_dyn();
+
+        let is_present = |v: &ReflectFieldRef| match v {
+            ReflectFieldRef::Optional(v) => v.is_some(),
+            ReflectFieldRef::Repeated(v) => !v.is_empty(),
+            ReflectFieldRef::Map(v) => !v.is_empty(),
+        };
+
+        let len = descriptor
+            .fields()
+            .filter(|f| is_present(&f.get_reflect(message)))
+            .count();
+        let mut map = serializer.serialize_map(Some(len))?;
+        for field in descriptor.fields() {
+            match field.get_reflect(message) {
+                ReflectFieldRef::Optional(None) => {}
+                ReflectFieldRef::Optional(Some(v)) => {
+                    map.serialize_entry(field.json_name(), &ValueSer(v))?
+                }
+                ReflectFieldRef::Repeated(v) => {
+                    if !v.is_empty() {
+                        map.serialize_entry(field.json_name(), &RepeatedSer(v))?;
+                    }
+                }
+                ReflectFieldRef::Map(v) => {
+                    if !v.is_empty() {
+                        map.serialize_entry(field.json_name(), &MapSer(v))?;
+                    }
+                }
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'a> Serialize for MessageSer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let message = self.0;
+        if let Some(duration) = message.downcast_ref::<Duration>() {
+            serializer.collect_str(duration)
+        } else if let Some(timestamp) = message.downcast_ref::<Timestamp>() {
+            let s = print::timestamp_to_json_string(timestamp).map_err(ser::Error::custom)?;
+            serializer.serialize_str(&s)
+        } else if let Some(field_mask) = message.downcast_ref::<FieldMask>() {
+            let s = print::field_mask_to_json_string(field_mask).map_err(ser::Error::custom)?;
+            serializer.serialize_str(&s)
+        } else if message.downcast_ref::<Any>().is_some() {
+            Err(ser::Error::custom(PrintError(
+                PrintErrorInner::AnyPrintingIsNotImplemented,
+            )))
+        } else if let Some(value) = message.downcast_ref::<Value>() {
+            MessageSer::serialize_wk_value(value, serializer)
+        } else if let Some(value) = message.downcast_ref::<DoubleValue>() {
+            ValueSer(ReflectValueRef::F64(value.value)).serialize(serializer)
+        } else if let Some(value) = message.downcast_ref::<FloatValue>() {
+            ValueSer(ReflectValueRef::F32(value.value)).serialize(serializer)
+        } else if let Some(value) = message.downcast_ref::<Int64Value>() {
+            ValueSer(ReflectValueRef::I64(value.value)).serialize(serializer)
+        } else if let Some(value) = message.downcast_ref::<UInt64Value>() {
+            ValueSer(ReflectValueRef::U64(value.value)).serialize(serializer)
+        } else if let Some(value) = message.downcast_ref::<Int32Value>() {
+            ValueSer(ReflectValueRef::I32(value.value)).serialize(serializer)
+        } else if let Some(value) = message.downcast_ref::<UInt32Value>() {
+            ValueSer(ReflectValueRef::U32(value.value)).serialize(serializer)
+        } else if let Some(value) = message.downcast_ref::<BoolValue>() {
+            ValueSer(ReflectValueRef::Bool(value.value)).serialize(serializer)
+        } else if let Some(value) = message.downcast_ref::<StringValue>() {
+            ValueSer(ReflectValueRef::String(&value.value)).serialize(serializer)
+        } else if let Some(value) = message.downcast_ref::<BytesValue>() {
+            ValueSer(ReflectValueRef::Bytes(&value.value)).serialize(serializer)
+        } else if let Some(value) = message.downcast_ref::<ListValue>() {
+            let mut seq = serializer.serialize_seq(Some(value.values.len()))?;
+            for v in &value.values {
+                seq.serialize_element(&MessageSer(v))?;
+            }
+            seq.end()
+        } else if let Some(value) = message.downcast_ref::<Struct>() {
+            let mut map = serializer.serialize_map(Some(value.fields.len()))?;
+            for (k, v) in &value.fields {
+                map.serialize_entry(k, &MessageSer(v))?;
+            }
+            map.end()
+        } else {
+            self.serialize_regular(serializer)
+        }
+    }
+}
+
+// Deserialization merges serde data directly into the message with reflection,
+// accepting the same values as `merge_from_str` with default options.
+
+fn parse_error<E: de::Error>(e: ParseErrorWithoutLoc) -> E {
+    E::custom(e)
+}
+
+/// Number, or number in a string.
+struct NumberVisitor<V>(PhantomData<V>);
+
+impl<'de, V: FromJsonNumber> de::Visitor<'de> for NumberVisitor<V> {
+    type Value = V;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "number or string")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<V, E> {
+        V::from_integer(v as i128).map_err(parse_error)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<V, E> {
+        V::from_integer(v as i128).map_err(parse_error)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<V, E> {
+        V::from_fractional(v).map_err(parse_error)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<V, E> {
+        parse::parse_number(v).map_err(parse_error)
+    }
+}
+
+fn deserialize_number<'de, V: FromJsonNumber, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<V, D::Error> {
+    deserializer.deserialize_any(NumberVisitor(PhantomData))
+}
+
+/// Base64 string, or bytes for binary formats which store them natively.
+struct BytesVisitor;
+
+impl<'de> de::Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "base64 string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<u8>, E> {
+        parse::parse_bytes(v).map_err(parse_error)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(v)
+    }
+}
+
+/// Value name or value number.
+struct EnumVisitor<'a>(&'a EnumDescriptor);
+
+impl<'de, 'a> de::Visitor<'de> for EnumVisitor<'a> {
+    type Value = EnumValueDescriptor;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "value of enum {}", self.0.full_name())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<EnumValueDescriptor, E> {
+        let number = i32::from_integer(v as i128).map_err(parse_error)?;
+        parse::enum_value_by_number(number, self.0).map_err(parse_error)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<EnumValueDescriptor, E> {
+        let number = i32::from_integer(v as i128).map_err(parse_error)?;
+        parse::enum_value_by_number(number, self.0).map_err(parse_error)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<EnumValueDescriptor, E> {
+        parse::parse_enum(v.to_owned(), self.0).map_err(parse_error)
+    }
+}
+
+fn deserialize_enum_value<'de, D: Deserializer<'de>>(
+    descriptor: &EnumDescriptor,
+    deserializer: D,
+) -> Result<EnumValueDescriptor, D::Error> {
+    if descriptor.is::<NullValue>() {
+        <()>::deserialize(deserializer)?;
+        return Ok(NullValue::NULL_VALUE.descriptor());
+    }
+    deserializer.deserialize_any(EnumVisitor(descriptor))
+}
+
+/// Map key as string, non-string keys are accepted for formats like YAML.
+struct MapKey(String);
+
+impl<'de> Deserialize<'de> for MapKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<MapKey, D::Error> {
+        struct MapKeyVisitor;
+
+        impl<'de> de::Visitor<'de> for MapKeyVisitor {
+            type Value = MapKey;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "map key")
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> Result<MapKey, E> {
+                Ok(MapKey(v.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<MapKey, E> {
+                Ok(MapKey(v.to_string()))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<MapKey, E> {
+                Ok(MapKey(v.to_string()))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<MapKey, E> {
+                Ok(MapKey(v.to_owned()))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<MapKey, E> {
+                Ok(MapKey(v))
+            }
+        }
+
+        deserializer.deserialize_any(MapKeyVisitor)
+    }
+}
+
+/// Value of `google.protobuf.Value`: any JSON value.
+struct WkValueSeed;
+
+impl<'de> DeserializeSeed<'de> for WkValueSeed {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        let mut value = Value::new();
+        value.kind = Some(deserializer.deserialize_any(WkValueVisitor)?);
+        Ok(value)
+    }
+}
+
+struct WkValueVisitor;
+
+impl<'de> de::Visitor<'de> for WkValueVisitor {
+    type Value = value::Kind;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "JSON value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<value::Kind, E> {
+        Ok(value::Kind::null_value(NullValue::NULL_VALUE.into()))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<value::Kind, E> {
+        self.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<value::Kind, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<value::Kind, E> {
+        Ok(value::Kind::bool_value(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<value::Kind, E> {
+        Ok(value::Kind::number_value(v as f64))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<value::Kind, E> {
+        Ok(value::Kind::number_value(v as f64))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<value::Kind, E> {
+        Ok(value::Kind::number_value(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<value::Kind, E> {
+        Ok(value::Kind::string_value(v.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<value::Kind, E> {
+        Ok(value::Kind::string_value(v))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<value::Kind, A::Error> {
+        let mut list = ListValue::new();
+        WkListValueVisitor(&mut list.values).visit_seq(seq)?;
+        Ok(value::Kind::list_value(list))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<value::Kind, A::Error> {
+        let mut struct_value = Struct::new();
+        WkStructVisitor(&mut struct_value).visit_map(map)?;
+        Ok(value::Kind::struct_value(struct_value))
+    }
+}
+
+/// Values of `google.protobuf.ListValue`, `null` is an empty list.
+struct WkListValueVisitor<'a>(&'a mut Vec<Value>);
+
+impl<'de, 'a> de::Visitor<'de> for WkListValueVisitor<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "list")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(v) = seq.next_element_seed(WkValueSeed)? {
+            self.0.push(v);
+        }
+        Ok(())
+    }
+}
+
+/// Fields of `google.protobuf.Struct`, `null` is an empty struct.
+struct WkStructVisitor<'a>(&'a mut Struct);
+
+impl<'de, 'a> de::Visitor<'de> for WkStructVisitor<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "object")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(MapKey(k)) = map.next_key()? {
+            let v = map.next_value_seed(WkValueSeed)?;
+            self.0.fields.insert(k, v);
+        }
+        Ok(())
+    }
+}
+
+/// Singular value of given type.
+struct ValueSeed<'a>(&'a RuntimeTypeBox);
+
+impl<'de, 'a> DeserializeSeed<'de> for ValueSeed<'a> {
+    type Value = ReflectValueBox;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<ReflectValueBox, D::Error> {
+        match self.0 {
+            RuntimeTypeBox::I32 => {
+                deserialize_number::<i32, _>(deserializer).map(ReflectValueBox::from)
+            }
+            RuntimeTypeBox::I64 => {
+                deserialize_number::<i64, _>(deserializer).map(ReflectValueBox::from)
+            }
+            RuntimeTypeBox::U32 => {
+                deserialize_number::<u32, _>(deserializer).map(ReflectValueBox::from)
+            }
+            RuntimeTypeBox::U64 => {
+                deserialize_number::<u64, _>(deserializer).map(ReflectValueBox::from)
+            }
+            RuntimeTypeBox::F32 => {
+                deserialize_number::<f32, _>(deserializer).map(ReflectValueBox::from)
+            }
+            RuntimeTypeBox::F64 => {
+                deserialize_number::<f64, _>(deserializer).map(ReflectValueBox::from)
+            }
+            RuntimeTypeBox::Bool => bool::deserialize(deserializer).map(ReflectValueBox::from),
+            RuntimeTypeBox::String => String::deserialize(deserializer).map(ReflectValueBox::from),
+            RuntimeTypeBox::VecU8 => deserializer
+                .deserialize_any(BytesVisitor)
+                .map(ReflectValueBox::from),
+            RuntimeTypeBox::Enum(e) => {
+                deserialize_enum_value(e, deserializer).map(ReflectValueBox::from)
+            }
+            RuntimeTypeBox::Message(m) => {
+                let mut message = m.new_instance();
+                MessageSeed(&mut *message).deserialize(deserializer)?;
+                Ok(ReflectValueBox::from(message))
+            }
+        }
+    }
+}
+
+/// Elements of a repeated field, `null` is an empty list.
+struct RepeatedVisitor<'a> {
+    repeated: ReflectRepeatedMut<'a>,
+    elem_type: &'a RuntimeTypeBox,
+}
+
+impl<'de, 'a> de::Visitor<'de> for RepeatedVisitor<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "list")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(v) = seq.next_element_seed(ValueSeed(self.elem_type))? {
+            self.repeated.try_push(v).map_err(de::Error::custom)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for RepeatedVisitor<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+/// Entries of a map field, `null` is an empty map.
+struct MapVisitor<'a> {
+    map: ReflectMapMut<'a>,
+    key_type: &'a RuntimeTypeBox,
+    value_type: &'a RuntimeTypeBox,
+}
+
+impl<'de, 'a> de::Visitor<'de> for MapVisitor<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "object")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+        while let Some(MapKey(k)) = map.next_key()? {
+            let k = parse::parse_key(k, self.key_type).map_err(parse_error)?;
+            let v = map.next_value_seed(ValueSeed(self.value_type))?;
+            self.map.try_insert(k, v).map_err(de::Error::custom)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for MapVisitor<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+/// Fields of a regular message.
+struct MessageVisitor<'a>(&'a mut dyn MessageDyn);
+
+impl<'de, 'a> de::Visitor<'de> for MessageVisitor<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "message {}", self.0.descriptor_dyn().full_name())
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let message = self.0;
+        let descriptor = message.descriptor_dyn();
+        while let Some(field_name) = map.next_key::<String>()? {
+            // Both the `lowerCamelCase` name and the proto field name are accepted.
+            let field = match descriptor.get_field_by_name_or_json_name(&field_name) {
+                Some(field) => field,
+                None => {
+                    return Err(parse_error(ParseErrorWithoutLoc(
+                        ParseErrorWithoutLocInner::UnknownFieldName(field_name),
+                    )))
+                }
+            };
+            match field.runtime_field_type() {
+                RuntimeFieldType::Singular(t) => {
+                    let v = map.next_value_seed(ValueSeed(&t))?;
+                    field
+                        .try_set_singular_field(message, v)
+                        .map_err(de::Error::custom)?;
+                }
+                RuntimeFieldType::Repeated(t) => {
+                    let mut repeated = field.mut_repeated(message);
+                    repeated.clear();
+                    map.next_value_seed(RepeatedVisitor {
+                        repeated,
+                        elem_type: &t,
+                    })?;
+                }
+                RuntimeFieldType::Map(kt, vt) => {
+                    let mut m = field.mut_map(message);
+                    m.clear();
+                    map.next_value_seed(MapVisitor {
+                        map: m,
+                        key_type: &kt,
+                        value_type: &vt,
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn merge_wrapper<'de, W, D>(wrapper: &mut W, deserializer: D) -> Result<(), D::Error>
+where
+    W: WellKnownWrapper,
+    W::Underlying: FromJsonNumber,
+    D: Deserializer<'de>,
+{
+    *wrapper.get_mut() = deserialize_number(deserializer)?;
+    Ok(())
+}
+
+/// Merge into a message of any type.
+struct MessageSeed<'a>(&'a mut dyn MessageDyn);
+
+impl<'de, 'a> DeserializeSeed<'de> for MessageSeed<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        let message = self.0;
+
+        if let Some(duration) = message.downcast_mut::<Duration>() {
+            let s = String::deserialize(deserializer)?;
+            return parse::merge_duration_str(duration, &s).map_err(parse_error);
+        }
+
+        if let Some(timestamp) = message.downcast_mut::<Timestamp>() {
+            let s = String::deserialize(deserializer)?;
+            return parse::merge_timestamp_str(timestamp, &s).map_err(parse_error);
+        }
+
+        if let Some(field_mask) = message.downcast_mut::<FieldMask>() {
+            let s = String::deserialize(deserializer)?;
+            parse::merge_field_mask_str(field_mask, &s);
+            return Ok(());
+        }
+
+        if let Some(value) = message.downcast_mut::<Value>() {
+            value.kind = Some(deserializer.deserialize_any(WkValueVisitor)?);
+            return Ok(());
+        }
+
+        if message.downcast_mut::<Any>().is_some() {
+            return Err(parse_error(ParseErrorWithoutLoc(
+                ParseErrorWithoutLocInner::AnyParsingIsNotImplemented,
+            )));
+        }
+
+        if let Some(value) = message.downcast_mut::<DoubleValue>() {
+            return merge_wrapper(value, deserializer);
+        }
+
+        if let Some(value) = message.downcast_mut::<FloatValue>() {
+            return merge_wrapper(value, deserializer);
+        }
+
+        if let Some(value) = message.downcast_mut::<Int64Value>() {
+            return merge_wrapper(value, deserializer);
+        }
+
+        if let Some(value) = message.downcast_mut::<UInt64Value>() {
+            return merge_wrapper(value, deserializer);
+        }
+
+        if let Some(value) = message.downcast_mut::<Int32Value>() {
+            return merge_wrapper(value, deserializer);
+        }
+
+        if let Some(value) = message.downcast_mut::<UInt32Value>() {
+            return merge_wrapper(value, deserializer);
+        }
+
+        if let Some(value) = message.downcast_mut::<BoolValue>() {
+            value.value = bool::deserialize(deserializer)?;
+            return Ok(());
+        }
+
+        if let Some(value) = message.downcast_mut::<StringValue>() {
+            value.value = String::deserialize(deserializer)?;
+            return Ok(());
+        }
+
+        if let Some(value) = message.downcast_mut::<BytesValue>() {
+            value.value = deserializer.deserialize_any(BytesVisitor)?;
+            return Ok(());
+        }
+
+        if let Some(value) = message.downcast_mut::<ListValue>() {
+            value.values.clear();
+            return deserializer.deserialize_any(WkListValueVisitor(&mut value.values));
+        }
+
+        if let Some(value) = message.downcast_mut::<Struct>() {
+            value.fields.clear();
+            return deserializer.deserialize_any(WkStructVisitor(value));
+        }
+
+        deserializer.deserialize_map(MessageVisitor(message))
+    }
+}
+
+fn serialize_dyn<S: Serializer>(
+    message: &dyn MessageDyn,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    MessageSer(message).serialize(serializer)
+}
+
+fn deserialize_dyn<'de, D: Deserializer<'de>>(
+    message: &mut dyn MessageDyn,
+    deserializer: D,
+) -> Result<(), D::Error> {
+    MessageSeed(message).deserialize(deserializer)?;
+    message.check_initialized_dyn().map_err(de::Error::custom)
+}
+
+/// Serialize a message following protobuf JSON mapping.
+///
+/// Can be used with `#[serde(with = "protobuf::json::serde")]`.
+pub fn serialize<M: Message, S: Serializer>(message: &M, serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_dyn(message, serializer)
+}
+
+/// Deserialize a message following protobuf JSON mapping.
+///
+/// Can be used with `#[serde(with = "protobuf::json::serde")]`.
+pub fn deserialize<'de, M: Message, D: Deserializer<'de>>(deserializer: D) -> Result<M, D::Error> {
+    let mut message = M::new();
+    deserialize_dyn(&mut message, deserializer)?;
+    Ok(message)
+}
+
+/// Serialize an enum value following protobuf JSON mapping, that is, as value name.
+///
+/// Used by code generated with `serde_json_mapping` option.
+pub fn serialize_enum<E: ProtobufEnum, S: Serializer>(
+    value: &E,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(value.descriptor().get_name())
+}
+
+/// Deserialize an enum value following protobuf JSON mapping.
+///
+/// Both value name and value number are accepted.
+/// Used by code generated with `serde_json_mapping` option.
+pub fn deserialize_enum<'de, E: ProtobufEnum, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<E, D::Error> {
+    let descriptor = E::enum_descriptor_static();
+    let value = deserialize_enum_value(&descriptor, deserializer)?;
+    Ok(value.cast().expect("value of the same enum"))
+}
+
+/// Message wrapper implementing `Serialize` and `Deserialize`
+/// following protobuf JSON mapping.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SerdeMessage<M: Message>(pub M);
+
+impl<M: Message> Serialize for SerdeMessage<M> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, M: Message> Deserialize<'de> for SerdeMessage<M> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<SerdeMessage<M>, D::Error> {
+        deserialize(deserializer).map(SerdeMessage)
+    }
+}
+
+/// Dynamic message reference implementing `Serialize`
+/// following protobuf JSON mapping.
+#[derive(Debug, Clone, Copy)]
+pub struct SerdeMessageDyn<'a>(pub &'a dyn MessageDyn);
+
+impl<'a> Serialize for SerdeMessageDyn<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_dyn(self.0, serializer)
+    }
+}
+
+/// [`DeserializeSeed`](serde::de::DeserializeSeed) for a dynamic message of given type
+/// following protobuf JSON mapping.
+#[derive(Debug, Clone)]
+pub struct SerdeMessageSeed(pub MessageDescriptor);
+
+impl<'de> de::DeserializeSeed<'de> for SerdeMessageSeed {
+    type Value = Box<dyn MessageDyn>;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Box<dyn MessageDyn>, D::Error> {
+        let mut message = self.0.new_instance();
+        deserialize_dyn(&mut *message, deserializer)?;
+        Ok(message)
+    }
+}
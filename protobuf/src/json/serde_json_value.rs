@@ -0,0 +1,292 @@
+//! Conversions between `Struct`, `Value`, `ListValue` and `serde_json` values.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::well_known_types::value;
+use crate::well_known_types::ListValue;
+use crate::well_known_types::Struct;
+use crate::well_known_types::Value;
+
+/// Integers with absolute value up to this are exactly representable as `f64`.
+const MAX_EXACT_INTEGER: u64 = 1 << 53;
+
+#[derive(Debug)]
+enum SerdeJsonValueErrorInner {
+    NonFiniteNumber(f64),
+    IntegerOutOfRange(serde_json::Number),
+    KindNotSet,
+    NotObject,
+    NotArray,
+}
+
+/// Error converting between well-known types and `serde_json` values.
+#[derive(Debug)]
+pub struct SerdeJsonValueError(SerdeJsonValueErrorInner);
+
+impl fmt::Display for SerdeJsonValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            SerdeJsonValueErrorInner::NonFiniteNumber(n) => {
+                write!(f, "number {} cannot be represented in JSON", n)
+            }
+            SerdeJsonValueErrorInner::IntegerOutOfRange(n) => {
+                write!(f, "integer {} cannot be exactly represented as double", n)
+            }
+            SerdeJsonValueErrorInner::KindNotSet => write!(f, "value kind is not set"),
+            SerdeJsonValueErrorInner::NotObject => write!(f, "JSON value is not an object"),
+            SerdeJsonValueErrorInner::NotArray => write!(f, "JSON value is not an array"),
+        }
+    }
+}
+
+impl std::error::Error for SerdeJsonValueError {}
+
+type SerdeJsonValueResult<T> = Result<T, SerdeJsonValueError>;
+
+fn is_negative_zero(n: f64) -> bool {
+    n == 0.0 && n.is_sign_negative()
+}
+
+fn number_to_json(n: f64) -> SerdeJsonValueResult<serde_json::Number> {
+    if n.fract() == 0.0 && n.abs() <= MAX_EXACT_INTEGER as f64 && !is_negative_zero(n) {
+        // Print integers without fractional part
+        return Ok(serde_json::Number::from(n as i64));
+    }
+    serde_json::Number::from_f64(n).ok_or(SerdeJsonValueError(
+        SerdeJsonValueErrorInner::NonFiniteNumber(n),
+    ))
+}
+
+fn number_from_json(n: serde_json::Number) -> SerdeJsonValueResult<f64> {
+    let exact = match (n.as_i64(), n.as_u64()) {
+        (Some(i), _) => i >= -(MAX_EXACT_INTEGER as i64) && i <= MAX_EXACT_INTEGER as i64,
+        (None, Some(u)) => u <= MAX_EXACT_INTEGER,
+        (None, None) => true,
+    };
+    match (exact, n.as_f64()) {
+        (true, Some(f)) => Ok(f),
+        _ => Err(SerdeJsonValueError(
+            SerdeJsonValueErrorInner::IntegerOutOfRange(n),
+        )),
+    }
+}
+
+fn struct_to_json(s: &Struct) -> SerdeJsonValueResult<serde_json::Map<String, serde_json::Value>> {
+    s.fields
+        .iter()
+        .map(|(k, v)| Ok((k.clone(), serde_json::Value::try_from(v)?)))
+        .collect()
+}
+
+fn struct_from_json(
+    map: serde_json::Map<String, serde_json::Value>,
+) -> SerdeJsonValueResult<Struct> {
+    Ok(Struct {
+        fields: map
+            .into_iter()
+            .map(|(k, v)| Ok((k, Value::try_from(v)?)))
+            .collect::<SerdeJsonValueResult<_>>()?,
+        ..Default::default()
+    })
+}
+
+fn list_to_json(l: &ListValue) -> SerdeJsonValueResult<Vec<serde_json::Value>> {
+    l.values.iter().map(serde_json::Value::try_from).collect()
+}
+
+fn list_from_json(values: Vec<serde_json::Value>) -> SerdeJsonValueResult<ListValue> {
+    Ok(ListValue {
+        values: values
+            .into_iter()
+            .map(Value::try_from)
+            .collect::<SerdeJsonValueResult<_>>()?,
+        ..Default::default()
+    })
+}
+
+/// Fails on non-finite numbers and values with unset kind.
+impl TryFrom<&Value> for serde_json::Value {
+    type Error = SerdeJsonValueError;
+
+    fn try_from(value: &Value) -> SerdeJsonValueResult<Self> {
+        Ok(match &value.kind {
+            None => return Err(SerdeJsonValueError(SerdeJsonValueErrorInner::KindNotSet)),
+            Some(value::Kind::null_value(..)) => serde_json::Value::Null,
+            Some(value::Kind::number_value(n)) => serde_json::Value::Number(number_to_json(*n)?),
+            Some(value::Kind::string_value(s)) => serde_json::Value::String(s.clone()),
+            Some(value::Kind::bool_value(b)) => serde_json::Value::Bool(*b),
+            Some(value::Kind::struct_value(s)) => serde_json::Value::Object(struct_to_json(s)?),
+            Some(value::Kind::list_value(l)) => serde_json::Value::Array(list_to_json(l)?),
+        })
+    }
+}
+
+/// Fails on non-finite numbers and values with unset kind.
+impl TryFrom<Value> for serde_json::Value {
+    type Error = SerdeJsonValueError;
+
+    fn try_from(value: Value) -> SerdeJsonValueResult<Self> {
+        serde_json::Value::try_from(&value)
+    }
+}
+
+/// Fails on integers which cannot be exactly represented as `f64`.
+impl TryFrom<serde_json::Value> for Value {
+    type Error = SerdeJsonValueError;
+
+    fn try_from(value: serde_json::Value) -> SerdeJsonValueResult<Self> {
+        Ok(match value {
+            serde_json::Value::Null => Value::null(),
+            serde_json::Value::Bool(b) => Value::from(b),
+            serde_json::Value::Number(n) => Value::from(number_from_json(n)?),
+            serde_json::Value::String(s) => Value::from(s),
+            serde_json::Value::Array(a) => Value::from(list_from_json(a)?),
+            serde_json::Value::Object(o) => Value::from(struct_from_json(o)?),
+        })
+    }
+}
+
+/// Fails on non-finite numbers and values with unset kind.
+impl TryFrom<&Struct> for serde_json::Map<String, serde_json::Value> {
+    type Error = SerdeJsonValueError;
+
+    fn try_from(s: &Struct) -> SerdeJsonValueResult<Self> {
+        struct_to_json(s)
+    }
+}
+
+/// Fails on non-finite numbers and values with unset kind.
+impl TryFrom<Struct> for serde_json::Map<String, serde_json::Value> {
+    type Error = SerdeJsonValueError;
+
+    fn try_from(s: Struct) -> SerdeJsonValueResult<Self> {
+        struct_to_json(&s)
+    }
+}
+
+/// Fails on integers which cannot be exactly represented as `f64`.
+impl TryFrom<serde_json::Map<String, serde_json::Value>> for Struct {
+    type Error = SerdeJsonValueError;
+
+    fn try_from(map: serde_json::Map<String, serde_json::Value>) -> SerdeJsonValueResult<Self> {
+        struct_from_json(map)
+    }
+}
+
+/// Fails if JSON value is not an object,
+/// or on integers which cannot be exactly represented as `f64`.
+impl TryFrom<serde_json::Value> for Struct {
+    type Error = SerdeJsonValueError;
+
+    fn try_from(value: serde_json::Value) -> SerdeJsonValueResult<Self> {
+        match value {
+            serde_json::Value::Object(map) => struct_from_json(map),
+            _ => Err(SerdeJsonValueError(SerdeJsonValueErrorInner::NotObject)),
+        }
+    }
+}
+
+/// Fails on non-finite numbers and values with unset kind.
+impl TryFrom<&ListValue> for serde_json::Value {
+    type Error = SerdeJsonValueError;
+
+    fn try_from(l: &ListValue) -> SerdeJsonValueResult<Self> {
+        Ok(serde_json::Value::Array(list_to_json(l)?))
+    }
+}
+
+/// Fails if JSON value is not an array,
+/// or on integers which cannot be exactly represented as `f64`.
+impl TryFrom<serde_json::Value> for ListValue {
+    type Error = SerdeJsonValueError;
+
+    fn try_from(value: serde_json::Value) -> SerdeJsonValueResult<Self> {
+        match value {
+            serde_json::Value::Array(values) => list_from_json(values),
+            _ => Err(SerdeJsonValueError(SerdeJsonValueErrorInner::NotArray)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use serde_json::json;
+
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+
+    #[test]
+    fn round_trip() {
+        let json = json!({
+            "null": null,
+            "int": -17,
+            "float": 1.5,
+            "string": "s",
+            "bool": true,
+            "list": [1, "a", [], {}],
+            "struct": { "x": { "y": false } },
+        });
+        let s = Struct::try_from(json.clone()).unwrap();
+        assert!(s.fields["null"].is_null());
+        assert_eq!(Some(-17.0), s.fields["int"].as_f64());
+        assert_eq!(Some("s"), s.fields["string"].as_str());
+        assert_eq!(
+            json,
+            serde_json::Value::from(serde_json::Map::try_from(s).unwrap())
+        );
+    }
+
+    #[test]
+    fn non_finite() {
+        for n in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(serde_json::Value::try_from(Value::from(*n)).is_err());
+        }
+    }
+
+    #[test]
+    fn negative_zero() {
+        let json = serde_json::Value::try_from(Value::from(-0.0)).unwrap();
+        assert_eq!("-0.0", json.to_string());
+        let back = Value::try_from(json).unwrap().as_f64().unwrap();
+        assert!(back == 0.0 && back.is_sign_negative());
+        assert_eq!(
+            "0",
+            serde_json::Value::try_from(Value::from(0.0))
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn integer_range() {
+        let max = 1i64 << 53;
+        assert_eq!(
+            Some(max as f64),
+            Value::try_from(json!(max)).unwrap().as_f64()
+        );
+        assert_eq!(
+            Some(-max as f64),
+            Value::try_from(json!(-max)).unwrap().as_f64()
+        );
+        assert!(Value::try_from(json!(max + 1)).is_err());
+        assert!(Value::try_from(json!(-max - 1)).is_err());
+        assert!(Value::try_from(json!(u64::MAX)).is_err());
+        assert_eq!(
+            json!(max),
+            serde_json::Value::try_from(Value::from(max as f64)).unwrap()
+        );
+    }
+
+    #[test]
+    fn kind_not_set() {
+        assert!(serde_json::Value::try_from(Value::new()).is_err());
+    }
+
+    #[test]
+    fn not_object() {
+        assert!(Struct::try_from(json!([1])).is_err());
+    }
+}
@@ -15,6 +15,13 @@ extern crate serde;
 #[macro_use]
 #[cfg(feature = "with-serde")]
 extern crate serde_derive;
+#[cfg(feature = "with-chrono")]
+extern crate chrono;
+#[cfg(feature = "with-serde-json")]
+extern crate serde_json;
+#[cfg(feature = "with-time")]
+extern crate time;
+pub use crate::builder::Buildable;
 pub use crate::clear::Clear;
 pub use crate::enums::ProtobufEnum;
 pub use crate::enums::ProtobufEnumOrUnknown;
@@ -42,6 +49,7 @@ pub mod descriptor;
 pub mod plugin;
 pub mod rustproto;
 
+mod builder;
 mod clear;
 mod enums;
 mod error;
@@ -54,13 +62,17 @@ mod message_field;
 mod oneof;
 pub mod reflect;
 pub mod rt;
+pub mod service;
 mod stream;
 pub mod text_format;
 pub mod well_known_types;
-mod well_known_types_util;
+pub mod well_known_types_util;
 
 // used by test
 #[cfg(test)]
+#[path = "../../protobuf-test-common/src/block_on.rs"]
+mod block_on;
+#[cfg(test)]
 #[path = "../../protobuf-test-common/src/hex.rs"]
 mod hex;
 
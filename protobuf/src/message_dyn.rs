@@ -1,4 +1,6 @@
 use crate::reflect::MessageDescriptor;
+use crate::reflect::MessageDifference;
+use crate::reflect::MessageDifferencer;
 use crate::reflect::ReflectEqMode;
 use crate::stream::WithCodedOutputStream;
 use crate::CodedInputStream;
@@ -223,7 +225,50 @@ impl dyn MessageDyn {
         self.descriptor_dyn().clone_message(self)
     }
 
+    /// Merge fields of another message of the same type through reflection.
+    ///
+    /// Messages may have different representations: for example,
+    /// `source` may be a dynamic message and `self` a generated message
+    /// created from the same `.proto` definition.
+    ///
+    /// Like with merging of serialized messages, singular fields set in `source`
+    /// overwrite fields of `self` (message fields are merged recursively),
+    /// repeated fields are appended, map entries are inserted
+    /// and unknown fields are appended.
+    ///
+    /// # Panics
+    ///
+    /// If messages are of different types, or a value cannot be converted
+    /// to the Rust type of a field (e. g. field generated with `rust_type` codegen option).
+    pub fn merge_from_message_dyn(&mut self, source: &dyn MessageDyn) {
+        MessageDescriptor::merge_from_message(self, source)
+    }
+
+    /// Convert this message to generated message type through reflection.
+    ///
+    /// This is typically used to convert a dynamic message to generated message
+    /// of the same type.
+    ///
+    /// # Panics
+    ///
+    /// If `M` is a different message type, or a value cannot be converted
+    /// to the Rust type of a field of `M`.
+    pub fn transcode<M: Message>(&self) -> M {
+        let mut m = M::new();
+        MessageDescriptor::merge_from_message(&mut m, self);
+        m
+    }
+
+    /// Find differences between messages with default [`MessageDifferencer`] options.
+    ///
+    /// Use [`MessageDifferencer`] directly to customize the comparison.
+    pub fn diff_dyn(&self, other: &dyn MessageDyn) -> MessageDifference {
+        MessageDifferencer::new().compare(self, other)
+    }
+
     /// Reflectively compare the messages.
+    ///
+    /// Messages may have different representations, e.g. generated and dynamic.
     pub fn reflect_eq_dyn(&self, other: &dyn MessageDyn, mode: &ReflectEqMode) -> bool {
         MessageDescriptor::reflect_eq_maybe_unrelated(self, other, mode)
     }
@@ -32,7 +32,7 @@ use crate::Message;
 /// // or using `Option` and `Into`
 /// customer.address = Some(make_address()).into();
 /// ```
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct MessageField<T>(pub Option<Box<T>>);
 
 impl<T> MessageField<T> {
@@ -140,6 +140,22 @@ impl From<u64> for well_known_types::UInt64Value {
     }
 }
 
+// String <-> StringValue
+
+impl From<well_known_types::StringValue> for String {
+    fn from(inner: well_known_types::StringValue) -> Self {
+        inner.value
+    }
+}
+
+impl From<String> for well_known_types::StringValue {
+    fn from(inner: String) -> Self {
+        let mut value = Self::new();
+        value.value = inner;
+        value
+    }
+}
+
 // () <-> Empty
 
 impl From<well_known_types::Empty> for () {
@@ -211,7 +211,7 @@ impl crate::Message for Version {
                     self.suffix = ::std::option::Option::Some(is.read_string()?);
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -457,7 +457,7 @@ impl crate::Message for CodeGeneratorRequest {
                     crate::rt::read_singular_message_into_field(wire_type, is, &mut self.compiler_version)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -705,7 +705,7 @@ impl crate::Message for CodeGeneratorResponse {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.file)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -1037,7 +1037,7 @@ pub mod code_generator_response {
                         self.content = ::std::option::Option::Some(is.read_string()?);
                     },
                     _ => {
-                        crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                        crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                     },
                 };
             }
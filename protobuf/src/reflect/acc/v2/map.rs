@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::hash::Hash;
 
 use crate::message::Message;
@@ -6,12 +7,14 @@ use crate::message::Message;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::acc::v2::AccessorV2;
 use crate::reflect::acc::FieldAccessor;
+use crate::reflect::map::generated::ConvertedMessageMap;
 use crate::reflect::map::ReflectMapMut;
 use crate::reflect::map::ReflectMapRef;
 use crate::reflect::runtime_types::RuntimeTypeHashable;
 use crate::reflect::ProtobufValue;
 use crate::reflect::RuntimeTypeBox;
 use std::fmt;
+use std::marker;
 
 pub(crate) trait MapFieldAccessor: Send + Sync + 'static {
     fn get_reflect<'a>(&self, m: &'a dyn MessageDyn) -> ReflectMapRef<'a>;
@@ -85,3 +88,63 @@ where
         }),
     )
 }
+
+struct MapFieldAccessorConvertedImpl<M, K, V, T>
+where
+    M: Message,
+    K: ProtobufValue,
+{
+    get_field: fn(&M) -> &HashMap<K, T>,
+    mut_field: fn(&mut M) -> &mut HashMap<K, T>,
+    _marker: marker::PhantomData<fn() -> V>,
+}
+
+impl<M, K, V, T> MapFieldAccessor for MapFieldAccessorConvertedImpl<M, K, V, T>
+where
+    M: Message,
+    K: ProtobufValue + Eq + Hash,
+    K::RuntimeType: RuntimeTypeHashable,
+    V: Message + ProtobufValue + TryFrom<T>,
+    T: TryFrom<V> + Clone + Send + Sync + 'static,
+{
+    fn get_reflect<'a>(&self, m: &'a dyn MessageDyn) -> ReflectMapRef<'a> {
+        let m = m.downcast_ref().unwrap();
+        let map = (self.get_field)(m);
+        ReflectMapRef::new(ConvertedMessageMap::<K, V, T>::from_map(map))
+    }
+
+    fn mut_reflect<'a>(&self, m: &'a mut dyn MessageDyn) -> ReflectMapMut<'a> {
+        let m = m.downcast_mut().unwrap();
+        let map = (self.mut_field)(m);
+        ReflectMapMut::new(ConvertedMessageMap::<K, V, T>::from_map_mut(map))
+    }
+
+    fn element_type(&self) -> (RuntimeTypeBox, RuntimeTypeBox) {
+        (K::runtime_type_box(), V::runtime_type_box())
+    }
+}
+
+/// Make accessor for map field with message `V` values stored as a different Rust type `T`
+pub fn make_map_converted_message_accessor<M, K, V, T>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M) -> &'a HashMap<K, T>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut HashMap<K, T>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    K: ProtobufValue + Hash + Eq,
+    K::RuntimeType: RuntimeTypeHashable,
+    V: Message + ProtobufValue + TryFrom<T>,
+    T: TryFrom<V> + Clone + Send + Sync + 'static,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Map(MapFieldAccessorHolder {
+            accessor: Box::new(MapFieldAccessorConvertedImpl::<M, K, V, T> {
+                get_field,
+                mut_field,
+                _marker: marker::PhantomData,
+            }),
+        }),
+    )
+}
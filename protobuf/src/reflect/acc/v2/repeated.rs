@@ -1,3 +1,4 @@
+use std::convert::TryFrom;
 use std::{fmt, marker};
 
 use crate::message::Message;
@@ -5,9 +6,12 @@ use crate::message::Message;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::acc::v2::AccessorV2;
 use crate::reflect::acc::FieldAccessor;
+use crate::reflect::repeated::ConvertedDerefVec;
+use crate::reflect::repeated::ConvertedMessageVec;
 use crate::reflect::repeated::ReflectRepeated;
 use crate::reflect::repeated::ReflectRepeatedMut;
 use crate::reflect::repeated::ReflectRepeatedRef;
+use crate::reflect::runtime_types::RuntimeTypeWithDeref;
 use crate::reflect::ProtobufValue;
 use crate::reflect::RuntimeTypeBox;
 
@@ -57,6 +61,62 @@ where
     }
 }
 
+struct RepeatedFieldGetMutConvertedImpl<M, V, T>
+where
+    M: Message + 'static,
+{
+    get_field: for<'a> fn(&'a M) -> &'a Vec<T>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut Vec<T>,
+    _marker: marker::PhantomData<V>,
+}
+
+impl<M, V, T> RepeatedFieldGetMut<M, dyn ReflectRepeated>
+    for RepeatedFieldGetMutConvertedImpl<M, V, T>
+where
+    M: Message + 'static,
+    V: Message + ProtobufValue + TryFrom<T>,
+    T: TryFrom<V> + Clone + fmt::Debug + Send + Sync + 'static,
+{
+    fn get_field<'a>(&self, m: &'a M) -> &'a dyn ReflectRepeated {
+        ConvertedMessageVec::<V, T>::from_vec((self.get_field)(m)) as &dyn ReflectRepeated
+    }
+
+    fn mut_field<'a>(&self, m: &'a mut M) -> &'a mut dyn ReflectRepeated {
+        ConvertedMessageVec::<V, T>::from_vec_mut((self.mut_field)(m)) as &mut dyn ReflectRepeated
+    }
+}
+
+struct RepeatedFieldGetMutConvertedDerefImpl<M, V, T>
+where
+    M: Message + 'static,
+{
+    get_field: for<'a> fn(&'a M) -> &'a Vec<T>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut Vec<T>,
+    _marker: marker::PhantomData<V>,
+}
+
+impl<M, V, T> RepeatedFieldGetMut<M, dyn ReflectRepeated>
+    for RepeatedFieldGetMutConvertedDerefImpl<M, V, T>
+where
+    M: Message + 'static,
+    V: ProtobufValue,
+    V::RuntimeType: RuntimeTypeWithDeref,
+    T: AsRef<<V::RuntimeType as RuntimeTypeWithDeref>::DerefTarget>
+        + TryFrom<V>
+        + fmt::Debug
+        + Send
+        + Sync
+        + 'static,
+{
+    fn get_field<'a>(&self, m: &'a M) -> &'a dyn ReflectRepeated {
+        ConvertedDerefVec::<V, T>::from_vec((self.get_field)(m)) as &dyn ReflectRepeated
+    }
+
+    fn mut_field<'a>(&self, m: &'a mut M) -> &'a mut dyn ReflectRepeated {
+        ConvertedDerefVec::<V, T>::from_vec_mut((self.mut_field)(m)) as &mut dyn ReflectRepeated
+    }
+}
+
 struct RepeatedFieldAccessorImpl<M, V>
 where
     M: Message,
@@ -111,3 +171,67 @@ where
         }),
     )
 }
+
+/// Make accessor for `Vec<T>` field storing messages `V` as a different Rust type,
+/// e. g. `Int32Value` elements stored as `i32`.
+///
+/// Elements are converted to messages on access, and mutable access to elements is not supported.
+pub fn make_vec_converted_message_accessor<M, V, T>(
+    name: &'static str,
+    get_vec: for<'a> fn(&'a M) -> &'a Vec<T>,
+    mut_vec: for<'a> fn(&'a mut M) -> &'a mut Vec<T>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    V: Message + ProtobufValue + TryFrom<T>,
+    T: TryFrom<V> + Clone + fmt::Debug + Send + Sync + 'static,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Repeated(RepeatedFieldAccessorHolder {
+            accessor: Box::new(RepeatedFieldAccessorImpl::<M, V> {
+                fns: Box::new(RepeatedFieldGetMutConvertedImpl::<M, V, T> {
+                    get_field: get_vec,
+                    mut_field: mut_vec,
+                    _marker: marker::PhantomData,
+                }),
+                _marker: marker::PhantomData::<V>,
+            }),
+        }),
+    )
+}
+
+/// Make accessor for `Vec<T>` field storing `string` or `bytes` values `V`
+/// as a different Rust type, e. g. `Arc<str>`.
+///
+/// Elements are viewed with `AsRef`, and mutable access to elements is not supported.
+pub fn make_vec_converted_accessor<M, V, T>(
+    name: &'static str,
+    get_vec: for<'a> fn(&'a M) -> &'a Vec<T>,
+    mut_vec: for<'a> fn(&'a mut M) -> &'a mut Vec<T>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    V: ProtobufValue,
+    V::RuntimeType: RuntimeTypeWithDeref,
+    T: AsRef<<V::RuntimeType as RuntimeTypeWithDeref>::DerefTarget>
+        + TryFrom<V>
+        + fmt::Debug
+        + Send
+        + Sync
+        + 'static,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Repeated(RepeatedFieldAccessorHolder {
+            accessor: Box::new(RepeatedFieldAccessorImpl::<M, V> {
+                fns: Box::new(RepeatedFieldGetMutConvertedDerefImpl::<M, V, T> {
+                    get_field: get_vec,
+                    mut_field: mut_vec,
+                    _marker: marker::PhantomData,
+                }),
+                _marker: marker::PhantomData::<V>,
+            }),
+        }),
+    )
+}
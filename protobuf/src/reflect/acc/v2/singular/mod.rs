@@ -1,3 +1,4 @@
+use std::convert::TryFrom;
 use std::fmt;
 use std::marker;
 
@@ -7,8 +8,12 @@ use crate::message::Message;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::acc::v2::AccessorV2;
 use crate::reflect::acc::FieldAccessor;
+use crate::reflect::repeated::converted_deref_as_ref;
+use crate::reflect::repeated::converted_from_value_box;
+use crate::reflect::runtime_types::RuntimeType;
 use crate::reflect::runtime_types::RuntimeTypeWithDeref;
 use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::ConversionError;
 use crate::reflect::MessageRef;
 use crate::reflect::ProtobufValue;
 use crate::reflect::ReflectValueBox;
@@ -23,6 +28,7 @@ trait OptionLike<T> {
     fn as_option_ref(&self) -> Option<&T>;
     fn as_option_mut(&mut self) -> Option<&mut T>;
     fn set_value(&mut self, value: T);
+    fn clear_value(&mut self);
 }
 
 impl<T> OptionLike<T> for Option<T> {
@@ -37,6 +43,10 @@ impl<T> OptionLike<T> for Option<T> {
     fn set_value(&mut self, value: T) {
         *self = Some(value);
     }
+
+    fn clear_value(&mut self) {
+        *self = None;
+    }
 }
 
 impl<T> OptionLike<T> for MessageField<T> {
@@ -51,14 +61,24 @@ impl<T> OptionLike<T> for MessageField<T> {
     fn set_value(&mut self, value: T) {
         *self = MessageField::some(value);
     }
+
+    fn clear_value(&mut self) {
+        *self = MessageField::none();
+    }
 }
 
 /// This trait should not be used directly, use `FieldDescriptor` instead
 pub(crate) trait SingularFieldAccessor: Send + Sync + 'static {
     fn get_field<'a>(&self, m: &'a dyn MessageDyn) -> Option<ReflectValueRef<'a>>;
     fn get_field_or_default<'a>(&self, m: &'a dyn MessageDyn) -> ReflectValueRef<'a>;
-    fn mut_field_or_default<'a>(&self, m: &'a mut dyn MessageDyn) -> ReflectValueMut<'a>;
-    fn set_field(&self, m: &mut dyn MessageDyn, value: ReflectValueBox);
+    /// `None` if the field is stored as a different Rust type and cannot be mutated in place.
+    fn mut_field_or_default<'a>(&self, m: &'a mut dyn MessageDyn) -> Option<ReflectValueMut<'a>>;
+    fn set_field(
+        &self,
+        m: &mut dyn MessageDyn,
+        value: ReflectValueBox,
+    ) -> Result<(), ConversionError>;
+    fn clear_field(&self, m: &mut dyn MessageDyn);
 }
 
 pub(crate) struct SingularFieldAccessorHolder {
@@ -80,41 +100,47 @@ trait GetOrDefaultImpl<M>: Send + Sync + 'static {
 }
 
 trait MutOrDefaultImpl<M>: Send + Sync + 'static {
-    fn mut_singular_field_or_default_impl<'a>(&self, m: &'a mut M) -> ReflectValueMut<'a>;
+    fn mut_singular_field_or_default_impl<'a>(&self, m: &'a mut M) -> Option<ReflectValueMut<'a>>;
 }
 
 trait SetImpl<M>: Send + Sync + 'static {
-    fn set_singular_field(&self, m: &mut M, value: ReflectValueBox);
+    fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) -> Result<(), ConversionError>;
 }
 
-struct MutOrDefaultUnmplemented<M>
+trait ClearImpl<M>: Send + Sync + 'static {
+    fn clear_singular_field(&self, m: &mut M);
+}
+
+/// Field which cannot be mutated in place: not a message field,
+/// or a message field stored as a different Rust type.
+struct MutOrDefaultUnsupported<M>
 where
     M: Message,
 {
     _marker: marker::PhantomData<M>,
 }
 
-impl<M> MutOrDefaultUnmplemented<M>
+impl<M> MutOrDefaultUnsupported<M>
 where
     M: Message,
 {
-    fn new() -> MutOrDefaultUnmplemented<M> {
-        MutOrDefaultUnmplemented {
+    fn new() -> MutOrDefaultUnsupported<M> {
+        MutOrDefaultUnsupported {
             _marker: marker::PhantomData,
         }
     }
 }
 
-impl<M> MutOrDefaultImpl<M> for MutOrDefaultUnmplemented<M>
+impl<M> MutOrDefaultImpl<M> for MutOrDefaultUnsupported<M>
 where
     M: Message,
 {
-    fn mut_singular_field_or_default_impl<'a>(&self, _m: &'a mut M) -> ReflectValueMut<'a> {
-        unimplemented!()
+    fn mut_singular_field_or_default_impl<'a>(&self, _m: &'a mut M) -> Option<ReflectValueMut<'a>> {
+        None
     }
 }
 
-struct SingularFieldAccessorImpl<M, V, G, D, E, S>
+struct SingularFieldAccessorImpl<M, V, G, D, E, S, C>
 where
     M: Message,
     V: ProtobufValue,
@@ -122,15 +148,17 @@ where
     D: GetOrDefaultImpl<M>,
     E: MutOrDefaultImpl<M>,
     S: SetImpl<M>,
+    C: ClearImpl<M>,
 {
     get_option_impl: G,
     get_or_default_impl: D,
     mut_or_default_impl: E,
     set_impl: S,
+    clear_impl: C,
     _marker: marker::PhantomData<(M, V)>,
 }
 
-impl<M, V, G, D, E, S> SingularFieldAccessor for SingularFieldAccessorImpl<M, V, G, D, E, S>
+impl<M, V, G, D, E, S, C> SingularFieldAccessor for SingularFieldAccessorImpl<M, V, G, D, E, S, C>
 where
     M: Message,
     V: ProtobufValue,
@@ -138,6 +166,7 @@ where
     D: GetOrDefaultImpl<M>,
     E: MutOrDefaultImpl<M>,
     S: SetImpl<M>,
+    C: ClearImpl<M>,
 {
     fn get_field<'a>(&self, m: &'a dyn MessageDyn) -> Option<ReflectValueRef<'a>> {
         let m = m.downcast_ref().unwrap();
@@ -150,16 +179,25 @@ where
             .get_singular_field_or_default_impl(m)
     }
 
-    fn mut_field_or_default<'a>(&self, m: &'a mut dyn MessageDyn) -> ReflectValueMut<'a> {
+    fn mut_field_or_default<'a>(&self, m: &'a mut dyn MessageDyn) -> Option<ReflectValueMut<'a>> {
         let m = m.downcast_mut().unwrap();
         self.mut_or_default_impl
             .mut_singular_field_or_default_impl(m)
     }
 
-    fn set_field(&self, m: &mut dyn MessageDyn, value: ReflectValueBox) {
+    fn set_field(
+        &self,
+        m: &mut dyn MessageDyn,
+        value: ReflectValueBox,
+    ) -> Result<(), ConversionError> {
         let m = m.downcast_mut().unwrap();
         self.set_impl.set_singular_field(m, value)
     }
+
+    fn clear_field(&self, m: &mut dyn MessageDyn) {
+        let m = m.downcast_mut().unwrap();
+        self.clear_impl.clear_singular_field(m)
+    }
 }
 
 struct GetOptionImplFieldPointer<M, V>
@@ -378,8 +416,8 @@ where
     M: Message,
     V: ProtobufValue,
 {
-    fn mut_singular_field_or_default_impl<'a>(&self, m: &'a mut M) -> ReflectValueMut<'a> {
-        V::as_mut((self.mut_field)(m))
+    fn mut_singular_field_or_default_impl<'a>(&self, m: &'a mut M) -> Option<ReflectValueMut<'a>> {
+        Some(V::as_mut((self.mut_field)(m)))
     }
 }
 
@@ -399,12 +437,12 @@ where
     V: ProtobufValue,
     O: OptionLike<V> + Sync + Send + 'static,
 {
-    fn mut_singular_field_or_default_impl<'a>(&self, m: &'a mut M) -> ReflectValueMut<'a> {
+    fn mut_singular_field_or_default_impl<'a>(&self, m: &'a mut M) -> Option<ReflectValueMut<'a>> {
         let option = (self.mut_field)(m);
         if option.as_option_ref().is_none() {
             option.set_value(V::default());
         }
-        V::as_mut(option.as_option_mut().unwrap())
+        Some(V::as_mut(option.as_option_mut().unwrap()))
     }
 }
 
@@ -439,8 +477,9 @@ where
     M: Message,
     V: ProtobufValue,
 {
-    fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) {
+    fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) -> Result<(), ConversionError> {
         V::set_from_value_box((self.mut_field)(m), value);
+        Ok(())
     }
 }
 
@@ -450,8 +489,9 @@ where
     V: ProtobufValue,
     O: OptionLike<V> + Sync + Send + 'static,
 {
-    fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) {
+    fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) -> Result<(), ConversionError> {
         (self.mut_field)(m).set_value(V::from_value_box(value).expect("wrong type"));
+        Ok(())
     }
 }
 
@@ -460,9 +500,70 @@ where
     M: Message,
     V: ProtobufValue,
 {
-    fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) {
+    fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) -> Result<(), ConversionError> {
         let value = value.downcast::<V>().expect("message");
-        (self.set_field)(m, value)
+        (self.set_field)(m, value);
+        Ok(())
+    }
+}
+
+struct ClearImplFieldPointer<M, V>
+where
+    M: Message,
+    V: ProtobufValue,
+{
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut V,
+}
+
+struct ClearImplOptionFieldPointer<M, V, O>
+where
+    M: Message,
+    V: ProtobufValue,
+    O: OptionLike<V> + Sync + Send + 'static,
+{
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut O,
+    _marker: marker::PhantomData<V>,
+}
+
+struct ClearImplHasClear<M>
+where
+    M: Message,
+{
+    has: for<'a> fn(&'a M) -> bool,
+    clear: for<'a> fn(&'a mut M),
+}
+
+impl<M, V> ClearImpl<M> for ClearImplFieldPointer<M, V>
+where
+    M: Message,
+    V: ProtobufValue,
+{
+    fn clear_singular_field(&self, m: &mut M) {
+        *(self.mut_field)(m) = V::default();
+    }
+}
+
+impl<M, V, O> ClearImpl<M> for ClearImplOptionFieldPointer<M, V, O>
+where
+    M: Message,
+    V: ProtobufValue,
+    O: OptionLike<V> + Sync + Send + 'static,
+{
+    fn clear_singular_field(&self, m: &mut M) {
+        (self.mut_field)(m).clear_value();
+    }
+}
+
+impl<M> ClearImpl<M> for ClearImplHasClear<M>
+where
+    M: Message,
+{
+    fn clear_singular_field(&self, m: &mut M) {
+        // Generated `clear_` function of `oneof` field resets the whole `oneof`,
+        // so only call it when this field is the one which is set.
+        if (self.has)(m) {
+            (self.clear)(m);
+        }
     }
 }
 
@@ -479,7 +580,7 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
                 get_option_impl: GetOptionImplOptionFieldPointer::<M, V, _> {
                     get_field,
                     _marker: marker::PhantomData,
@@ -496,6 +597,354 @@ where
                     mut_field,
                     _marker: marker::PhantomData,
                 },
+                clear_impl: ClearImplOptionFieldPointer::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
+                _marker: marker::PhantomData,
+            }),
+        }),
+    )
+}
+
+/// Message field stored as `Option` of a different Rust type.
+struct ConvertedMessageImpl<M, V, T>
+where
+    M: Message,
+    V: Message,
+    T: Send + Sync + 'static,
+{
+    get_field: for<'a> fn(&'a M) -> &'a Option<T>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut Option<T>,
+    _marker: marker::PhantomData<V>,
+}
+
+impl<M, V, T> ConvertedMessageImpl<M, V, T>
+where
+    M: Message,
+    V: Message,
+    T: Send + Sync + 'static,
+{
+    fn new(
+        get_field: for<'a> fn(&'a M) -> &'a Option<T>,
+        mut_field: for<'a> fn(&'a mut M) -> &'a mut Option<T>,
+    ) -> ConvertedMessageImpl<M, V, T> {
+        ConvertedMessageImpl {
+            get_field,
+            mut_field,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M, V, T> GetOptionImpl<M> for ConvertedMessageImpl<M, V, T>
+where
+    M: Message,
+    V: Message + TryFrom<T>,
+    T: Clone + Send + Sync + 'static,
+{
+    fn get_reflect_impl<'a>(&self, m: &'a M) -> Option<ReflectValueRef<'a>> {
+        (self.get_field)(m)
+            .as_ref()
+            .map(|v| ReflectValueRef::Message(MessageRef::converted::<V, T>(v)))
+    }
+}
+
+impl<M, V, T> GetOrDefaultImpl<M> for ConvertedMessageImpl<M, V, T>
+where
+    M: Message,
+    V: Message + TryFrom<T>,
+    T: Clone + Send + Sync + 'static,
+{
+    fn get_singular_field_or_default_impl<'a>(&self, m: &'a M) -> ReflectValueRef<'a> {
+        match self.get_reflect_impl(m) {
+            Some(v) => v,
+            None => ReflectValueRef::Message(MessageRef::from(V::default_instance())),
+        }
+    }
+}
+
+impl<M, V, T> SetImpl<M> for ConvertedMessageImpl<M, V, T>
+where
+    M: Message,
+    V: Message + ProtobufValue,
+    T: TryFrom<V> + Send + Sync + 'static,
+{
+    fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) -> Result<(), ConversionError> {
+        *(self.mut_field)(m) = Some(converted_from_value_box::<V, T>(value)?);
+        Ok(())
+    }
+}
+
+impl<M, V, T> ClearImpl<M> for ConvertedMessageImpl<M, V, T>
+where
+    M: Message,
+    V: Message,
+    T: Send + Sync + 'static,
+{
+    fn clear_singular_field(&self, m: &mut M) {
+        *(self.mut_field)(m) = None;
+    }
+}
+
+/// Make accessor for message field stored as `Option<T>`
+/// where `T` is not a message type, e. g. `Timestamp` field stored as `chrono` type.
+///
+/// Getters return a message converted from the field value,
+/// setter returns an error if the message cannot be converted to `T`,
+/// and mutable access is not supported.
+pub fn make_option_converted_message_accessor<M, V, T>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M) -> &'a Option<T>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut Option<T>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    V: Message + ProtobufValue + TryFrom<T> + 'static,
+    T: TryFrom<V> + Clone + Send + Sync + 'static,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Singular(SingularFieldAccessorHolder {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
+                get_option_impl: ConvertedMessageImpl::<M, V, T>::new(get_field, mut_field),
+                get_or_default_impl: ConvertedMessageImpl::<M, V, T>::new(get_field, mut_field),
+                mut_or_default_impl: MutOrDefaultUnsupported::new(),
+                set_impl: ConvertedMessageImpl::<M, V, T>::new(get_field, mut_field),
+                clear_impl: ConvertedMessageImpl::<M, V, T>::new(get_field, mut_field),
+                _marker: marker::PhantomData,
+            }),
+        }),
+    )
+}
+
+/// `string` or `bytes` field stored as `Option` of a different Rust type.
+struct ConvertedDerefOptionImpl<M, V, T>
+where
+    M: Message,
+    T: Send + Sync + 'static,
+{
+    get_field: for<'a> fn(&'a M) -> &'a Option<T>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut Option<T>,
+    _marker: marker::PhantomData<V>,
+}
+
+impl<M, V, T> ConvertedDerefOptionImpl<M, V, T>
+where
+    M: Message,
+    T: Send + Sync + 'static,
+{
+    fn new(
+        get_field: for<'a> fn(&'a M) -> &'a Option<T>,
+        mut_field: for<'a> fn(&'a mut M) -> &'a mut Option<T>,
+    ) -> ConvertedDerefOptionImpl<M, V, T> {
+        ConvertedDerefOptionImpl {
+            get_field,
+            mut_field,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M, V, T> GetOptionImpl<M> for ConvertedDerefOptionImpl<M, V, T>
+where
+    M: Message,
+    V: ProtobufValue,
+    V::RuntimeType: RuntimeTypeWithDeref,
+    T: AsRef<<V::RuntimeType as RuntimeTypeWithDeref>::DerefTarget> + Send + Sync + 'static,
+{
+    fn get_reflect_impl<'a>(&self, m: &'a M) -> Option<ReflectValueRef<'a>> {
+        (self.get_field)(m)
+            .as_ref()
+            .map(converted_deref_as_ref::<V, T>)
+    }
+}
+
+impl<M, V, T> GetOrDefaultImpl<M> for ConvertedDerefOptionImpl<M, V, T>
+where
+    M: Message,
+    V: ProtobufValue,
+    V::RuntimeType: RuntimeTypeWithDeref,
+    T: AsRef<<V::RuntimeType as RuntimeTypeWithDeref>::DerefTarget> + Send + Sync + 'static,
+{
+    fn get_singular_field_or_default_impl<'a>(&self, m: &'a M) -> ReflectValueRef<'a> {
+        match self.get_reflect_impl(m) {
+            Some(v) => v,
+            None => V::RuntimeType::default_value_ref(),
+        }
+    }
+}
+
+impl<M, V, T> SetImpl<M> for ConvertedDerefOptionImpl<M, V, T>
+where
+    M: Message,
+    V: ProtobufValue,
+    T: TryFrom<V> + Send + Sync + 'static,
+{
+    fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) -> Result<(), ConversionError> {
+        *(self.mut_field)(m) = Some(converted_from_value_box::<V, T>(value)?);
+        Ok(())
+    }
+}
+
+impl<M, V, T> ClearImpl<M> for ConvertedDerefOptionImpl<M, V, T>
+where
+    M: Message,
+    V: ProtobufValue,
+    T: Send + Sync + 'static,
+{
+    fn clear_singular_field(&self, m: &mut M) {
+        *(self.mut_field)(m) = None;
+    }
+}
+
+/// Make accessor for `string` or `bytes` field stored as `Option<T>`
+/// where `T` is a different Rust type, e. g. `Arc<str>`.
+///
+/// Getters view the value with `AsRef`,
+/// setter returns an error if the value cannot be converted to `T`,
+/// and mutable access is not supported.
+pub fn make_option_converted_accessor<M, V, T>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M) -> &'a Option<T>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut Option<T>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    V: ProtobufValue,
+    V::RuntimeType: RuntimeTypeWithDeref,
+    T: AsRef<<V::RuntimeType as RuntimeTypeWithDeref>::DerefTarget>
+        + TryFrom<V>
+        + Send
+        + Sync
+        + 'static,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Singular(SingularFieldAccessorHolder {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
+                get_option_impl: ConvertedDerefOptionImpl::<M, V, T>::new(get_field, mut_field),
+                get_or_default_impl: ConvertedDerefOptionImpl::<M, V, T>::new(get_field, mut_field),
+                mut_or_default_impl: MutOrDefaultUnsupported::new(),
+                set_impl: ConvertedDerefOptionImpl::<M, V, T>::new(get_field, mut_field),
+                clear_impl: ConvertedDerefOptionImpl::<M, V, T>::new(get_field, mut_field),
+                _marker: marker::PhantomData,
+            }),
+        }),
+    )
+}
+
+/// proto3 `string` or `bytes` field stored as a different Rust type.
+struct ConvertedDerefImpl<M, V, T>
+where
+    M: Message,
+    T: Send + Sync + 'static,
+{
+    get_field: for<'a> fn(&'a M) -> &'a T,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut T,
+    _marker: marker::PhantomData<V>,
+}
+
+impl<M, V, T> ConvertedDerefImpl<M, V, T>
+where
+    M: Message,
+    T: Send + Sync + 'static,
+{
+    fn new(
+        get_field: for<'a> fn(&'a M) -> &'a T,
+        mut_field: for<'a> fn(&'a mut M) -> &'a mut T,
+    ) -> ConvertedDerefImpl<M, V, T> {
+        ConvertedDerefImpl {
+            get_field,
+            mut_field,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M, V, T> GetOptionImpl<M> for ConvertedDerefImpl<M, V, T>
+where
+    M: Message,
+    V: ProtobufValue,
+    V::RuntimeType: RuntimeTypeWithDeref,
+    T: AsRef<<V::RuntimeType as RuntimeTypeWithDeref>::DerefTarget> + Send + Sync + 'static,
+{
+    fn get_reflect_impl<'a>(&self, m: &'a M) -> Option<ReflectValueRef<'a>> {
+        // proto3 field is unset when it contains empty string or bytes
+        match converted_deref_as_ref::<V, T>((self.get_field)(m)) {
+            ReflectValueRef::String("") => None,
+            ReflectValueRef::Bytes(b) if b.is_empty() => None,
+            v => Some(v),
+        }
+    }
+}
+
+impl<M, V, T> GetOrDefaultImpl<M> for ConvertedDerefImpl<M, V, T>
+where
+    M: Message,
+    V: ProtobufValue,
+    V::RuntimeType: RuntimeTypeWithDeref,
+    T: AsRef<<V::RuntimeType as RuntimeTypeWithDeref>::DerefTarget> + Send + Sync + 'static,
+{
+    fn get_singular_field_or_default_impl<'a>(&self, m: &'a M) -> ReflectValueRef<'a> {
+        converted_deref_as_ref::<V, T>((self.get_field)(m))
+    }
+}
+
+impl<M, V, T> SetImpl<M> for ConvertedDerefImpl<M, V, T>
+where
+    M: Message,
+    V: ProtobufValue,
+    T: TryFrom<V> + Send + Sync + 'static,
+{
+    fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) -> Result<(), ConversionError> {
+        *(self.mut_field)(m) = converted_from_value_box::<V, T>(value)?;
+        Ok(())
+    }
+}
+
+impl<M, V, T> ClearImpl<M> for ConvertedDerefImpl<M, V, T>
+where
+    M: Message,
+    V: ProtobufValue,
+    T: Default + Send + Sync + 'static,
+{
+    fn clear_singular_field(&self, m: &mut M) {
+        *(self.mut_field)(m) = T::default();
+    }
+}
+
+/// Make accessor for proto3 `string` or `bytes` field stored as `T`
+/// where `T` is a different Rust type, e. g. `Arc<str>`.
+///
+/// Getters view the value with `AsRef`,
+/// setter returns an error if the value cannot be converted to `T`,
+/// and mutable access is not supported.
+pub fn make_simpler_field_converted_accessor<M, V, T>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M) -> &'a T,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut T,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    V: ProtobufValue,
+    V::RuntimeType: RuntimeTypeWithDeref,
+    T: AsRef<<V::RuntimeType as RuntimeTypeWithDeref>::DerefTarget>
+        + TryFrom<V>
+        + Default
+        + Send
+        + Sync
+        + 'static,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Singular(SingularFieldAccessorHolder {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
+                get_option_impl: ConvertedDerefImpl::<M, V, T>::new(get_field, mut_field),
+                get_or_default_impl: ConvertedDerefImpl::<M, V, T>::new(get_field, mut_field),
+                mut_or_default_impl: MutOrDefaultUnsupported::new(),
+                set_impl: ConvertedDerefImpl::<M, V, T>::new(get_field, mut_field),
+                clear_impl: ConvertedDerefImpl::<M, V, T>::new(get_field, mut_field),
                 _marker: marker::PhantomData,
             }),
         }),
@@ -516,7 +965,7 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
                 get_option_impl: GetOptionImplOptionFieldPointer::<M, V, _> {
                     get_field,
                     _marker: marker::PhantomData,
@@ -524,11 +973,15 @@ where
                 get_or_default_impl: GetOrDefaultGetCopy::<M, V> {
                     get_field: get_value,
                 },
-                mut_or_default_impl: MutOrDefaultUnmplemented::new(),
+                mut_or_default_impl: MutOrDefaultUnsupported::new(),
                 set_impl: SetImplOptionFieldPointer::<M, V, _> {
                     mut_field,
                     _marker: marker::PhantomData,
                 },
+                clear_impl: ClearImplOptionFieldPointer::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -573,6 +1026,7 @@ where
                 _,
                 _,
                 _,
+                _,
             > {
                 get_option_impl: GetOptionImplOptionFieldPointer::<
                     M,
@@ -586,7 +1040,7 @@ where
                     get_field,
                     default_value,
                 },
-                mut_or_default_impl: MutOrDefaultUnmplemented::new(),
+                mut_or_default_impl: MutOrDefaultUnsupported::new(),
                 set_impl: SetImplOptionFieldPointer::<
                     M,
                     ProtobufEnumOrUnknown<E>,
@@ -595,6 +1049,14 @@ where
                     mut_field,
                     _marker: marker::PhantomData,
                 },
+                clear_impl: ClearImplOptionFieldPointer::<
+                    M,
+                    ProtobufEnumOrUnknown<E>,
+                    Option<ProtobufEnumOrUnknown<E>>,
+                > {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -616,7 +1078,7 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
                 get_option_impl: GetOptionImplOptionFieldPointer::<M, V, _> {
                     get_field,
                     _marker: marker::PhantomData,
@@ -624,11 +1086,15 @@ where
                 get_or_default_impl: GetOrDefaultGetRefDeref::<M, V> {
                     get_field: get_value,
                 },
-                mut_or_default_impl: MutOrDefaultUnmplemented::new(),
+                mut_or_default_impl: MutOrDefaultUnsupported::new(),
                 set_impl: SetImplOptionFieldPointer::<M, V, _> {
                     mut_field,
                     _marker: marker::PhantomData,
                 },
+                clear_impl: ClearImplOptionFieldPointer::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -648,11 +1114,12 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
                 get_option_impl: GetOptionImplFieldPointer::<M, V> { get_field },
                 get_or_default_impl: GetOrDefaultGetRef::<M, V> { get_field },
                 mut_or_default_impl: MutOrDefaultGetMut::<M, V> { mut_field },
                 set_impl: SetImplFieldPointer::<M, V> { mut_field },
+                clear_impl: ClearImplFieldPointer::<M, V> { mut_field },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -1,3 +1,4 @@
+use crate::reflect::acc::v2::singular::ClearImplHasClear;
 use crate::reflect::acc::v2::singular::GetOptionImplHasGetCopy;
 use crate::reflect::acc::v2::singular::GetOptionImplHasGetRef;
 use crate::reflect::acc::v2::singular::GetOptionImplHasGetRefDeref;
@@ -5,7 +6,7 @@ use crate::reflect::acc::v2::singular::GetOrDefaultGetCopy;
 use crate::reflect::acc::v2::singular::GetOrDefaultGetRef;
 use crate::reflect::acc::v2::singular::GetOrDefaultGetRefDeref;
 use crate::reflect::acc::v2::singular::MutOrDefaultGetMut;
-use crate::reflect::acc::v2::singular::MutOrDefaultUnmplemented;
+use crate::reflect::acc::v2::singular::MutOrDefaultUnsupported;
 use crate::reflect::acc::v2::singular::SetImplSetField;
 use crate::reflect::acc::v2::singular::SingularFieldAccessorHolder;
 use crate::reflect::acc::v2::singular::SingularFieldAccessorImpl;
@@ -23,6 +24,7 @@ pub fn make_oneof_message_has_get_mut_set_accessor<M, F>(
     get_field: for<'a> fn(&'a M) -> &'a F,
     mut_field: for<'a> fn(&'a mut M) -> &'a mut F,
     set_field: fn(&mut M, F),
+    clear_field: fn(&mut M),
 ) -> FieldAccessor
 where
     M: Message + 'static,
@@ -31,7 +33,7 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, F, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, F, _, _, _, _, _> {
                 get_option_impl: GetOptionImplHasGetRef::<M, F> {
                     get: get_field,
                     has: has_field,
@@ -39,6 +41,10 @@ where
                 get_or_default_impl: GetOrDefaultGetRef::<M, F> { get_field },
                 mut_or_default_impl: MutOrDefaultGetMut::<M, F> { mut_field },
                 set_impl: SetImplSetField::<M, F> { set_field },
+                clear_impl: ClearImplHasClear::<M> {
+                    has: has_field,
+                    clear: clear_field,
+                },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -51,6 +57,7 @@ pub fn make_oneof_copy_has_get_set_simpler_accessors<M, V>(
     has: fn(&M) -> bool,
     get: fn(&M) -> V,
     set: fn(&mut M, V),
+    clear: fn(&mut M),
 ) -> FieldAccessor
 where
     M: Message + 'static,
@@ -59,11 +66,12 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _, _> {
                 get_option_impl: GetOptionImplHasGetCopy::<M, V> { has, get },
                 get_or_default_impl: GetOrDefaultGetCopy::<M, V> { get_field: get },
-                mut_or_default_impl: MutOrDefaultUnmplemented::new(),
+                mut_or_default_impl: MutOrDefaultUnsupported::new(),
                 set_impl: SetImplSetField::<M, V> { set_field: set },
+                clear_impl: ClearImplHasClear::<M> { has, clear },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -76,6 +84,7 @@ pub fn make_oneof_deref_has_get_set_simpler_accessor<M, F>(
     has: fn(&M) -> bool,
     get: for<'a> fn(&'a M) -> &'a <F::RuntimeType as RuntimeTypeWithDeref>::DerefTarget,
     set: fn(&mut M, F),
+    clear: fn(&mut M),
 ) -> FieldAccessor
 where
     M: Message + 'static,
@@ -85,11 +94,12 @@ where
     FieldAccessor::new_v2(
         name,
         AccessorV2::Singular(SingularFieldAccessorHolder {
-            accessor: Box::new(SingularFieldAccessorImpl::<M, F, _, _, _, _> {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, F, _, _, _, _, _> {
                 get_option_impl: GetOptionImplHasGetRefDeref::<M, F> { has, get },
                 get_or_default_impl: GetOrDefaultGetRefDeref::<M, F> { get_field: get },
-                mut_or_default_impl: MutOrDefaultUnmplemented::new(),
+                mut_or_default_impl: MutOrDefaultUnsupported::new(),
                 set_impl: SetImplSetField::<M, F> { set_field: set },
+                clear_impl: ClearImplHasClear::<M> { has, clear },
                 _marker: marker::PhantomData,
             }),
         }),
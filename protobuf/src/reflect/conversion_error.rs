@@ -0,0 +1,29 @@
+use std::fmt;
+
+/// Error returned when a value set through reflection cannot be converted
+/// to the Rust type the field is stored as (e. g. field with `rust_type` option
+/// or a `Timestamp` field stored as `chrono` type).
+#[derive(Debug)]
+pub struct ConversionError {
+    rust_type: &'static str,
+}
+
+impl ConversionError {
+    pub(crate) fn new<T>() -> ConversionError {
+        ConversionError {
+            rust_type: std::any::type_name::<T>(),
+        }
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "value cannot be converted to field type `{}`",
+            self.rust_type
+        )
+    }
+}
+
+impl std::error::Error for ConversionError {}
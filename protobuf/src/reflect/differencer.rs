@@ -0,0 +1,498 @@
+//! Field-level comparison of messages.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::FieldPath;
+use crate::reflect::ReflectMapRef;
+use crate::reflect::ReflectRepeatedRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::text_format::print_value_to;
+
+/// How elements of repeated fields are matched by [`MessageDifferencer`].
+#[derive(Clone)]
+pub enum RepeatedFieldComparison {
+    /// Elements are matched by index.
+    AsList,
+    /// Elements are matched regardless of their order.
+    ///
+    /// Elements without equal counterpart are reported as added or removed.
+    AsSet,
+    /// Message elements are matched by the value of given field of element message.
+    ///
+    /// Matched elements are compared field by field, elements without counterpart
+    /// are reported as added or removed.
+    Keyed(FieldDescriptor),
+}
+
+/// Kind of [`FieldDifference`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FieldDifferenceKind {
+    /// Value is present only in the second message.
+    Added,
+    /// Value is present only in the first message.
+    Removed,
+    /// Values are different.
+    Modified,
+}
+
+impl fmt::Display for FieldDifferenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldDifferenceKind::Added => write!(f, "added"),
+            FieldDifferenceKind::Removed => write!(f, "removed"),
+            FieldDifferenceKind::Modified => write!(f, "modified"),
+        }
+    }
+}
+
+/// Single difference found by [`MessageDifferencer`].
+#[derive(Debug)]
+pub struct FieldDifference {
+    /// What happened to the value.
+    pub kind: FieldDifferenceKind,
+    /// Path to the value relative to the compared messages.
+    ///
+    /// Repeated field indices refer to the first message for removed elements,
+    /// and to the second message otherwise.
+    pub path: FieldPath,
+    /// Value in the first message, `None` if the value is added.
+    pub left: Option<ReflectValueBox>,
+    /// Value in the second message, `None` if the value is removed.
+    pub right: Option<ReflectValueBox>,
+}
+
+fn fmt_value(value: &Option<ReflectValueBox>) -> String {
+    let mut r = String::new();
+    print_value_to(&value.as_ref().unwrap().as_value_ref(), &mut r);
+    r
+}
+
+impl fmt::Display for FieldDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: ", self.kind, self.path)?;
+        match self.kind {
+            FieldDifferenceKind::Added => write!(f, "{}", fmt_value(&self.right)),
+            FieldDifferenceKind::Removed => write!(f, "{}", fmt_value(&self.left)),
+            FieldDifferenceKind::Modified => {
+                write!(f, "{} -> {}", fmt_value(&self.left), fmt_value(&self.right))
+            }
+        }
+    }
+}
+
+/// Result of [`MessageDifferencer::compare`].
+///
+/// `Display` prints one difference per line.
+#[derive(Debug, Default)]
+pub struct MessageDifference {
+    differences: Vec<FieldDifference>,
+}
+
+impl MessageDifference {
+    /// Found differences, in the order of field declaration.
+    pub fn differences(&self) -> &[FieldDifference] {
+        &self.differences
+    }
+
+    /// Messages are equal.
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+impl fmt::Display for MessageDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for d in &self.differences {
+            writeln!(f, "{}", d)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare messages and report differences by field path.
+///
+/// Messages may have different representations, e.g. one may be a generated message
+/// and another a dynamic message of the same type. Unknown fields are not compared.
+///
+/// ```
+/// # use protobuf::reflect::MessageDifferencer;
+/// # use protobuf::MessageDyn;
+/// # fn foo(a: &dyn MessageDyn, b: &dyn MessageDyn) {
+/// let difference = MessageDifferencer::new()
+///     .treat_unset_as_default(true)
+///     .compare(a, b);
+/// if !difference.is_empty() {
+///     println!("{}", difference);
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct MessageDifferencer {
+    ignored_fields: Vec<FieldDescriptor>,
+    repeated_field_comparisons: Vec<(FieldDescriptor, RepeatedFieldComparison)>,
+    default_repeated_field_comparison: RepeatedFieldComparison,
+    float_fraction: f64,
+    float_margin: f64,
+    treat_nan_as_equal: bool,
+    treat_unset_as_default: bool,
+}
+
+/// Fields are the same field of the same message type, possibly of different representations.
+fn same_field(a: &FieldDescriptor, b: &FieldDescriptor) -> bool {
+    a.index == b.index && a.message_descriptor.same_schema(&b.message_descriptor)
+}
+
+fn cmp_map_keys(a: &ReflectValueRef, b: &ReflectValueRef) -> Ordering {
+    match (a, b) {
+        (ReflectValueRef::U32(a), ReflectValueRef::U32(b)) => a.cmp(b),
+        (ReflectValueRef::U64(a), ReflectValueRef::U64(b)) => a.cmp(b),
+        (ReflectValueRef::I32(a), ReflectValueRef::I32(b)) => a.cmp(b),
+        (ReflectValueRef::I64(a), ReflectValueRef::I64(b)) => a.cmp(b),
+        (ReflectValueRef::Bool(a), ReflectValueRef::Bool(b)) => a.cmp(b),
+        (ReflectValueRef::String(a), ReflectValueRef::String(b)) => a.cmp(b),
+        (a, b) => panic!("not map keys or different types: {:?} and {:?}", a, b),
+    }
+}
+
+impl Default for MessageDifferencer {
+    fn default() -> MessageDifferencer {
+        MessageDifferencer::new()
+    }
+}
+
+impl MessageDifferencer {
+    /// Differencer with default options: exact comparison,
+    /// repeated fields are compared as lists, unset fields are not equal to default.
+    pub fn new() -> MessageDifferencer {
+        MessageDifferencer {
+            ignored_fields: Vec::new(),
+            repeated_field_comparisons: Vec::new(),
+            default_repeated_field_comparison: RepeatedFieldComparison::AsList,
+            float_fraction: 0.0,
+            float_margin: 0.0,
+            treat_nan_as_equal: false,
+            treat_unset_as_default: false,
+        }
+    }
+
+    /// Do not compare given field.
+    pub fn ignore_field(&mut self, field: FieldDescriptor) -> &mut Self {
+        self.ignored_fields.push(field);
+        self
+    }
+
+    /// Specify how elements of given repeated field are matched.
+    ///
+    /// # Panics
+    ///
+    /// If field is not repeated, or if the key field of
+    /// [`Keyed`](RepeatedFieldComparison::Keyed) comparison
+    /// is not a singular field of the element message.
+    pub fn repeated_field_comparison(
+        &mut self,
+        field: FieldDescriptor,
+        comparison: RepeatedFieldComparison,
+    ) -> &mut Self {
+        match field.runtime_field_type() {
+            RuntimeFieldType::Repeated(t) => {
+                if let RepeatedFieldComparison::Keyed(key) = &comparison {
+                    match t {
+                        RuntimeTypeBox::Message(m) => {
+                            assert!(
+                                m.same_schema(&key.message_descriptor) && key.is_singular(),
+                                "{} is not a singular field of {}",
+                                key,
+                                m
+                            );
+                        }
+                        _ => panic!("elements of {} are not messages", field),
+                    }
+                }
+            }
+            _ => panic!("not a repeated field: {}", field),
+        }
+        self.repeated_field_comparisons.push((field, comparison));
+        self
+    }
+
+    /// How elements of repeated fields are matched unless specified
+    /// for particular field. Default is [`AsList`](RepeatedFieldComparison::AsList).
+    ///
+    /// # Panics
+    ///
+    /// For [`Keyed`](RepeatedFieldComparison::Keyed) comparison.
+    pub fn default_repeated_field_comparison(
+        &mut self,
+        comparison: RepeatedFieldComparison,
+    ) -> &mut Self {
+        if let RepeatedFieldComparison::Keyed(..) = comparison {
+            panic!("keyed comparison can only be specified for a field");
+        }
+        self.default_repeated_field_comparison = comparison;
+        self
+    }
+
+    /// Consider `float` and `double` values equal when
+    /// `|a - b| <= margin` or `|a - b| <= fraction * max(|a|, |b|)`.
+    pub fn float_tolerance(&mut self, fraction: f64, margin: f64) -> &mut Self {
+        self.float_fraction = fraction;
+        self.float_margin = margin;
+        self
+    }
+
+    /// Consider `NaN` values equal to each other.
+    pub fn treat_nan_as_equal(&mut self, treat_nan_as_equal: bool) -> &mut Self {
+        self.treat_nan_as_equal = treat_nan_as_equal;
+        self
+    }
+
+    /// Consider unset singular fields equal to fields explicitly set to default value.
+    pub fn treat_unset_as_default(&mut self, treat_unset_as_default: bool) -> &mut Self {
+        self.treat_unset_as_default = treat_unset_as_default;
+        self
+    }
+
+    /// Compare two messages.
+    ///
+    /// # Panics
+    ///
+    /// If messages are of different types.
+    pub fn compare(&self, a: &dyn MessageDyn, b: &dyn MessageDyn) -> MessageDifference {
+        let mut comparison = Comparison {
+            differencer: self,
+            path: FieldPath::empty(&a.descriptor_dyn()),
+            differences: Vec::new(),
+        };
+        comparison.compare_messages(a, b);
+        MessageDifference {
+            differences: comparison.differences,
+        }
+    }
+
+    fn is_ignored(&self, field: &FieldDescriptor) -> bool {
+        self.ignored_fields.iter().any(|f| same_field(f, field))
+    }
+
+    fn get_repeated_field_comparison(&self, field: &FieldDescriptor) -> &RepeatedFieldComparison {
+        self.repeated_field_comparisons
+            .iter()
+            .find(|(f, _)| same_field(f, field))
+            .map(|(_, c)| c)
+            .unwrap_or(&self.default_repeated_field_comparison)
+    }
+
+    fn floats_equal(&self, a: f64, b: f64) -> bool {
+        if a == b {
+            return true;
+        }
+        if a.is_nan() || b.is_nan() {
+            return a.is_nan() && b.is_nan() && self.treat_nan_as_equal;
+        }
+        let diff = (a - b).abs();
+        diff <= self.float_margin || diff <= self.float_fraction * a.abs().max(b.abs())
+    }
+
+    fn values_equal(&self, a: &ReflectValueRef, b: &ReflectValueRef) -> bool {
+        match (a, b) {
+            (ReflectValueRef::F32(a), ReflectValueRef::F32(b)) => {
+                self.floats_equal(*a as f64, *b as f64)
+            }
+            (ReflectValueRef::F64(a), ReflectValueRef::F64(b)) => self.floats_equal(*a, *b),
+            (ReflectValueRef::Enum(_, a), ReflectValueRef::Enum(_, b)) => a == b,
+            (a, b) => a == b,
+        }
+    }
+}
+
+struct Comparison<'d> {
+    differencer: &'d MessageDifferencer,
+    path: FieldPath,
+    differences: Vec<FieldDifference>,
+}
+
+impl<'d> Comparison<'d> {
+    fn report(
+        &mut self,
+        kind: FieldDifferenceKind,
+        left: Option<ReflectValueRef>,
+        right: Option<ReflectValueRef>,
+    ) {
+        self.differences.push(FieldDifference {
+            kind,
+            path: self.path.clone(),
+            left: left.map(|v| v.to_box()),
+            right: right.map(|v| v.to_box()),
+        });
+    }
+
+    fn compare_messages(&mut self, a: &dyn MessageDyn, b: &dyn MessageDyn) {
+        let ad = a.descriptor_dyn();
+        let bd = b.descriptor_dyn();
+        assert!(ad.same_schema(&bd), "cannot compare {} with {}", ad, bd);
+
+        for (fa, fb) in ad.fields().zip(bd.fields()) {
+            if self.differencer.is_ignored(&fa) {
+                continue;
+            }
+            match fa.runtime_field_type() {
+                RuntimeFieldType::Singular(..) => self.compare_singular(&fa, a, &fb, b),
+                RuntimeFieldType::Repeated(..) => {
+                    self.compare_repeated(&fa, fa.get_repeated(a), fb.get_repeated(b))
+                }
+                RuntimeFieldType::Map(..) => self.compare_map(&fa, fa.get_map(a), fb.get_map(b)),
+            }
+        }
+    }
+
+    fn compare_singular(
+        &mut self,
+        fa: &FieldDescriptor,
+        a: &dyn MessageDyn,
+        fb: &FieldDescriptor,
+        b: &dyn MessageDyn,
+    ) {
+        let (va, vb) = match (fa.get_singular(a), fb.get_singular(b)) {
+            (None, None) => return,
+            (va, vb) if self.differencer.treat_unset_as_default => (
+                Some(va.unwrap_or_else(|| fa.get_singular_field_or_default(a))),
+                Some(vb.unwrap_or_else(|| fb.get_singular_field_or_default(b))),
+            ),
+            (va, vb) => (va, vb),
+        };
+        self.path.push_field(fa);
+        match (va, vb) {
+            (Some(va), Some(vb)) => self.compare_values(va, vb),
+            (va, vb) => self.compare_missing(va, vb),
+        }
+        self.path.pop();
+    }
+
+    fn compare_missing(&mut self, va: Option<ReflectValueRef>, vb: Option<ReflectValueRef>) {
+        match (va, vb) {
+            (Some(va), None) => self.report(FieldDifferenceKind::Removed, Some(va), None),
+            (None, Some(vb)) => self.report(FieldDifferenceKind::Added, None, Some(vb)),
+            _ => unreachable!(),
+        }
+    }
+
+    fn compare_values(&mut self, va: ReflectValueRef, vb: ReflectValueRef) {
+        match (&va, &vb) {
+            (ReflectValueRef::Message(ma), ReflectValueRef::Message(mb)) => {
+                self.compare_messages(&**ma, &**mb)
+            }
+            _ => {
+                if !self.differencer.values_equal(&va, &vb) {
+                    self.report(FieldDifferenceKind::Modified, Some(va), Some(vb));
+                }
+            }
+        }
+    }
+
+    /// Values are equal according to differencer options.
+    fn values_match(&self, va: ReflectValueRef, vb: ReflectValueRef) -> bool {
+        let mut comparison = Comparison {
+            differencer: self.differencer,
+            path: self.path.clone(),
+            differences: Vec::new(),
+        };
+        comparison.compare_values(va, vb);
+        comparison.differences.is_empty()
+    }
+
+    fn compare_repeated(
+        &mut self,
+        field: &FieldDescriptor,
+        ra: ReflectRepeatedRef,
+        rb: ReflectRepeatedRef,
+    ) {
+        // Pairs of matched indices
+        let mut matched: Vec<(usize, usize)> = Vec::new();
+        let mut matched_a = vec![false; ra.len()];
+        let mut matched_b = vec![false; rb.len()];
+
+        match self.differencer.get_repeated_field_comparison(field) {
+            RepeatedFieldComparison::AsList => {
+                for i in 0..ra.len().min(rb.len()) {
+                    matched.push((i, i));
+                }
+            }
+            RepeatedFieldComparison::AsSet => {
+                // Matched elements are equal, so only unmatched elements are reported
+                for (i, matched_a_i) in matched_a.iter_mut().enumerate() {
+                    let found = (0..rb.len())
+                        .find(|&j| !matched_b[j] && self.values_match(ra.get(i), rb.get(j)));
+                    if let Some(j) = found {
+                        *matched_a_i = true;
+                        matched_b[j] = true;
+                    }
+                }
+            }
+            RepeatedFieldComparison::Keyed(key) => {
+                let key_of = |v: ReflectValueRef| -> ReflectValueBox {
+                    let m = v.to_message().expect("element is not a message");
+                    let key = FieldDescriptor {
+                        message_descriptor: m.descriptor_dyn(),
+                        index: key.index,
+                    };
+                    key.get_singular_field_or_default(&*m).to_box()
+                };
+                let keys_b: Vec<ReflectValueBox> =
+                    (0..rb.len()).map(|j| key_of(rb.get(j))).collect();
+                for i in 0..ra.len() {
+                    let key_a = key_of(ra.get(i));
+                    let found = (0..rb.len()).find(|&j| !matched_b[j] && keys_b[j] == key_a);
+                    if let Some(j) = found {
+                        matched.push((i, j));
+                        matched_b[j] = true;
+                    }
+                }
+            }
+        }
+
+        for &(i, j) in &matched {
+            matched_a[i] = true;
+            matched_b[j] = true;
+        }
+
+        for (i, j) in matched {
+            self.path.push_index(field, j);
+            self.compare_values(ra.get(i), rb.get(j));
+            self.path.pop();
+        }
+        for i in (0..ra.len()).filter(|&i| !matched_a[i]) {
+            self.path.push_index(field, i);
+            self.report(FieldDifferenceKind::Removed, Some(ra.get(i)), None);
+            self.path.pop();
+        }
+        for j in (0..rb.len()).filter(|&j| !matched_b[j]) {
+            self.path.push_index(field, j);
+            self.report(FieldDifferenceKind::Added, None, Some(rb.get(j)));
+            self.path.pop();
+        }
+    }
+
+    fn compare_map(&mut self, field: &FieldDescriptor, ma: ReflectMapRef, mb: ReflectMapRef) {
+        let mut keys: Vec<ReflectValueRef> = (&ma).into_iter().map(|(k, _)| k).collect();
+        for (k, _) in &mb {
+            if ma.get(k.clone()).is_none() {
+                keys.push(k);
+            }
+        }
+        keys.sort_by(cmp_map_keys);
+
+        for k in keys {
+            self.path.push_key(field, k.to_box());
+            match (ma.get(k.clone()), mb.get(k)) {
+                (Some(va), Some(vb)) => self.compare_values(va, vb),
+                (va, vb) => self.compare_missing(va, vb),
+            }
+            self.path.pop();
+        }
+    }
+}
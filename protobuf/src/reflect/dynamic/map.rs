@@ -2,6 +2,8 @@ use crate::reflect::map::ReflectMap;
 use crate::reflect::map::ReflectMapIter;
 use crate::reflect::map::ReflectMapIterTrait;
 use crate::reflect::runtime_types::RuntimeType;
+use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::ConversionError;
 use crate::reflect::ProtobufValue;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
@@ -163,7 +165,24 @@ impl ReflectMap for DynamicMap {
         .map(ReflectValueBox::as_value_ref)
     }
 
-    fn insert(&mut self, key: ReflectValueBox, value: ReflectValueBox) {
+    fn get_mut<'a>(&'a mut self, key: ReflectValueRef) -> Option<ReflectValueMut<'a>> {
+        match (&mut self.maps, key) {
+            (Maps::U32(m), ReflectValueRef::U32(v)) => m.get_mut(&v),
+            (Maps::U64(m), ReflectValueRef::U64(v)) => m.get_mut(&v),
+            (Maps::I32(m), ReflectValueRef::I32(v)) => m.get_mut(&v),
+            (Maps::I64(m), ReflectValueRef::I64(v)) => m.get_mut(&v),
+            (Maps::Bool(m), ReflectValueRef::Bool(v)) => m.get_mut(&v),
+            (Maps::String(m), ReflectValueRef::String(v)) => m.get_mut(&*v),
+            _ => None,
+        }
+        .map(ReflectValueBox::as_value_mut)
+    }
+
+    fn insert(
+        &mut self,
+        key: ReflectValueBox,
+        value: ReflectValueBox,
+    ) -> Result<(), ConversionError> {
         assert!(value.get_type() == self.value);
         match (&mut self.maps, &key) {
             (Maps::U32(m), ReflectValueBox::U32(k)) => m.insert(*k, value),
@@ -177,6 +196,19 @@ impl ReflectMap for DynamicMap {
             },
             _ => panic!("wrong key type"),
         };
+        Ok(())
+    }
+
+    fn remove(&mut self, key: ReflectValueRef) -> Option<ReflectValueBox> {
+        match (&mut self.maps, key) {
+            (Maps::U32(m), ReflectValueRef::U32(v)) => m.remove(&v),
+            (Maps::U64(m), ReflectValueRef::U64(v)) => m.remove(&v),
+            (Maps::I32(m), ReflectValueRef::I32(v)) => m.remove(&v),
+            (Maps::I64(m), ReflectValueRef::I64(v)) => m.remove(&v),
+            (Maps::Bool(m), ReflectValueRef::Bool(v)) => m.remove(&v),
+            (Maps::String(m), ReflectValueRef::String(v)) => m.remove(&*v),
+            _ => None,
+        }
     }
 
     fn clear(&mut self) {
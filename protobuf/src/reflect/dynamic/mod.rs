@@ -125,7 +125,6 @@ impl DynamicMessage {
         assert_eq!(field.message_descriptor, self.descriptor);
         self.init_fields();
         self.clear_oneof_group_fields_except(field);
-        // TODO: reset oneof group fields
         match &mut self.fields[field.index] {
             DynamicFieldValue::Singular(f) => f.mut_or_default(),
             _ => panic!("Not a singular field"),
@@ -138,7 +137,6 @@ impl DynamicMessage {
     ) -> ReflectRepeatedMut<'a> {
         assert_eq!(self.descriptor, field.message_descriptor);
         self.init_fields();
-        // TODO: reset oneof group fields
         match &mut self.fields[field.index] {
             DynamicFieldValue::Repeated(r) => ReflectRepeatedMut::new(r),
             _ => panic!("Not a repeated field: {}", field),
@@ -148,7 +146,6 @@ impl DynamicMessage {
     pub(crate) fn mut_map<'a>(&'a mut self, field: &FieldDescriptor) -> ReflectMapMut<'a> {
         assert_eq!(field.message_descriptor, self.descriptor);
         self.init_fields();
-        // TODO: reset oneof group fields
         match &mut self.fields[field.index] {
             DynamicFieldValue::Map(m) => ReflectMapMut::new(m),
             _ => panic!("Not a map field: {}", field),
@@ -158,7 +155,7 @@ impl DynamicMessage {
     pub(crate) fn set_field(&mut self, field: &FieldDescriptor, value: ReflectValueBox) {
         assert_eq!(field.message_descriptor, self.descriptor);
         self.init_fields();
-        // TODO: reset oneof group fields
+        self.clear_oneof_group_fields_except(field);
         match &mut self.fields[field.index] {
             DynamicFieldValue::Singular(s) => s.set(value),
             _ => panic!("Not a singular field: {}", field),
@@ -176,7 +173,10 @@ impl DynamicMessage {
 
 impl Clear for DynamicMessage {
     fn clear(&mut self) {
-        unimplemented!()
+        for f in self.fields.iter_mut() {
+            f.clear();
+        }
+        self.unknown_fields.clear();
     }
 }
 
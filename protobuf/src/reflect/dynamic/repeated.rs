@@ -1,5 +1,7 @@
 use crate::reflect::repeated::ReflectRepeated;
 use crate::reflect::repeated::ReflectRepeatedIter;
+use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::ConversionError;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
 use crate::reflect::RuntimeTypeBox;
@@ -23,14 +25,20 @@ impl ReflectRepeated for DynamicRepeated {
         self.vec[index].as_value_ref()
     }
 
-    fn set(&mut self, index: usize, value: ReflectValueBox) {
+    fn get_mut(&mut self, index: usize) -> Option<ReflectValueMut> {
+        Some(self.vec[index].as_value_mut())
+    }
+
+    fn set(&mut self, index: usize, value: ReflectValueBox) -> Result<(), ConversionError> {
         assert_eq!(self.elem, value.get_type());
         self.vec[index] = value;
+        Ok(())
     }
 
-    fn push(&mut self, value: ReflectValueBox) {
+    fn push(&mut self, value: ReflectValueBox) -> Result<(), ConversionError> {
         assert_eq!(self.elem, value.get_type());
         self.vec.push(value);
+        Ok(())
     }
 
     fn clear(&mut self) {
@@ -5,6 +5,7 @@ use std::hash::Hasher;
 
 use crate::descriptor::EnumDescriptorProto;
 use crate::descriptor::EnumValueDescriptorProto;
+use crate::descriptor::FileDescriptorProto;
 use crate::enums::ProtobufEnum;
 use crate::reflect::enums::dynamic::DynamicEnumDescriptor;
 use crate::reflect::enums::generated::GeneratedEnumDescriptor;
@@ -137,6 +138,16 @@ impl EnumDescriptor {
         }
     }
 
+    /// [`FileDescriptor`] containing this enum.
+    pub fn file_descriptor(&self) -> &FileDescriptor {
+        &self.file_descriptor
+    }
+
+    /// `FileDescriptorProto` containg this enum type
+    pub fn file_descriptor_proto(&self) -> &FileDescriptorProto {
+        self.file_descriptor().proto()
+    }
+
     /// Enum name as given in `.proto` file
     pub fn get_name(&self) -> &str {
         // TODO: get_proto is inefficient
@@ -151,6 +162,13 @@ impl EnumDescriptor {
         }
     }
 
+    /// Both descriptors describe the same enum, possibly one is generated
+    /// and another is dynamic, created from the same `.proto` definition.
+    pub(crate) fn same_schema(&self, other: &EnumDescriptor) -> bool {
+        self == other
+            || (self.full_name() == other.full_name() && self.get_proto() == other.get_proto())
+    }
+
     /// Get `EnumDescriptor` object for given enum type
     pub fn for_type<E: ProtobufEnum>() -> EnumDescriptor {
         E::enum_descriptor_static()
@@ -19,6 +19,7 @@ use crate::reflect::reflect_eq::ReflectEqMode;
 use crate::reflect::repeated::ReflectRepeatedMut;
 use crate::reflect::repeated::ReflectRepeatedRef;
 use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::ConversionError;
 use crate::reflect::MessageDescriptor;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
@@ -109,9 +110,11 @@ impl FieldDescriptor {
     }
 
     /// Oneof descriptor containing this field.
+    ///
+    /// Synthetic oneofs of proto3 `optional` fields are not returned.
     pub fn containing_oneof(&self) -> Option<OneofDescriptor> {
         let proto = self.get_proto();
-        if proto.has_oneof_index() {
+        if proto.has_oneof_index() && !proto.get_proto3_optional() {
             Some(OneofDescriptor {
                 message_descriptor: self.message_descriptor.clone(),
                 index: proto.get_oneof_index() as usize,
@@ -182,6 +185,10 @@ impl FieldDescriptor {
     /// For repeated field or map field return `true` if
     /// collection is not empty.
     ///
+    /// For proto3 singular fields without `optional` label return `true`
+    /// if field value is not default, proto3 `optional` fields track presence
+    /// like proto2 optional fields.
+    ///
     /// # Panics
     ///
     /// If this field belongs to a different message type.
@@ -250,11 +257,39 @@ impl FieldDescriptor {
     /// # Panics
     ///
     /// If this field belongs to a different message type or
-    /// field type is not singular message.
+    /// field type is not singular message, or the field is stored
+    /// as a different Rust type (e. g. with `rust_type` codegen option)
+    /// and cannot be mutated in place.
     pub fn mut_message<'a>(&self, m: &'a mut dyn MessageDyn) -> &'a mut dyn MessageDyn {
         match self.mut_singular_field_or_default(m) {
-            ReflectValueMut::Message(m) => m,
+            Some(ReflectValueMut::Message(m)) => m,
+            None => panic!("field cannot be mutated in place: {}", self),
+        }
+    }
+
+    /// Modify a message field with a function.
+    /// Initialize field with default message if unset.
+    ///
+    /// Field stored as a different Rust type is converted to a message,
+    /// modified and converted back; an error is returned if it cannot be converted back,
+    /// and the field is left unchanged in this case.
+    ///
+    /// # Panics
+    ///
+    /// If this field belongs to a different message type or
+    /// field type is not singular message.
+    pub(crate) fn mut_message_with<R>(
+        &self,
+        m: &mut dyn MessageDyn,
+        f: impl FnOnce(&mut dyn MessageDyn) -> R,
+    ) -> Result<R, ConversionError> {
+        if let Some(ReflectValueMut::Message(message)) = self.mut_singular_field_or_default(m) {
+            return Ok(f(message));
         }
+        let mut message = self.get_message(m).clone_box();
+        let r = f(&mut *message);
+        self.try_set_singular_field(m, ReflectValueBox::Message(message))?;
+        Ok(r)
     }
 
     /// Default value.
@@ -290,12 +325,16 @@ impl FieldDescriptor {
         }
     }
 
-    // Not public because it is not implemented for all types
-    fn mut_singular_field_or_default<'a>(&self, m: &'a mut dyn MessageDyn) -> ReflectValueMut<'a> {
+    // Not public because it is not implemented for all types.
+    // Return `None` if the field is stored as a different Rust type.
+    pub(crate) fn mut_singular_field_or_default<'a>(
+        &self,
+        m: &'a mut dyn MessageDyn,
+    ) -> Option<ReflectValueMut<'a>> {
         match self.singular() {
             SingularFieldAccessorRef::Generated(g) => g.accessor.mut_field_or_default(m),
             SingularFieldAccessorRef::Dynamic(..) => {
-                DynamicMessage::downcast_mut(m).mut_singular_field_or_default(self)
+                Some(DynamicMessage::downcast_mut(m).mut_singular_field_or_default(self))
             }
         }
     }
@@ -317,11 +356,55 @@ impl FieldDescriptor {
     /// # Panics
     ///
     /// If this field belongs to a different message type or
-    /// field is not singular or value is of different type.
+    /// field is not singular or value is of different type,
+    /// or value cannot be converted to the Rust type of the field.
     pub fn set_singular_field(&self, m: &mut dyn MessageDyn, value: ReflectValueBox) {
+        if let Err(e) = self.try_set_singular_field(m, value) {
+            panic!("{}: {}", self, e);
+        }
+    }
+
+    /// Set singular field.
+    ///
+    /// Return an error if value cannot be converted to the Rust type of the field
+    /// (e. g. field generated with `rust_type` codegen option).
+    ///
+    /// # Panics
+    ///
+    /// If this field belongs to a different message type or
+    /// field is not singular or value is of different type.
+    pub fn try_set_singular_field(
+        &self,
+        m: &mut dyn MessageDyn,
+        value: ReflectValueBox,
+    ) -> Result<(), ConversionError> {
         match self.singular() {
             SingularFieldAccessorRef::Generated(g) => g.accessor.set_field(m, value),
-            SingularFieldAccessorRef::Dynamic(d) => d.set_field(m, value),
+            SingularFieldAccessorRef::Dynamic(d) => {
+                d.set_field(m, value);
+                Ok(())
+            }
+        }
+    }
+
+    /// Clear field.
+    ///
+    /// Singular field is unset, repeated and map fields are emptied.
+    /// Clearing a `oneof` field which is not currently set is no-op.
+    ///
+    /// # Panics
+    ///
+    /// If this field belongs to a different message type.
+    pub fn clear_field(&self, m: &mut dyn MessageDyn) {
+        if self.is_repeated() {
+            self.mut_repeated(m).clear();
+        } else if self.is_map() {
+            self.mut_map(m).clear();
+        } else {
+            match self.singular() {
+                SingularFieldAccessorRef::Generated(g) => g.accessor.clear_field(m),
+                SingularFieldAccessorRef::Dynamic(d) => d.clear_field(m),
+            }
         }
     }
 
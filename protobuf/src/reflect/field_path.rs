@@ -0,0 +1,735 @@
+//! Addressing values inside a message instance by textual path.
+
+use std::fmt;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::ConversionError;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::MessageRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::text_format::lexer::int;
+use crate::text_format::lexer::ParserLanguage;
+use crate::text_format::lexer::Tokenizer;
+use crate::text_format::lexer::TokenizerError;
+use crate::text_format::quote_escape_bytes;
+use crate::UnknownFields;
+
+#[derive(Debug)]
+enum FieldPathErrorInner {
+    TokenizerError(TokenizerError),
+    UnknownField(String, String),
+    NotMessage(String),
+    NotRepeatedOrMap(String),
+    ExpectingIndex(String),
+    ExpectingMapKey(String, RuntimeTypeBox),
+    IntegerOverflow,
+    IndexOutOfBounds(usize, usize),
+    EmptyPath,
+    NotMutable(String),
+    ConversionError(ConversionError),
+}
+
+/// Error returned when field path cannot be parsed or applied to a message.
+#[derive(Debug)]
+pub struct FieldPathError(FieldPathErrorInner);
+
+impl fmt::Display for FieldPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            FieldPathErrorInner::TokenizerError(e) => write!(f, "{}", e),
+            FieldPathErrorInner::UnknownField(message, field) => {
+                write!(f, "unknown field `{}` in message `{}`", field, message)
+            }
+            FieldPathErrorInner::NotMessage(field) => {
+                write!(f, "field `{}` does not contain a message", field)
+            }
+            FieldPathErrorInner::NotRepeatedOrMap(field) => {
+                write!(f, "field `{}` is neither repeated nor map", field)
+            }
+            FieldPathErrorInner::ExpectingIndex(field) => {
+                write!(f, "expecting index for repeated field `{}`", field)
+            }
+            FieldPathErrorInner::ExpectingMapKey(field, key_type) => write!(
+                f,
+                "expecting map key of type {} for field `{}`",
+                key_type, field
+            ),
+            FieldPathErrorInner::IntegerOverflow => write!(f, "integer overflow"),
+            FieldPathErrorInner::IndexOutOfBounds(index, len) => {
+                write!(f, "index {} is out of bounds, len is {}", index, len)
+            }
+            FieldPathErrorInner::EmptyPath => write!(f, "empty path does not address a field"),
+            FieldPathErrorInner::NotMutable(field) => write!(
+                f,
+                "field `{}` is stored as a different Rust type and cannot be mutated in place",
+                field
+            ),
+            FieldPathErrorInner::ConversionError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FieldPathError {}
+
+impl From<ConversionError> for FieldPathError {
+    fn from(e: ConversionError) -> Self {
+        FieldPathError(FieldPathErrorInner::ConversionError(e))
+    }
+}
+
+impl From<TokenizerError> for FieldPathError {
+    fn from(e: TokenizerError) -> Self {
+        FieldPathError(FieldPathErrorInner::TokenizerError(e))
+    }
+}
+
+impl From<int::Overflow> for FieldPathError {
+    fn from(_: int::Overflow) -> Self {
+        FieldPathError(FieldPathErrorInner::IntegerOverflow)
+    }
+}
+
+/// Element of repeated or map field addressed by field path.
+#[derive(Debug, Clone)]
+enum Subscript {
+    Index(usize),
+    Key(ReflectValueBox),
+}
+
+#[derive(Clone)]
+struct FieldPathStep {
+    field: FieldDescriptor,
+    subscript: Option<Subscript>,
+}
+
+impl FieldPathStep {
+    /// Type of value addressed by this step.
+    ///
+    /// `None` if this step addresses whole repeated or map field.
+    fn value_type(&self) -> Option<RuntimeTypeBox> {
+        match (self.field.runtime_field_type(), &self.subscript) {
+            (RuntimeFieldType::Singular(t), None) => Some(t),
+            (RuntimeFieldType::Repeated(t), Some(Subscript::Index(..))) => Some(t),
+            (RuntimeFieldType::Map(_, v), Some(Subscript::Key(..))) => Some(v),
+            (RuntimeFieldType::Repeated(..), None) | (RuntimeFieldType::Map(..), None) => None,
+            _ => unreachable!(),
+        }
+    }
+
+    fn get<'a>(&self, m: &'a dyn MessageDyn) -> Option<ReflectValueRef<'a>> {
+        match &self.subscript {
+            None => self.field.get_singular(m),
+            Some(Subscript::Index(index)) => {
+                let repeated = self.field.get_repeated(m);
+                if *index < repeated.len() {
+                    Some(repeated.get(*index))
+                } else {
+                    None
+                }
+            }
+            Some(Subscript::Key(key)) => self.field.get_map(m).get(key.as_value_ref()),
+        }
+    }
+
+    /// Create the message addressed by this step if it does not exist.
+    ///
+    /// When `create` is `false`, return `false` if the message does not exist.
+    fn prepare_message(
+        &self,
+        m: &mut dyn MessageDyn,
+        create: bool,
+    ) -> Result<bool, FieldPathError> {
+        let message_descriptor = match self.value_type() {
+            Some(RuntimeTypeBox::Message(d)) => d,
+            _ => panic!("not a message: {}", self.field),
+        };
+        match &self.subscript {
+            None => {
+                if !create && !self.field.has_field(m) {
+                    return Ok(false);
+                }
+            }
+            Some(Subscript::Index(index)) => {
+                let mut repeated = self.field.mut_repeated(m);
+                let len = repeated.len();
+                if *index == len && create {
+                    repeated
+                        .try_push(ReflectValueBox::Message(message_descriptor.new_instance()))?;
+                } else if *index >= len {
+                    return match create {
+                        true => Err(FieldPathError(FieldPathErrorInner::IndexOutOfBounds(
+                            *index, len,
+                        ))),
+                        false => Ok(false),
+                    };
+                }
+            }
+            Some(Subscript::Key(key)) => {
+                let mut map = self.field.mut_map(m);
+                if map.get(key.as_value_ref()).is_none() {
+                    if !create {
+                        return Ok(false);
+                    }
+                    map.try_insert(
+                        key.clone(),
+                        ReflectValueBox::Message(message_descriptor.new_instance()),
+                    )?;
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Mutable message addressed by this step, which must exist.
+    ///
+    /// `None` if the message is stored as a different Rust type.
+    fn value_mut<'a>(&self, m: &'a mut dyn MessageDyn) -> Option<&'a mut dyn MessageDyn> {
+        let value = match &self.subscript {
+            None => self.field.mut_singular_field_or_default(m),
+            Some(Subscript::Index(index)) => self.field.mut_repeated(m).into_value_mut(*index),
+            Some(Subscript::Key(key)) => self.field.mut_map(m).into_value_mut(key.as_value_ref()),
+        };
+        match value {
+            Some(ReflectValueMut::Message(m)) => Some(m),
+            None => None,
+        }
+    }
+
+    /// Get mutable message addressed by this step.
+    ///
+    /// When `create` is `false`, return `None` if the message does not exist,
+    /// otherwise create it.
+    fn mut_message<'a>(
+        &self,
+        m: &'a mut dyn MessageDyn,
+        create: bool,
+    ) -> Result<Option<&'a mut dyn MessageDyn>, FieldPathError> {
+        if !self.prepare_message(m, create)? {
+            return Ok(None);
+        }
+        match self.value_mut(m) {
+            Some(m) => Ok(Some(m)),
+            None => Err(FieldPathError(FieldPathErrorInner::NotMutable(
+                self.field.to_string(),
+            ))),
+        }
+    }
+
+    /// Modify message addressed by this step with a function.
+    ///
+    /// When `create` is `false`, return `None` if the message does not exist,
+    /// otherwise create it. Message stored as a different Rust type
+    /// is converted to a message, modified and converted back.
+    fn with_mut_message<R>(
+        &self,
+        m: &mut dyn MessageDyn,
+        create: bool,
+        f: impl FnOnce(&mut dyn MessageDyn) -> Result<R, FieldPathError>,
+    ) -> Result<Option<R>, FieldPathError> {
+        if !self.prepare_message(m, create)? {
+            return Ok(None);
+        }
+        if let Some(message) = self.value_mut(m) {
+            return f(message).map(Some);
+        }
+        let mut message = match &self.subscript {
+            None => self.field.get_message(m),
+            Some(..) => self.get(m).and_then(|v| v.to_message()).unwrap(),
+        }
+        .clone_box();
+        let r = f(&mut *message)?;
+        let message = ReflectValueBox::Message(message);
+        match &self.subscript {
+            None => self.field.try_set_singular_field(m, message)?,
+            Some(Subscript::Index(index)) => self.field.mut_repeated(m).try_set(*index, message)?,
+            Some(Subscript::Key(key)) => self.field.mut_map(m).try_insert(key.clone(), message)?,
+        }
+        Ok(Some(r))
+    }
+}
+
+/// Apply `f` to the message addressed by `steps`, see [`FieldPathStep::with_mut_message`].
+fn with_mut_message_at<R>(
+    steps: &[FieldPathStep],
+    m: &mut dyn MessageDyn,
+    create: bool,
+    f: impl FnOnce(&mut dyn MessageDyn) -> Result<R, FieldPathError>,
+) -> Result<Option<R>, FieldPathError> {
+    match steps.split_first() {
+        None => f(m).map(Some),
+        Some((first, rest)) => Ok(first
+            .with_mut_message(m, create, |m| with_mut_message_at(rest, m, create, f))?
+            .flatten()),
+    }
+}
+
+/// Path to a value inside a message, like `items[3].attrs["color"]`.
+///
+/// Path is a sequence of field names (`.proto` names or JSON names)
+/// separated by dots. Repeated field name may be followed by an index in brackets,
+/// and map field name may be followed by a key in brackets: string keys are quoted,
+/// integer keys are written as is, bool keys are `true` or `false`.
+///
+/// Fields in the middle of the path must address a message:
+/// a singular message field, an element of repeated message field,
+/// or a value of map field with message values.
+///
+/// The last element of the path may address a whole repeated or map field
+/// (without index or key), but such path can only be used with [`FieldPath::clear`].
+#[derive(Clone)]
+pub struct FieldPath {
+    message_descriptor: MessageDescriptor,
+    steps: Vec<FieldPathStep>,
+}
+
+struct Parser<'a> {
+    tokenizer: Tokenizer<'a>,
+}
+
+impl<'a> Parser<'a> {
+    fn next_i64(&mut self) -> Result<i64, FieldPathError> {
+        if self.tokenizer.next_symbol_if_eq('-')? {
+            let int_lit = self.tokenizer.next_int_lit()?;
+            Ok(int::neg(int_lit)?)
+        } else {
+            let int_lit = self.tokenizer.next_int_lit()?;
+            if int_lit > i64::max_value() as u64 {
+                return Err(FieldPathError(FieldPathErrorInner::IntegerOverflow));
+            }
+            Ok(int_lit as i64)
+        }
+    }
+
+    fn next_u64(&mut self) -> Result<u64, FieldPathError> {
+        Ok(self.tokenizer.next_int_lit()?)
+    }
+
+    fn next_map_key(
+        &mut self,
+        field: &FieldDescriptor,
+        key_type: &RuntimeTypeBox,
+    ) -> Result<ReflectValueBox, FieldPathError> {
+        let overflow = || FieldPathError(FieldPathErrorInner::IntegerOverflow);
+        Ok(match key_type {
+            RuntimeTypeBox::I32 => {
+                let v = self.next_i64()?;
+                if v < i32::min_value() as i64 || v > i32::max_value() as i64 {
+                    return Err(overflow());
+                }
+                ReflectValueBox::I32(v as i32)
+            }
+            RuntimeTypeBox::I64 => ReflectValueBox::I64(self.next_i64()?),
+            RuntimeTypeBox::U32 => {
+                let v = self.next_u64()?;
+                if v > u32::max_value() as u64 {
+                    return Err(overflow());
+                }
+                ReflectValueBox::U32(v as u32)
+            }
+            RuntimeTypeBox::U64 => ReflectValueBox::U64(self.next_u64()?),
+            RuntimeTypeBox::Bool => {
+                if self.tokenizer.next_ident_if_eq("true")? {
+                    ReflectValueBox::Bool(true)
+                } else if self.tokenizer.next_ident_if_eq("false")? {
+                    ReflectValueBox::Bool(false)
+                } else {
+                    return Err(FieldPathError(FieldPathErrorInner::ExpectingMapKey(
+                        field.to_string(),
+                        key_type.clone(),
+                    )));
+                }
+            }
+            RuntimeTypeBox::String => {
+                if !self.tokenizer.lookahead_is_str_lit()? {
+                    return Err(FieldPathError(FieldPathErrorInner::ExpectingMapKey(
+                        field.to_string(),
+                        key_type.clone(),
+                    )));
+                }
+                let str_lit = self.tokenizer.next_str_lit()?;
+                ReflectValueBox::String(str_lit.decode_utf8().map_err(TokenizerError::from)?)
+            }
+            t => panic!("type cannot be map key: {}", t),
+        })
+    }
+
+    fn next_step(&mut self, message: &MessageDescriptor) -> Result<FieldPathStep, FieldPathError> {
+        let name = self.tokenizer.next_ident()?;
+        let field = match message.get_field_by_name_or_json_name(&name) {
+            Some(field) => field,
+            None => {
+                return Err(FieldPathError(FieldPathErrorInner::UnknownField(
+                    message.full_name().to_owned(),
+                    name,
+                )))
+            }
+        };
+
+        let subscript = if self.tokenizer.next_symbol_if_eq('[')? {
+            let subscript = match field.runtime_field_type() {
+                RuntimeFieldType::Repeated(..) => {
+                    if !self.tokenizer.lookahead_is_int_lit()? {
+                        return Err(FieldPathError(FieldPathErrorInner::ExpectingIndex(
+                            field.to_string(),
+                        )));
+                    }
+                    let index = self.next_u64()?;
+                    if index > usize::max_value() as u64 {
+                        return Err(FieldPathError(FieldPathErrorInner::IntegerOverflow));
+                    }
+                    Subscript::Index(index as usize)
+                }
+                RuntimeFieldType::Map(k, _) => Subscript::Key(self.next_map_key(&field, &k)?),
+                RuntimeFieldType::Singular(..) => {
+                    return Err(FieldPathError(FieldPathErrorInner::NotRepeatedOrMap(
+                        field.to_string(),
+                    )))
+                }
+            };
+            self.tokenizer.next_symbol_expect_eq(']')?;
+            Some(subscript)
+        } else {
+            None
+        };
+
+        Ok(FieldPathStep { field, subscript })
+    }
+
+    fn parse(&mut self, message: &MessageDescriptor) -> Result<Vec<FieldPathStep>, FieldPathError> {
+        let mut steps = Vec::new();
+        let mut message = message.clone();
+        loop {
+            let step = self.next_step(&message)?;
+            if self.tokenizer.syntax_eof()? {
+                steps.push(step);
+                return Ok(steps);
+            }
+            self.tokenizer.next_symbol_expect_eq('.')?;
+            message = match step.value_type() {
+                Some(RuntimeTypeBox::Message(m)) => m,
+                _ => {
+                    return Err(FieldPathError(FieldPathErrorInner::NotMessage(
+                        step.field.to_string(),
+                    )))
+                }
+            };
+            steps.push(step);
+        }
+    }
+}
+
+impl FieldPath {
+    /// Parse a path relative to given message type.
+    pub fn parse(message: &MessageDescriptor, path: &str) -> Result<FieldPath, FieldPathError> {
+        let mut parser = Parser {
+            tokenizer: Tokenizer::new(path, ParserLanguage::TextFormat),
+        };
+        let steps = parser.parse(message)?;
+        Ok(FieldPath {
+            message_descriptor: message.clone(),
+            steps,
+        })
+    }
+
+    /// Empty path, addressing the message itself.
+    pub(crate) fn empty(message: &MessageDescriptor) -> FieldPath {
+        FieldPath {
+            message_descriptor: message.clone(),
+            steps: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push_field(&mut self, field: &FieldDescriptor) {
+        self.steps.push(FieldPathStep {
+            field: field.clone(),
+            subscript: None,
+        });
+    }
+
+    pub(crate) fn push_index(&mut self, field: &FieldDescriptor, index: usize) {
+        self.steps.push(FieldPathStep {
+            field: field.clone(),
+            subscript: Some(Subscript::Index(index)),
+        });
+    }
+
+    pub(crate) fn push_key(&mut self, field: &FieldDescriptor, key: ReflectValueBox) {
+        self.steps.push(FieldPathStep {
+            field: field.clone(),
+            subscript: Some(Subscript::Key(key)),
+        });
+    }
+
+    pub(crate) fn pop(&mut self) {
+        self.steps.pop().unwrap();
+    }
+
+    /// Message type this path is relative to.
+    pub fn message_descriptor(&self) -> &MessageDescriptor {
+        &self.message_descriptor
+    }
+
+    /// Path is empty when it addresses the message itself.
+    ///
+    /// Such paths cannot be parsed, but they are passed to
+    /// [`MessageVisitor`](crate::reflect::MessageVisitor) for the root message.
+    /// [`get`](FieldPath::get) and [`mut_message`](FieldPath::mut_message) return
+    /// the message itself for an empty path, [`clear`](FieldPath::clear) clears
+    /// the whole message and [`set`](FieldPath::set) returns an error.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Field addressed by the last element of the path,
+    /// `None` if the path is empty.
+    pub fn field(&self) -> Option<&FieldDescriptor> {
+        self.steps.last().map(|step| &step.field)
+    }
+
+    fn split_last(&self) -> Option<(&[FieldPathStep], &FieldPathStep)> {
+        let (last, init) = self.steps.split_last()?;
+        Some((init, last))
+    }
+
+    fn check_message_type(&self, m: &dyn MessageDyn) {
+        assert_eq!(
+            self.message_descriptor,
+            m.descriptor_dyn(),
+            "path is for a different message type"
+        );
+    }
+
+    /// Get the value addressed by this path.
+    ///
+    /// Return `None` if the value or any message on the path is not set,
+    /// repeated field index is out of bounds or map key is not found.
+    /// `None` is also returned when a message in the middle of the path
+    /// is stored as a different Rust type (e. g. with `rust_type` codegen option),
+    /// because such message is converted on access and cannot be borrowed.
+    /// Empty path returns the message itself.
+    ///
+    /// # Panics
+    ///
+    /// If message has a different type or the path ends with repeated or map field
+    /// without index or key.
+    pub fn get<'a>(&self, m: &'a dyn MessageDyn) -> Option<ReflectValueRef<'a>> {
+        self.check_message_type(m);
+        let (init, last) = match self.split_last() {
+            Some(split) => split,
+            None => return Some(ReflectValueRef::Message(MessageRef::new(m))),
+        };
+        assert!(
+            last.value_type().is_some(),
+            "path does not address a single value: {}",
+            self
+        );
+        let mut m = m;
+        for step in init {
+            m = step.get(m)?.to_message()?.as_message()?;
+        }
+        last.get(m)
+    }
+
+    fn mut_parent<'a>(
+        &self,
+        m: &'a mut dyn MessageDyn,
+        create: bool,
+    ) -> Result<Option<&'a mut dyn MessageDyn>, FieldPathError> {
+        self.check_message_type(m);
+        let init = match self.split_last() {
+            Some((init, _last)) => init,
+            None => &[],
+        };
+        let mut m = m;
+        for step in init {
+            m = match step.mut_message(m, create)? {
+                Some(m) => m,
+                None => return Ok(None),
+            };
+        }
+        Ok(Some(m))
+    }
+
+    /// Modify the message containing the value addressed by this path with a function.
+    ///
+    /// Return `None` if the message does not exist and `create` is `false`.
+    fn with_mut_parent<R>(
+        &self,
+        m: &mut dyn MessageDyn,
+        create: bool,
+        f: impl FnOnce(&mut dyn MessageDyn) -> Result<R, FieldPathError>,
+    ) -> Result<Option<R>, FieldPathError> {
+        self.check_message_type(m);
+        let init = match self.split_last() {
+            Some((init, _last)) => init,
+            None => &[],
+        };
+        with_mut_message_at(init, m, create, f)
+    }
+
+    /// Set the value addressed by this path.
+    ///
+    /// Messages on the path are created when not set. Index equal to the length
+    /// of repeated field appends an element. Missing map keys are inserted.
+    ///
+    /// Messages on the path stored as a different Rust type (e. g. with `rust_type`
+    /// codegen option) are converted to messages, modified and converted back.
+    ///
+    /// Empty path returns an error, as well as a value or a message on the path
+    /// which cannot be converted to the Rust type of the field.
+    ///
+    /// # Panics
+    ///
+    /// If message has a different type, value has a wrong type,
+    /// or the path ends with repeated or map field without index or key.
+    pub fn set(
+        &self,
+        m: &mut dyn MessageDyn,
+        value: ReflectValueBox,
+    ) -> Result<(), FieldPathError> {
+        let last = match self.steps.last() {
+            Some(last) => last,
+            None => return Err(FieldPathError(FieldPathErrorInner::EmptyPath)),
+        };
+        self.with_mut_parent(m, true, |m| {
+            match &last.subscript {
+                None => {
+                    assert!(
+                        last.field.is_singular(),
+                        "path does not address a single value: {}",
+                        self
+                    );
+                    last.field.try_set_singular_field(m, value)?;
+                }
+                Some(Subscript::Index(index)) => {
+                    let mut repeated = last.field.mut_repeated(m);
+                    let len = repeated.len();
+                    if *index < len {
+                        repeated.try_set(*index, value)?;
+                    } else if *index == len {
+                        repeated.try_push(value)?;
+                    } else {
+                        return Err(FieldPathError(FieldPathErrorInner::IndexOutOfBounds(
+                            *index, len,
+                        )));
+                    }
+                }
+                Some(Subscript::Key(key)) => {
+                    last.field.mut_map(m).try_insert(key.clone(), value)?;
+                }
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Clear the value addressed by this path.
+    ///
+    /// Singular field is unset, repeated or map field without index or key is emptied,
+    /// map entry is removed, and repeated field element is reset to default value.
+    ///
+    /// This operation does not create messages on the path, and it is no-op
+    /// if the value does not exist. Empty path clears all fields of the message.
+    ///
+    /// Messages on the path stored as a different Rust type are converted to messages,
+    /// modified and converted back; an error is returned if they cannot be converted back.
+    ///
+    /// # Panics
+    ///
+    /// If message has a different type.
+    pub fn clear(&self, m: &mut dyn MessageDyn) -> Result<(), FieldPathError> {
+        self.with_mut_parent(m, false, |m| {
+            let last = match self.steps.last() {
+                Some(last) => last,
+                None => {
+                    for field in m.descriptor_dyn().fields() {
+                        field.clear_field(m);
+                    }
+                    *m.mut_unknown_fields_dyn() = UnknownFields::new();
+                    return Ok(());
+                }
+            };
+            match &last.subscript {
+                None => last.field.clear_field(m),
+                Some(Subscript::Index(index)) => {
+                    let mut repeated = last.field.mut_repeated(m);
+                    if *index < repeated.len() {
+                        let value = match repeated.element_type() {
+                            RuntimeTypeBox::Message(d) => {
+                                ReflectValueBox::Message(d.new_instance())
+                            }
+                            t => t.default_value_ref().to_box(),
+                        };
+                        repeated.try_set(*index, value)?;
+                    }
+                }
+                Some(Subscript::Key(key)) => {
+                    last.field.mut_map(m).remove(key.as_value_ref());
+                }
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Get a mutable reference to the message addressed by this path,
+    /// creating it and all messages on the path when not set.
+    ///
+    /// Index equal to the length of repeated field appends a new element.
+    /// Empty path returns the message itself.
+    ///
+    /// Return an error if a message on the path is stored as a different Rust type
+    /// (e. g. with `rust_type` codegen option), because it cannot be mutated in place.
+    ///
+    /// # Panics
+    ///
+    /// If message has a different type or the path does not address a message.
+    pub fn mut_message<'a>(
+        &self,
+        m: &'a mut dyn MessageDyn,
+    ) -> Result<&'a mut dyn MessageDyn, FieldPathError> {
+        let m = self.mut_parent(m, true)?.unwrap();
+        match self.steps.last() {
+            Some(last) => Ok(last.mut_message(m, true)?.unwrap()),
+            None => Ok(m),
+        }
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i != 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{}", step.field.get_name())?;
+            match &step.subscript {
+                None => {}
+                Some(Subscript::Index(index)) => write!(f, "[{}]", index)?,
+                Some(Subscript::Key(ReflectValueBox::String(s))) => {
+                    write!(f, "[{}]", quote_escape_bytes(s.as_bytes()))?
+                }
+                Some(Subscript::Key(key)) => match key.as_value_ref() {
+                    ReflectValueRef::U32(v) => write!(f, "[{}]", v)?,
+                    ReflectValueRef::U64(v) => write!(f, "[{}]", v)?,
+                    ReflectValueRef::I32(v) => write!(f, "[{}]", v)?,
+                    ReflectValueRef::I64(v) => write!(f, "[{}]", v)?,
+                    ReflectValueRef::Bool(v) => write!(f, "[{}]", v)?,
+                    v => unreachable!("not a map key: {:?}", v),
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
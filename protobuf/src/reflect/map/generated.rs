@@ -1,13 +1,22 @@
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::hash::Hash;
+use std::marker;
+
+use crate::message::Message;
 use crate::reflect::map::ReflectMap;
 use crate::reflect::map::{ReflectMapIter, ReflectMapIterTrait};
+use crate::reflect::repeated::converted_from_value_box;
+use crate::reflect::runtime_types::RuntimeType;
 use crate::reflect::runtime_types::RuntimeTypeHashable;
+use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::ConversionError;
+use crate::reflect::MessageRef;
 use crate::reflect::ProtobufValue;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
 use crate::reflect::RuntimeTypeBox;
-use std::collections::hash_map;
-use std::collections::HashMap;
-use std::hash::Hash;
 
 impl<K, V> ReflectMap for HashMap<K, V>
 where
@@ -31,10 +40,24 @@ where
         <K::RuntimeType as RuntimeTypeHashable>::hash_map_get(self, key).map(V::as_ref)
     }
 
-    fn insert(&mut self, key: ReflectValueBox, value: ReflectValueBox) {
+    fn get_mut<'a>(&'a mut self, key: ReflectValueRef) -> Option<ReflectValueMut<'a>> {
+        <K::RuntimeType as RuntimeTypeHashable>::hash_map_get_mut(self, key).map(V::as_mut)
+    }
+
+    fn insert(
+        &mut self,
+        key: ReflectValueBox,
+        value: ReflectValueBox,
+    ) -> Result<(), ConversionError> {
         let key: K = key.downcast().expect("wrong key type");
         let value: V = value.downcast().expect("wrong value type");
         self.insert(key, value);
+        Ok(())
+    }
+
+    fn remove(&mut self, key: ReflectValueRef) -> Option<ReflectValueBox> {
+        <K::RuntimeType as RuntimeTypeHashable>::hash_map_remove(self, key)
+            .map(V::RuntimeType::into_value_box)
     }
 
     fn clear(&mut self) {
@@ -72,3 +95,113 @@ impl<'a, K: ProtobufValue + Eq + Hash, V: ProtobufValue> ReflectMapIterTrait<'a>
         V::runtime_type_box()
     }
 }
+
+/// `HashMap<K, T>` viewed as a map with message `V` values,
+/// where `T` is a different Rust type storing the message.
+#[repr(transparent)]
+pub(crate) struct ConvertedMessageMap<K, V, T> {
+    map: HashMap<K, T>,
+    _marker: marker::PhantomData<fn() -> V>,
+}
+
+impl<K, V, T> ConvertedMessageMap<K, V, T> {
+    pub(crate) fn from_map(map: &HashMap<K, T>) -> &ConvertedMessageMap<K, V, T> {
+        // Safe because `ConvertedMessageMap` is `repr(transparent)` wrapper of `HashMap<K, T>`
+        unsafe { &*(map as *const HashMap<K, T> as *const ConvertedMessageMap<K, V, T>) }
+    }
+
+    pub(crate) fn from_map_mut(map: &mut HashMap<K, T>) -> &mut ConvertedMessageMap<K, V, T> {
+        // Safe because `ConvertedMessageMap` is `repr(transparent)` wrapper of `HashMap<K, T>`
+        unsafe { &mut *(map as *mut HashMap<K, T> as *mut ConvertedMessageMap<K, V, T>) }
+    }
+}
+
+impl<K, V, T> ReflectMap for ConvertedMessageMap<K, V, T>
+where
+    K: ProtobufValue + Eq + Hash,
+    K::RuntimeType: RuntimeTypeHashable,
+    V: Message + ProtobufValue + TryFrom<T>,
+    T: TryFrom<V> + Clone + Send + Sync + 'static,
+{
+    fn reflect_iter<'a>(&'a self) -> ReflectMapIter<'a> {
+        ReflectMapIter::new(ConvertedMapIterImpl::<'a, K, V, T> {
+            iter: self.map.iter(),
+            _marker: marker::PhantomData,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    fn get<'a>(&'a self, key: ReflectValueRef) -> Option<ReflectValueRef<'a>> {
+        <K::RuntimeType as RuntimeTypeHashable>::hash_map_get(&self.map, key)
+            .map(|v| ReflectValueRef::Message(MessageRef::converted::<V, T>(v)))
+    }
+
+    fn get_mut<'a>(&'a mut self, _key: ReflectValueRef) -> Option<ReflectValueMut<'a>> {
+        None
+    }
+
+    fn insert(
+        &mut self,
+        key: ReflectValueBox,
+        value: ReflectValueBox,
+    ) -> Result<(), ConversionError> {
+        let key: K = key.downcast().expect("wrong key type");
+        let value: T = converted_from_value_box::<V, T>(value)?;
+        self.map.insert(key, value);
+        Ok(())
+    }
+
+    fn remove(&mut self, key: ReflectValueRef) -> Option<ReflectValueBox> {
+        <K::RuntimeType as RuntimeTypeHashable>::hash_map_remove(&mut self.map, key).map(|v| {
+            ReflectValueBox::Message(Box::new(V::try_from(v).unwrap_or_else(|_| V::new())))
+        })
+    }
+
+    fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    fn key_type(&self) -> RuntimeTypeBox {
+        K::runtime_type_box()
+    }
+
+    fn value_type(&self) -> RuntimeTypeBox {
+        V::runtime_type_box()
+    }
+}
+
+struct ConvertedMapIterImpl<'a, K: Eq + Hash + 'static, V, T: 'static> {
+    iter: hash_map::Iter<'a, K, T>,
+    _marker: marker::PhantomData<fn() -> V>,
+}
+
+impl<'a, K, V, T> ReflectMapIterTrait<'a> for ConvertedMapIterImpl<'a, K, V, T>
+where
+    K: ProtobufValue + Eq + Hash,
+    V: Message + ProtobufValue + TryFrom<T>,
+    T: Clone + 'static,
+{
+    fn next(&mut self) -> Option<(ReflectValueRef<'a>, ReflectValueRef<'a>)> {
+        self.iter.next().map(|(k, v)| {
+            (
+                K::as_ref(k),
+                ReflectValueRef::Message(MessageRef::converted::<V, T>(v)),
+            )
+        })
+    }
+
+    fn key_type(&self) -> RuntimeTypeBox {
+        K::runtime_type_box()
+    }
+
+    fn value_type(&self) -> RuntimeTypeBox {
+        V::runtime_type_box()
+    }
+}
@@ -1,10 +1,12 @@
 use crate::reflect::reflect_eq::ReflectEq;
 use crate::reflect::reflect_eq::ReflectEqMode;
+use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::ConversionError;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
 use crate::reflect::RuntimeTypeBox;
 
-mod generated;
+pub(crate) mod generated;
 
 /// Implemented for `HashMap` with appropriate keys and values
 pub(crate) trait ReflectMap: Send + Sync + 'static {
@@ -16,7 +18,17 @@ pub(crate) trait ReflectMap: Send + Sync + 'static {
 
     fn get<'a>(&'a self, key: ReflectValueRef) -> Option<ReflectValueRef<'a>>;
 
-    fn insert(&mut self, key: ReflectValueBox, value: ReflectValueBox);
+    /// `None` if there is no such key or values are stored as a different Rust type
+    /// and cannot be mutated in place.
+    fn get_mut<'a>(&'a mut self, key: ReflectValueRef) -> Option<ReflectValueMut<'a>>;
+
+    fn insert(
+        &mut self,
+        key: ReflectValueBox,
+        value: ReflectValueBox,
+    ) -> Result<(), ConversionError>;
+
+    fn remove(&mut self, key: ReflectValueRef) -> Option<ReflectValueBox>;
 
     fn clear(&mut self);
 
@@ -85,7 +97,7 @@ impl<'a> ReflectMapRef<'a> {
     }
 
     /// Find a value by given key.
-    pub fn get(&self, key: ReflectValueRef) -> Option<ReflectValueRef> {
+    pub fn get(&self, key: ReflectValueRef) -> Option<ReflectValueRef<'a>> {
         self.map.get(key)
     }
 
@@ -161,15 +173,45 @@ impl<'a> ReflectMapMut<'a> {
     ///
     /// # Panics
     ///
-    /// If given key has an incompatible key type.
+    /// If given key has an incompatible key type,
+    /// or value cannot be converted to the Rust type of map values.
     pub fn insert(&mut self, key: ReflectValueBox, value: ReflectValueBox) {
+        if let Err(e) = self.try_insert(key, value) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Insert a value into the map.
+    ///
+    /// Return an error if value cannot be converted to the Rust type of map values.
+    ///
+    /// # Panics
+    ///
+    /// If given key has an incompatible key type.
+    pub fn try_insert(
+        &mut self,
+        key: ReflectValueBox,
+        value: ReflectValueBox,
+    ) -> Result<(), ConversionError> {
         self.map.insert(key, value)
     }
 
+    /// Remove an entry with given key.
+    ///
+    /// Return removed value or `None` if there was no such key.
+    pub fn remove(&mut self, key: ReflectValueRef) -> Option<ReflectValueBox> {
+        self.map.remove(key)
+    }
+
     /// Clear
     pub fn clear(&mut self) {
         self.map.clear();
     }
+
+    /// Get a mutable reference to a value for given key.
+    pub(crate) fn into_value_mut(self, key: ReflectValueRef) -> Option<ReflectValueMut<'a>> {
+        self.map.get_mut(key)
+    }
 }
 
 /// Iterator over map
@@ -3,12 +3,14 @@ use crate::reflect::dynamic::DynamicMessage;
 use crate::reflect::reflect_eq::{ReflectEq, ReflectEqMode};
 use crate::reflect::MessageDescriptor;
 use crate::Message;
+use std::convert::TryFrom;
 use std::ops::Deref;
 
 #[derive(Clone, Debug)]
 enum MessageRefImpl<'a> {
     Message(&'a dyn MessageDyn),
     EmptyDynamic(DynamicMessage),
+    Owned(Box<dyn MessageDyn>),
 }
 
 /// Wrapper around either [`Message`] reference or a container for an empty dynamic message.
@@ -37,7 +39,7 @@ impl<'a> ReflectEq for MessageRef<'a> {
     fn reflect_eq(&self, that: &Self, mode: &ReflectEqMode) -> bool {
         let ad = self.descriptor_dyn();
         let bd = that.descriptor_dyn();
-        ad == bd && ad.reflect_eq(&**self, &**that, mode)
+        ad.same_schema(&bd) && ad.reflect_eq(&**self, &**that, mode)
     }
 }
 
@@ -60,6 +62,33 @@ impl<'a> MessageRef<'a> {
             },
         }
     }
+
+    /// Message owned by this object, e. g. converted from a field of a different Rust type.
+    pub(crate) fn new_owned(message: Box<dyn MessageDyn>) -> MessageRef<'static> {
+        MessageRef {
+            imp: MessageRefImpl::Owned(message),
+        }
+    }
+
+    /// Message converted from a value of message field stored as a different Rust type.
+    ///
+    /// Value which cannot be converted is viewed as an empty message.
+    pub(crate) fn converted<M, T>(value: &T) -> MessageRef<'static>
+    where
+        M: Message + TryFrom<T>,
+        T: Clone,
+    {
+        let m = M::try_from(value.clone()).unwrap_or_else(|_| M::new());
+        MessageRef::new_owned(Box::new(m))
+    }
+
+    /// Referenced message, unless this is a message owned by this object.
+    pub(crate) fn as_message(&self) -> Option<&'a dyn MessageDyn> {
+        match &self.imp {
+            MessageRefImpl::Message(m) => Some(*m),
+            MessageRefImpl::EmptyDynamic(..) | MessageRefImpl::Owned(..) => None,
+        }
+    }
 }
 
 impl<'a> Deref for MessageRef<'a> {
@@ -69,6 +98,7 @@ impl<'a> Deref for MessageRef<'a> {
         match &self.imp {
             MessageRefImpl::Message(m) => *m,
             MessageRefImpl::EmptyDynamic(e) => e,
+            MessageRefImpl::Owned(m) => &**m,
         }
     }
 }
@@ -15,6 +15,11 @@ use crate::reflect::message::index::MessageIndex;
 use crate::reflect::reflect_eq::ReflectEq;
 use crate::reflect::reflect_eq::ReflectEqMode;
 use crate::reflect::FileDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
 use crate::reflect::{EnumDescriptor, FieldDescriptor, OneofDescriptor};
 
 pub(crate) mod dynamic;
@@ -23,6 +28,22 @@ pub(crate) mod index;
 pub(crate) mod message_ref;
 pub(crate) mod path;
 
+/// Convert a value to given type, which is either the same type,
+/// or the same message or enum type with different representation.
+fn transcode_value(value: ReflectValueRef, t: &RuntimeTypeBox) -> ReflectValueBox {
+    match (value, t) {
+        (ReflectValueRef::Message(m), RuntimeTypeBox::Message(d)) => {
+            let mut r = d.new_instance();
+            MessageDescriptor::merge_from_message(&mut *r, &*m);
+            ReflectValueBox::Message(r)
+        }
+        (ReflectValueRef::Enum(_, v), RuntimeTypeBox::Enum(d)) => {
+            ReflectValueBox::Enum(d.clone(), v)
+        }
+        (value, _) => value.to_box(),
+    }
+}
+
 /// Dynamic representation of message type.
 ///
 /// Used for reflection.
@@ -182,13 +203,23 @@ impl MessageDescriptor {
 
     /// Check if two messages equal.
     ///
+    /// Messages may have different representations, e.g. one may be
+    /// a generated message and another a dynamic message of the same type.
+    ///
     /// # Panics
     ///
     /// Is any message has different type than this descriptor.
     pub fn eq(&self, a: &dyn MessageDyn, b: &dyn MessageDyn) -> bool {
         match self.get_impl() {
-            MessageDescriptorImplRef::Generated(g) => g.non_map().factory.eq(a, b),
-            MessageDescriptorImplRef::Dynamic(..) => unimplemented!(),
+            MessageDescriptorImplRef::Generated(g)
+                if &a.descriptor_dyn() == self && &b.descriptor_dyn() == self =>
+            {
+                g.non_map().factory.eq(a, b)
+            }
+            _ => {
+                self.reflect_eq(a, b, &ReflectEqMode::default())
+                    && a.get_unknown_fields_dyn() == b.get_unknown_fields_dyn()
+            }
         }
     }
 
@@ -204,12 +235,14 @@ impl MessageDescriptor {
         mode: &ReflectEqMode,
     ) -> bool {
         // Explicitly force panic even if field list is empty
-        assert_eq!(self, &a.descriptor_dyn());
-        assert_eq!(self, &b.descriptor_dyn());
+        let ad = a.descriptor_dyn();
+        let bd = b.descriptor_dyn();
+        assert!(self.same_schema(&ad), "{} is not {}", ad, self);
+        assert!(self.same_schema(&bd), "{} is not {}", bd, self);
 
-        for field in self.fields() {
-            let af = field.get_reflect(a);
-            let bf = field.get_reflect(b);
+        for (af, bf) in ad.fields().zip(bd.fields()) {
+            let af = af.get_reflect(a);
+            let bf = bf.get_reflect(b);
             if !af.reflect_eq(&bf, mode) {
                 return false;
             }
@@ -224,7 +257,75 @@ impl MessageDescriptor {
     ) -> bool {
         let ad = a.descriptor_dyn();
         let bd = b.descriptor_dyn();
-        ad == bd && ad.reflect_eq(a, b, mode)
+        ad.same_schema(&bd) && ad.reflect_eq(a, b, mode)
+    }
+
+    /// Both descriptors describe the same message type, possibly one is generated
+    /// and another is dynamic, created from the same `.proto` definition.
+    pub(crate) fn same_schema(&self, other: &MessageDescriptor) -> bool {
+        self == other
+            || (self.full_name() == other.full_name() && self.get_proto() == other.get_proto())
+    }
+
+    /// Merge fields of `source` into `target` through reflection.
+    ///
+    /// # Panics
+    ///
+    /// If messages are of different types, or a value cannot be converted
+    /// to the Rust type of a field.
+    pub(crate) fn merge_from_message(target: &mut dyn MessageDyn, source: &dyn MessageDyn) {
+        let td = target.descriptor_dyn();
+        let sd = source.descriptor_dyn();
+        assert!(td.same_schema(&sd), "cannot merge {} into {}", sd, td);
+
+        for (tf, sf) in td.fields().zip(sd.fields()) {
+            MessageDescriptor::merge_field_from_message(&tf, target, &sf, source);
+        }
+
+        for (number, values) in source.get_unknown_fields_dyn() {
+            for value in values {
+                target
+                    .mut_unknown_fields_dyn()
+                    .add_value(number, value.to_box());
+            }
+        }
+    }
+
+    /// Merge single field `sf` of `source` into field `tf` of `target`.
+    ///
+    /// Fields must be the same field of messages of the same schema.
+    pub(crate) fn merge_field_from_message(
+        tf: &FieldDescriptor,
+        target: &mut dyn MessageDyn,
+        sf: &FieldDescriptor,
+        source: &dyn MessageDyn,
+    ) {
+        match (sf.get_reflect(source), tf.runtime_field_type()) {
+            (ReflectFieldRef::Optional(None), _) => {}
+            (ReflectFieldRef::Optional(Some(ReflectValueRef::Message(m))), _) => {
+                if let Err(e) =
+                    tf.mut_message_with(target, |t| MessageDescriptor::merge_from_message(t, &*m))
+                {
+                    panic!("{}: {}", tf, e);
+                }
+            }
+            (ReflectFieldRef::Optional(Some(v)), RuntimeFieldType::Singular(t)) => {
+                tf.set_singular_field(target, transcode_value(v, &t));
+            }
+            (ReflectFieldRef::Repeated(r), RuntimeFieldType::Repeated(t)) => {
+                let mut target_repeated = tf.mut_repeated(target);
+                for v in r {
+                    target_repeated.push(transcode_value(v, &t));
+                }
+            }
+            (ReflectFieldRef::Map(m), RuntimeFieldType::Map(_, t)) => {
+                let mut target_map = tf.mut_map(target);
+                for (k, v) in &m {
+                    target_map.insert(k.to_box(), transcode_value(v, &t));
+                }
+            }
+            _ => unreachable!(),
+        }
     }
 
     /// Message name as given in `.proto` file
@@ -245,16 +346,24 @@ impl MessageDescriptor {
         })
     }
 
-    /// Nested oneofs
+    /// Nested oneofs.
+    ///
+    /// Synthetic oneofs of proto3 `optional` fields are not included.
     pub fn oneofs<'a>(&'a self) -> impl ExactSizeIterator<Item = OneofDescriptor> + 'a {
-        self.get_proto()
-            .oneof_decl
-            .iter()
-            .enumerate()
-            .map(move |(index, _)| OneofDescriptor {
-                message_descriptor: self.clone(),
-                index,
+        // synthetic oneofs are always placed after all regular oneofs
+        let count = (0..self.get_proto().oneof_decl.len())
+            .take_while(|&index| {
+                !OneofDescriptor {
+                    message_descriptor: self.clone(),
+                    index,
+                }
+                .is_synthetic()
             })
+            .count();
+        (0..count).map(move |index| OneofDescriptor {
+            message_descriptor: self.clone(),
+            index,
+        })
     }
 
     pub(crate) fn get_index(&self) -> &MessageIndex {
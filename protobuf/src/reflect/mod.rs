@@ -1,9 +1,12 @@
 //! Reflection implementation for protobuf types.
 
 mod acc;
+mod conversion_error;
+mod differencer;
 mod dynamic;
 mod enums;
 mod field;
+mod field_path;
 mod file;
 mod find_message_or_enum;
 mod map;
@@ -13,6 +16,7 @@ mod repeated;
 mod runtime_type_box;
 mod type_dynamic;
 pub(crate) mod value;
+mod visit;
 
 pub mod runtime_types;
 pub mod types;
@@ -47,6 +51,22 @@ pub use self::field::FieldDescriptor;
 pub use self::field::ReflectFieldRef;
 pub use self::field::RuntimeFieldType;
 
+pub use self::conversion_error::ConversionError;
+
+pub use self::field_path::FieldPath;
+pub use self::field_path::FieldPathError;
+
+pub use self::differencer::FieldDifference;
+pub use self::differencer::FieldDifferenceKind;
+pub use self::differencer::MessageDifference;
+pub use self::differencer::MessageDifferencer;
+pub use self::differencer::RepeatedFieldComparison;
+
+pub use self::visit::walk_message;
+pub use self::visit::walk_message_mut;
+pub use self::visit::MessageVisitor;
+pub use self::visit::MessageVisitorMut;
+
 pub use self::oneof::OneofDescriptor;
 
 #[doc(hidden)]
@@ -1,4 +1,5 @@
 use crate::descriptor::OneofDescriptorProto;
+use crate::message_dyn::MessageDyn;
 use crate::reflect::FieldDescriptor;
 use crate::reflect::MessageDescriptor;
 
@@ -20,10 +21,39 @@ impl OneofDescriptor {
         self.get_proto().get_name()
     }
 
+    /// Is this oneof generated by protobuf compiler for proto3 `optional` field.
+    pub(crate) fn is_synthetic(&self) -> bool {
+        self.message_descriptor.get_proto().field.iter().any(|f| {
+            f.has_oneof_index()
+                && f.get_oneof_index() as usize == self.index
+                && f.get_proto3_optional()
+        })
+    }
+
     /// Fields in this oneof.
     pub fn fields<'a>(&'a self) -> impl Iterator<Item = FieldDescriptor> + 'a {
         self.message_descriptor
             .fields()
             .filter(move |f| f.containing_oneof().as_ref() == Some(self))
     }
+
+    /// Field of this oneof which is set in given message.
+    ///
+    /// # Panics
+    ///
+    /// If this oneof belongs to a different message type.
+    pub fn get_field(&self, m: &dyn MessageDyn) -> Option<FieldDescriptor> {
+        self.fields().find(|f| f.has_field(m))
+    }
+
+    /// Unset any field of this oneof.
+    ///
+    /// # Panics
+    ///
+    /// If this oneof belongs to a different message type.
+    pub fn clear(&self, m: &mut dyn MessageDyn) {
+        for field in self.fields() {
+            field.clear_field(m);
+        }
+    }
 }
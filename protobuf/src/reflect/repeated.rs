@@ -1,10 +1,18 @@
+use std::convert::TryFrom;
 use std::fmt;
+use std::marker;
 use std::slice;
 
+use crate::message::Message;
+
 use crate::reflect::dynamic::repeated::DynamicRepeated;
 use crate::reflect::reflect_eq::ReflectEq;
 use crate::reflect::reflect_eq::ReflectEqMode;
+use crate::reflect::runtime_types::RuntimeTypeWithDeref;
+use crate::reflect::value::value_ref::ReflectValueMut;
 use crate::reflect::value::value_ref::ReflectValueRef;
+use crate::reflect::ConversionError;
+use crate::reflect::MessageRef;
 use crate::reflect::ProtobufValue;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::RuntimeTypeBox;
@@ -13,8 +21,10 @@ pub(crate) trait ReflectRepeated: Sync + 'static + fmt::Debug {
     fn reflect_iter(&self) -> ReflectRepeatedIter;
     fn len(&self) -> usize;
     fn get(&self, index: usize) -> ReflectValueRef;
-    fn set(&mut self, index: usize, value: ReflectValueBox);
-    fn push(&mut self, value: ReflectValueBox);
+    /// `None` if elements are stored as a different Rust type and cannot be mutated in place.
+    fn get_mut(&mut self, index: usize) -> Option<ReflectValueMut>;
+    fn set(&mut self, index: usize, value: ReflectValueBox) -> Result<(), ConversionError>;
+    fn push(&mut self, value: ReflectValueBox) -> Result<(), ConversionError>;
     fn clear(&mut self);
     fn element_type(&self) -> RuntimeTypeBox;
 }
@@ -34,14 +44,20 @@ impl<V: ProtobufValue> ReflectRepeated for Vec<V> {
         V::as_ref(&self[index])
     }
 
-    fn set(&mut self, index: usize, value: ReflectValueBox) {
+    fn get_mut(&mut self, index: usize) -> Option<ReflectValueMut> {
+        Some(V::as_mut(&mut self[index]))
+    }
+
+    fn set(&mut self, index: usize, value: ReflectValueBox) -> Result<(), ConversionError> {
         let value = value.downcast().expect("wrong type");
         self[index] = value;
+        Ok(())
     }
 
-    fn push(&mut self, value: ReflectValueBox) {
+    fn push(&mut self, value: ReflectValueBox) -> Result<(), ConversionError> {
         let value = value.downcast().expect("wrong type");
-        self.push(value)
+        self.push(value);
+        Ok(())
     }
 
     fn clear(&mut self) {
@@ -69,12 +85,17 @@ impl<V: ProtobufValue> ReflectRepeated for [V] {
         V::as_ref(&self[index])
     }
 
-    fn set(&mut self, index: usize, value: ReflectValueBox) {
+    fn get_mut(&mut self, index: usize) -> Option<ReflectValueMut> {
+        Some(V::as_mut(&mut self[index]))
+    }
+
+    fn set(&mut self, index: usize, value: ReflectValueBox) -> Result<(), ConversionError> {
         let value = value.downcast().expect("wrong type");
         self[index] = value;
+        Ok(())
     }
 
-    fn push(&mut self, _value: ReflectValueBox) {
+    fn push(&mut self, _value: ReflectValueBox) -> Result<(), ConversionError> {
         panic!("push is not possible for [V]");
     }
 
@@ -87,6 +108,178 @@ impl<V: ProtobufValue> ReflectRepeated for [V] {
     }
 }
 
+/// `Vec<T>` of message field elements stored as a different Rust type,
+/// viewed as a repeated field of messages `V`.
+#[repr(transparent)]
+pub(crate) struct ConvertedMessageVec<V, T> {
+    vec: Vec<T>,
+    _marker: marker::PhantomData<V>,
+}
+
+impl<V, T> ConvertedMessageVec<V, T> {
+    pub(crate) fn from_vec(vec: &Vec<T>) -> &ConvertedMessageVec<V, T> {
+        // Safe because `ConvertedMessageVec` is `repr(transparent)` wrapper of `Vec<T>`
+        unsafe { &*(vec as *const Vec<T> as *const ConvertedMessageVec<V, T>) }
+    }
+
+    pub(crate) fn from_vec_mut(vec: &mut Vec<T>) -> &mut ConvertedMessageVec<V, T> {
+        // Safe because `ConvertedMessageVec` is `repr(transparent)` wrapper of `Vec<T>`
+        unsafe { &mut *(vec as *mut Vec<T> as *mut ConvertedMessageVec<V, T>) }
+    }
+}
+
+impl<V, T: fmt::Debug> fmt::Debug for ConvertedMessageVec<V, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.vec, f)
+    }
+}
+
+impl<V, T> ReflectRepeated for ConvertedMessageVec<V, T>
+where
+    V: Message + ProtobufValue + TryFrom<T>,
+    T: TryFrom<V> + Clone + fmt::Debug + Send + Sync + 'static,
+{
+    fn reflect_iter<'a>(&'a self) -> ReflectRepeatedIter<'a> {
+        ReflectRepeatedIter {
+            imp: Box::new(ReflectRepeatedIterImplConverted::<'a, V, T> {
+                iter: self.vec.iter(),
+                _marker: marker::PhantomData,
+            }),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    fn get(&self, index: usize) -> ReflectValueRef {
+        ReflectValueRef::Message(MessageRef::converted::<V, T>(&self.vec[index]))
+    }
+
+    fn get_mut(&mut self, _index: usize) -> Option<ReflectValueMut> {
+        None
+    }
+
+    fn set(&mut self, index: usize, value: ReflectValueBox) -> Result<(), ConversionError> {
+        self.vec[index] = converted_from_value_box::<V, T>(value)?;
+        Ok(())
+    }
+
+    fn push(&mut self, value: ReflectValueBox) -> Result<(), ConversionError> {
+        self.vec.push(converted_from_value_box::<V, T>(value)?);
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.vec.clear()
+    }
+
+    fn element_type(&self) -> RuntimeTypeBox {
+        V::runtime_type_box()
+    }
+}
+
+/// `Vec<T>` where `T` is a Rust type of `string` or `bytes` field elements,
+/// viewed as a repeated field of `V` (`String` or `Vec<u8>`).
+#[repr(transparent)]
+pub(crate) struct ConvertedDerefVec<V, T> {
+    vec: Vec<T>,
+    _marker: marker::PhantomData<V>,
+}
+
+impl<V, T> ConvertedDerefVec<V, T> {
+    pub(crate) fn from_vec(vec: &Vec<T>) -> &ConvertedDerefVec<V, T> {
+        // Safe because `ConvertedDerefVec` is `repr(transparent)` wrapper of `Vec<T>`
+        unsafe { &*(vec as *const Vec<T> as *const ConvertedDerefVec<V, T>) }
+    }
+
+    pub(crate) fn from_vec_mut(vec: &mut Vec<T>) -> &mut ConvertedDerefVec<V, T> {
+        // Safe because `ConvertedDerefVec` is `repr(transparent)` wrapper of `Vec<T>`
+        unsafe { &mut *(vec as *mut Vec<T> as *mut ConvertedDerefVec<V, T>) }
+    }
+}
+
+impl<V, T: fmt::Debug> fmt::Debug for ConvertedDerefVec<V, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.vec, f)
+    }
+}
+
+impl<V, T> ReflectRepeated for ConvertedDerefVec<V, T>
+where
+    V: ProtobufValue,
+    V::RuntimeType: RuntimeTypeWithDeref,
+    T: AsRef<<V::RuntimeType as RuntimeTypeWithDeref>::DerefTarget>
+        + TryFrom<V>
+        + fmt::Debug
+        + Send
+        + Sync
+        + 'static,
+{
+    fn reflect_iter<'a>(&'a self) -> ReflectRepeatedIter<'a> {
+        ReflectRepeatedIter {
+            imp: Box::new(ReflectRepeatedIterImplConvertedDeref::<'a, V, T> {
+                iter: self.vec.iter(),
+                _marker: marker::PhantomData,
+            }),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    fn get(&self, index: usize) -> ReflectValueRef {
+        converted_deref_as_ref::<V, T>(&self.vec[index])
+    }
+
+    fn get_mut(&mut self, _index: usize) -> Option<ReflectValueMut> {
+        None
+    }
+
+    fn set(&mut self, index: usize, value: ReflectValueBox) -> Result<(), ConversionError> {
+        self.vec[index] = converted_from_value_box::<V, T>(value)?;
+        Ok(())
+    }
+
+    fn push(&mut self, value: ReflectValueBox) -> Result<(), ConversionError> {
+        self.vec.push(converted_from_value_box::<V, T>(value)?);
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.vec.clear()
+    }
+
+    fn element_type(&self) -> RuntimeTypeBox {
+        V::runtime_type_box()
+    }
+}
+
+/// View value of `string` or `bytes` field stored as a different Rust type.
+pub(crate) fn converted_deref_as_ref<V, T>(value: &T) -> ReflectValueRef
+where
+    V: ProtobufValue,
+    V::RuntimeType: RuntimeTypeWithDeref,
+    T: AsRef<<V::RuntimeType as RuntimeTypeWithDeref>::DerefTarget>,
+{
+    <V::RuntimeType as RuntimeTypeWithDeref>::defef_as_ref(value.as_ref())
+}
+
+/// Convert reflective value to the Rust type `T` of a field of type `V`.
+///
+/// # Panics
+///
+/// If value is not of type `V`.
+pub(crate) fn converted_from_value_box<V, T>(value: ReflectValueBox) -> Result<T, ConversionError>
+where
+    V: ProtobufValue,
+    T: TryFrom<V>,
+{
+    let value: V = value.downcast().expect("wrong type");
+    T::try_from(value).map_err(|_| ConversionError::new::<T>())
+}
+
 trait ReflectRepeatedIterTrait<'a> {
     fn next(&mut self) -> Option<ReflectValueRef<'a>>;
 }
@@ -103,6 +296,39 @@ impl<'a, V: ProtobufValue + 'static> ReflectRepeatedIterTrait<'a>
     }
 }
 
+struct ReflectRepeatedIterImplConverted<'a, V, T: 'static> {
+    iter: slice::Iter<'a, T>,
+    _marker: marker::PhantomData<V>,
+}
+
+impl<'a, V, T> ReflectRepeatedIterTrait<'a> for ReflectRepeatedIterImplConverted<'a, V, T>
+where
+    V: Message + TryFrom<T>,
+    T: Clone + 'static,
+{
+    fn next(&mut self) -> Option<ReflectValueRef<'a>> {
+        self.iter
+            .next()
+            .map(|v| ReflectValueRef::Message(MessageRef::converted::<V, T>(v)))
+    }
+}
+
+struct ReflectRepeatedIterImplConvertedDeref<'a, V, T: 'static> {
+    iter: slice::Iter<'a, T>,
+    _marker: marker::PhantomData<V>,
+}
+
+impl<'a, V, T> ReflectRepeatedIterTrait<'a> for ReflectRepeatedIterImplConvertedDeref<'a, V, T>
+where
+    V: ProtobufValue,
+    V::RuntimeType: RuntimeTypeWithDeref,
+    T: AsRef<<V::RuntimeType as RuntimeTypeWithDeref>::DerefTarget> + 'static,
+{
+    fn next(&mut self) -> Option<ReflectValueRef<'a>> {
+        self.iter.next().map(converted_deref_as_ref::<V, T>)
+    }
+}
+
 pub struct ReflectRepeatedIter<'a> {
     imp: Box<dyn ReflectRepeatedIterTrait<'a> + 'a>,
 }
@@ -295,22 +521,62 @@ impl<'a> ReflectRepeatedMut<'a> {
         self.repeated.element_type()
     }
 
+    /// Get a mutable reference to an item by index.
+    ///
+    /// Return `None` if elements are stored as a different Rust type
+    /// and cannot be mutated in place.
+    ///
+    /// # Panics
+    ///
+    /// If index is out of range.
+    pub(crate) fn into_value_mut(self, index: usize) -> Option<ReflectValueMut<'a>> {
+        self.repeated.get_mut(index)
+    }
+
     /// Set a value at given index.
     ///
     /// # Panics
     ///
-    /// If index if out of range or value type does not match container element type
+    /// If index if out of range or value type does not match container element type,
+    /// or value cannot be converted to the Rust type of elements.
     pub fn set(&mut self, index: usize, value: ReflectValueBox) {
-        self.repeated.set(index, value);
+        if let Err(e) = self.try_set(index, value) {
+            panic!("{}", e);
+        }
     }
 
-    /// Push an item to repeated field.
+    /// Set a value at given index.
+    ///
+    /// Return an error if value cannot be converted to the Rust type of elements.
     ///
     /// # Panics
     ///
     /// If index if out of range or value type does not match container element type
+    pub fn try_set(&mut self, index: usize, value: ReflectValueBox) -> Result<(), ConversionError> {
+        self.repeated.set(index, value)
+    }
+
+    /// Push an item to repeated field.
+    ///
+    /// # Panics
+    ///
+    /// If value type does not match container element type,
+    /// or value cannot be converted to the Rust type of elements.
     pub fn push(&mut self, value: ReflectValueBox) {
-        self.repeated.push(value);
+        if let Err(e) = self.try_push(value) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Push an item to repeated field.
+    ///
+    /// Return an error if value cannot be converted to the Rust type of elements.
+    ///
+    /// # Panics
+    ///
+    /// If value type does not match container element type
+    pub fn try_push(&mut self, value: ReflectValueBox) -> Result<(), ConversionError> {
+        self.repeated.push(value)
     }
 
     /// Self-explanatory
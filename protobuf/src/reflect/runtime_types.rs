@@ -19,6 +19,7 @@ use crate::enums::ProtobufEnum;
 use crate::enums::ProtobufEnumOrUnknown;
 use crate::message::Message;
 use std::collections::HashMap;
+use std::hash::Hash;
 
 /// `RuntimeType` is not implemented by all protobuf types directly
 /// because it's not possible to implement `RuntimeType` for all `Message`
@@ -97,6 +98,31 @@ pub trait RuntimeTypeHashable: RuntimeType {
     /// Query hash map with a given key.
     fn hash_map_get<'a, V>(map: &'a HashMap<Self::Value, V>, key: ReflectValueRef)
         -> Option<&'a V>;
+
+    /// Query hash map with a given key for a mutable value.
+    ///
+    /// Default implementation converts the key to the value type.
+    fn hash_map_get_mut<'a, V>(
+        map: &'a mut HashMap<Self::Value, V>,
+        key: ReflectValueRef,
+    ) -> Option<&'a mut V>
+    where
+        Self::Value: Eq + Hash,
+    {
+        let key = Self::from_value_box(key.to_box()).ok()?;
+        map.get_mut(&key)
+    }
+
+    /// Remove an entry with a given key from hash map.
+    ///
+    /// Default implementation converts the key to the value type.
+    fn hash_map_remove<V>(map: &mut HashMap<Self::Value, V>, key: ReflectValueRef) -> Option<V>
+    where
+        Self::Value: Eq + Hash,
+    {
+        let key = Self::from_value_box(key.to_box()).ok()?;
+        map.remove(&key)
+    }
 }
 
 /// Implementation for `f32`
@@ -276,6 +302,23 @@ impl RuntimeTypeHashable for RuntimeTypeI32 {
             _ => None,
         }
     }
+
+    fn hash_map_get_mut<'a, V>(
+        map: &'a mut HashMap<i32, V>,
+        key: ReflectValueRef,
+    ) -> Option<&'a mut V> {
+        match key {
+            ReflectValueRef::I32(i) => map.get_mut(&i),
+            _ => None,
+        }
+    }
+
+    fn hash_map_remove<V>(map: &mut HashMap<i32, V>, key: ReflectValueRef) -> Option<V> {
+        match key {
+            ReflectValueRef::I32(i) => map.remove(&i),
+            _ => None,
+        }
+    }
 }
 
 impl RuntimeType for RuntimeTypeI64 {
@@ -326,6 +369,23 @@ impl RuntimeTypeHashable for RuntimeTypeI64 {
             _ => None,
         }
     }
+
+    fn hash_map_get_mut<'a, V>(
+        map: &'a mut HashMap<i64, V>,
+        key: ReflectValueRef,
+    ) -> Option<&'a mut V> {
+        match key {
+            ReflectValueRef::I64(i) => map.get_mut(&i),
+            _ => None,
+        }
+    }
+
+    fn hash_map_remove<V>(map: &mut HashMap<i64, V>, key: ReflectValueRef) -> Option<V> {
+        match key {
+            ReflectValueRef::I64(i) => map.remove(&i),
+            _ => None,
+        }
+    }
 }
 
 impl RuntimeType for RuntimeTypeU32 {
@@ -376,6 +436,23 @@ impl RuntimeTypeHashable for RuntimeTypeU32 {
             _ => None,
         }
     }
+
+    fn hash_map_get_mut<'a, V>(
+        map: &'a mut HashMap<u32, V>,
+        key: ReflectValueRef,
+    ) -> Option<&'a mut V> {
+        match key {
+            ReflectValueRef::U32(i) => map.get_mut(&i),
+            _ => None,
+        }
+    }
+
+    fn hash_map_remove<V>(map: &mut HashMap<u32, V>, key: ReflectValueRef) -> Option<V> {
+        match key {
+            ReflectValueRef::U32(i) => map.remove(&i),
+            _ => None,
+        }
+    }
 }
 
 impl RuntimeType for RuntimeTypeU64 {
@@ -426,6 +503,23 @@ impl RuntimeTypeHashable for RuntimeTypeU64 {
             _ => None,
         }
     }
+
+    fn hash_map_get_mut<'a, V>(
+        map: &'a mut HashMap<u64, V>,
+        key: ReflectValueRef,
+    ) -> Option<&'a mut V> {
+        match key {
+            ReflectValueRef::U64(i) => map.get_mut(&i),
+            _ => None,
+        }
+    }
+
+    fn hash_map_remove<V>(map: &mut HashMap<u64, V>, key: ReflectValueRef) -> Option<V> {
+        match key {
+            ReflectValueRef::U64(i) => map.remove(&i),
+            _ => None,
+        }
+    }
 }
 
 impl RuntimeType for RuntimeTypeBool {
@@ -476,6 +570,23 @@ impl RuntimeTypeHashable for RuntimeTypeBool {
             _ => None,
         }
     }
+
+    fn hash_map_get_mut<'a, V>(
+        map: &'a mut HashMap<bool, V>,
+        key: ReflectValueRef,
+    ) -> Option<&'a mut V> {
+        match key {
+            ReflectValueRef::Bool(i) => map.get_mut(&i),
+            _ => None,
+        }
+    }
+
+    fn hash_map_remove<V>(map: &mut HashMap<bool, V>, key: ReflectValueRef) -> Option<V> {
+        match key {
+            ReflectValueRef::Bool(i) => map.remove(&i),
+            _ => None,
+        }
+    }
 }
 
 impl RuntimeType for RuntimeTypeString {
@@ -529,6 +640,23 @@ impl RuntimeTypeHashable for RuntimeTypeString {
             _ => None,
         }
     }
+
+    fn hash_map_get_mut<'a, V>(
+        map: &'a mut HashMap<String, V>,
+        key: ReflectValueRef,
+    ) -> Option<&'a mut V> {
+        match key {
+            ReflectValueRef::String(s) => map.get_mut(*&s),
+            _ => None,
+        }
+    }
+
+    fn hash_map_remove<V>(map: &mut HashMap<String, V>, key: ReflectValueRef) -> Option<V> {
+        match key {
+            ReflectValueRef::String(s) => map.remove(*&s),
+            _ => None,
+        }
+    }
 }
 
 impl RuntimeType for RuntimeTypeVecU8 {
@@ -678,6 +806,23 @@ impl RuntimeTypeHashable for RuntimeTypeCarllercheChars {
             _ => None,
         }
     }
+
+    fn hash_map_get_mut<'a, V>(
+        map: &'a mut HashMap<Chars, V>,
+        key: ReflectValueRef,
+    ) -> Option<&'a mut V> {
+        match key {
+            ReflectValueRef::String(s) => map.get_mut(&*s),
+            _ => None,
+        }
+    }
+
+    fn hash_map_remove<V>(map: &mut HashMap<Chars, V>, key: ReflectValueRef) -> Option<V> {
+        match key {
+            ReflectValueRef::String(s) => map.remove(&*s),
+            _ => None,
+        }
+    }
 }
 
 impl<E> RuntimeType for RuntimeTypeEnum<E>
@@ -2,6 +2,7 @@
 
 #![doc(hidden)]
 
+use std::convert::TryFrom;
 use std::fmt;
 use std::marker;
 
@@ -12,7 +13,9 @@ use crate::chars::Chars;
 
 use crate::enums::ProtobufEnum;
 use crate::enums::ProtobufEnumOrUnknown;
+use crate::error::ProtobufError;
 use crate::error::ProtobufResult;
+use crate::error::WireError;
 use crate::message::Message;
 use crate::reflect::type_dynamic::ProtobufTypeDynamicImpl;
 use crate::reflect::ProtobufValue;
@@ -159,6 +162,9 @@ pub struct ProtobufTypeEnumOrUnknown<E: ProtobufEnum>(marker::PhantomData<E>);
 /// `message`
 #[derive(Copy, Clone)]
 pub struct ProtobufTypeMessage<M: Message>(marker::PhantomData<M>);
+/// `message` stored as a different Rust type, e. g. `Int32Value` as `i32`
+#[derive(Copy, Clone)]
+pub struct ProtobufTypeConvertedMessage<M: Message, T>(marker::PhantomData<(M, T)>);
 
 impl ProtobufType for ProtobufTypeFloat {
     type ProtobufValue = f32;
@@ -737,3 +743,42 @@ impl<M: Message + Clone + ProtobufValue + Default> ProtobufType for ProtobufType
         Ok(())
     }
 }
+
+impl<M, T> ProtobufType for ProtobufTypeConvertedMessage<M, T>
+where
+    M: Message + Clone + TryFrom<T>,
+    T: ProtobufValue + TryFrom<M>,
+{
+    type ProtobufValue = T;
+
+    const WIRE_TYPE: WireType = WireType::WireTypeLengthDelimited;
+
+    fn read(is: &mut CodedInputStream) -> ProtobufResult<T> {
+        T::try_from(is.read_message::<M>()?)
+            .map_err(|_| ProtobufError::WireError(WireError::ValueOutOfRange))
+    }
+
+    fn get_from_unknown(unknown_values: &UnknownValues) -> Option<T> {
+        unknown_values
+            .length_delimited
+            .iter()
+            .rev()
+            .next()
+            .and_then(|bytes| M::parse_from_bytes(bytes).ok())
+            .and_then(|m| T::try_from(m).ok())
+    }
+
+    fn compute_size(value: &T) -> u32 {
+        M::try_from(value.clone())
+            .unwrap_or_else(|_| M::new())
+            .compute_size()
+    }
+
+    fn write_with_cached_size(
+        field_number: u32,
+        value: &T,
+        os: &mut CodedOutputStream,
+    ) -> ProtobufResult<()> {
+        rt::write_converted_message::<M, T>(field_number, value, os)
+    }
+}
@@ -206,7 +206,7 @@ impl<'a> ReflectEq for ReflectValueRef<'a> {
             (Bool(a), Bool(b)) => a == b,
             (String(a), String(b)) => a == b,
             (Bytes(a), Bytes(b)) => a == b,
-            (Enum(ad, a), Enum(bd, b)) => ad == bd && a == b,
+            (Enum(ad, a), Enum(bd, b)) => ad.same_schema(bd) && a == b,
             (Message(a), Message(b)) => a.reflect_eq(b, mode),
             _ => false,
         }
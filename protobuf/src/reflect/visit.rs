@@ -0,0 +1,271 @@
+//! Recursive traversal of message trees.
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::FieldPath;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::UnknownValueRef;
+
+/// Callbacks for [`walk_message`].
+///
+/// Set fields are visited in the order of field declaration,
+/// followed by unknown fields. Message values are not passed to value
+/// callbacks, the walker enters them instead.
+///
+/// Each callback receives the path to the value (or message) relative
+/// to the message passed to [`walk_message`]. Path is empty for that message,
+/// so [`FieldPath::field`] returns `None` in message and unknown field callbacks
+/// for the root message.
+///
+/// All callbacks do nothing by default.
+#[allow(unused_variables)]
+pub trait MessageVisitor {
+    /// Called before visiting message fields.
+    fn enter_message(&mut self, path: &FieldPath, message: &dyn MessageDyn) {}
+    /// Called after all message fields are visited.
+    fn leave_message(&mut self, path: &FieldPath, message: &dyn MessageDyn) {}
+    /// Singular field value which is not a message.
+    fn visit_singular(&mut self, path: &FieldPath, value: ReflectValueRef) {}
+    /// Repeated field element which is not a message.
+    fn visit_repeated_element(&mut self, path: &FieldPath, index: usize, value: ReflectValueRef) {}
+    /// Map entry which value is not a message.
+    fn visit_map_entry(&mut self, path: &FieldPath, key: ReflectValueRef, value: ReflectValueRef) {}
+    /// Unknown field of a message addressed by `path`.
+    fn visit_unknown_field(&mut self, path: &FieldPath, number: u32, value: UnknownValueRef) {}
+}
+
+/// Callbacks for [`walk_message_mut`].
+///
+/// Traversal order is the same as in [`MessageVisitor`].
+/// Value callbacks may return a new value to replace the visited one.
+/// Messages can be modified in [`enter_message`](MessageVisitorMut::enter_message),
+/// and the walker visits fields set after that callback returns.
+///
+/// All callbacks do nothing by default.
+#[allow(unused_variables)]
+pub trait MessageVisitorMut {
+    /// Called before visiting message fields.
+    fn enter_message(&mut self, path: &FieldPath, message: &mut dyn MessageDyn) {}
+    /// Called after all message fields are visited.
+    fn leave_message(&mut self, path: &FieldPath, message: &mut dyn MessageDyn) {}
+    /// Singular field value which is not a message.
+    fn visit_singular(
+        &mut self,
+        path: &FieldPath,
+        value: ReflectValueRef,
+    ) -> Option<ReflectValueBox> {
+        None
+    }
+    /// Repeated field element which is not a message.
+    fn visit_repeated_element(
+        &mut self,
+        path: &FieldPath,
+        index: usize,
+        value: ReflectValueRef,
+    ) -> Option<ReflectValueBox> {
+        None
+    }
+    /// Map entry which value is not a message. Returned value replaces the entry value.
+    fn visit_map_entry(
+        &mut self,
+        path: &FieldPath,
+        key: ReflectValueRef,
+        value: ReflectValueRef,
+    ) -> Option<ReflectValueBox> {
+        None
+    }
+    /// Unknown field of a message addressed by `path`.
+    fn visit_unknown_field(&mut self, path: &FieldPath, number: u32, value: UnknownValueRef) {}
+}
+
+fn is_message(t: &RuntimeTypeBox) -> bool {
+    match t {
+        RuntimeTypeBox::Message(..) => true,
+        _ => false,
+    }
+}
+
+/// Recursively visit all set fields of the message.
+pub fn walk_message(message: &dyn MessageDyn, visitor: &mut dyn MessageVisitor) {
+    let mut path = FieldPath::empty(&message.descriptor_dyn());
+    walk_message_impl(message, &mut path, visitor);
+}
+
+fn walk_message_impl(m: &dyn MessageDyn, path: &mut FieldPath, visitor: &mut dyn MessageVisitor) {
+    visitor.enter_message(path, m);
+    for field in m.descriptor_dyn().fields() {
+        match field.runtime_field_type() {
+            RuntimeFieldType::Singular(t) => {
+                if let Some(v) = field.get_singular(m) {
+                    path.push_field(&field);
+                    match is_message(&t) {
+                        true => walk_message_value(v, path, visitor),
+                        false => visitor.visit_singular(path, v),
+                    }
+                    path.pop();
+                }
+            }
+            RuntimeFieldType::Repeated(t) => {
+                for (i, v) in field.get_repeated(m).into_iter().enumerate() {
+                    path.push_index(&field, i);
+                    match is_message(&t) {
+                        true => walk_message_value(v, path, visitor),
+                        false => visitor.visit_repeated_element(path, i, v),
+                    }
+                    path.pop();
+                }
+            }
+            RuntimeFieldType::Map(_, t) => {
+                for (k, v) in &field.get_map(m) {
+                    path.push_key(&field, k.to_box());
+                    match is_message(&t) {
+                        true => walk_message_value(v, path, visitor),
+                        false => visitor.visit_map_entry(path, k, v),
+                    }
+                    path.pop();
+                }
+            }
+        }
+    }
+    for (number, values) in m.get_unknown_fields_dyn() {
+        for value in values {
+            visitor.visit_unknown_field(path, number, value);
+        }
+    }
+    visitor.leave_message(path, m);
+}
+
+fn walk_message_value(
+    value: ReflectValueRef,
+    path: &mut FieldPath,
+    visitor: &mut dyn MessageVisitor,
+) {
+    let message = value.to_message().unwrap();
+    walk_message_impl(&*message, path, visitor);
+}
+
+/// Recursively visit all set fields of the message, possibly modifying them.
+///
+/// Message fields stored as a different Rust type (e. g. with `rust_type` codegen option)
+/// are converted to messages, visited and converted back.
+///
+/// # Panics
+///
+/// If a replacement value returned by the visitor or a modified message
+/// cannot be converted to the Rust type of the field.
+pub fn walk_message_mut(message: &mut dyn MessageDyn, visitor: &mut dyn MessageVisitorMut) {
+    let mut path = FieldPath::empty(&message.descriptor_dyn());
+    walk_message_mut_impl(message, &mut path, visitor);
+}
+
+fn walk_message_mut_impl(
+    m: &mut dyn MessageDyn,
+    path: &mut FieldPath,
+    visitor: &mut dyn MessageVisitorMut,
+) {
+    visitor.enter_message(path, m);
+    for field in m.descriptor_dyn().fields() {
+        walk_field_mut(m, &field, path, visitor);
+    }
+    for (number, values) in m.get_unknown_fields_dyn() {
+        for value in values {
+            visitor.visit_unknown_field(path, number, value);
+        }
+    }
+    visitor.leave_message(path, m);
+}
+
+fn walk_field_mut(
+    m: &mut dyn MessageDyn,
+    field: &FieldDescriptor,
+    path: &mut FieldPath,
+    visitor: &mut dyn MessageVisitorMut,
+) {
+    match field.runtime_field_type() {
+        RuntimeFieldType::Singular(t) => {
+            if !field.has_field(m) {
+                return;
+            }
+            path.push_field(field);
+            if is_message(&t) {
+                if let Err(e) =
+                    field.mut_message_with(m, |m| walk_message_mut_impl(m, path, visitor))
+                {
+                    panic!("{}: {}", field, e);
+                }
+            } else if let Some(v) = field.get_singular(m) {
+                if let Some(v) = visitor.visit_singular(path, v) {
+                    field.set_singular_field(m, v);
+                }
+            }
+            path.pop();
+        }
+        RuntimeFieldType::Repeated(t) => {
+            let len = field.get_repeated(m).len();
+            for i in 0..len {
+                path.push_index(field, i);
+                if is_message(&t) {
+                    match field.mut_repeated(m).into_value_mut(i) {
+                        Some(ReflectValueMut::Message(m)) => {
+                            walk_message_mut_impl(m, path, visitor)
+                        }
+                        None => {
+                            let mut message = field.get_repeated(m).get(i).to_box();
+                            walk_message_box_mut(&mut message, path, visitor);
+                            field.mut_repeated(m).set(i, message);
+                        }
+                    }
+                } else {
+                    let v = field.get_repeated(m).get(i);
+                    if let Some(v) = visitor.visit_repeated_element(path, i, v) {
+                        field.mut_repeated(m).set(i, v);
+                    }
+                }
+                path.pop();
+            }
+        }
+        RuntimeFieldType::Map(_, t) => {
+            let keys: Vec<ReflectValueBox> = field
+                .get_map(m)
+                .into_iter()
+                .map(|(k, _)| k.to_box())
+                .collect();
+            for k in keys {
+                path.push_key(field, k.clone());
+                if is_message(&t) {
+                    match field.mut_map(m).into_value_mut(k.as_value_ref()) {
+                        Some(ReflectValueMut::Message(m)) => {
+                            walk_message_mut_impl(m, path, visitor)
+                        }
+                        None => {
+                            let mut message =
+                                field.get_map(m).get(k.as_value_ref()).unwrap().to_box();
+                            walk_message_box_mut(&mut message, path, visitor);
+                            field.mut_map(m).insert(k, message);
+                        }
+                    }
+                } else {
+                    let v = field.get_map(m).get(k.as_value_ref()).unwrap();
+                    if let Some(v) = visitor.visit_map_entry(path, k.as_value_ref(), v) {
+                        field.mut_map(m).insert(k, v);
+                    }
+                }
+                path.pop();
+            }
+        }
+    }
+}
+
+fn walk_message_box_mut(
+    value: &mut ReflectValueBox,
+    path: &mut FieldPath,
+    visitor: &mut dyn MessageVisitorMut,
+) {
+    match value.as_value_mut() {
+        ReflectValueMut::Message(m) => walk_message_mut_impl(m, path, visitor),
+    }
+}
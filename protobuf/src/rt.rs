@@ -2,9 +2,12 @@
 //!
 //! Should rarely be used by programs written by hands.
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::default::Default;
 use std::hash::Hash;
+use std::hash::Hasher;
 
 #[cfg(feature = "bytes")]
 use crate::bytes::Bytes;
@@ -24,6 +27,7 @@ use crate::wire_format::WireType;
 use crate::wire_format::WireTypeFixed32;
 use crate::wire_format::WireTypeFixed64;
 use crate::wire_format::WireTypeLengthDelimited;
+use crate::wire_format::WireTypeStartGroup;
 use crate::wire_format::WireTypeVarint;
 use crate::zigzag::*;
 use crate::MessageField;
@@ -294,6 +298,10 @@ pub fn unknown_fields_size(unknown_fields: &UnknownFields) -> u32 {
         for bytes in &values.length_delimited {
             r += bytes_size_no_tag(&bytes);
         }
+
+        for group in &values.groups {
+            r += tag_size(number) * 2 + unknown_fields_size(group);
+        }
     }
     r
 }
@@ -853,34 +861,232 @@ where
     }
 }
 
-fn skip_group(is: &mut CodedInputStream) -> ProtobufResult<()> {
-    loop {
-        let (_, wire_type) = is.read_tag_unpack()?;
-        if wire_type == wire_format::WireTypeEndGroup {
-            return Ok(());
+/// Read repeated `group` field.
+pub fn read_repeated_group_into_vec<M: Message + Default>(
+    field_number: u32,
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Vec<M>,
+) -> ProtobufResult<()> {
+    match wire_type {
+        WireTypeStartGroup => {
+            target.push(is.read_group(field_number)?);
+            Ok(())
         }
-        is.skip_field(wire_type)?;
+        _ => Err(unexpected_wire_type(wire_type)),
     }
 }
 
-/// Handle unknown field in generated code.
-/// Either store a value in unknown, or skip a group.
-pub fn read_unknown_or_skip_group(
+/// Read singular `group` field.
+pub fn read_singular_group_into_field<M>(
     field_number: u32,
     wire_type: WireType,
     is: &mut CodedInputStream,
-    unknown_fields: &mut UnknownFields,
-) -> ProtobufResult<()> {
+    target: &mut MessageField<M>,
+) -> ProtobufResult<()>
+where
+    M: Message + Default,
+{
     match wire_type {
-        wire_format::WireTypeStartGroup => skip_group(is),
-        _ => {
-            let unknown = is.read_unknown(wire_type)?;
-            unknown_fields.add_value(field_number, unknown);
+        WireTypeStartGroup => {
+            let mut m = M::new();
+            let res = is.merge_group(field_number, &mut m);
+            *target = MessageField::some(m);
+            res
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+/// Read singular `message` field stored as a different Rust type.
+///
+/// Fields of the message are merged with the current field value, if any.
+/// Fails if the current value cannot be converted to the message
+/// (the value is left unchanged then) or if the message cannot be converted to the field type.
+pub fn read_singular_converted_message_into<M, T>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Option<T>,
+) -> ProtobufResult<()>
+where
+    M: Message + TryFrom<T>,
+    T: TryFrom<M> + Clone,
+{
+    match wire_type {
+        WireTypeLengthDelimited => {
+            let mut m = match target {
+                Some(v) => M::try_from(v.clone())
+                    .map_err(|_| ProtobufError::WireError(WireError::ValueOutOfRange))?,
+                None => M::new(),
+            };
+            is.incr_recursion()?;
+            let res = is.merge_message(&mut m);
+            is.decr_recursion();
+            res?;
+            let v =
+                T::try_from(m).map_err(|_| ProtobufError::WireError(WireError::ValueOutOfRange))?;
+            *target = Some(v);
+            Ok(())
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+/// Read repeated `message` field with elements stored as a different Rust type.
+///
+/// Fails if the message cannot be converted to the element type.
+pub fn read_repeated_converted_message_into<M, T>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Vec<T>,
+) -> ProtobufResult<()>
+where
+    M: Message,
+    T: TryFrom<M>,
+{
+    match wire_type {
+        WireTypeLengthDelimited => {
+            is.incr_recursion()?;
+            let res = is.read_message::<M>();
+            is.decr_recursion();
+            let v = T::try_from(res?)
+                .map_err(|_| ProtobufError::WireError(WireError::ValueOutOfRange))?;
+            target.push(v);
             Ok(())
         }
+        _ => Err(unexpected_wire_type(wire_type)),
     }
 }
 
+/// Size of `message` field value stored as a different Rust type,
+/// including tag and length.
+///
+/// Such value has no cached size, so it is converted to message here,
+/// again in [`converted_message_is_initialized`] and again
+/// in [`write_converted_message`]: serializing it costs three conversions,
+/// and each conversion also converts fields of the message stored as different types.
+///
+/// Value which cannot be converted to message is counted as an empty message.
+/// Such value makes the containing message not initialized
+/// (see [`converted_message_is_initialized`]), so serialization fails
+/// before the size is used, like [`write_converted_message`] does.
+pub fn converted_message_size<M, T>(field_number: u32, value: &T) -> u32
+where
+    M: Message + TryFrom<T>,
+    T: Clone,
+{
+    let len = M::try_from(value.clone())
+        .unwrap_or_else(|_| M::new())
+        .compute_size();
+    tag_size(field_number) + compute_raw_varint32_size(len) + len
+}
+
+/// Check `message` field value stored as a different Rust type
+/// can be converted to message and the message is initialized.
+pub fn converted_message_is_initialized<M, T>(value: &T) -> bool
+where
+    M: Message + TryFrom<T>,
+    T: Clone,
+{
+    match M::try_from(value.clone()) {
+        Ok(m) => m.is_initialized(),
+        Err(_) => false,
+    }
+}
+
+/// Write `message` field value stored as a different Rust type.
+///
+/// The value is converted once, and the message is written with its own size
+/// rather than the size computed by [`converted_message_size`].
+///
+/// Fails if the value cannot be converted to message.
+pub fn write_converted_message<M, T>(
+    field_number: u32,
+    value: &T,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()>
+where
+    M: Message + TryFrom<T>,
+    T: Clone,
+{
+    let m = M::try_from(value.clone())
+        .map_err(|_| ProtobufError::WireError(WireError::ValueOutOfRange))?;
+    os.write_message(field_number, &m)
+}
+
+// Read `string` or `bytes` value and convert it to the field type.
+fn read_converted<P, T>(wire_type: WireType, is: &mut CodedInputStream) -> ProtobufResult<T>
+where
+    P: ProtobufType,
+    T: TryFrom<P::ProtobufValue>,
+{
+    if wire_type != P::WIRE_TYPE {
+        return Err(unexpected_wire_type(wire_type));
+    }
+    T::try_from(P::read(is)?).map_err(|_| ProtobufError::WireError(WireError::ValueOutOfRange))
+}
+
+/// Read singular `string` or `bytes` field stored as a different Rust type.
+///
+/// Fails if the value cannot be converted to the field type.
+pub fn read_singular_converted_into<P, T>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Option<T>,
+) -> ProtobufResult<()>
+where
+    P: ProtobufType,
+    T: TryFrom<P::ProtobufValue>,
+{
+    *target = Some(read_converted::<P, T>(wire_type, is)?);
+    Ok(())
+}
+
+/// Read proto3 singular `string` or `bytes` field stored as a different Rust type.
+///
+/// Fails if the value cannot be converted to the field type.
+pub fn read_singular_proto3_converted_into<P, T>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut T,
+) -> ProtobufResult<()>
+where
+    P: ProtobufType,
+    T: TryFrom<P::ProtobufValue>,
+{
+    *target = read_converted::<P, T>(wire_type, is)?;
+    Ok(())
+}
+
+/// Read repeated `string` or `bytes` field with elements stored as a different Rust type.
+///
+/// Fails if the value cannot be converted to the element type.
+pub fn read_repeated_converted_into<P, T>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Vec<T>,
+) -> ProtobufResult<()>
+where
+    P: ProtobufType,
+    T: TryFrom<P::ProtobufValue>,
+{
+    target.push(read_converted::<P, T>(wire_type, is)?);
+    Ok(())
+}
+
+/// Handle unknown field in generated code.
+/// Store a value in unknown, groups are stored as nested unknown fields.
+pub fn read_unknown_field_into(
+    field_number: u32,
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    unknown_fields: &mut UnknownFields,
+) -> ProtobufResult<()> {
+    let unknown = is.read_unknown_field(field_number, wire_type)?;
+    unknown_fields.add_value(field_number, unknown);
+    Ok(())
+}
+
 /// Create an error for unexpected wire type.
 ///
 /// Function is used in generated code, so error types can be changed,
@@ -955,6 +1161,18 @@ where
     message.write_to_with_cached_sizes(os)
 }
 
+/// Write group with field number to the stream.
+pub fn write_group_field_with_cached_size<M>(
+    field_number: u32,
+    message: &M,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()>
+where
+    M: Message,
+{
+    os.write_group(field_number, message)
+}
+
 /// Read `map` field.
 pub fn read_map_into<K, V>(
     wire_type: WireType,
@@ -999,3 +1217,29 @@ where
 
     Ok(())
 }
+
+// Map entries sorted by key, so map hash and order do not depend on `HashMap` iteration order.
+fn sorted_map_entries<K: Ord, V, S>(map: &HashMap<K, V, S>) -> Vec<(&K, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// Hash map field, used in generated `Hash` implementations.
+pub fn hash_map<K, V, S, H>(map: &HashMap<K, V, S>, state: &mut H)
+where
+    K: Hash + Ord,
+    V: Hash,
+    H: Hasher,
+{
+    Hash::hash(&sorted_map_entries(map), state);
+}
+
+/// Compare map fields, used in generated `Ord` implementations.
+pub fn cmp_map<K, V, S>(a: &HashMap<K, V, S>, b: &HashMap<K, V, S>) -> Ordering
+where
+    K: Ord,
+    V: Ord,
+{
+    sorted_map_entries(a).cmp(&sorted_map_entries(b))
+}
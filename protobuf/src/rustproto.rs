@@ -36,12 +36,28 @@ pub mod exts {
 
     pub const carllerche_bytes_for_string_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
 
+    pub const chrono_for_timestamp_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17020, phantom: ::std::marker::PhantomData };
+
+    pub const time_for_timestamp_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17021, phantom: ::std::marker::PhantomData };
+
+    pub const wrappers_as_option_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17022, phantom: ::std::marker::PhantomData };
+
     pub const serde_derive_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17030, phantom: ::std::marker::PhantomData };
 
     pub const serde_derive_cfg_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17031, phantom: ::std::marker::PhantomData };
 
+    pub const serde_json_mapping_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17032, phantom: ::std::marker::PhantomData };
+
     pub const lite_runtime_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17035, phantom: ::std::marker::PhantomData };
 
+    pub const extra_derives_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17040, phantom: ::std::marker::PhantomData };
+
+    pub const extra_type_attrs_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17041, phantom: ::std::marker::PhantomData };
+
+    pub const extra_field_attrs_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17042, phantom: ::std::marker::PhantomData };
+
+    pub const extra_enum_attrs_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17043, phantom: ::std::marker::PhantomData };
+
     pub const expose_oneof: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17001, phantom: ::std::marker::PhantomData };
 
     pub const expose_fields: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17003, phantom: ::std::marker::PhantomData };
@@ -54,10 +70,24 @@ pub mod exts {
 
     pub const carllerche_bytes_for_string: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
 
+    pub const chrono_for_timestamp: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17020, phantom: ::std::marker::PhantomData };
+
+    pub const time_for_timestamp: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17021, phantom: ::std::marker::PhantomData };
+
+    pub const wrappers_as_option: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17022, phantom: ::std::marker::PhantomData };
+
     pub const serde_derive: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17030, phantom: ::std::marker::PhantomData };
 
     pub const serde_derive_cfg: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17031, phantom: ::std::marker::PhantomData };
 
+    pub const serde_json_mapping: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17032, phantom: ::std::marker::PhantomData };
+
+    pub const extra_derives: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17040, phantom: ::std::marker::PhantomData };
+
+    pub const extra_type_attrs: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17041, phantom: ::std::marker::PhantomData };
+
+    pub const extra_field_attrs: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17042, phantom: ::std::marker::PhantomData };
+
     pub const expose_fields_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17003, phantom: ::std::marker::PhantomData };
 
     pub const generate_accessors_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17004, phantom: ::std::marker::PhantomData };
@@ -67,6 +97,20 @@ pub mod exts {
     pub const carllerche_bytes_for_bytes_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17011, phantom: ::std::marker::PhantomData };
 
     pub const carllerche_bytes_for_string_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
+
+    pub const chrono_for_timestamp_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17020, phantom: ::std::marker::PhantomData };
+
+    pub const time_for_timestamp_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17021, phantom: ::std::marker::PhantomData };
+
+    pub const wrappers_as_option_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17022, phantom: ::std::marker::PhantomData };
+
+    pub const rust_type_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17050, phantom: ::std::marker::PhantomData };
+
+    pub const extra_field_attrs_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17042, phantom: ::std::marker::PhantomData };
+
+    pub const extra_derives_enum: crate::ext::ExtFieldOptional<crate::descriptor::EnumOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17040, phantom: ::std::marker::PhantomData };
+
+    pub const extra_enum_attrs: crate::ext::ExtFieldOptional<crate::descriptor::EnumOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17043, phantom: ::std::marker::PhantomData };
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
@@ -81,13 +125,26 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     \xf3\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x1acarlle\
     rcheBytesForBytesAll:d\n\x1fcarllerche_bytes_for_string_all\x18\xf4\x84\
     \x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x1bcarllercheByte\
-    sForStringAll:H\n\x10serde_derive_all\x18\x86\x85\x01\x20\x01(\x08\x12\
-    \x1c.google.protobuf.FileOptionsR\x0eserdeDeriveAll:O\n\x14serde_derive_\
-    cfg_all\x18\x87\x85\x01\x20\x01(\t\x12\x1c.google.protobuf.FileOptionsR\
-    \x11serdeDeriveCfgAll:H\n\x10lite_runtime_all\x18\x8b\x85\x01\x20\x01(\
-    \x08\x12\x1c.google.protobuf.FileOptionsR\x0eliteRuntimeAll:D\n\x0cexpos\
-    e_oneof\x18\xe9\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOpti\
-    onsR\x0bexposeOneof:F\n\rexpose_fields\x18\xeb\x84\x01\x20\x01(\x08\x12\
+    sForStringAll:W\n\x18chrono_for_timestamp_all\x18\xfc\x84\x01\x20\x01(\
+    \x08\x12\x1c.google.protobuf.FileOptionsR\x15chronoForTimestampAll:S\n\
+    \x16time_for_timestamp_all\x18\xfd\x84\x01\x20\x01(\x08\x12\x1c.google.p\
+    rotobuf.FileOptionsR\x13timeForTimestampAll:S\n\x16wrappers_as_option_al\
+    l\x18\xfe\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x13w\
+    rappersAsOptionAll:H\n\x10serde_derive_all\x18\x86\x85\x01\x20\x01(\x08\
+    \x12\x1c.google.protobuf.FileOptionsR\x0eserdeDeriveAll:O\n\x14serde_der\
+    ive_cfg_all\x18\x87\x85\x01\x20\x01(\t\x12\x1c.google.protobuf.FileOptio\
+    nsR\x11serdeDeriveCfgAll:S\n\x16serde_json_mapping_all\x18\x88\x85\x01\
+    \x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x13serdeJsonMappingAl\
+    l:H\n\x10lite_runtime_all\x18\x8b\x85\x01\x20\x01(\x08\x12\x1c.google.pr\
+    otobuf.FileOptionsR\x0eliteRuntimeAll:J\n\x11extra_derives_all\x18\x90\
+    \x85\x01\x20\x01(\t\x12\x1c.google.protobuf.FileOptionsR\x0fextraDerives\
+    All:O\n\x14extra_type_attrs_all\x18\x91\x85\x01\x20\x01(\t\x12\x1c.googl\
+    e.protobuf.FileOptionsR\x11extraTypeAttrsAll:Q\n\x15extra_field_attrs_al\
+    l\x18\x92\x85\x01\x20\x01(\t\x12\x1c.google.protobuf.FileOptionsR\x12ext\
+    raFieldAttrsAll:O\n\x14extra_enum_attrs_all\x18\x93\x85\x01\x20\x01(\t\
+    \x12\x1c.google.protobuf.FileOptionsR\x11extraEnumAttrsAll:D\n\x0cexpose\
+    _oneof\x18\xe9\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptio\
+    nsR\x0bexposeOneof:F\n\rexpose_fields\x18\xeb\x84\x01\x20\x01(\x08\x12\
     \x1f.google.protobuf.MessageOptionsR\x0cexposeFields:P\n\x12generate_acc\
     essors\x18\xec\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptio\
     nsR\x11generateAccessors:J\n\x0fgenerate_getter\x18\xed\x84\x01\x20\x01(\
@@ -95,120 +152,239 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     rllerche_bytes_for_bytes\x18\xf3\x84\x01\x20\x01(\x08\x12\x1f.google.pro\
     tobuf.MessageOptionsR\x17carllercheBytesForBytes:`\n\x1bcarllerche_bytes\
     _for_string\x18\xf4\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.Message\
-    OptionsR\x18carllercheBytesForString:D\n\x0cserde_derive\x18\x86\x85\x01\
-    \x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x0bserdeDerive:K\n\
-    \x10serde_derive_cfg\x18\x87\x85\x01\x20\x01(\t\x12\x1f.google.protobuf.\
-    MessageOptionsR\x0eserdeDeriveCfg:O\n\x13expose_fields_field\x18\xeb\x84\
-    \x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x11exposeFieldsF\
-    ield:Y\n\x18generate_accessors_field\x18\xec\x84\x01\x20\x01(\x08\x12\
-    \x1d.google.protobuf.FieldOptionsR\x16generateAccessorsField:S\n\x15gene\
-    rate_getter_field\x18\xed\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.F\
-    ieldOptionsR\x13generateGetterField:g\n\x20carllerche_bytes_for_bytes_fi\
-    eld\x18\xf3\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\
-    \x1ccarllercheBytesForBytesField:i\n!carllerche_bytes_for_string_field\
+    OptionsR\x18carllercheBytesForString:S\n\x14chrono_for_timestamp\x18\xfc\
+    \x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x12chronoF\
+    orTimestamp:O\n\x12time_for_timestamp\x18\xfd\x84\x01\x20\x01(\x08\x12\
+    \x1f.google.protobuf.MessageOptionsR\x10timeForTimestamp:O\n\x12wrappers\
+    _as_option\x18\xfe\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageO\
+    ptionsR\x10wrappersAsOption:D\n\x0cserde_derive\x18\x86\x85\x01\x20\x01(\
+    \x08\x12\x1f.google.protobuf.MessageOptionsR\x0bserdeDerive:K\n\x10serde\
+    _derive_cfg\x18\x87\x85\x01\x20\x01(\t\x12\x1f.google.protobuf.MessageOp\
+    tionsR\x0eserdeDeriveCfg:O\n\x12serde_json_mapping\x18\x88\x85\x01\x20\
+    \x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x10serdeJsonMapping:F\
+    \n\rextra_derives\x18\x90\x85\x01\x20\x01(\t\x12\x1f.google.protobuf.Mes\
+    sageOptionsR\x0cextraDerives:K\n\x10extra_type_attrs\x18\x91\x85\x01\x20\
+    \x01(\t\x12\x1f.google.protobuf.MessageOptionsR\x0eextraTypeAttrs:M\n\
+    \x11extra_field_attrs\x18\x92\x85\x01\x20\x01(\t\x12\x1f.google.protobuf\
+    .MessageOptionsR\x0fextraFieldAttrs:O\n\x13expose_fields_field\x18\xeb\
+    \x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x11exposeFie\
+    ldsField:Y\n\x18generate_accessors_field\x18\xec\x84\x01\x20\x01(\x08\
+    \x12\x1d.google.protobuf.FieldOptionsR\x16generateAccessorsField:S\n\x15\
+    generate_getter_field\x18\xed\x84\x01\x20\x01(\x08\x12\x1d.google.protob\
+    uf.FieldOptionsR\x13generateGetterField:g\n\x20carllerche_bytes_for_byte\
+    s_field\x18\xf3\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOption\
+    sR\x1ccarllercheBytesForBytesField:i\n!carllerche_bytes_for_string_field\
     \x18\xf4\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x1dc\
-    arllercheBytesForStringFieldJ\x8e\x18\n\x06\x12\x04\0\0A\x01\n\x08\n\x01\
-    \x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\x12\x03\x02\0*\n\xe5\x01\n\x01\x02\
-    \x12\x03\n\0\x122^\x20see\x20https://github.com/gogo/protobuf/blob/maste\
-    r/gogoproto/gogo.proto\n\x20for\x20the\x20original\x20idea\n2{\x20Genera\
-    ted\x20files\x20can\x20be\x20customized\x20using\x20this\x20proto\n\x20o\
-    r\x20using\x20`Customize`\x20struct\x20when\x20codegen\x20is\x20invoked\
-    \x20programmatically.\n\n\t\n\x01\x07\x12\x04\x0c\0!\x01\n7\n\x02\x07\0\
-    \x12\x03\x0e\x04+\x1a,\x20When\x20true,\x20oneof\x20field\x20is\x20gener\
-    ated\x20public\n\n\n\n\x03\x07\0\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\0\
-    \x04\x12\x03\x0e\x04\x0c\n\n\n\x03\x07\0\x05\x12\x03\x0e\r\x11\n\n\n\x03\
-    \x07\0\x01\x12\x03\x0e\x12\"\n\n\n\x03\x07\0\x03\x12\x03\x0e%*\nI\n\x02\
-    \x07\x01\x12\x03\x10\x04,\x1a>\x20When\x20true\x20all\x20fields\x20are\
-    \x20public,\x20and\x20not\x20accessors\x20generated\n\n\n\n\x03\x07\x01\
-    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x01\x04\x12\x03\x10\x04\x0c\n\n\n\
-    \x03\x07\x01\x05\x12\x03\x10\r\x11\n\n\n\x03\x07\x01\x01\x12\x03\x10\x12\
-    #\n\n\n\x03\x07\x01\x03\x12\x03\x10&+\nP\n\x02\x07\x02\x12\x03\x12\x041\
-    \x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20acces\
-    sors\x20are\x20not\x20generated\n\n\n\n\x03\x07\x02\x02\x12\x03\x0c\x07\
-    \"\n\n\n\x03\x07\x02\x04\x12\x03\x12\x04\x0c\n\n\n\x03\x07\x02\x05\x12\
-    \x03\x12\r\x11\n\n\n\x03\x07\x02\x01\x12\x03\x12\x12(\n\n\n\x03\x07\x02\
-    \x03\x12\x03\x12+0\nL\n\x02\x07\x03\x12\x03\x14\x04.\x1aA\x20When\x20fal\
-    se,\x20`get_`\x20is\x20not\x20generated\x20even\x20if\x20`syntax\x20=\
-    \x20\"proto2\"`\n\n\n\n\x03\x07\x03\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\
-    \x03\x04\x12\x03\x14\x04\x0c\n\n\n\x03\x07\x03\x05\x12\x03\x14\r\x11\n\n\
-    \n\x03\x07\x03\x01\x12\x03\x14\x12%\n\n\n\x03\x07\x03\x03\x12\x03\x14(-\
-    \n2\n\x02\x07\x04\x12\x03\x16\x049\x1a'\x20Use\x20`bytes::Bytes`\x20for\
-    \x20`bytes`\x20fields\n\n\n\n\x03\x07\x04\x02\x12\x03\x0c\x07\"\n\n\n\
-    \x03\x07\x04\x04\x12\x03\x16\x04\x0c\n\n\n\x03\x07\x04\x05\x12\x03\x16\r\
-    \x11\n\n\n\x03\x07\x04\x01\x12\x03\x16\x120\n\n\n\x03\x07\x04\x03\x12\
-    \x03\x1638\n3\n\x02\x07\x05\x12\x03\x18\x04:\x1a(\x20Use\x20`bytes::Byte\
-    s`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\x05\x02\x12\x03\x0c\x07\
-    \"\n\n\n\x03\x07\x05\x04\x12\x03\x18\x04\x0c\n\n\n\x03\x07\x05\x05\x12\
-    \x03\x18\r\x11\n\n\n\x03\x07\x05\x01\x12\x03\x18\x121\n\n\n\x03\x07\x05\
-    \x03\x12\x03\x1849\nJ\n\x02\x07\x06\x12\x03\x1b\x04+\x1a?\x20Use\x20`ser\
-    de_derive`\x20to\x20implement\x20`Serialize`\x20and\x20`Deserialize`\n\n\
-    \n\n\x03\x07\x06\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x06\x04\x12\x03\x1b\
-    \x04\x0c\n\n\n\x03\x07\x06\x05\x12\x03\x1b\r\x11\n\n\n\x03\x07\x06\x01\
-    \x12\x03\x1b\x12\"\n\n\n\x03\x07\x06\x03\x12\x03\x1b%*\n3\n\x02\x07\x07\
-    \x12\x03\x1d\x041\x1a(\x20Guard\x20serde\x20annotations\x20with\x20cfg\
-    \x20attr.\n\n\n\n\x03\x07\x07\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x07\
-    \x04\x12\x03\x1d\x04\x0c\n\n\n\x03\x07\x07\x05\x12\x03\x1d\r\x13\n\n\n\
-    \x03\x07\x07\x01\x12\x03\x1d\x14(\n\n\n\x03\x07\x07\x03\x12\x03\x1d+0\nN\
-    \n\x02\x07\x08\x12\x03\x20\x04+\x1aC\x20When\x20true,\x20will\x20only\
-    \x20generate\x20codes\x20that\x20works\x20with\x20lite\x20runtime.\n\n\n\
-    \n\x03\x07\x08\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x08\x04\x12\x03\x20\
-    \x04\x0c\n\n\n\x03\x07\x08\x05\x12\x03\x20\r\x11\n\n\n\x03\x07\x08\x01\
-    \x12\x03\x20\x12\"\n\n\n\x03\x07\x08\x03\x12\x03\x20%*\n\t\n\x01\x07\x12\
-    \x04#\04\x01\n7\n\x02\x07\t\x12\x03%\x04'\x1a,\x20When\x20true,\x20oneof\
-    \x20field\x20is\x20generated\x20public\n\n\n\n\x03\x07\t\x02\x12\x03#\
-    \x07%\n\n\n\x03\x07\t\x04\x12\x03%\x04\x0c\n\n\n\x03\x07\t\x05\x12\x03%\
-    \r\x11\n\n\n\x03\x07\t\x01\x12\x03%\x12\x1e\n\n\n\x03\x07\t\x03\x12\x03%\
-    !&\nI\n\x02\x07\n\x12\x03'\x04(\x1a>\x20When\x20true\x20all\x20fields\
-    \x20are\x20public,\x20and\x20not\x20accessors\x20generated\n\n\n\n\x03\
-    \x07\n\x02\x12\x03#\x07%\n\n\n\x03\x07\n\x04\x12\x03'\x04\x0c\n\n\n\x03\
-    \x07\n\x05\x12\x03'\r\x11\n\n\n\x03\x07\n\x01\x12\x03'\x12\x1f\n\n\n\x03\
-    \x07\n\x03\x12\x03'\"'\nP\n\x02\x07\x0b\x12\x03)\x04-\x1aE\x20When\x20fa\
-    lse,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20accessors\x20are\x20not\
-    \x20generated\n\n\n\n\x03\x07\x0b\x02\x12\x03#\x07%\n\n\n\x03\x07\x0b\
-    \x04\x12\x03)\x04\x0c\n\n\n\x03\x07\x0b\x05\x12\x03)\r\x11\n\n\n\x03\x07\
-    \x0b\x01\x12\x03)\x12$\n\n\n\x03\x07\x0b\x03\x12\x03)',\nL\n\x02\x07\x0c\
-    \x12\x03+\x04*\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20generate\
-    d\x20even\x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x0c\x02\
-    \x12\x03#\x07%\n\n\n\x03\x07\x0c\x04\x12\x03+\x04\x0c\n\n\n\x03\x07\x0c\
-    \x05\x12\x03+\r\x11\n\n\n\x03\x07\x0c\x01\x12\x03+\x12!\n\n\n\x03\x07\
-    \x0c\x03\x12\x03+$)\n2\n\x02\x07\r\x12\x03-\x045\x1a'\x20Use\x20`bytes::\
-    Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\x03\x07\r\x02\x12\x03#\x07%\n\
-    \n\n\x03\x07\r\x04\x12\x03-\x04\x0c\n\n\n\x03\x07\r\x05\x12\x03-\r\x11\n\
-    \n\n\x03\x07\r\x01\x12\x03-\x12,\n\n\n\x03\x07\r\x03\x12\x03-/4\n3\n\x02\
-    \x07\x0e\x12\x03/\x046\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\
-    \x20fields\n\n\n\n\x03\x07\x0e\x02\x12\x03#\x07%\n\n\n\x03\x07\x0e\x04\
-    \x12\x03/\x04\x0c\n\n\n\x03\x07\x0e\x05\x12\x03/\r\x11\n\n\n\x03\x07\x0e\
-    \x01\x12\x03/\x12-\n\n\n\x03\x07\x0e\x03\x12\x03/05\nJ\n\x02\x07\x0f\x12\
-    \x031\x04'\x1a?\x20Use\x20`serde_derive`\x20to\x20implement\x20`Serializ\
-    e`\x20and\x20`Deserialize`\n\n\n\n\x03\x07\x0f\x02\x12\x03#\x07%\n\n\n\
-    \x03\x07\x0f\x04\x12\x031\x04\x0c\n\n\n\x03\x07\x0f\x05\x12\x031\r\x11\n\
-    \n\n\x03\x07\x0f\x01\x12\x031\x12\x1e\n\n\n\x03\x07\x0f\x03\x12\x031!&\n\
-    3\n\x02\x07\x10\x12\x033\x04-\x1a(\x20Guard\x20serde\x20annotations\x20w\
-    ith\x20cfg\x20attr.\n\n\n\n\x03\x07\x10\x02\x12\x03#\x07%\n\n\n\x03\x07\
-    \x10\x04\x12\x033\x04\x0c\n\n\n\x03\x07\x10\x05\x12\x033\r\x13\n\n\n\x03\
-    \x07\x10\x01\x12\x033\x14$\n\n\n\x03\x07\x10\x03\x12\x033',\n\t\n\x01\
-    \x07\x12\x046\0A\x01\nI\n\x02\x07\x11\x12\x038\x04.\x1a>\x20When\x20true\
-    \x20all\x20fields\x20are\x20public,\x20and\x20not\x20accessors\x20genera\
-    ted\n\n\n\n\x03\x07\x11\x02\x12\x036\x07#\n\n\n\x03\x07\x11\x04\x12\x038\
-    \x04\x0c\n\n\n\x03\x07\x11\x05\x12\x038\r\x11\n\n\n\x03\x07\x11\x01\x12\
-    \x038\x12%\n\n\n\x03\x07\x11\x03\x12\x038(-\nP\n\x02\x07\x12\x12\x03:\
-    \x043\x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20\
-    accessors\x20are\x20not\x20generated\n\n\n\n\x03\x07\x12\x02\x12\x036\
-    \x07#\n\n\n\x03\x07\x12\x04\x12\x03:\x04\x0c\n\n\n\x03\x07\x12\x05\x12\
-    \x03:\r\x11\n\n\n\x03\x07\x12\x01\x12\x03:\x12*\n\n\n\x03\x07\x12\x03\
-    \x12\x03:-2\nL\n\x02\x07\x13\x12\x03<\x040\x1aA\x20When\x20false,\x20`ge\
-    t_`\x20is\x20not\x20generated\x20even\x20if\x20`syntax\x20=\x20\"proto2\
-    \"`\n\n\n\n\x03\x07\x13\x02\x12\x036\x07#\n\n\n\x03\x07\x13\x04\x12\x03<\
-    \x04\x0c\n\n\n\x03\x07\x13\x05\x12\x03<\r\x11\n\n\n\x03\x07\x13\x01\x12\
-    \x03<\x12'\n\n\n\x03\x07\x13\x03\x12\x03<*/\n2\n\x02\x07\x14\x12\x03>\
-    \x04;\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\
-    \x03\x07\x14\x02\x12\x036\x07#\n\n\n\x03\x07\x14\x04\x12\x03>\x04\x0c\n\
-    \n\n\x03\x07\x14\x05\x12\x03>\r\x11\n\n\n\x03\x07\x14\x01\x12\x03>\x122\
-    \n\n\n\x03\x07\x14\x03\x12\x03>5:\n3\n\x02\x07\x15\x12\x03@\x04<\x1a(\
-    \x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\
-    \x15\x02\x12\x036\x07#\n\n\n\x03\x07\x15\x04\x12\x03@\x04\x0c\n\n\n\x03\
-    \x07\x15\x05\x12\x03@\r\x11\n\n\n\x03\x07\x15\x01\x12\x03@\x123\n\n\n\
-    \x03\x07\x15\x03\x12\x03@6;\
+    arllercheBytesForStringField:\\\n\x1achrono_for_timestamp_field\x18\xfc\
+    \x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x17chronoFor\
+    TimestampField:X\n\x18time_for_timestamp_field\x18\xfd\x84\x01\x20\x01(\
+    \x08\x12\x1d.google.protobuf.FieldOptionsR\x15timeForTimestampField:X\n\
+    \x18wrappers_as_option_field\x18\xfe\x84\x01\x20\x01(\x08\x12\x1d.google\
+    .protobuf.FieldOptionsR\x15wrappersAsOptionField:G\n\x0frust_type_field\
+    \x18\x9a\x85\x01\x20\x01(\t\x12\x1d.google.protobuf.FieldOptionsR\rrustT\
+    ypeField:V\n\x17extra_field_attrs_field\x18\x92\x85\x01\x20\x01(\t\x12\
+    \x1d.google.protobuf.FieldOptionsR\x14extraFieldAttrsField:L\n\x12extra_\
+    derives_enum\x18\x90\x85\x01\x20\x01(\t\x12\x1c.google.protobuf.EnumOpti\
+    onsR\x10extraDerivesEnum:H\n\x10extra_enum_attrs\x18\x93\x85\x01\x20\x01\
+    (\t\x12\x1c.google.protobuf.EnumOptionsR\x0eextraEnumAttrsJ\xcc1\n\x06\
+    \x12\x04\0\0t\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\x12\
+    \x03\x02\0*\n\xe5\x01\n\x01\x02\x12\x03\n\0\x122^\x20see\x20https://gith\
+    ub.com/gogo/protobuf/blob/master/gogoproto/gogo.proto\n\x20for\x20the\
+    \x20original\x20idea\n2{\x20Generated\x20files\x20can\x20be\x20customize\
+    d\x20using\x20this\x20proto\n\x20or\x20using\x20`Customize`\x20struct\
+    \x20when\x20codegen\x20is\x20invoked\x20programmatically.\n\n\t\n\x01\
+    \x07\x12\x04\x0c\02\x01\n7\n\x02\x07\0\x12\x03\x0e\x04+\x1a,\x20When\x20\
+    true,\x20oneof\x20field\x20is\x20generated\x20public\n\n\n\n\x03\x07\0\
+    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\0\x04\x12\x03\x0e\x04\x0c\n\n\n\x03\
+    \x07\0\x05\x12\x03\x0e\r\x11\n\n\n\x03\x07\0\x01\x12\x03\x0e\x12\"\n\n\n\
+    \x03\x07\0\x03\x12\x03\x0e%*\nI\n\x02\x07\x01\x12\x03\x10\x04,\x1a>\x20W\
+    hen\x20true\x20all\x20fields\x20are\x20public,\x20and\x20not\x20accessor\
+    s\x20generated\n\n\n\n\x03\x07\x01\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\
+    \x01\x04\x12\x03\x10\x04\x0c\n\n\n\x03\x07\x01\x05\x12\x03\x10\r\x11\n\n\
+    \n\x03\x07\x01\x01\x12\x03\x10\x12#\n\n\n\x03\x07\x01\x03\x12\x03\x10&+\
+    \nP\n\x02\x07\x02\x12\x03\x12\x041\x1aE\x20When\x20false,\x20`get_`,\x20\
+    `set_`,\x20`mut_`\x20etc.\x20accessors\x20are\x20not\x20generated\n\n\n\
+    \n\x03\x07\x02\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x02\x04\x12\x03\x12\
+    \x04\x0c\n\n\n\x03\x07\x02\x05\x12\x03\x12\r\x11\n\n\n\x03\x07\x02\x01\
+    \x12\x03\x12\x12(\n\n\n\x03\x07\x02\x03\x12\x03\x12+0\nL\n\x02\x07\x03\
+    \x12\x03\x14\x04.\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20gener\
+    ated\x20even\x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x03\
+    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x03\x04\x12\x03\x14\x04\x0c\n\n\n\
+    \x03\x07\x03\x05\x12\x03\x14\r\x11\n\n\n\x03\x07\x03\x01\x12\x03\x14\x12\
+    %\n\n\n\x03\x07\x03\x03\x12\x03\x14(-\n2\n\x02\x07\x04\x12\x03\x16\x049\
+    \x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\x03\
+    \x07\x04\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x04\x04\x12\x03\x16\x04\x0c\
+    \n\n\n\x03\x07\x04\x05\x12\x03\x16\r\x11\n\n\n\x03\x07\x04\x01\x12\x03\
+    \x16\x120\n\n\n\x03\x07\x04\x03\x12\x03\x1638\n3\n\x02\x07\x05\x12\x03\
+    \x18\x04:\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\
+    \n\n\x03\x07\x05\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x05\x04\x12\x03\x18\
+    \x04\x0c\n\n\n\x03\x07\x05\x05\x12\x03\x18\r\x11\n\n\n\x03\x07\x05\x01\
+    \x12\x03\x18\x121\n\n\n\x03\x07\x05\x03\x12\x03\x1849\n`\n\x02\x07\x06\
+    \x12\x03\x1a\x043\x1aU\x20Use\x20`chrono::DateTime<chrono::Utc>`\x20for\
+    \x20singular\x20`google.protobuf.Timestamp`\x20fields\n\n\n\n\x03\x07\
+    \x06\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x06\x04\x12\x03\x1a\x04\x0c\n\n\
+    \n\x03\x07\x06\x05\x12\x03\x1a\r\x11\n\n\n\x03\x07\x06\x01\x12\x03\x1a\
+    \x12*\n\n\n\x03\x07\x06\x03\x12\x03\x1a-2\nW\n\x02\x07\x07\x12\x03\x1c\
+    \x041\x1aL\x20Use\x20`time::OffsetDateTime`\x20for\x20singular\x20`googl\
+    e.protobuf.Timestamp`\x20fields\n\n\n\n\x03\x07\x07\x02\x12\x03\x0c\x07\
+    \"\n\n\n\x03\x07\x07\x04\x12\x03\x1c\x04\x0c\n\n\n\x03\x07\x07\x05\x12\
+    \x03\x1c\r\x11\n\n\n\x03\x07\x07\x01\x12\x03\x1c\x12(\n\n\n\x03\x07\x07\
+    \x03\x12\x03\x1c+0\nb\n\x02\x07\x08\x12\x03\x1e\x041\x1aW\x20Use\x20`Opt\
+    ion<i32>`\x20etc.\x20for\x20`google.protobuf.Int32Value`\x20and\x20other\
+    \x20wrapper\x20type\x20fields\n\n\n\n\x03\x07\x08\x02\x12\x03\x0c\x07\"\
+    \n\n\n\x03\x07\x08\x04\x12\x03\x1e\x04\x0c\n\n\n\x03\x07\x08\x05\x12\x03\
+    \x1e\r\x11\n\n\n\x03\x07\x08\x01\x12\x03\x1e\x12(\n\n\n\x03\x07\x08\x03\
+    \x12\x03\x1e+0\nJ\n\x02\x07\t\x12\x03!\x04+\x1a?\x20Use\x20`serde_derive\
+    `\x20to\x20implement\x20`Serialize`\x20and\x20`Deserialize`\n\n\n\n\x03\
+    \x07\t\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\t\x04\x12\x03!\x04\x0c\n\n\n\
+    \x03\x07\t\x05\x12\x03!\r\x11\n\n\n\x03\x07\t\x01\x12\x03!\x12\"\n\n\n\
+    \x03\x07\t\x03\x12\x03!%*\n3\n\x02\x07\n\x12\x03#\x041\x1a(\x20Guard\x20\
+    serde\x20annotations\x20with\x20cfg\x20attr.\n\n\n\n\x03\x07\n\x02\x12\
+    \x03\x0c\x07\"\n\n\n\x03\x07\n\x04\x12\x03#\x04\x0c\n\n\n\x03\x07\n\x05\
+    \x12\x03#\r\x13\n\n\n\x03\x07\n\x01\x12\x03#\x14(\n\n\n\x03\x07\n\x03\
+    \x12\x03#+0\n]\n\x02\x07\x0b\x12\x03%\x041\x1aR\x20Implement\x20serde\
+    \x20traits\x20following\x20protobuf\x20JSON\x20mapping\x20instead\x20of\
+    \x20deriving\x20them.\n\n\n\n\x03\x07\x0b\x02\x12\x03\x0c\x07\"\n\n\n\
+    \x03\x07\x0b\x04\x12\x03%\x04\x0c\n\n\n\x03\x07\x0b\x05\x12\x03%\r\x11\n\
+    \n\n\x03\x07\x0b\x01\x12\x03%\x12(\n\n\n\x03\x07\x0b\x03\x12\x03%+0\nN\n\
+    \x02\x07\x0c\x12\x03(\x04+\x1aC\x20When\x20true,\x20will\x20only\x20gene\
+    rate\x20codes\x20that\x20works\x20with\x20lite\x20runtime.\n\n\n\n\x03\
+    \x07\x0c\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x0c\x04\x12\x03(\x04\x0c\n\
+    \n\n\x03\x07\x0c\x05\x12\x03(\r\x11\n\n\n\x03\x07\x0c\x01\x12\x03(\x12\"\
+    \n\n\n\x03\x07\x0c\x03\x12\x03(%*\nX\n\x02\x07\r\x12\x03+\x04.\x1aM\x20A\
+    dditional\x20comma-separated\x20derives\x20for\x20messages\x20and\x20enu\
+    ms,\x20e.\x20g.\x20\"Hash,\x20Eq\"\n\n\n\n\x03\x07\r\x02\x12\x03\x0c\x07\
+    \"\n\n\n\x03\x07\r\x04\x12\x03+\x04\x0c\n\n\n\x03\x07\r\x05\x12\x03+\r\
+    \x13\n\n\n\x03\x07\r\x01\x12\x03+\x14%\n\n\n\x03\x07\r\x03\x12\x03+(-\nX\
+    \n\x02\x07\x0e\x12\x03-\x041\x1aM\x20Attributes\x20added\x20verbatim\x20\
+    before\x20message\x20structs,\x20e.\x20g.\x20\"#[non_exhaustive]\"\n\n\n\
+    \n\x03\x07\x0e\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x0e\x04\x12\x03-\x04\
+    \x0c\n\n\n\x03\x07\x0e\x05\x12\x03-\r\x13\n\n\n\x03\x07\x0e\x01\x12\x03-\
+    \x14(\n\n\n\x03\x07\x0e\x03\x12\x03-+0\nS\n\x02\x07\x0f\x12\x03/\x042\
+    \x1aH\x20Attributes\x20added\x20verbatim\x20before\x20message\x20fields,\
+    \x20e.\x20g.\x20\"#[deprecated]\"\n\n\n\n\x03\x07\x0f\x02\x12\x03\x0c\
+    \x07\"\n\n\n\x03\x07\x0f\x04\x12\x03/\x04\x0c\n\n\n\x03\x07\x0f\x05\x12\
+    \x03/\r\x13\n\n\n\x03\x07\x0f\x01\x12\x03/\x14)\n\n\n\x03\x07\x0f\x03\
+    \x12\x03/,1\n3\n\x02\x07\x10\x12\x031\x041\x1a(\x20Attributes\x20added\
+    \x20verbatim\x20before\x20enums\n\n\n\n\x03\x07\x10\x02\x12\x03\x0c\x07\
+    \"\n\n\n\x03\x07\x10\x04\x12\x031\x04\x0c\n\n\n\x03\x07\x10\x05\x12\x031\
+    \r\x13\n\n\n\x03\x07\x10\x01\x12\x031\x14(\n\n\n\x03\x07\x10\x03\x12\x03\
+    1+0\n\t\n\x01\x07\x12\x044\0T\x01\n7\n\x02\x07\x11\x12\x036\x04'\x1a,\
+    \x20When\x20true,\x20oneof\x20field\x20is\x20generated\x20public\n\n\n\n\
+    \x03\x07\x11\x02\x12\x034\x07%\n\n\n\x03\x07\x11\x04\x12\x036\x04\x0c\n\
+    \n\n\x03\x07\x11\x05\x12\x036\r\x11\n\n\n\x03\x07\x11\x01\x12\x036\x12\
+    \x1e\n\n\n\x03\x07\x11\x03\x12\x036!&\nI\n\x02\x07\x12\x12\x038\x04(\x1a\
+    >\x20When\x20true\x20all\x20fields\x20are\x20public,\x20and\x20not\x20ac\
+    cessors\x20generated\n\n\n\n\x03\x07\x12\x02\x12\x034\x07%\n\n\n\x03\x07\
+    \x12\x04\x12\x038\x04\x0c\n\n\n\x03\x07\x12\x05\x12\x038\r\x11\n\n\n\x03\
+    \x07\x12\x01\x12\x038\x12\x1f\n\n\n\x03\x07\x12\x03\x12\x038\"'\nP\n\x02\
+    \x07\x13\x12\x03:\x04-\x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`\
+    mut_`\x20etc.\x20accessors\x20are\x20not\x20generated\n\n\n\n\x03\x07\
+    \x13\x02\x12\x034\x07%\n\n\n\x03\x07\x13\x04\x12\x03:\x04\x0c\n\n\n\x03\
+    \x07\x13\x05\x12\x03:\r\x11\n\n\n\x03\x07\x13\x01\x12\x03:\x12$\n\n\n\
+    \x03\x07\x13\x03\x12\x03:',\nL\n\x02\x07\x14\x12\x03<\x04*\x1aA\x20When\
+    \x20false,\x20`get_`\x20is\x20not\x20generated\x20even\x20if\x20`syntax\
+    \x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x14\x02\x12\x034\x07%\n\n\n\x03\x07\
+    \x14\x04\x12\x03<\x04\x0c\n\n\n\x03\x07\x14\x05\x12\x03<\r\x11\n\n\n\x03\
+    \x07\x14\x01\x12\x03<\x12!\n\n\n\x03\x07\x14\x03\x12\x03<$)\n2\n\x02\x07\
+    \x15\x12\x03>\x045\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\x20fi\
+    elds\n\n\n\n\x03\x07\x15\x02\x12\x034\x07%\n\n\n\x03\x07\x15\x04\x12\x03\
+    >\x04\x0c\n\n\n\x03\x07\x15\x05\x12\x03>\r\x11\n\n\n\x03\x07\x15\x01\x12\
+    \x03>\x12,\n\n\n\x03\x07\x15\x03\x12\x03>/4\n3\n\x02\x07\x16\x12\x03@\
+    \x046\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\
+    \x03\x07\x16\x02\x12\x034\x07%\n\n\n\x03\x07\x16\x04\x12\x03@\x04\x0c\n\
+    \n\n\x03\x07\x16\x05\x12\x03@\r\x11\n\n\n\x03\x07\x16\x01\x12\x03@\x12-\
+    \n\n\n\x03\x07\x16\x03\x12\x03@05\n`\n\x02\x07\x17\x12\x03B\x04/\x1aU\
+    \x20Use\x20`chrono::DateTime<chrono::Utc>`\x20for\x20singular\x20`google\
+    .protobuf.Timestamp`\x20fields\n\n\n\n\x03\x07\x17\x02\x12\x034\x07%\n\n\
+    \n\x03\x07\x17\x04\x12\x03B\x04\x0c\n\n\n\x03\x07\x17\x05\x12\x03B\r\x11\
+    \n\n\n\x03\x07\x17\x01\x12\x03B\x12&\n\n\n\x03\x07\x17\x03\x12\x03B).\nW\
+    \n\x02\x07\x18\x12\x03D\x04-\x1aL\x20Use\x20`time::OffsetDateTime`\x20fo\
+    r\x20singular\x20`google.protobuf.Timestamp`\x20fields\n\n\n\n\x03\x07\
+    \x18\x02\x12\x034\x07%\n\n\n\x03\x07\x18\x04\x12\x03D\x04\x0c\n\n\n\x03\
+    \x07\x18\x05\x12\x03D\r\x11\n\n\n\x03\x07\x18\x01\x12\x03D\x12$\n\n\n\
+    \x03\x07\x18\x03\x12\x03D',\nb\n\x02\x07\x19\x12\x03F\x04-\x1aW\x20Use\
+    \x20`Option<i32>`\x20etc.\x20for\x20`google.protobuf.Int32Value`\x20and\
+    \x20other\x20wrapper\x20type\x20fields\n\n\n\n\x03\x07\x19\x02\x12\x034\
+    \x07%\n\n\n\x03\x07\x19\x04\x12\x03F\x04\x0c\n\n\n\x03\x07\x19\x05\x12\
+    \x03F\r\x11\n\n\n\x03\x07\x19\x01\x12\x03F\x12$\n\n\n\x03\x07\x19\x03\
+    \x12\x03F',\nJ\n\x02\x07\x1a\x12\x03H\x04'\x1a?\x20Use\x20`serde_derive`\
+    \x20to\x20implement\x20`Serialize`\x20and\x20`Deserialize`\n\n\n\n\x03\
+    \x07\x1a\x02\x12\x034\x07%\n\n\n\x03\x07\x1a\x04\x12\x03H\x04\x0c\n\n\n\
+    \x03\x07\x1a\x05\x12\x03H\r\x11\n\n\n\x03\x07\x1a\x01\x12\x03H\x12\x1e\n\
+    \n\n\x03\x07\x1a\x03\x12\x03H!&\n3\n\x02\x07\x1b\x12\x03J\x04-\x1a(\x20G\
+    uard\x20serde\x20annotations\x20with\x20cfg\x20attr.\n\n\n\n\x03\x07\x1b\
+    \x02\x12\x034\x07%\n\n\n\x03\x07\x1b\x04\x12\x03J\x04\x0c\n\n\n\x03\x07\
+    \x1b\x05\x12\x03J\r\x13\n\n\n\x03\x07\x1b\x01\x12\x03J\x14$\n\n\n\x03\
+    \x07\x1b\x03\x12\x03J',\n]\n\x02\x07\x1c\x12\x03L\x04-\x1aR\x20Implement\
+    \x20serde\x20traits\x20following\x20protobuf\x20JSON\x20mapping\x20inste\
+    ad\x20of\x20deriving\x20them.\n\n\n\n\x03\x07\x1c\x02\x12\x034\x07%\n\n\
+    \n\x03\x07\x1c\x04\x12\x03L\x04\x0c\n\n\n\x03\x07\x1c\x05\x12\x03L\r\x11\
+    \n\n\n\x03\x07\x1c\x01\x12\x03L\x12$\n\n\n\x03\x07\x1c\x03\x12\x03L',\nX\
+    \n\x02\x07\x1d\x12\x03O\x04*\x1aM\x20Additional\x20comma-separated\x20de\
+    rives\x20for\x20messages\x20and\x20enums,\x20e.\x20g.\x20\"Hash,\x20Eq\"\
+    \n\n\n\n\x03\x07\x1d\x02\x12\x034\x07%\n\n\n\x03\x07\x1d\x04\x12\x03O\
+    \x04\x0c\n\n\n\x03\x07\x1d\x05\x12\x03O\r\x13\n\n\n\x03\x07\x1d\x01\x12\
+    \x03O\x14!\n\n\n\x03\x07\x1d\x03\x12\x03O$)\nX\n\x02\x07\x1e\x12\x03Q\
+    \x04-\x1aM\x20Attributes\x20added\x20verbatim\x20before\x20message\x20st\
+    ructs,\x20e.\x20g.\x20\"#[non_exhaustive]\"\n\n\n\n\x03\x07\x1e\x02\x12\
+    \x034\x07%\n\n\n\x03\x07\x1e\x04\x12\x03Q\x04\x0c\n\n\n\x03\x07\x1e\x05\
+    \x12\x03Q\r\x13\n\n\n\x03\x07\x1e\x01\x12\x03Q\x14$\n\n\n\x03\x07\x1e\
+    \x03\x12\x03Q',\nS\n\x02\x07\x1f\x12\x03S\x04.\x1aH\x20Attributes\x20add\
+    ed\x20verbatim\x20before\x20message\x20fields,\x20e.\x20g.\x20\"#[deprec\
+    ated]\"\n\n\n\n\x03\x07\x1f\x02\x12\x034\x07%\n\n\n\x03\x07\x1f\x04\x12\
+    \x03S\x04\x0c\n\n\n\x03\x07\x1f\x05\x12\x03S\r\x13\n\n\n\x03\x07\x1f\x01\
+    \x12\x03S\x14%\n\n\n\x03\x07\x1f\x03\x12\x03S(-\n\t\n\x01\x07\x12\x04V\0\
+    m\x01\nI\n\x02\x07\x20\x12\x03X\x04.\x1a>\x20When\x20true\x20all\x20fiel\
+    ds\x20are\x20public,\x20and\x20not\x20accessors\x20generated\n\n\n\n\x03\
+    \x07\x20\x02\x12\x03V\x07#\n\n\n\x03\x07\x20\x04\x12\x03X\x04\x0c\n\n\n\
+    \x03\x07\x20\x05\x12\x03X\r\x11\n\n\n\x03\x07\x20\x01\x12\x03X\x12%\n\n\
+    \n\x03\x07\x20\x03\x12\x03X(-\nP\n\x02\x07!\x12\x03Z\x043\x1aE\x20When\
+    \x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20accessors\x20are\
+    \x20not\x20generated\n\n\n\n\x03\x07!\x02\x12\x03V\x07#\n\n\n\x03\x07!\
+    \x04\x12\x03Z\x04\x0c\n\n\n\x03\x07!\x05\x12\x03Z\r\x11\n\n\n\x03\x07!\
+    \x01\x12\x03Z\x12*\n\n\n\x03\x07!\x03\x12\x03Z-2\nL\n\x02\x07\"\x12\x03\
+    \\\x040\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20generated\x20ev\
+    en\x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\"\x02\x12\x03V\
+    \x07#\n\n\n\x03\x07\"\x04\x12\x03\\\x04\x0c\n\n\n\x03\x07\"\x05\x12\x03\
+    \\\r\x11\n\n\n\x03\x07\"\x01\x12\x03\\\x12'\n\n\n\x03\x07\"\x03\x12\x03\
+    \\*/\n2\n\x02\x07#\x12\x03^\x04;\x1a'\x20Use\x20`bytes::Bytes`\x20for\
+    \x20`bytes`\x20fields\n\n\n\n\x03\x07#\x02\x12\x03V\x07#\n\n\n\x03\x07#\
+    \x04\x12\x03^\x04\x0c\n\n\n\x03\x07#\x05\x12\x03^\r\x11\n\n\n\x03\x07#\
+    \x01\x12\x03^\x122\n\n\n\x03\x07#\x03\x12\x03^5:\n3\n\x02\x07$\x12\x03`\
+    \x04<\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\
+    \x03\x07$\x02\x12\x03V\x07#\n\n\n\x03\x07$\x04\x12\x03`\x04\x0c\n\n\n\
+    \x03\x07$\x05\x12\x03`\r\x11\n\n\n\x03\x07$\x01\x12\x03`\x123\n\n\n\x03\
+    \x07$\x03\x12\x03`6;\n_\n\x02\x07%\x12\x03b\x045\x1aT\x20Use\x20`chrono:\
+    :DateTime<chrono::Utc>`\x20for\x20singular\x20`google.protobuf.Timestamp\
+    `\x20field\n\n\n\n\x03\x07%\x02\x12\x03V\x07#\n\n\n\x03\x07%\x04\x12\x03\
+    b\x04\x0c\n\n\n\x03\x07%\x05\x12\x03b\r\x11\n\n\n\x03\x07%\x01\x12\x03b\
+    \x12,\n\n\n\x03\x07%\x03\x12\x03b/4\nV\n\x02\x07&\x12\x03d\x043\x1aK\x20\
+    Use\x20`time::OffsetDateTime`\x20for\x20singular\x20`google.protobuf.Tim\
+    estamp`\x20field\n\n\n\n\x03\x07&\x02\x12\x03V\x07#\n\n\n\x03\x07&\x04\
+    \x12\x03d\x04\x0c\n\n\n\x03\x07&\x05\x12\x03d\r\x11\n\n\n\x03\x07&\x01\
+    \x12\x03d\x12*\n\n\n\x03\x07&\x03\x12\x03d-2\na\n\x02\x07'\x12\x03f\x043\
+    \x1aV\x20Use\x20`Option<i32>`\x20etc.\x20for\x20`google.protobuf.Int32Va\
+    lue`\x20and\x20other\x20wrapper\x20type\x20field\n\n\n\n\x03\x07'\x02\
+    \x12\x03V\x07#\n\n\n\x03\x07'\x04\x12\x03f\x04\x0c\n\n\n\x03\x07'\x05\
+    \x12\x03f\r\x11\n\n\n\x03\x07'\x01\x12\x03f\x12*\n\n\n\x03\x07'\x03\x12\
+    \x03f-2\nX\n\x02\x07(\x12\x03i\x04,\x1aM\x20Rust\x20type\x20used\x20inst\
+    ead\x20of\x20the\x20generated\x20type,\x20e.\x20g.\x20\"::std::sync::Arc\
+    <str>\"\n\n\n\n\x03\x07(\x02\x12\x03V\x07#\n\n\n\x03\x07(\x04\x12\x03i\
+    \x04\x0c\n\n\n\x03\x07(\x05\x12\x03i\r\x13\n\n\n\x03\x07(\x01\x12\x03i\
+    \x14#\n\n\n\x03\x07(\x03\x12\x03i&+\nN\n\x02\x07)\x12\x03l\x044\x1aC\x20\
+    Attributes\x20added\x20verbatim\x20before\x20the\x20field,\x20e.\x20g.\
+    \x20\"#[deprecated]\"\n\n\n\n\x03\x07)\x02\x12\x03V\x07#\n\n\n\x03\x07)\
+    \x04\x12\x03l\x04\x0c\n\n\n\x03\x07)\x05\x12\x03l\r\x13\n\n\n\x03\x07)\
+    \x01\x12\x03l\x14+\n\n\n\x03\x07)\x03\x12\x03l.3\n\t\n\x01\x07\x12\x04o\
+    \0t\x01\nH\n\x02\x07*\x12\x03q\x04/\x1a=\x20Additional\x20comma-separate\
+    d\x20derives,\x20e.\x20g.\x20\"PartialOrd,\x20Ord\"\n\n\n\n\x03\x07*\x02\
+    \x12\x03o\x07\"\n\n\n\x03\x07*\x04\x12\x03q\x04\x0c\n\n\n\x03\x07*\x05\
+    \x12\x03q\r\x13\n\n\n\x03\x07*\x01\x12\x03q\x14&\n\n\n\x03\x07*\x03\x12\
+    \x03q).\n6\n\x02\x07+\x12\x03s\x04-\x1a+\x20Attributes\x20added\x20verba\
+    tim\x20before\x20the\x20enum\n\n\n\n\x03\x07+\x02\x12\x03o\x07\"\n\n\n\
+    \x03\x07+\x04\x12\x03s\x04\x0c\n\n\n\x03\x07+\x05\x12\x03s\r\x13\n\n\n\
+    \x03\x07+\x01\x12\x03s\x14$\n\n\n\x03\x07+\x03\x12\x03s',\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::service::service_name_of_method;
+use crate::service::AsyncServiceDispatcher;
+use crate::service::AsyncServiceTransport;
+use crate::service::ServiceDispatcher;
+use crate::service::ServiceError;
+use crate::service::ServiceFuture;
+use crate::service::ServiceResult;
+use crate::service::ServiceTransport;
+
+/// Transport which invokes services registered in the same process.
+///
+/// Requests and responses are still serialized, so this transport
+/// can be used to test clients and services without networking.
+#[derive(Default, Clone)]
+pub struct InMemoryTransport {
+    services: HashMap<String, Arc<dyn ServiceDispatcher>>,
+}
+
+impl InMemoryTransport {
+    /// Create a transport without services.
+    pub fn new() -> InMemoryTransport {
+        Default::default()
+    }
+
+    /// Register a service, replacing previously registered service with the same name.
+    pub fn add_service<D: ServiceDispatcher + 'static>(&mut self, dispatcher: D) {
+        self.services
+            .insert(dispatcher.service_name().to_owned(), Arc::new(dispatcher));
+    }
+}
+
+impl ServiceTransport for InMemoryTransport {
+    fn call(&self, method: &str, request: Vec<u8>) -> ServiceResult<Vec<u8>> {
+        match self.services.get(service_name_of_method(method)) {
+            Some(service) => service.dispatch(method, &request),
+            None => Err(ServiceError::UnknownMethod(method.to_owned())),
+        }
+    }
+}
+
+impl fmt::Debug for InMemoryTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InMemoryTransport")
+            .field("services", &self.services.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Transport which invokes async services registered in the same process.
+///
+/// Requests and responses are still serialized, so this transport
+/// can be used to test clients and services without networking.
+#[derive(Default, Clone)]
+pub struct AsyncInMemoryTransport {
+    services: HashMap<String, Arc<dyn AsyncServiceDispatcher>>,
+}
+
+impl AsyncInMemoryTransport {
+    /// Create a transport without services.
+    pub fn new() -> AsyncInMemoryTransport {
+        Default::default()
+    }
+
+    /// Register a service, replacing previously registered service with the same name.
+    pub fn add_service<D: AsyncServiceDispatcher + 'static>(&mut self, dispatcher: D) {
+        self.services
+            .insert(dispatcher.service_name().to_owned(), Arc::new(dispatcher));
+    }
+}
+
+impl AsyncServiceTransport for AsyncInMemoryTransport {
+    fn call<'a>(&'a self, method: &'a str, request: Vec<u8>) -> ServiceFuture<'a, Vec<u8>> {
+        let service = self.services.get(service_name_of_method(method));
+        Box::pin(async move {
+            match service {
+                Some(service) => service.dispatch(method, &request).await,
+                None => Err(ServiceError::UnknownMethod(method.to_owned())),
+            }
+        })
+    }
+}
+
+impl fmt::Debug for AsyncInMemoryTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AsyncInMemoryTransport")
+            .field("services", &self.services.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::block_on::block_on;
+    use crate::service::call_unary;
+    use crate::service::call_unary_async;
+    use crate::service::dispatch_unary;
+    use crate::service::dispatch_unary_async;
+    use crate::service::ready;
+    use crate::well_known_types::StringValue;
+
+    const UPPER: &str = "test.Echo.Upper";
+
+    fn upper(request: StringValue) -> StringValue {
+        let mut response = StringValue::new();
+        response.value = request.value.to_uppercase();
+        response
+    }
+
+    fn string_value(value: &str) -> StringValue {
+        let mut r = StringValue::new();
+        r.value = value.to_owned();
+        r
+    }
+
+    struct EchoDispatcher;
+
+    impl ServiceDispatcher for EchoDispatcher {
+        fn service_name(&self) -> &str {
+            "test.Echo"
+        }
+
+        fn dispatch(&self, method: &str, request: &[u8]) -> ServiceResult<Vec<u8>> {
+            match method {
+                UPPER => dispatch_unary(request, |r| Ok(upper(r))),
+                _ => Err(ServiceError::UnknownMethod(method.to_owned())),
+            }
+        }
+    }
+
+    struct AsyncEchoDispatcher;
+
+    impl AsyncServiceDispatcher for AsyncEchoDispatcher {
+        fn service_name(&self) -> &str {
+            "test.Echo"
+        }
+
+        fn dispatch<'a>(
+            &'a self,
+            method: &'a str,
+            request: &'a [u8],
+        ) -> ServiceFuture<'a, Vec<u8>> {
+            match method {
+                UPPER => dispatch_unary_async(request, |r| ready(Ok(upper(r)))),
+                _ => ready(Err(ServiceError::UnknownMethod(method.to_owned()))),
+            }
+        }
+    }
+
+    #[test]
+    fn call() {
+        let mut transport = InMemoryTransport::new();
+        transport.add_service(EchoDispatcher);
+
+        let response: StringValue = call_unary(&transport, UPPER, &string_value("ab")).unwrap();
+        assert_eq!("AB", response.value);
+    }
+
+    #[test]
+    fn unknown_method() {
+        let mut transport = InMemoryTransport::new();
+        transport.add_service(EchoDispatcher);
+
+        for method in &["test.Echo.Lower", "test.Other.Upper"] {
+            match call_unary::<_, _, StringValue>(&transport, method, &string_value("")) {
+                Err(ServiceError::UnknownMethod(m)) => assert_eq!(*method, m),
+                r => panic!("{:?}", r),
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_request() {
+        let mut transport = InMemoryTransport::new();
+        transport.add_service(EchoDispatcher);
+
+        match transport.call(UPPER, vec![0xff]) {
+            Err(ServiceError::Protobuf(..)) => {}
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn call_async() {
+        let mut transport = AsyncInMemoryTransport::new();
+        transport.add_service(AsyncEchoDispatcher);
+
+        let response: StringValue =
+            block_on(call_unary_async(&transport, UPPER, &string_value("ab"))).unwrap();
+        assert_eq!("AB", response.value);
+
+        match block_on(call_unary_async::<_, _, StringValue>(
+            &transport,
+            "test.Other.Upper",
+            &string_value(""),
+        )) {
+            Err(ServiceError::UnknownMethod(m)) => assert_eq!("test.Other.Upper", m),
+            r => panic!("{:?}", r),
+        }
+    }
+}
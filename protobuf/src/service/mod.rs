@@ -0,0 +1,219 @@
+//! Runtime support for code generated from `service` definitions.
+//!
+//! For each service codegen emits:
+//!
+//! * a server trait with a method per RPC, which user implements,
+//! * a client generic over [`ServiceTransport`],
+//! * a dispatcher implementing [`ServiceDispatcher`], which routes
+//!   serialized requests to the server trait.
+//!
+//! When `gen_async_services` codegen option is enabled, async variants
+//! of these are generated too, working with [`AsyncServiceTransport`]
+//! and [`AsyncServiceDispatcher`].
+//!
+//! Methods are identified by full protobuf name like `my.package.Greeter.SayHello`,
+//! messages are passed between client and server serialized in protobuf binary format.
+//! This module does not depend on any RPC framework:
+//! a transport can be implemented on top of any network library,
+//! and [`InMemoryTransport`] connects clients to services within the process.
+//!
+//! Client and server streaming methods are not supported,
+//! no code is generated for them.
+
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::error::ProtobufError;
+use crate::Message;
+
+mod in_memory;
+
+pub use self::in_memory::AsyncInMemoryTransport;
+pub use self::in_memory::InMemoryTransport;
+
+/// Error returned by service methods, clients, transports and dispatchers.
+#[derive(Debug)]
+pub enum ServiceError {
+    /// Method with given full name is not known to the service or transport.
+    UnknownMethod(String),
+    /// Failed to serialize or parse a request or a response.
+    Protobuf(ProtobufError),
+    /// Error returned by service implementation or transport.
+    Other(Box<dyn Error + Send + Sync>),
+}
+
+impl ServiceError {
+    /// Construct an error from service implementation or transport error.
+    pub fn other<E>(error: E) -> ServiceError
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        ServiceError::Other(error.into())
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServiceError::UnknownMethod(method) => write!(f, "unknown method: {}", method),
+            ServiceError::Protobuf(e) => fmt::Display::fmt(e, f),
+            ServiceError::Other(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for ServiceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ServiceError::UnknownMethod(..) => None,
+            ServiceError::Protobuf(e) => Some(e),
+            ServiceError::Other(e) => Some(&**e),
+        }
+    }
+}
+
+impl From<ProtobufError> for ServiceError {
+    fn from(e: ProtobufError) -> Self {
+        ServiceError::Protobuf(e)
+    }
+}
+
+/// `Result` alias for `ServiceError`.
+pub type ServiceResult<T> = Result<T, ServiceError>;
+
+/// Future returned by async service methods, clients, transports and dispatchers.
+pub type ServiceFuture<'a, T> = Pin<Box<dyn Future<Output = ServiceResult<T>> + Send + 'a>>;
+
+/// Transport used by generated clients to invoke methods.
+pub trait ServiceTransport {
+    /// Invoke a method by its full name with serialized request,
+    /// and return serialized response.
+    fn call(&self, method: &str, request: Vec<u8>) -> ServiceResult<Vec<u8>>;
+}
+
+/// Transport used by generated async clients to invoke methods.
+pub trait AsyncServiceTransport: Send + Sync {
+    /// Invoke a method by its full name with serialized request,
+    /// and return serialized response.
+    fn call<'a>(&'a self, method: &'a str, request: Vec<u8>) -> ServiceFuture<'a, Vec<u8>>;
+}
+
+/// Routes serialized requests to a service implementation.
+///
+/// Implemented by generated dispatchers.
+pub trait ServiceDispatcher: Send + Sync {
+    /// Full name of the service, e. g. `my.package.Greeter`.
+    fn service_name(&self) -> &str;
+
+    /// Invoke a method by its full name with serialized request,
+    /// and return serialized response.
+    fn dispatch(&self, method: &str, request: &[u8]) -> ServiceResult<Vec<u8>>;
+}
+
+/// Routes serialized requests to an async service implementation.
+///
+/// Implemented by generated async dispatchers.
+pub trait AsyncServiceDispatcher: Send + Sync {
+    /// Full name of the service, e. g. `my.package.Greeter`.
+    fn service_name(&self) -> &str;
+
+    /// Invoke a method by its full name with serialized request,
+    /// and return serialized response.
+    fn dispatch<'a>(&'a self, method: &'a str, request: &'a [u8]) -> ServiceFuture<'a, Vec<u8>>;
+}
+
+/// Future which is immediately ready with given result.
+///
+/// Useful to implement async service methods which do not need to wait.
+pub fn ready<'a, T: Send + 'a>(result: ServiceResult<T>) -> ServiceFuture<'a, T> {
+    Box::pin(async move { result })
+}
+
+/// Name of the service from method full name.
+pub(crate) fn service_name_of_method(method: &str) -> &str {
+    match method.rfind('.') {
+        Some(pos) => &method[..pos],
+        None => "",
+    }
+}
+
+/// Invoke unary method using given transport.
+///
+/// Used in generated clients.
+pub fn call_unary<T, Req, Resp>(transport: &T, method: &str, request: &Req) -> ServiceResult<Resp>
+where
+    T: ServiceTransport + ?Sized,
+    Req: Message,
+    Resp: Message,
+{
+    let request = request.write_to_bytes()?;
+    let response = transport.call(method, request)?;
+    Ok(Resp::parse_from_bytes(&response)?)
+}
+
+/// Invoke unary method using given async transport.
+///
+/// Used in generated async clients.
+pub fn call_unary_async<'a, T, Req, Resp>(
+    transport: &'a T,
+    method: &'a str,
+    request: &Req,
+) -> ServiceFuture<'a, Resp>
+where
+    T: AsyncServiceTransport + ?Sized,
+    Req: Message,
+    Resp: Message,
+{
+    let request = request.write_to_bytes();
+    Box::pin(async move {
+        let response = transport.call(method, request?).await?;
+        Ok(Resp::parse_from_bytes(&response)?)
+    })
+}
+
+/// Parse a request, invoke service method and serialize the response.
+///
+/// Used in generated dispatchers.
+pub fn dispatch_unary<Req, Resp, F>(request: &[u8], method: F) -> ServiceResult<Vec<u8>>
+where
+    Req: Message,
+    Resp: Message,
+    F: FnOnce(Req) -> ServiceResult<Resp>,
+{
+    let request = Req::parse_from_bytes(request)?;
+    let response = method(request)?;
+    Ok(response.write_to_bytes()?)
+}
+
+/// Parse a request, invoke async service method and serialize the response.
+///
+/// Used in generated async dispatchers.
+pub fn dispatch_unary_async<'a, Req, Resp, F>(
+    request: &[u8],
+    method: F,
+) -> ServiceFuture<'a, Vec<u8>>
+where
+    Req: Message,
+    Resp: Message,
+    F: FnOnce(Req) -> ServiceFuture<'a, Resp>,
+{
+    let response = match Req::parse_from_bytes(request) {
+        Ok(request) => method(request),
+        Err(e) => return ready(Err(e.into())),
+    };
+    Box::pin(async move { Ok(response.await?.write_to_bytes()?) })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_service_name_of_method() {
+        assert_eq!("a.b.Svc", service_name_of_method("a.b.Svc.Method"));
+        assert_eq!("Svc", service_name_of_method("Svc.Method"));
+        assert_eq!("", service_name_of_method("Method"));
+    }
+}
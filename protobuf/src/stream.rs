@@ -55,11 +55,27 @@ const DEFAULT_RECURSION_LIMIT: u32 = 100;
 // Max allocated vec when reading length-delimited from unknown input stream
 pub(crate) const READ_RAW_BYTES_MAX_ALLOC: usize = 10_000_000;
 
+/// State of the group being read.
+#[derive(Clone, Copy)]
+struct GroupState {
+    /// Number of the group field.
+    field_number: u32,
+    /// Number of pushed limits when the group started.
+    /// `END_GROUP` tag is only expected at this depth.
+    limit_depth: u32,
+    /// `END_GROUP` tag was consumed.
+    ended: bool,
+}
+
 /// Buffered read with handy utilities.
 pub struct CodedInputStream<'a> {
     source: BufReadIter<'a>,
     recursion_level: u32,
     recursion_limit: u32,
+    limit_depth: u32,
+    group: Option<GroupState>,
+    // Tag read by `eof` while looking for the end of group.
+    pending_tag: Option<wire_format::Tag>,
 }
 
 impl<'a> CodedInputStream<'a> {
@@ -97,6 +113,9 @@ impl<'a> CodedInputStream<'a> {
             source: source,
             recursion_level: 0,
             recursion_limit: DEFAULT_RECURSION_LIMIT,
+            limit_depth: 0,
+            group: None,
+            pending_tag: None,
         }
     }
 
@@ -155,18 +174,55 @@ impl<'a> CodedInputStream<'a> {
 
     /// Push new limit, return previous limit.
     pub fn push_limit(&mut self, limit: u64) -> ProtobufResult<u64> {
-        self.source.push_limit(limit)
+        let old_limit = self.source.push_limit(limit)?;
+        self.limit_depth += 1;
+        Ok(old_limit)
     }
 
     /// Restore previous limit.
     pub fn pop_limit(&mut self, old_limit: u64) {
         self.source.pop_limit(old_limit);
+        self.limit_depth -= 1;
     }
 
     /// Are we at EOF?
+    ///
+    /// When reading group content, `END_GROUP` tag of the group is EOF.
     #[inline(always)]
     pub fn eof(&mut self) -> ProtobufResult<bool> {
-        self.source.eof()
+        match self.group {
+            Some(group) if group.limit_depth == self.limit_depth => self.group_eof(group),
+            _ => self.source.eof(),
+        }
+    }
+
+    fn group_eof(&mut self, group: GroupState) -> ProtobufResult<bool> {
+        if group.ended {
+            return Ok(true);
+        }
+        if self.pending_tag.is_some() {
+            return Ok(false);
+        }
+        // Truncated group is reported by `merge_group_with`
+        if self.source.eof()? {
+            return Ok(true);
+        }
+        let tag = self.read_tag()?;
+        let (field_number, wire_type) = tag.unpack();
+        if wire_type != wire_format::WireTypeEndGroup {
+            self.pending_tag = Some(tag);
+            return Ok(false);
+        }
+        if field_number != group.field_number {
+            return Err(ProtobufError::WireError(WireError::IncorrectTag(
+                tag.value(),
+            )));
+        }
+        self.group = Some(GroupState {
+            ended: true,
+            ..group
+        });
+        Ok(true)
     }
 
     /// Check we are at EOF.
@@ -288,6 +344,9 @@ impl<'a> CodedInputStream<'a> {
     /// Read tag
     #[inline]
     pub fn read_tag(&mut self) -> ProtobufResult<wire_format::Tag> {
+        if let Some(tag) = self.pending_tag.take() {
+            return Ok(tag);
+        }
         let v = self.read_raw_varint32()?;
         match wire_format::Tag::new(v) {
             Some(tag) => Ok(tag),
@@ -644,6 +703,77 @@ impl<'a> CodedInputStream<'a> {
         r.check_initialized()?;
         Ok(r)
     }
+
+    /// Read group content up to the matching `END_GROUP` tag
+    /// and merge it with given function.
+    ///
+    /// Group content is read in place: `eof` returns `true`
+    /// when `END_GROUP` tag of the group is reached.
+    fn merge_group_with<F>(&mut self, field_number: u32, merge: F) -> ProtobufResult<()>
+    where
+        F: FnOnce(&mut CodedInputStream) -> ProtobufResult<()>,
+    {
+        self.incr_recursion()?;
+        let outer_group = self.group.replace(GroupState {
+            field_number,
+            limit_depth: self.limit_depth,
+            ended: false,
+        });
+        let r = merge(self);
+        let group = mem::replace(&mut self.group, outer_group);
+        self.decr_recursion();
+        r?;
+        match group {
+            Some(GroupState { ended: true, .. }) => Ok(()),
+            _ => Err(ProtobufError::WireError(WireError::TruncatedMessage)),
+        }
+    }
+
+    /// Read group after `START_GROUP` tag of given field number,
+    /// do not check if message is initialized
+    pub fn merge_group<M: Message>(
+        &mut self,
+        field_number: u32,
+        message: &mut M,
+    ) -> ProtobufResult<()> {
+        self.merge_group_with(field_number, |is| message.merge_from(is))
+    }
+
+    /// Read group after `START_GROUP` tag of given field number
+    pub fn read_group<M: Message>(&mut self, field_number: u32) -> ProtobufResult<M> {
+        let mut r: M = Message::new();
+        self.merge_group(field_number, &mut r)?;
+        r.check_initialized()?;
+        Ok(r)
+    }
+
+    /// Read group after `START_GROUP` tag of given field number as unknown fields
+    pub fn read_unknown_group(&mut self, field_number: u32) -> ProtobufResult<UnknownFields> {
+        let mut r = UnknownFields::new();
+        self.merge_group_with(field_number, |is| {
+            while !is.eof()? {
+                let (number, wire_type) = is.read_tag_unpack()?;
+                let value = is.read_unknown_field(number, wire_type)?;
+                r.add_value(number, value);
+            }
+            Ok(())
+        })?;
+        Ok(r)
+    }
+
+    /// Read `UnknownValue` of given field, including groups
+    pub fn read_unknown_field(
+        &mut self,
+        field_number: u32,
+        wire_type: wire_format::WireType,
+    ) -> ProtobufResult<UnknownValue> {
+        match wire_type {
+            wire_format::WireTypeStartGroup => self
+                .read_unknown_group(field_number)
+                .map(UnknownValue::Group),
+            _ => self.read_unknown(wire_type),
+        }
+    }
 }
 
 impl<'a> Read for CodedInputStream<'a> {
@@ -1000,12 +1130,16 @@ impl<'a> CodedOutputStream<'a> {
     }
 
     /// Write unknown value
+    ///
+    /// Only content is written for groups, `END_GROUP` tag is written by
+    /// [`write_unknown`](Self::write_unknown).
     pub fn write_unknown_no_tag(&mut self, unknown: UnknownValueRef) -> ProtobufResult<()> {
         match unknown {
             UnknownValueRef::Fixed64(fixed64) => self.write_raw_little_endian64(fixed64),
             UnknownValueRef::Fixed32(fixed32) => self.write_raw_little_endian32(fixed32),
             UnknownValueRef::Varint(varint) => self.write_raw_varint64(varint),
             UnknownValueRef::LengthDelimited(bytes) => self.write_bytes_no_tag(bytes),
+            UnknownValueRef::Group(fields) => self.write_unknown_fields(fields),
         }
     }
 
@@ -1120,7 +1254,11 @@ impl<'a> CodedOutputStream<'a> {
         value: UnknownValueRef,
     ) -> ProtobufResult<()> {
         self.write_tag(field_number, value.wire_type())?;
+        let is_group = value.wire_type() == wire_format::WireTypeStartGroup;
         self.write_unknown_no_tag(value)?;
+        if is_group {
+            self.write_tag(field_number, wire_format::WireTypeEndGroup)?;
+        }
         Ok(())
     }
 
@@ -1171,6 +1309,14 @@ impl<'a> CodedOutputStream<'a> {
         self.write_message_no_tag(msg)?;
         Ok(())
     }
+
+    /// Write group field using sizes cached by previous `compute_size` call
+    pub fn write_group<M: Message>(&mut self, field_number: u32, msg: &M) -> ProtobufResult<()> {
+        self.write_tag(field_number, wire_format::WireTypeStartGroup)?;
+        msg.write_to_with_cached_sizes(self)?;
+        self.write_tag(field_number, wire_format::WireTypeEndGroup)?;
+        Ok(())
+    }
 }
 
 impl<'a> Write for CodedOutputStream<'a> {
@@ -1411,6 +1557,79 @@ mod test {
         assert!(is.eof().expect("eof"));
     }
 
+    #[test]
+    fn test_input_stream_read_unknown_group() {
+        // group 1 { field 2 varint 3, group 4 { field 5 fixed32 }, field 6 bytes }
+        let content = "10 03 23 2d 01 02 03 04 24 32 01 aa";
+        test_read(&format!("{} 0c", content), |is| {
+            let group = is.read_unknown_group(1).expect("read group");
+            assert_eq!(&[3], &group.get(2).unwrap().varint[..]);
+            assert_eq!(&[vec![0xaa]], &group.get(6).unwrap().length_delimited[..]);
+            assert_eq!(
+                decode_hex(content).len(),
+                crate::rt::unknown_fields_size(&group) as usize
+            );
+            assert_eq!(
+                crate::rt::unknown_fields_size(&group) as usize,
+                group.write_to_bytes().len()
+            );
+            let nested = &group.get(4).expect("nested group").groups;
+            assert_eq!(1, nested.len());
+            assert_eq!(&[0x04030201], &nested[0].get(5).unwrap().fixed32[..]);
+        });
+    }
+
+    #[test]
+    fn test_input_stream_read_unknown_group_mismatched_end() {
+        test_read_partial("10 03 14", |is| {
+            assert!(is.read_unknown_group(1).is_err());
+        });
+    }
+
+    #[test]
+    fn test_input_stream_read_unknown_group_truncated() {
+        test_read_partial("10 03", |is| {
+            assert!(is.read_unknown_group(1).is_err());
+        });
+    }
+
+    #[test]
+    fn test_input_stream_read_unknown_group_recursion_limit() {
+        test_read_partial("0b 0b 0b 0c 0c 0c", |is| {
+            is.set_recursion_limit(2);
+            assert!(is.read_unknown_group(1).is_err());
+        });
+    }
+
+    #[test]
+    fn test_input_stream_read_group_with_nested_limit() {
+        // group 1 { field 2 packed [1, 2] } field 3 varint 4
+        test_read("12 02 01 02 0c 18 04", |is| {
+            is.merge_group_with(1, |is| {
+                assert_eq!(
+                    (2, wire_format::WireTypeLengthDelimited),
+                    is.read_tag_unpack()?
+                );
+                let len = is.read_raw_varint64()?;
+                let old_limit = is.push_limit(len)?;
+                let mut values = Vec::new();
+                while !is.eof()? {
+                    values.push(is.read_raw_varint32()?);
+                }
+                is.pop_limit(old_limit);
+                assert_eq!(vec![1, 2], values);
+                assert!(is.eof()?);
+                Ok(())
+            })
+            .expect("read group");
+            assert_eq!(
+                (3, wire_format::WireTypeVarint),
+                is.read_tag_unpack().unwrap()
+            );
+            assert_eq!(4, is.read_raw_varint32().unwrap());
+        });
+    }
+
     fn test_write<F>(expected: &str, mut gen: F)
     where
         F: FnMut(&mut CodedOutputStream) -> ProtobufResult<()>,
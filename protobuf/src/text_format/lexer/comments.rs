@@ -0,0 +1,72 @@
+use std::mem;
+
+/// Comments found between two tokens, attached the same way `protoc` does.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Comments {
+    /// Comment after the previous token, on the same line or the line right below it
+    pub trailing: Option<String>,
+    /// Comments attached neither to the previous nor to the next token
+    pub detached: Vec<String>,
+    /// Comment right before the next token
+    pub leading: Option<String>,
+}
+
+/// Port of `protoc` tokenizer `CommentCollector`.
+#[derive(Default)]
+pub(crate) struct CommentCollector {
+    comments: Comments,
+    buffer: String,
+    has_comment: bool,
+    is_line_comment: bool,
+    detached_from_prev: bool,
+}
+
+impl CommentCollector {
+    /// Consecutive line comments are merged into one comment
+    pub fn line_comment(&mut self, content: &str) {
+        if self.has_comment && !self.is_line_comment {
+            self.flush();
+        }
+        self.has_comment = true;
+        self.is_line_comment = true;
+        self.buffer.push_str(content);
+    }
+
+    pub fn block_comment(&mut self, content: &str) {
+        self.flush();
+        self.has_comment = true;
+        self.is_line_comment = false;
+        self.buffer.push_str(content);
+    }
+
+    pub fn clear_buffer(&mut self) {
+        self.buffer.clear();
+        self.has_comment = false;
+    }
+
+    /// Comment in the buffer is complete and cannot be attached to the next token
+    pub fn flush(&mut self) {
+        if self.has_comment {
+            let comment = mem::take(&mut self.buffer);
+            if self.detached_from_prev {
+                self.comments.detached.push(comment);
+            } else {
+                self.comments.trailing = Some(comment);
+                self.detached_from_prev = true;
+            }
+            self.has_comment = false;
+        }
+    }
+
+    pub fn detach_from_prev(&mut self) {
+        self.detached_from_prev = true;
+    }
+
+    /// Comment left in the buffer is attached to the next token
+    pub fn finish(mut self) -> Comments {
+        if self.has_comment {
+            self.comments.leading = Some(mem::take(&mut self.buffer));
+        }
+        self.comments
+    }
+}
@@ -5,6 +5,8 @@ use std::fmt;
 use std::num::ParseFloatError;
 use std::num::ParseIntError;
 
+use super::comments::CommentCollector;
+use super::comments::Comments;
 use super::float;
 use super::loc::Loc;
 use super::loc::FIRST_COL;
@@ -104,6 +106,11 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Byte offset of the next char in the input
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
     /// No more chars
     pub fn eof(&self) -> bool {
         self.pos == self.input.len()
@@ -214,6 +221,112 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Skip whitespaces except newline
+    fn skip_whitespaces_no_newline(&mut self) {
+        self.take_while(|c| c != '\n' && c.is_whitespace());
+    }
+
+    /// Content of `//` comment including trailing newline,
+    /// comment start must be already consumed
+    fn next_line_comment_content(&mut self) -> &'a str {
+        let start = self.pos;
+        self.take_while(|c| c != '\n');
+        self.next_char_if_eq('\n');
+        &self.input[start..self.pos]
+    }
+
+    /// Content of `/* */` comment without leading `*` of continuation lines,
+    /// comment start must be already consumed
+    fn next_block_comment_content(&mut self) -> LexerResult<String> {
+        let mut content = String::new();
+        let mut start = self.pos;
+        loop {
+            self.take_while(|c| c != '*' && c != '/' && c != '\n');
+            if self.next_char_if_eq('\n') {
+                content.push_str(&self.input[start..self.pos]);
+                self.skip_whitespaces_no_newline();
+                if self.next_char_if_eq('*') && self.next_char_if_eq('/') {
+                    return Ok(content);
+                }
+                start = self.pos;
+            } else if self.lookahead_is_str("*/") {
+                content.push_str(&self.input[start..self.pos]);
+                self.skip_if_lookahead_is_str("*/");
+                return Ok(content);
+            } else if self.next_char_opt().is_none() {
+                return Err(LexerError::UnexpectedEof);
+            }
+        }
+    }
+
+    /// Collect comments up to the next token like `protoc` does.
+    ///
+    /// `after_token` is `false` at the beginning of the input,
+    /// `before_scope_end` is `true` if the next token closes a scope or there's no next token.
+    fn next_comments(
+        &mut self,
+        after_token: bool,
+        before_scope_end: bool,
+    ) -> LexerResult<Comments> {
+        let mut collector = CommentCollector::default();
+
+        if after_token {
+            self.skip_whitespaces_no_newline();
+            if self.skip_if_lookahead_is_str("//") {
+                collector.line_comment(self.next_line_comment_content());
+                collector.flush();
+            } else if self.skip_if_lookahead_is_str("/*") {
+                collector.block_comment(&self.next_block_comment_content()?);
+                self.skip_whitespaces_no_newline();
+                if !self.next_char_if_eq('\n') {
+                    // Comment is followed by a token on the same line,
+                    // it is not attached to anything
+                    collector.clear_buffer();
+                    return Ok(collector.finish());
+                }
+                collector.flush();
+            } else if !self.next_char_if_eq('\n') {
+                return Ok(collector.finish());
+            }
+        } else {
+            collector.detach_from_prev();
+        }
+
+        loop {
+            self.skip_whitespaces_no_newline();
+            if self.skip_if_lookahead_is_str("//") {
+                collector.line_comment(self.next_line_comment_content());
+            } else if self.skip_if_lookahead_is_str("/*") {
+                collector.block_comment(&self.next_block_comment_content()?);
+                self.skip_whitespaces_no_newline();
+                self.next_char_if_eq('\n');
+            } else if self.next_char_if_eq('\n') {
+                // Blank line
+                collector.flush();
+                collector.detach_from_prev();
+            } else {
+                if before_scope_end {
+                    collector.flush();
+                }
+                return Ok(collector.finish());
+            }
+        }
+    }
+
+    /// Comments in the `pos..end_pos` range of the input
+    /// which contains only whitespaces and comments.
+    /// See `next_comments` for the meaning of flags.
+    pub fn comments_between(
+        &self,
+        pos: usize,
+        end_pos: usize,
+        after_token: bool,
+        before_scope_end: bool,
+    ) -> LexerResult<Comments> {
+        Lexer::new(&self.input[pos..end_pos], self.language)
+            .next_comments(after_token, before_scope_end)
+    }
+
     pub fn take_while<F>(&mut self, f: F) -> &'a str
     where
         F: Fn(char) -> bool,
@@ -665,15 +778,22 @@ impl<'a> Lexer<'a> {
     pub fn next_token(&mut self) -> LexerResult<Option<TokenWithLocation>> {
         self.skip_ws()?;
         let loc = self.loc;
+        let pos = self.pos;
 
         Ok(if self.eof() {
             None
         } else {
             let token = self.next_token_inner()?;
+            let end_pos = self.pos;
             // Skip whitespace here to update location
             // to the beginning of the next token
             self.skip_ws()?;
-            Some(TokenWithLocation { token, loc })
+            Some(TokenWithLocation {
+                token,
+                loc,
+                pos,
+                end_pos,
+            })
         })
     }
 }
@@ -716,4 +836,25 @@ mod test {
         let mess = lex(msg, |p| p.next_token_inner());
         assert_eq!(Token::FloatLit(12.3), mess);
     }
+
+    #[test]
+    fn test_lexer_comments() {
+        let msg = " // trailing\n\n/* detached */\n\n// leading\n// more\n";
+        let mess = lex(msg, |p| p.next_comments(true, false));
+        assert_eq!(
+            Comments {
+                trailing: Some(" trailing\n".to_owned()),
+                detached: vec![" detached ".to_owned()],
+                leading: Some(" leading\n more\n".to_owned()),
+            },
+            mess
+        );
+    }
+
+    #[test]
+    fn test_lexer_block_comment() {
+        let msg = "\n/*\n * first\n * second\n */\n";
+        let mess = lex(msg, |p| p.next_comments(true, false));
+        assert_eq!(Some("\n first\n second\n".to_owned()), mess.leading);
+    }
 }
@@ -1,5 +1,6 @@
 //! Implementation of lexer for both protobuf parser and for text format parser.
 
+mod comments;
 pub mod float;
 pub mod int;
 mod json_number_lit;
@@ -11,6 +12,7 @@ mod str_lit;
 mod token;
 mod tokenizer;
 
+pub use self::comments::Comments;
 pub use self::json_number_lit::JsonNumberLit;
 pub use self::lexer_impl::Lexer;
 pub use self::lexer_impl::LexerError;
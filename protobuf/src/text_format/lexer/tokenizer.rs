@@ -1,3 +1,4 @@
+use crate::text_format::lexer::Comments;
 use crate::text_format::lexer::Lexer;
 use crate::text_format::lexer::LexerError;
 use crate::text_format::lexer::Loc;
@@ -71,6 +72,8 @@ pub struct Tokenizer<'a> {
     lexer: Lexer<'a>,
     next_token: Option<TokenWithLocation>,
     last_token_loc: Option<Loc>,
+    /// End of the last consumed token
+    prev_token_end_pos: Option<usize>,
 }
 
 impl<'a> Tokenizer<'a> {
@@ -79,6 +82,7 @@ impl<'a> Tokenizer<'a> {
             lexer: Lexer::new(input, comment_style),
             next_token: None,
             last_token_loc: None,
+            prev_token_end_pos: None,
         }
     }
 
@@ -99,6 +103,45 @@ impl<'a> Tokenizer<'a> {
         self.loc()
     }
 
+    /// Byte offset of the next token in the input, or input length at EOF
+    pub fn lookahead_pos(&mut self) -> TokenizerResult<usize> {
+        self.lookahead()?;
+        Ok(match self.next_token {
+            Some(ref token) => token.pos,
+            None => self.lexer.pos(),
+        })
+    }
+
+    /// Byte offset of the end of the last consumed token
+    pub fn prev_token_end_pos(&self) -> usize {
+        self.prev_token_end_pos.unwrap_or(0)
+    }
+
+    /// Comments between the last consumed token and the next token
+    pub fn next_comments(&mut self) -> TokenizerResult<Comments> {
+        let before_scope_end = match self.lookahead()? {
+            None => true,
+            Some(Token::Symbol(c)) => *c == '}' || *c == ']' || *c == ')',
+            Some(_) => false,
+        };
+        let end_pos = self.lookahead_pos()?;
+        Ok(self.lexer.comments_between(
+            self.prev_token_end_pos(),
+            end_pos,
+            self.prev_token_end_pos.is_some(),
+            before_scope_end,
+        )?)
+    }
+
+    fn take_next_token(&mut self) -> Option<Token> {
+        self.next_token
+            .take()
+            .map(|TokenWithLocation { token, end_pos, .. }| {
+                self.prev_token_end_pos = Some(end_pos);
+                token
+            })
+    }
+
     fn lookahead(&mut self) -> TokenizerResult<Option<&Token>> {
         Ok(match self.next_token {
             Some(ref token) => Some(&token.token),
@@ -122,10 +165,7 @@ impl<'a> Tokenizer<'a> {
 
     fn next(&mut self) -> TokenizerResult<Option<Token>> {
         self.lookahead()?;
-        Ok(self
-            .next_token
-            .take()
-            .map(|TokenWithLocation { token, .. }| token))
+        Ok(self.take_next_token())
     }
 
     pub fn next_some(&mut self) -> TokenizerResult<Token> {
@@ -137,10 +177,7 @@ impl<'a> Tokenizer<'a> {
 
     /// Can be called only after lookahead, otherwise it's error
     pub fn advance(&mut self) -> TokenizerResult<Token> {
-        self.next_token
-            .take()
-            .map(|TokenWithLocation { token, .. }| token)
-            .ok_or(TokenizerError::InternalError)
+        self.take_next_token().ok_or(TokenizerError::InternalError)
     }
 
     /// No more tokens
@@ -160,7 +197,7 @@ impl<'a> Tokenizer<'a> {
             },
             _ => return Ok(None),
         };
-        self.next_token = None;
+        self.take_next_token();
         Ok(Some(v))
     }
 
@@ -174,7 +211,7 @@ impl<'a> Tokenizer<'a> {
             Some(ref token) => p(&token.token)?,
             None => return Err(TokenizerError::UnexpectedEof.into()),
         };
-        self.next_token = None;
+        self.take_next_token();
         Ok(r)
     }
 
@@ -31,6 +31,7 @@ pub mod lexer;
 pub use self::print::fmt;
 pub use self::print::print_to;
 pub use self::print::print_to_string;
+pub(crate) use self::print::print_value_to;
 #[doc(hidden)]
 pub use self::print::quote_bytes_to;
 #[doc(hidden)]
@@ -39,3 +40,24 @@ pub use self::print::quote_escape_bytes;
 pub use self::parse::merge_from_str;
 pub use self::parse::parse_from_str;
 pub use self::parse::ParseError;
+
+use crate::descriptor::field_descriptor_proto;
+use crate::reflect::FieldDescriptor;
+
+/// Field name in text format.
+///
+/// Group fields are named after the group message type (e. g. `MyGroup`),
+/// other fields use field name.
+fn text_format_field_name(field: &FieldDescriptor) -> &str {
+    let proto = field.get_proto();
+    match proto.get_field_type() {
+        field_descriptor_proto::Type::TYPE_GROUP => {
+            let type_name = proto.get_type_name();
+            match type_name.rfind('.') {
+                Some(pos) => &type_name[pos + 1..],
+                None => type_name,
+            }
+        }
+        _ => field.get_name(),
+    }
+}
@@ -4,6 +4,7 @@ use std::str;
 use crate::message::Message;
 
 use crate::message_dyn::MessageDyn;
+use crate::reflect::ConversionError;
 use crate::reflect::EnumDescriptor;
 use crate::reflect::EnumValueDescriptor;
 use crate::reflect::MessageDescriptor;
@@ -16,6 +17,7 @@ use crate::text_format::lexer::ParserLanguage;
 use crate::text_format::lexer::StrLitDecodeError;
 use crate::text_format::lexer::Tokenizer;
 use crate::text_format::lexer::TokenizerError;
+use crate::text_format::text_format_field_name;
 
 #[derive(Debug)]
 pub enum ParseErrorWithoutLoc {
@@ -27,6 +29,7 @@ pub enum ParseErrorWithoutLoc {
     IntegerOverflow,
     ExpectingBool,
     MessageNotInitialized,
+    ConversionError(ConversionError),
 }
 
 impl From<TokenizerError> for ParseErrorWithoutLoc {
@@ -41,6 +44,12 @@ impl From<StrLitDecodeError> for ParseErrorWithoutLoc {
     }
 }
 
+impl From<ConversionError> for ParseErrorWithoutLoc {
+    fn from(e: ConversionError) -> Self {
+        ParseErrorWithoutLoc::ConversionError(e)
+    }
+}
+
 impl From<int::Overflow> for ParseErrorWithoutLoc {
     fn from(_: int::Overflow) -> Self {
         ParseErrorWithoutLoc::IntegerOverflow
@@ -260,7 +269,11 @@ impl<'a> Parser<'a> {
     ) -> ParseResult<()> {
         let field_name = self.next_field_name()?;
 
-        let field = match descriptor.get_field_by_name(&field_name) {
+        let field = match descriptor
+            .get_field_by_name(&field_name)
+            .or_else(|| descriptor.get_field_by_name(&field_name.to_lowercase()))
+            .filter(|f| text_format_field_name(f) == field_name || f.get_name() == field_name)
+        {
             Some(field) => field,
             None => {
                 // TODO: shouldn't unknown fields be quietly skipped?
@@ -271,15 +284,15 @@ impl<'a> Parser<'a> {
         match field.runtime_field_type() {
             RuntimeFieldType::Singular(t) => {
                 let value = self.read_value_of_type(&t)?;
-                field.set_singular_field(message, value);
+                field.try_set_singular_field(message, value)?;
             }
             RuntimeFieldType::Repeated(t) => {
                 let value = self.read_value_of_type(&t)?;
-                field.mut_repeated(message).push(value);
+                field.mut_repeated(message).try_push(value)?;
             }
             RuntimeFieldType::Map(k, v) => {
                 let (k, v) = self.read_map_entry(&k, &v)?;
-                field.mut_map(message).insert(k, v);
+                field.mut_map(message).try_insert(k, v)?;
             }
         };
 
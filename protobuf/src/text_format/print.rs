@@ -5,6 +5,9 @@ use crate::message_dyn::MessageDyn;
 use crate::reflect::MessageRef;
 use crate::reflect::ReflectFieldRef;
 use crate::reflect::ReflectValueRef;
+use crate::text_format::text_format_field_name;
+use crate::UnknownFields;
+use crate::UnknownValueRef;
 
 #[doc(hidden)]
 pub fn quote_bytes_to(bytes: &[u8], buf: &mut String) {
@@ -98,55 +101,48 @@ fn print_field<F: FieldName>(
             do_indent(buf, pretty, indent);
             buf.push_str("}");
         }
-        ReflectValueRef::Enum(d, v) => {
+        value => {
             buf.push_str(": ");
-            match d.get_value_by_number(v) {
-                Some(e) => buf.push_str(e.get_name()),
-                None => write!(buf, ": {}", v).unwrap(),
-            }
-        }
-        ReflectValueRef::String(s) => {
-            buf.push_str(": ");
-            print_str_to(s, buf);
-        }
-        ReflectValueRef::Bytes(b) => {
-            buf.push_str(": ");
-            quote_escape_bytes_to(b, buf);
-        }
-        ReflectValueRef::I32(v) => {
-            write!(buf, ": {}", v).unwrap();
-        }
-        ReflectValueRef::I64(v) => {
-            write!(buf, ": {}", v).unwrap();
-        }
-        ReflectValueRef::U32(v) => {
-            write!(buf, ": {}", v).unwrap();
-        }
-        ReflectValueRef::U64(v) => {
-            write!(buf, ": {}", v).unwrap();
-        }
-        ReflectValueRef::Bool(v) => {
-            write!(buf, ": {}", v).unwrap();
-        }
-        ReflectValueRef::F32(v) => {
-            write!(buf, ": {}", v).unwrap();
-        }
-        ReflectValueRef::F64(v) => {
-            write!(buf, ": {}", v).unwrap();
+            print_value_to(&value, buf);
         }
     }
 
     print_end_field(buf, pretty);
 }
 
+/// Print a value without field name, message values are printed in braces.
+pub(crate) fn print_value_to(value: &ReflectValueRef, buf: &mut String) {
+    match value {
+        ReflectValueRef::Message(m) => {
+            buf.push('{');
+            print_to_internal(m, buf, false, 0);
+            buf.push('}');
+        }
+        ReflectValueRef::Enum(d, v) => match d.get_value_by_number(*v) {
+            Some(e) => buf.push_str(e.get_name()),
+            None => write!(buf, "{}", v).unwrap(),
+        },
+        ReflectValueRef::String(s) => print_str_to(s, buf),
+        ReflectValueRef::Bytes(b) => quote_escape_bytes_to(b, buf),
+        ReflectValueRef::I32(v) => write!(buf, "{}", v).unwrap(),
+        ReflectValueRef::I64(v) => write!(buf, "{}", v).unwrap(),
+        ReflectValueRef::U32(v) => write!(buf, "{}", v).unwrap(),
+        ReflectValueRef::U64(v) => write!(buf, "{}", v).unwrap(),
+        ReflectValueRef::Bool(v) => write!(buf, "{}", v).unwrap(),
+        ReflectValueRef::F32(v) => write!(buf, "{}", v).unwrap(),
+        ReflectValueRef::F64(v) => write!(buf, "{}", v).unwrap(),
+    }
+}
+
 fn print_to_internal(m: &MessageRef, buf: &mut String, pretty: bool, indent: usize) {
     let d = m.descriptor_dyn();
     let mut first = true;
     for f in d.fields() {
+        let name = text_format_field_name(&f);
         match f.get_reflect(&**m) {
             ReflectFieldRef::Map(map) => {
                 for (k, v) in &map {
-                    print_start_field(buf, pretty, indent, &mut first, f.get_name());
+                    print_start_field(buf, pretty, indent, &mut first, name);
                     buf.push_str(" {");
                     if pretty {
                         buf.push_str("\n");
@@ -164,25 +160,51 @@ fn print_to_internal(m: &MessageRef, buf: &mut String, pretty: bool, indent: usi
             ReflectFieldRef::Repeated(repeated) => {
                 // TODO: do not print zeros for v3
                 for v in repeated {
-                    print_field(buf, pretty, indent, &mut first, f.get_name(), v);
+                    print_field(buf, pretty, indent, &mut first, name, v);
                 }
             }
             ReflectFieldRef::Optional(optional) => {
                 if let Some(v) = optional {
-                    print_field(buf, pretty, indent, &mut first, f.get_name(), v);
+                    print_field(buf, pretty, indent, &mut first, name, v);
                 }
             }
         }
     }
 
-    let unknown_fields = m.get_unknown_fields_dyn();
-    let mut numbers: Vec<u32> = m.get_unknown_fields_dyn().iter().map(|(n, _)| n).collect();
+    print_unknown_fields(m.get_unknown_fields_dyn(), buf, pretty, indent, &mut first);
+}
+
+fn print_unknown_fields(
+    unknown_fields: &UnknownFields,
+    buf: &mut String,
+    pretty: bool,
+    indent: usize,
+    first: &mut bool,
+) {
+    let mut numbers: Vec<u32> = unknown_fields.iter().map(|(n, _)| n).collect();
     // Sort for stable output
     numbers.sort();
     for &n in &numbers {
         for v in unknown_fields.get(n).unwrap() {
-            // TODO: try decode nested message for length-delimited
-            print_field(buf, pretty, indent, &mut first, n, v.to_reflect_value_ref());
+            match v {
+                UnknownValueRef::Group(group) => {
+                    print_start_field(buf, pretty, indent, first, n);
+                    buf.push_str(" {");
+                    if pretty {
+                        buf.push('\n');
+                    }
+                    let mut group_first = true;
+                    print_unknown_fields(group, buf, pretty, indent + 1, &mut group_first);
+                    do_indent(buf, pretty, indent);
+                    buf.push('}');
+                    print_end_field(buf, pretty);
+                }
+                // TODO: try decode nested message for length-delimited
+                v => {
+                    let value = v.to_reflect_value_ref().unwrap();
+                    print_field(buf, pretty, indent, first, n, value);
+                }
+            }
         }
     }
 }
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::collections::hash_map;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
@@ -28,6 +29,8 @@ pub enum UnknownValue {
     Varint(u64),
     /// Length-delimited unknown (e. g. `message` or `string`)
     LengthDelimited(Vec<u8>),
+    /// Group unknown, content is stored as unknown fields
+    Group(UnknownFields),
 }
 
 impl UnknownValue {
@@ -43,6 +46,7 @@ impl UnknownValue {
             UnknownValue::Fixed64(fixed64) => UnknownValueRef::Fixed64(fixed64),
             UnknownValue::Varint(varint) => UnknownValueRef::Varint(varint),
             UnknownValue::LengthDelimited(ref bytes) => UnknownValueRef::LengthDelimited(&bytes),
+            UnknownValue::Group(ref fields) => UnknownValueRef::Group(fields),
         }
     }
 
@@ -99,6 +103,8 @@ pub enum UnknownValueRef<'o> {
     Varint(u64),
     /// Length-delimited unknown
     LengthDelimited(&'o [u8]),
+    /// Group unknown
+    Group(&'o UnknownFields),
 }
 
 impl<'o> UnknownValueRef<'o> {
@@ -109,15 +115,31 @@ impl<'o> UnknownValueRef<'o> {
             UnknownValueRef::Fixed64(_) => wire_format::WireTypeFixed64,
             UnknownValueRef::Varint(_) => wire_format::WireTypeVarint,
             UnknownValueRef::LengthDelimited(_) => wire_format::WireTypeLengthDelimited,
+            UnknownValueRef::Group(_) => wire_format::WireTypeStartGroup,
         }
     }
 
-    pub(crate) fn to_reflect_value_ref(&'o self) -> ReflectValueRef<'o> {
+    /// Clone the value.
+    pub fn to_box(&self) -> UnknownValue {
+        match *self {
+            UnknownValueRef::Fixed32(fixed32) => UnknownValue::Fixed32(fixed32),
+            UnknownValueRef::Fixed64(fixed64) => UnknownValue::Fixed64(fixed64),
+            UnknownValueRef::Varint(varint) => UnknownValue::Varint(varint),
+            UnknownValueRef::LengthDelimited(bytes) => {
+                UnknownValue::LengthDelimited(bytes.to_vec())
+            }
+            UnknownValueRef::Group(fields) => UnknownValue::Group(fields.clone()),
+        }
+    }
+
+    /// Scalar value, `None` for groups.
+    pub(crate) fn to_reflect_value_ref(&'o self) -> Option<ReflectValueRef<'o>> {
         match self {
-            UnknownValueRef::Fixed32(v) => ReflectValueRef::U32(*v),
-            UnknownValueRef::Fixed64(v) => ReflectValueRef::U64(*v),
-            UnknownValueRef::Varint(v) => ReflectValueRef::U64(*v),
-            UnknownValueRef::LengthDelimited(v) => ReflectValueRef::Bytes(v),
+            UnknownValueRef::Fixed32(v) => Some(ReflectValueRef::U32(*v)),
+            UnknownValueRef::Fixed64(v) => Some(ReflectValueRef::U64(*v)),
+            UnknownValueRef::Varint(v) => Some(ReflectValueRef::U64(*v)),
+            UnknownValueRef::LengthDelimited(v) => Some(ReflectValueRef::Bytes(v)),
+            UnknownValueRef::Group(_) => None,
         }
     }
 }
@@ -125,7 +147,7 @@ impl<'o> UnknownValueRef<'o> {
 /// Field unknown values.
 ///
 /// See [`UnknownFields`](crate::UnknownFields) for explanations.
-#[derive(Clone, PartialEq, Eq, Debug, Default, Hash)]
+#[derive(Clone, PartialEq, Eq, Debug, Default, Hash, PartialOrd, Ord)]
 pub struct UnknownValues {
     /// 32-bit unknowns
     pub fixed32: Vec<u32>,
@@ -135,6 +157,8 @@ pub struct UnknownValues {
     pub varint: Vec<u64>,
     /// Length-delimited unknowns
     pub length_delimited: Vec<Vec<u8>>,
+    /// Group unknowns
+    pub groups: Vec<UnknownFields>,
 }
 
 impl UnknownValues {
@@ -147,6 +171,7 @@ impl UnknownValues {
             UnknownValue::LengthDelimited(length_delimited) => {
                 self.length_delimited.push(length_delimited)
             }
+            UnknownValue::Group(group) => self.groups.push(group),
         };
     }
 
@@ -157,6 +182,7 @@ impl UnknownValues {
             fixed64: self.fixed64.iter(),
             varint: self.varint.iter(),
             length_delimited: self.length_delimited.iter(),
+            groups: self.groups.iter(),
         }
     }
 }
@@ -176,6 +202,7 @@ pub struct UnknownValuesIter<'o> {
     fixed64: slice::Iter<'o, u64>,
     varint: slice::Iter<'o, u64>,
     length_delimited: slice::Iter<'o, Vec<u8>>,
+    groups: slice::Iter<'o, UnknownFields>,
 }
 
 impl<'o> Iterator for UnknownValuesIter<'o> {
@@ -198,6 +225,9 @@ impl<'o> Iterator for UnknownValuesIter<'o> {
         if length_delimited.is_some() {
             return Some(UnknownValueRef::LengthDelimited(&length_delimited.unwrap()));
         }
+        if let Some(group) = self.groups.next() {
+            return Some(UnknownValueRef::Group(group));
+        }
         None
     }
 }
@@ -238,6 +268,27 @@ impl Hash for UnknownFields {
     }
 }
 
+impl PartialOrd for UnknownFields {
+    fn partial_cmp(&self, other: &UnknownFields) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Unknown fields are compared as lists of entries sorted by field number,
+/// so the order is consistent with `Eq`.
+impl Ord for UnknownFields {
+    fn cmp(&self, other: &UnknownFields) -> Ordering {
+        fn sorted(fields: &UnknownFields) -> Option<Vec<(&u32, &UnknownValues)>> {
+            fields.fields.as_ref().map(|map| {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by_key(|(k, _)| **k);
+                entries
+            })
+        }
+        sorted(self).cmp(&sorted(other))
+    }
+}
+
 impl UnknownFields {
     /// Empty unknown fields
     pub const fn new() -> UnknownFields {
@@ -281,6 +332,11 @@ impl UnknownFields {
             .push(length_delimited);
     }
 
+    /// Add unknown group
+    pub fn add_group(&mut self, number: u32, group: UnknownFields) {
+        self.find_field(&number).groups.push(group);
+    }
+
     /// Add unknown value
     pub fn add_value(&mut self, number: u32, value: UnknownValue) {
         self.find_field(&number).add_value(value);
@@ -116,7 +116,7 @@ impl crate::Message for Any {
                     self.value = is.read_bytes()?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
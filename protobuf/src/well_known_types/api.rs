@@ -193,7 +193,7 @@ impl crate::Message for Api {
                     self.syntax = is.read_enum_or_unknown()?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -455,7 +455,7 @@ impl crate::Message for Method {
                     self.syntax = is.read_enum_or_unknown()?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -647,7 +647,7 @@ impl crate::Message for Mixin {
                     self.root = is.read_string()?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
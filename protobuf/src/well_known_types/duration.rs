@@ -96,7 +96,7 @@ impl crate::Message for Duration {
                     self.nanos = is.read_int32()?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -82,7 +82,7 @@ impl crate::Message for Struct {
                     crate::rt::read_map_into::<crate::reflect::types::ProtobufTypeString, crate::reflect::types::ProtobufTypeMessage<Value>>(wire_type, is, &mut self.fields)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -406,24 +406,28 @@ impl Value {
             Value::has_null_value,
             Value::get_null_value,
             Value::set_null_value,
+            Value::clear_null_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_copy_has_get_set_simpler_accessors::<_, _>(
             "number_value",
             Value::has_number_value,
             Value::get_number_value,
             Value::set_number_value,
+            Value::clear_number_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_deref_has_get_set_simpler_accessor::<_, _>(
             "string_value",
             Value::has_string_value,
             Value::get_string_value,
             Value::set_string_value,
+            Value::clear_string_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_copy_has_get_set_simpler_accessors::<_, _>(
             "bool_value",
             Value::has_bool_value,
             Value::get_bool_value,
             Value::set_bool_value,
+            Value::clear_bool_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, Struct>(
             "struct_value",
@@ -431,6 +435,7 @@ impl Value {
             Value::get_struct_value,
             Value::mut_struct_value,
             Value::set_struct_value,
+            Value::clear_struct_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, ListValue>(
             "list_value",
@@ -438,6 +443,7 @@ impl Value {
             Value::get_list_value,
             Value::mut_list_value,
             Value::set_list_value,
+            Value::clear_list_value,
         ));
         crate::reflect::GeneratedMessageDescriptorData::new_2::<Value>(
             "Value",
@@ -503,7 +509,7 @@ impl crate::Message for Value {
                     self.kind = ::std::option::Option::Some(value::Kind::list_value(is.read_message()?));
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -700,7 +706,7 @@ impl crate::Message for ListValue {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.values)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -145,7 +145,7 @@ impl crate::Message for Type {
                     self.syntax = is.read_enum_or_unknown()?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -439,7 +439,7 @@ impl crate::Message for Field {
                     self.default_value = is.read_string()?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -860,7 +860,7 @@ impl crate::Message for Enum {
                     self.syntax = is.read_enum_or_unknown()?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -1053,7 +1053,7 @@ impl crate::Message for EnumValue {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.options)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -1222,7 +1222,7 @@ impl crate::Message for Option {
                     crate::rt::read_singular_message_into_field(wire_type, is, &mut self.value)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_field_into(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
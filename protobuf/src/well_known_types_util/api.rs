@@ -0,0 +1,152 @@
+use crate::descriptor::MethodDescriptorProto;
+use crate::descriptor::ServiceDescriptorProto;
+use crate::reflect::FileDescriptor;
+use crate::well_known_types::Api;
+use crate::well_known_types::Method;
+use crate::well_known_types_util::type_pb::options_to_type_options;
+use crate::well_known_types_util::type_pb::simple_name;
+use crate::well_known_types_util::type_pb::source_context_of_file;
+use crate::well_known_types_util::type_pb::syntax_of_file;
+use crate::well_known_types_util::type_pb::type_name_from_type_url;
+use crate::well_known_types_util::type_pb::type_options_to_options;
+use crate::well_known_types_util::type_pb::type_url;
+use crate::ProtobufEnumOrUnknown;
+
+impl Api {
+    /// Describe a service in `google.protobuf.Api` form.
+    ///
+    /// `service` must be one of the services of `file`.
+    /// Request and response types are referenced by type URL
+    /// with `type.googleapis.com` prefix.
+    pub fn from_service_descriptor_proto(
+        file: &FileDescriptor,
+        service: &ServiceDescriptorProto,
+    ) -> Api {
+        let file = file.proto();
+        let syntax = ProtobufEnumOrUnknown::new(syntax_of_file(file));
+        let name = match file.get_package() {
+            "" => service.get_name().to_owned(),
+            package => format!("{}.{}", package, service.get_name()),
+        };
+        Api {
+            name,
+            methods: service
+                .method
+                .iter()
+                .map(|m| Method {
+                    name: m.get_name().to_owned(),
+                    request_type_url: type_url(m.get_input_type()),
+                    request_streaming: m.get_client_streaming(),
+                    response_type_url: type_url(m.get_output_type()),
+                    response_streaming: m.get_server_streaming(),
+                    options: options_to_type_options(m.options.get_or_default()),
+                    syntax,
+                    ..Default::default()
+                })
+                .collect(),
+            options: options_to_type_options(service.options.get_or_default()),
+            source_context: source_context_of_file(file),
+            syntax,
+            ..Default::default()
+        }
+    }
+
+    /// Describe all services of a file in `google.protobuf.Api` form.
+    pub fn from_file_descriptor(file: &FileDescriptor) -> Vec<Api> {
+        file.proto()
+            .service
+            .iter()
+            .map(|s| Api::from_service_descriptor_proto(file, s))
+            .collect()
+    }
+
+    /// Convert this API back to a service descriptor proto.
+    ///
+    /// Descriptor name is the last component of the API name,
+    /// `version` and `mixins` are not representable in a descriptor and ignored.
+    pub fn to_service_descriptor_proto(&self) -> ServiceDescriptorProto {
+        let mut r = ServiceDescriptorProto::new();
+        r.set_name(simple_name(&self.name).to_owned());
+        r.method = self
+            .methods
+            .iter()
+            .map(|m| {
+                let mut method = MethodDescriptorProto::new();
+                method.set_name(m.name.clone());
+                method.set_input_type(type_name_from_type_url(&m.request_type_url));
+                method.set_output_type(type_name_from_type_url(&m.response_type_url));
+                if m.request_streaming {
+                    method.set_client_streaming(true);
+                }
+                if m.response_streaming {
+                    method.set_server_streaming(true);
+                }
+                method.options = type_options_to_options(&m.options);
+                method
+            })
+            .collect();
+        r.options = type_options_to_options(&self.options);
+        r
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::Syntax;
+
+    fn test_file() -> FileDescriptor {
+        let mut method = MethodDescriptorProto::new();
+        method.set_name("Get".to_owned());
+        method.set_input_type(".google.protobuf.StringValue".to_owned());
+        method.set_output_type(".google.protobuf.Struct".to_owned());
+        method.set_server_streaming(true);
+        method.options.mut_or_default().set_deprecated(true);
+
+        let mut service = ServiceDescriptorProto::new();
+        service.set_name("Store".to_owned());
+        service.method.push(method);
+
+        let mut file = FileDescriptorProto::new();
+        file.set_name("store.proto".to_owned());
+        file.set_package("test.store".to_owned());
+        file.set_syntax("proto3".to_owned());
+        file.service.push(service);
+
+        FileDescriptor::new_dynamic(file, Vec::new())
+    }
+
+    #[test]
+    fn api_from_service() {
+        let file = test_file();
+        let apis = Api::from_file_descriptor(&file);
+        assert_eq!(1, apis.len());
+        let api = &apis[0];
+        assert_eq!("test.store.Store", api.name);
+        assert_eq!(Syntax::SYNTAX_PROTO3, api.syntax.enum_value_or_default());
+        assert_eq!("store.proto", api.source_context.get_or_default().file_name);
+        let method = &api.methods[0];
+        assert_eq!("Get", method.name);
+        assert_eq!(
+            "type.googleapis.com/google.protobuf.StringValue",
+            method.request_type_url
+        );
+        assert_eq!(
+            "type.googleapis.com/google.protobuf.Struct",
+            method.response_type_url
+        );
+        assert!(!method.request_streaming);
+        assert!(method.response_streaming);
+        assert_eq!("deprecated", method.options[0].name);
+    }
+
+    #[test]
+    fn api_round_trip() {
+        let file = test_file();
+        let api = Api::from_file_descriptor(&file).remove(0);
+        let service = api.to_service_descriptor_proto();
+        assert_eq!(&file.proto().service[0], &service);
+        assert!(service.method[0].options.get_or_default().get_deprecated());
+    }
+}
@@ -0,0 +1,145 @@
+//! Conversions between [`Timestamp`], [`Duration`] and `chrono` types.
+
+use std::convert::TryFrom;
+
+use ::chrono::DateTime;
+use ::chrono::TimeDelta;
+use ::chrono::TimeZone;
+use ::chrono::Utc;
+
+use crate::well_known_types::Duration;
+use crate::well_known_types::Timestamp;
+use crate::well_known_types_util::OutOfRangeError;
+
+/// Fails if the time is outside of years 1 to 9999.
+impl<Tz: TimeZone> TryFrom<DateTime<Tz>> for Timestamp {
+    type Error = OutOfRangeError;
+
+    fn try_from(time: DateTime<Tz>) -> Result<Self, OutOfRangeError> {
+        let timestamp = Timestamp {
+            seconds: time.timestamp(),
+            // Leap second is represented as nanos over one second
+            nanos: time.timestamp_subsec_nanos() as i32,
+            ..Default::default()
+        };
+        // Leap second is smeared into the next second
+        let timestamp = Timestamp::from_total_nanos(timestamp.total_nanos());
+        timestamp.ok_or(OutOfRangeError(()))
+    }
+}
+
+/// Fails if the timestamp is not [valid](Timestamp::is_valid).
+impl TryFrom<Timestamp> for DateTime<Utc> {
+    type Error = OutOfRangeError;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, OutOfRangeError> {
+        if !timestamp.is_valid() {
+            return Err(OutOfRangeError(()));
+        }
+        DateTime::from_timestamp(timestamp.seconds, timestamp.nanos as u32)
+            .ok_or(OutOfRangeError(()))
+    }
+}
+
+/// Fails if the duration is longer than [`Duration::MAX_SECONDS`].
+impl TryFrom<TimeDelta> for Duration {
+    type Error = OutOfRangeError;
+
+    fn try_from(delta: TimeDelta) -> Result<Self, OutOfRangeError> {
+        let duration = Duration {
+            seconds: delta.num_seconds(),
+            nanos: delta.subsec_nanos(),
+            ..Default::default()
+        };
+        if duration.is_valid() {
+            Ok(duration)
+        } else {
+            Err(OutOfRangeError(()))
+        }
+    }
+}
+
+/// Fails if the duration is not [valid](Duration::is_valid).
+impl TryFrom<Duration> for TimeDelta {
+    type Error = OutOfRangeError;
+
+    fn try_from(duration: Duration) -> Result<Self, OutOfRangeError> {
+        if !duration.is_valid() {
+            return Err(OutOfRangeError(()));
+        }
+        TimeDelta::try_seconds(duration.seconds)
+            .and_then(|d| d.checked_add(&TimeDelta::nanoseconds(duration.nanos as i64)))
+            .ok_or(OutOfRangeError(()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use chrono::DateTime;
+    use chrono::FixedOffset;
+    use chrono::TimeDelta;
+    use chrono::Utc;
+
+    use crate::well_known_types::Duration;
+    use crate::well_known_types::Timestamp;
+
+    #[test]
+    fn timestamp() {
+        let time = DateTime::parse_from_rfc3339("2021-02-03T04:05:06.007+01:00").unwrap();
+        let timestamp = Timestamp::try_from(time).unwrap();
+        assert_eq!("2021-02-03T03:05:06.007Z", timestamp.to_string());
+        assert_eq!(
+            time.with_timezone(&Utc),
+            DateTime::<Utc>::try_from(timestamp).unwrap()
+        );
+
+        let before_epoch = "1969-12-31T23:59:59.5Z".parse::<Timestamp>().unwrap();
+        assert_eq!(
+            before_epoch,
+            Timestamp::try_from(DateTime::<Utc>::try_from(before_epoch.clone()).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn timestamp_out_of_range() {
+        let time = DateTime::parse_from_rfc3339("0000-12-31T23:59:59Z").unwrap();
+        assert!(Timestamp::try_from(time).is_err());
+        let time: DateTime<FixedOffset> = DateTime::<Utc>::MAX_UTC.into();
+        assert!(Timestamp::try_from(time).is_err());
+
+        let mut timestamp = Timestamp::new();
+        timestamp.nanos = -1;
+        assert!(DateTime::<Utc>::try_from(timestamp).is_err());
+    }
+
+    #[test]
+    fn duration() {
+        for delta in &[
+            TimeDelta::zero(),
+            TimeDelta::milliseconds(1500),
+            TimeDelta::milliseconds(-1500),
+            TimeDelta::nanoseconds(-1),
+        ] {
+            let duration = Duration::try_from(*delta).unwrap();
+            assert!(duration.is_valid());
+            assert_eq!(*delta, TimeDelta::try_from(duration).unwrap());
+        }
+        assert_eq!(
+            "-1.500s",
+            Duration::try_from(TimeDelta::milliseconds(-1500))
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn duration_out_of_range() {
+        assert!(Duration::try_from(TimeDelta::MAX).is_err());
+        let mut duration = Duration::new();
+        duration.seconds = 1;
+        duration.nanos = -1;
+        assert!(TimeDelta::try_from(duration).is_err());
+    }
+}
@@ -1,7 +1,56 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+use std::str::FromStr;
+
 use crate::cached_size::CachedSize;
 use crate::well_known_types::Duration;
 use crate::UnknownFields;
 
+pub(crate) const NANOS_PER_SECOND: i32 = 1_000_000_000;
+
+/// Error returned when parsing [`Duration`] from string fails.
+#[derive(Debug)]
+pub struct DurationParseError(());
+
+impl fmt::Display for DurationParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "incorrect duration")
+    }
+}
+
+impl std::error::Error for DurationParseError {}
+
+/// Error returned when converting negative [`Duration`] to `std::time::Duration`.
+#[derive(Debug)]
+pub struct NegativeDurationError(());
+
+impl fmt::Display for NegativeDurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "negative duration")
+    }
+}
+
+impl std::error::Error for NegativeDurationError {}
+
+/// Error returned when converting [`Duration`] or [`Timestamp`](crate::well_known_types::Timestamp)
+/// to or from a type which supports different range or precision.
+#[derive(Debug)]
+pub struct OutOfRangeError(pub(crate) ());
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value is out of range")
+    }
+}
+
+impl std::error::Error for OutOfRangeError {}
+
 impl Duration {
     /// Zero seconds zero nanoseconds.
     pub const ZERO: Duration = Duration {
@@ -10,6 +59,69 @@ impl Duration {
         unknown_fields: UnknownFields::new(),
         cached_size: CachedSize::new(),
     };
+
+    /// Maximum absolute value of `seconds` of valid duration, approximately 10000 years.
+    pub const MAX_SECONDS: i64 = 315_576_000_000;
+
+    /// Total number of nanoseconds.
+    pub(crate) fn total_nanos(&self) -> i128 {
+        self.seconds as i128 * NANOS_PER_SECOND as i128 + self.nanos as i128
+    }
+
+    /// Normalized duration from nanoseconds, `None` if outside of the valid range.
+    pub(crate) fn from_total_nanos(nanos: i128) -> Option<Duration> {
+        let duration = Duration {
+            seconds: i64::try_from(nanos / NANOS_PER_SECOND as i128).ok()?,
+            nanos: (nanos % NANOS_PER_SECOND as i128) as i32,
+            ..Default::default()
+        };
+        if duration.is_valid() {
+            Some(duration)
+        } else {
+            None
+        }
+    }
+
+    /// Check the duration is within `±MAX_SECONDS`,
+    /// `nanos` is within `±999_999_999` and has the same sign as `seconds`.
+    pub fn is_valid(&self) -> bool {
+        self.seconds >= -Duration::MAX_SECONDS
+            && self.seconds <= Duration::MAX_SECONDS
+            && self.nanos > -NANOS_PER_SECOND
+            && self.nanos < NANOS_PER_SECOND
+            && !(self.seconds > 0 && self.nanos < 0)
+            && !(self.seconds < 0 && self.nanos > 0)
+    }
+
+    /// Move whole seconds from `nanos` to `seconds`
+    /// and make `nanos` sign match the sign of `seconds`.
+    ///
+    /// Range of `seconds` is not checked.
+    pub fn normalize(&mut self) {
+        let mut seconds = self
+            .seconds
+            .saturating_add((self.nanos / NANOS_PER_SECOND) as i64);
+        let mut nanos = self.nanos % NANOS_PER_SECOND;
+        if seconds > 0 && nanos < 0 {
+            seconds -= 1;
+            nanos += NANOS_PER_SECOND;
+        } else if seconds < 0 && nanos > 0 {
+            seconds += 1;
+            nanos -= NANOS_PER_SECOND;
+        }
+        self.seconds = seconds;
+        self.nanos = nanos;
+    }
+
+    /// Sum of two durations, `None` if the result is not [valid](Duration::is_valid).
+    pub fn checked_add(&self, other: &Duration) -> Option<Duration> {
+        Duration::from_total_nanos(self.total_nanos() + other.total_nanos())
+    }
+
+    /// Difference of two durations, `None` if the result is not [valid](Duration::is_valid).
+    pub fn checked_sub(&self, other: &Duration) -> Option<Duration> {
+        Duration::from_total_nanos(self.total_nanos() - other.total_nanos())
+    }
 }
 
 /// Convert from `std::time::Duration`.
@@ -31,20 +143,184 @@ impl From<std::time::Duration> for Duration {
 ///
 /// This conversion might be lossy if `std::time::Duration` precision is smaller than nanoseconds.
 ///
+/// Fails if `Duration` is negative.
+impl TryFrom<Duration> for std::time::Duration {
+    type Error = NegativeDurationError;
+
+    fn try_from(duration: Duration) -> Result<Self, NegativeDurationError> {
+        let nanos = duration.total_nanos();
+        if nanos < 0 {
+            return Err(NegativeDurationError(()));
+        }
+        let seconds = nanos / NANOS_PER_SECOND as i128;
+        let nanos = nanos % NANOS_PER_SECOND as i128;
+        Ok(std::time::Duration::new(seconds as u64, nanos as u32))
+    }
+}
+
+/// Compare `seconds` then `nanos`,
+/// which is meaningful for [normalized](Duration::normalize) durations.
+///
+/// Unknown fields are compared last, so the order is consistent with `PartialEq`.
+impl Ord for Duration {
+    fn cmp(&self, other: &Duration) -> Ordering {
+        (self.seconds, self.nanos, &self.unknown_fields).cmp(&(
+            other.seconds,
+            other.nanos,
+            &other.unknown_fields,
+        ))
+    }
+}
+
+impl PartialOrd for Duration {
+    fn partial_cmp(&self, other: &Duration) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for Duration {}
+
 /// # Panics
 ///
-/// If `Duration` value is outside of `std::time::Duration` supported range.
-impl Into<std::time::Duration> for Duration {
-    fn into(self) -> std::time::Duration {
-        assert!(self.seconds >= 0);
-        std::time::Duration::from_secs(self.seconds as u64)
-            + std::time::Duration::from_nanos(self.nanos as u64)
+/// If the result is not [valid](Duration::is_valid).
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, other: Duration) -> Duration {
+        self.checked_add(&other)
+            .expect("overflow when adding durations")
+    }
+}
+
+/// # Panics
+///
+/// If the result is not [valid](Duration::is_valid).
+impl Sub for Duration {
+    type Output = Duration;
+
+    fn sub(self, other: Duration) -> Duration {
+        self.checked_sub(&other)
+            .expect("overflow when subtracting durations")
+    }
+}
+
+impl AddAssign for Duration {
+    fn add_assign(&mut self, other: Duration) {
+        *self = self.clone() + other;
+    }
+}
+
+impl SubAssign for Duration {
+    fn sub_assign(&mut self, other: Duration) {
+        *self = self.clone() - other;
+    }
+}
+
+/// Negation saturates on overflow, which only happens
+/// for durations which are not [valid](Duration::is_valid).
+impl Neg for Duration {
+    type Output = Duration;
+
+    fn neg(self) -> Duration {
+        Duration {
+            seconds: self.seconds.saturating_neg(),
+            nanos: self.nanos.saturating_neg(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Write number of nanoseconds as fraction with 0, 3, 6 or 9 digits.
+pub(crate) fn write_subsec_nanos(f: &mut fmt::Formatter<'_>, nanos: u32) -> fmt::Result {
+    if nanos == 0 {
+        Ok(())
+    } else if nanos % 1_000_000 == 0 {
+        write!(f, ".{:03}", nanos / 1_000_000)
+    } else if nanos % 1_000 == 0 {
+        write!(f, ".{:06}", nanos / 1_000)
+    } else {
+        write!(f, ".{:09}", nanos)
+    }
+}
+
+/// Format as in JSON with 0, 3, 6 or 9 fractional digits, e. g. `1.500s` or `-0.000001s`.
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = self.clone();
+        d.normalize();
+        if d.seconds < 0 || d.nanos < 0 {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", (d.seconds as i128).abs())?;
+        write_subsec_nanos(f, d.nanos.abs() as u32)?;
+        write!(f, "s")
+    }
+}
+
+/// Parse JSON representation like `1.5s` or `-3s`.
+///
+/// Fails if the duration is not [valid](Duration::is_valid).
+impl FromStr for Duration {
+    type Err = DurationParseError;
+
+    fn from_str(s: &str) -> Result<Duration, DurationParseError> {
+        let err = || DurationParseError(());
+
+        let s = s.strip_suffix('s').ok_or_else(err)?;
+        let (minus, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let (seconds, fraction) = match s.find('.') {
+            Some(dot) => (&s[..dot], &s[dot + 1..]),
+            None => (s, "0"),
+        };
+
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        if !is_digits(seconds) || !is_digits(fraction) || fraction.len() > 9 {
+            return Err(err());
+        }
+
+        let seconds: i64 = seconds.parse().map_err(|_| err())?;
+        let mut nanos: i32 = fraction.parse().map_err(|_| err())?;
+        for _ in fraction.len()..9 {
+            nanos *= 10;
+        }
+
+        let duration = match minus {
+            false => Duration {
+                seconds,
+                nanos,
+                ..Default::default()
+            },
+            true => Duration {
+                seconds: -seconds,
+                nanos: -nanos,
+                ..Default::default()
+            },
+        };
+        if !duration.is_valid() {
+            return Err(err());
+        }
+        Ok(duration)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::cmp::Ordering;
+    use std::convert::TryFrom;
+
     use crate::well_known_types::Duration;
+    use crate::Message;
+
+    fn duration(seconds: i64, nanos: i32) -> Duration {
+        Duration {
+            seconds,
+            nanos,
+            ..Default::default()
+        }
+    }
 
     #[test]
     fn to_from_duration() {
@@ -52,7 +328,7 @@ mod test {
             assert_eq!(duration, Duration::from(std_time_duration));
             assert_eq!(
                 std_time_duration,
-                Into::<std::time::Duration>::into(duration)
+                std::time::Duration::try_from(duration).unwrap()
             );
         }
 
@@ -66,4 +342,102 @@ mod test {
             std::time::Duration::from_millis(4_123),
         );
     }
+
+    #[test]
+    fn to_std_negative() {
+        assert!(std::time::Duration::try_from(duration(-1, 0)).is_err());
+        assert!(std::time::Duration::try_from(duration(0, -1)).is_err());
+        assert!(std::time::Duration::try_from(duration(1, -1)).is_ok());
+    }
+
+    #[test]
+    fn normalize() {
+        fn check(expected: Duration, mut d: Duration) {
+            d.normalize();
+            assert_eq!(expected, d);
+        }
+
+        check(duration(1, 500_000_000), duration(0, 1_500_000_000));
+        check(duration(0, 500_000_000), duration(1, -500_000_000));
+        check(duration(0, -500_000_000), duration(-1, 500_000_000));
+        check(duration(-3, -1), duration(-1, -2_000_000_001));
+    }
+
+    #[test]
+    fn is_valid() {
+        assert!(duration(1, 1).is_valid());
+        assert!(duration(-1, -1).is_valid());
+        assert!(duration(0, -1).is_valid());
+        assert!(duration(Duration::MAX_SECONDS, 999_999_999).is_valid());
+        assert!(!duration(Duration::MAX_SECONDS + 1, 0).is_valid());
+        assert!(!duration(1, -1).is_valid());
+        assert!(!duration(0, 1_000_000_000).is_valid());
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(
+            duration(0, 500_000_000),
+            duration(1, 0) - duration(0, 500_000_000)
+        );
+        assert_eq!(
+            duration(-1, -500_000_000),
+            duration(-2, 0) + duration(0, 500_000_000)
+        );
+        assert_eq!(duration(-1, -2), -duration(1, 2));
+        assert_eq!(duration(i64::MAX, i32::MAX), -duration(i64::MIN, i32::MIN));
+        assert_eq!(
+            None,
+            duration(Duration::MAX_SECONDS, 0).checked_add(&duration(1, 0))
+        );
+        let mut d = duration(1, 0);
+        d += duration(1, 1);
+        d -= duration(0, 2);
+        assert_eq!(duration(1, 999_999_999), d);
+    }
+
+    #[test]
+    fn ord() {
+        assert!(duration(-1, -500_000_000) < duration(-1, -200_000_000));
+        assert!(duration(-1, 0) < duration(0, -1));
+        assert!(duration(0, 1) < duration(1, 0));
+
+        let mut with_unknown = duration(0, 0);
+        with_unknown.mut_unknown_fields().add_varint(10, 1);
+        assert_ne!(Duration::ZERO, with_unknown);
+        assert_ne!(Ordering::Equal, Duration::ZERO.cmp(&with_unknown));
+        assert!(duration(0, 1) > with_unknown);
+    }
+
+    #[test]
+    fn display_from_str() {
+        fn check(s: &str, d: Duration) {
+            assert_eq!(s, d.to_string());
+            assert_eq!(d, s.parse().unwrap());
+        }
+
+        check("0s", Duration::ZERO);
+        check("1.500s", duration(1, 500_000_000));
+        check("-0.000001s", duration(0, -1_000));
+        check("-3.000000001s", duration(-3, -1));
+        check("1.000340012s", duration(1, 340_012));
+
+        assert_eq!(duration(1, 0), "1.000s".parse().unwrap());
+        assert_eq!(duration(1, 500_000_000), "1.5s".parse().unwrap());
+        assert_eq!("-1.500s", duration(-2, 500_000_000).to_string());
+        for s in &[
+            "",
+            "s",
+            "1",
+            "1.s",
+            ".5s",
+            "1.0000000001s",
+            "+1s",
+            "1e3s",
+            "-s",
+        ] {
+            assert!(s.parse::<Duration>().is_err(), "{}", s);
+        }
+        assert!("315576000001s".parse::<Duration>().is_err());
+    }
 }
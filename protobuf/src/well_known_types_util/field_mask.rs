@@ -0,0 +1,425 @@
+use std::collections::BTreeMap;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::well_known_types::FieldMask;
+
+/// Field mask as a tree of field names.
+///
+/// A node without children is a leaf which covers the whole field.
+/// Trees are always canonical: no path is covered by another path.
+#[derive(Default)]
+struct FieldMaskTree {
+    children: BTreeMap<String, FieldMaskTree>,
+}
+
+impl FieldMaskTree {
+    fn from_paths<'a>(paths: impl IntoIterator<Item = &'a String>) -> FieldMaskTree {
+        let mut tree = FieldMaskTree::default();
+        for path in paths {
+            tree.add_path(path);
+        }
+        tree
+    }
+
+    fn add_path(&mut self, path: &str) {
+        if path.is_empty() {
+            return;
+        }
+        let mut node = self;
+        let mut is_root = true;
+        let mut new_branch = false;
+        for part in path.split('.') {
+            if !is_root && !new_branch && node.children.is_empty() {
+                // Path is covered by existing path
+                return;
+            }
+            node = node.children.entry(part.to_owned()).or_insert_with(|| {
+                new_branch = true;
+                FieldMaskTree::default()
+            });
+            is_root = false;
+        }
+        // Path covers all sub-paths
+        node.children.clear();
+    }
+
+    fn collect_paths(&self, prefix: &str, paths: &mut Vec<String>) {
+        for (name, child) in &self.children {
+            let path = match prefix {
+                "" => name.clone(),
+                prefix => format!("{}.{}", prefix, name),
+            };
+            if child.children.is_empty() {
+                paths.push(path);
+            } else {
+                child.collect_paths(&path, paths);
+            }
+        }
+    }
+
+    fn to_field_mask(&self) -> FieldMask {
+        let mut paths = Vec::new();
+        self.collect_paths("", &mut paths);
+        FieldMask {
+            paths,
+            ..Default::default()
+        }
+    }
+
+    /// Add to `out` the part of `path` covered by this tree.
+    fn intersect_path(&self, path: &str, out: &mut FieldMaskTree) {
+        if path.is_empty() {
+            return;
+        }
+        let mut node = self;
+        for part in path.split('.') {
+            if !std::ptr::eq(node, self) && node.children.is_empty() {
+                out.add_path(path);
+                return;
+            }
+            node = match node.children.get(part) {
+                Some(child) => child,
+                None => return,
+            };
+        }
+        if node.children.is_empty() {
+            out.add_path(path);
+        } else {
+            // Add all sub-paths of the path
+            let mut sub_paths = Vec::new();
+            node.collect_paths(path, &mut sub_paths);
+            for sub_path in &sub_paths {
+                out.add_path(sub_path);
+            }
+        }
+    }
+
+    fn trim(&self, m: &mut dyn MessageDyn) {
+        for field in m.descriptor_dyn().fields() {
+            match self.children.get(field.get_name()) {
+                None => field.clear_field(m),
+                Some(child) if child.children.is_empty() => {}
+                Some(child) => {
+                    if is_singular_message(&field) && field.has_field(m) {
+                        if let Err(e) = field.mut_message_with(m, |m| child.trim(m)) {
+                            panic!("{}: {}", field, e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn merge(
+        &self,
+        source: &dyn MessageDyn,
+        destination: &mut dyn MessageDyn,
+        replace_repeated_fields: bool,
+    ) {
+        let source_descriptor = source.descriptor_dyn();
+        let destination_descriptor = destination.descriptor_dyn();
+        for (name, child) in &self.children {
+            let (sf, df) = match (
+                source_descriptor.get_field_by_name(name),
+                destination_descriptor.get_field_by_name(name),
+            ) {
+                (Some(sf), Some(df)) => (sf, df),
+                _ => panic!(
+                    "field mask is not valid for message {}: unknown field `{}`",
+                    source_descriptor, name
+                ),
+            };
+
+            if !child.children.is_empty() {
+                assert!(
+                    is_singular_message(&sf),
+                    "field mask is not valid for message {}: field `{}` is not a message",
+                    source_descriptor,
+                    name
+                );
+                if sf.has_field(source) || df.has_field(destination) {
+                    let source = sf.get_message(source);
+                    if let Err(e) = df.mut_message_with(destination, |d| {
+                        child.merge(&*source, d, replace_repeated_fields)
+                    }) {
+                        panic!("{}: {}", df, e);
+                    }
+                }
+                continue;
+            }
+
+            match sf.get_reflect(source) {
+                ReflectFieldRef::Repeated(..) | ReflectFieldRef::Map(..) => {
+                    if replace_repeated_fields {
+                        df.clear_field(destination);
+                    }
+                    merge_field(&sf, source, &df, destination);
+                }
+                ReflectFieldRef::Optional(Some(..)) => merge_field(&sf, source, &df, destination),
+                ReflectFieldRef::Optional(None) => {
+                    // Unset message fields are left as is, like in message merge
+                    if !is_singular_message(&sf) {
+                        df.clear_field(destination);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn is_singular_message(field: &FieldDescriptor) -> bool {
+    match field.runtime_field_type() {
+        RuntimeFieldType::Singular(RuntimeTypeBox::Message(..)) => true,
+        _ => false,
+    }
+}
+
+/// Merge single field of `source` into `destination`.
+fn merge_field(
+    sf: &FieldDescriptor,
+    source: &dyn MessageDyn,
+    df: &FieldDescriptor,
+    destination: &mut dyn MessageDyn,
+) {
+    MessageDescriptor::merge_field_from_message(df, destination, sf, source);
+}
+
+fn is_valid_path(descriptor: &MessageDescriptor, path: &str) -> bool {
+    if path.is_empty() {
+        return false;
+    }
+    let mut descriptor = descriptor.clone();
+    let mut parts = path.split('.').peekable();
+    while let Some(part) = parts.next() {
+        let field = match descriptor.get_field_by_name(part) {
+            Some(field) => field,
+            None => return false,
+        };
+        if parts.peek().is_some() {
+            descriptor = match field.runtime_field_type() {
+                RuntimeFieldType::Singular(RuntimeTypeBox::Message(m)) => m,
+                _ => return false,
+            };
+        }
+    }
+    true
+}
+
+/// Convert `foo_bar` to `fooBar`, `None` if conversion cannot be reversed.
+fn snake_case_to_camel_case(path: &str) -> Option<String> {
+    let mut result = String::with_capacity(path.len());
+    let mut after_underscore = false;
+    for c in path.chars() {
+        if c.is_ascii_uppercase() {
+            return None;
+        }
+        if after_underscore {
+            if !c.is_ascii_lowercase() {
+                return None;
+            }
+            result.push(c.to_ascii_uppercase());
+            after_underscore = false;
+        } else if c == '_' {
+            after_underscore = true;
+        } else {
+            result.push(c);
+        }
+    }
+    if after_underscore {
+        return None;
+    }
+    Some(result)
+}
+
+/// Convert `fooBar` to `foo_bar`.
+fn camel_case_to_snake_case(path: &str) -> String {
+    let mut result = String::with_capacity(path.len() + 4);
+    for c in path.chars() {
+        if c.is_ascii_uppercase() {
+            result.push('_');
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+impl FieldMask {
+    /// Create a field mask from paths like `foo.bar_baz`.
+    pub fn from_paths<S: AsRef<str>>(paths: &[S]) -> FieldMask {
+        FieldMask {
+            paths: paths.iter().map(|p| p.as_ref().to_owned()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Check that all paths of this mask refer to existing fields of given message.
+    ///
+    /// All fields in the path except the last one must be singular message fields.
+    pub fn is_valid_for(&self, descriptor: &MessageDescriptor) -> bool {
+        self.paths.iter().all(|p| is_valid_path(descriptor, p))
+    }
+
+    /// Check if the path is covered by this mask.
+    ///
+    /// Path is covered if it is in the mask, or if any of its prefixes is in the mask.
+    pub fn covers(&self, path: &str) -> bool {
+        let mut out = FieldMaskTree::default();
+        FieldMaskTree::from_paths(&self.paths).intersect_path(path, &mut out);
+        out.to_field_mask().paths == [path]
+    }
+
+    /// Canonical form of this mask: paths are sorted, duplicates
+    /// and paths covered by other paths are removed.
+    pub fn canonical(&self) -> FieldMask {
+        FieldMaskTree::from_paths(&self.paths).to_field_mask()
+    }
+
+    /// Canonical mask covering paths covered by either of masks.
+    pub fn union(&self, other: &FieldMask) -> FieldMask {
+        FieldMaskTree::from_paths(self.paths.iter().chain(other.paths.iter())).to_field_mask()
+    }
+
+    /// Canonical mask covering paths covered by both masks.
+    pub fn intersection(&self, other: &FieldMask) -> FieldMask {
+        let tree = FieldMaskTree::from_paths(&self.paths);
+        let mut out = FieldMaskTree::default();
+        for path in &other.paths {
+            tree.intersect_path(path, &mut out);
+        }
+        out.to_field_mask()
+    }
+
+    /// Clear all fields of the message not covered by this mask.
+    ///
+    /// Note empty mask clears the whole message.
+    /// Paths which do not exist in the message are ignored.
+    ///
+    /// Message fields stored as a different Rust type (e. g. with `rust_type` codegen option)
+    /// are converted to messages, trimmed and converted back.
+    ///
+    /// # Panics
+    ///
+    /// If a trimmed message cannot be converted back to the Rust type of the field.
+    pub fn trim(&self, message: &mut dyn MessageDyn) {
+        FieldMaskTree::from_paths(&self.paths).trim(message);
+    }
+
+    /// Merge fields covered by this mask from `source` into `destination`.
+    ///
+    /// Singular non-message fields covered by the mask are copied, or cleared when not set
+    /// in `source`. Singular message fields are merged recursively, and left untouched
+    /// when not set in `source`, like in message merge. Repeated and map fields are appended
+    /// to the fields of `destination`, unless `replace_repeated_fields` is `true`,
+    /// in which case they are replaced.
+    ///
+    /// Source and destination may have different representations,
+    /// e.g. generated and dynamic message of the same type.
+    ///
+    /// Message fields of `destination` stored as a different Rust type are converted
+    /// to messages, merged and converted back.
+    ///
+    /// # Panics
+    ///
+    /// If mask is not [valid](FieldMask::is_valid_for) for the messages,
+    /// or a merged value cannot be converted to the Rust type of the field.
+    pub fn merge_message(
+        &self,
+        source: &dyn MessageDyn,
+        destination: &mut dyn MessageDyn,
+        replace_repeated_fields: bool,
+    ) {
+        FieldMaskTree::from_paths(&self.paths).merge(source, destination, replace_repeated_fields);
+    }
+
+    /// Format this mask as JSON string: comma-separated paths in `lowerCamelCase`.
+    ///
+    /// Return `None` if any path cannot be converted to `lowerCamelCase` and back.
+    pub fn to_json_string(&self) -> Option<String> {
+        let paths: Option<Vec<String>> = self
+            .paths
+            .iter()
+            .map(|p| snake_case_to_camel_case(p))
+            .collect();
+        Some(paths?.join(","))
+    }
+
+    /// Parse comma-separated `lowerCamelCase` paths of JSON representation.
+    pub fn from_json_string(json: &str) -> FieldMask {
+        FieldMask {
+            paths: json
+                .split(',')
+                .filter(|p| !p.is_empty())
+                .map(camel_case_to_snake_case)
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::well_known_types::FieldMask;
+
+    #[test]
+    fn canonical() {
+        assert_eq!(
+            FieldMask::from_paths(&["a", "b.c"]),
+            FieldMask::from_paths(&["b.c", "a.x", "a", "b.c.d", "a"]).canonical()
+        );
+        assert_eq!(
+            FieldMask::from_paths(&["a.b", "a.c"]),
+            FieldMask::from_paths(&["a.c", "a.b"]).canonical()
+        );
+    }
+
+    #[test]
+    fn union() {
+        assert_eq!(
+            FieldMask::from_paths(&["a", "b.c", "d"]),
+            FieldMask::from_paths(&["a.x", "b.c"])
+                .union(&FieldMask::from_paths(&["a", "d", "b.c.e"]))
+        );
+    }
+
+    #[test]
+    fn intersection() {
+        assert_eq!(
+            FieldMask::from_paths(&["a.x", "b.c.e"]),
+            FieldMask::from_paths(&["a", "b.c", "d"])
+                .intersection(&FieldMask::from_paths(&["a.x", "b.c.e", "f"]))
+        );
+        assert_eq!(
+            FieldMask::from_paths(&["b.c.d", "b.c.e"]),
+            FieldMask::from_paths(&["b.c.d", "b.c.e", "x"])
+                .intersection(&FieldMask::from_paths(&["b"]))
+        );
+    }
+
+    #[test]
+    fn covers() {
+        let mask = FieldMask::from_paths(&["a", "b.c"]);
+        assert!(mask.covers("a"));
+        assert!(mask.covers("a.x"));
+        assert!(mask.covers("b.c.d"));
+        assert!(!mask.covers("b"));
+        assert!(!mask.covers("d"));
+    }
+
+    #[test]
+    fn json() {
+        let mask = FieldMask::from_paths(&["foo_bar.baz", "x"]);
+        assert_eq!(Some("fooBar.baz,x".to_owned()), mask.to_json_string());
+        assert_eq!(mask, FieldMask::from_json_string("fooBar.baz,x"));
+        assert_eq!(FieldMask::new(), FieldMask::from_json_string(""));
+        assert_eq!(None, FieldMask::from_paths(&["fooBar"]).to_json_string());
+        assert_eq!(None, FieldMask::from_paths(&["foo__bar"]).to_json_string());
+        assert_eq!(None, FieldMask::from_paths(&["foo_1"]).to_json_string());
+    }
+}
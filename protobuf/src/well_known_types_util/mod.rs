@@ -1,3 +1,18 @@
+//! Utilities and error types for [well known types](crate::well_known_types).
+
 mod any;
+mod api;
+#[cfg(feature = "with-chrono")]
+mod chrono;
 mod duration;
+mod field_mask;
+mod struct_pb;
+#[cfg(feature = "with-time")]
+mod time;
 mod timestamp;
+mod type_pb;
+
+pub use self::duration::DurationParseError;
+pub use self::duration::NegativeDurationError;
+pub use self::duration::OutOfRangeError;
+pub use self::timestamp::TimestampParseError;
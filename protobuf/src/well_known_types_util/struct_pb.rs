@@ -0,0 +1,179 @@
+use std::iter::FromIterator;
+
+use crate::well_known_types::value;
+use crate::well_known_types::ListValue;
+use crate::well_known_types::NullValue;
+use crate::well_known_types::Struct;
+use crate::well_known_types::Value;
+use crate::ProtobufEnumOrUnknown;
+
+impl Value {
+    /// Create a `null` value.
+    pub fn null() -> Value {
+        Value::from_kind(value::Kind::null_value(ProtobufEnumOrUnknown::new(
+            NullValue::NULL_VALUE,
+        )))
+    }
+
+    fn from_kind(kind: value::Kind) -> Value {
+        Value {
+            kind: Some(kind),
+            ..Default::default()
+        }
+    }
+
+    /// Is this value `null`?
+    pub fn is_null(&self) -> bool {
+        match self.kind {
+            Some(value::Kind::null_value(..)) => true,
+            _ => false,
+        }
+    }
+
+    /// Get number if this value is a number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.kind {
+            Some(value::Kind::number_value(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Get string if this value is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.kind {
+            Some(value::Kind::string_value(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Get bool if this value is a bool.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.kind {
+            Some(value::Kind::bool_value(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Get struct if this value is a struct.
+    pub fn as_struct(&self) -> Option<&Struct> {
+        match &self.kind {
+            Some(value::Kind::struct_value(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Get list if this value is a list.
+    pub fn as_list(&self) -> Option<&ListValue> {
+        match &self.kind {
+            Some(value::Kind::list_value(v)) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        Value::from_kind(value::Kind::number_value(n))
+    }
+}
+
+impl From<i32> for Value {
+    fn from(n: i32) -> Self {
+        Value::from(n as f64)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(n: u32) -> Self {
+        Value::from(n as f64)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::from_kind(value::Kind::bool_value(b))
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::from_kind(value::Kind::string_value(s))
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::from(s.to_owned())
+    }
+}
+
+impl From<Struct> for Value {
+    fn from(s: Struct) -> Self {
+        Value::from_kind(value::Kind::struct_value(s))
+    }
+}
+
+impl From<ListValue> for Value {
+    fn from(l: ListValue) -> Self {
+        Value::from_kind(value::Kind::list_value(l))
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(values: Vec<Value>) -> Self {
+        Value::from(ListValue {
+            values,
+            ..Default::default()
+        })
+    }
+}
+
+impl FromIterator<Value> for ListValue {
+    fn from_iter<T: IntoIterator<Item = Value>>(iter: T) -> Self {
+        ListValue {
+            values: iter.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, Value)> for Struct {
+    fn from_iter<T: IntoIterator<Item = (K, Value)>>(iter: T) -> Self {
+        Struct {
+            fields: iter.into_iter().map(|(k, v)| (k.into(), v)).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::well_known_types::ListValue;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+
+    #[test]
+    fn constructors_and_getters() {
+        assert!(Value::null().is_null());
+        assert_eq!(Some(1.5), Value::from(1.5).as_f64());
+        assert_eq!(Some(-3.0), Value::from(-3).as_f64());
+        assert_eq!(Some("a"), Value::from("a").as_str());
+        assert_eq!(Some(true), Value::from(true).as_bool());
+        assert_eq!(None, Value::from(true).as_str());
+        assert_eq!(None, Value::new().as_bool());
+        assert!(!Value::new().is_null());
+
+        let list = Value::from(vec![Value::from(1), Value::null()]);
+        assert_eq!(2, list.as_list().unwrap().values.len());
+
+        let s: Struct = vec![("x", Value::from("y"))].into_iter().collect();
+        let s = Value::from(s);
+        assert_eq!(
+            Some("y"),
+            s.as_struct().unwrap().fields.get("x").unwrap().as_str()
+        );
+
+        let l: ListValue = vec![Value::from(false)].into_iter().collect();
+        assert_eq!(vec![Value::from(false)], l.values);
+    }
+}
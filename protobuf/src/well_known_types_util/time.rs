@@ -0,0 +1,110 @@
+//! Conversions between [`Timestamp`], [`Duration`] and `time` types.
+
+use std::convert::TryFrom;
+
+use ::time::OffsetDateTime;
+
+use crate::well_known_types::Duration;
+use crate::well_known_types::Timestamp;
+use crate::well_known_types_util::OutOfRangeError;
+
+/// Fails if the time is outside of years 1 to 9999.
+impl TryFrom<OffsetDateTime> for Timestamp {
+    type Error = OutOfRangeError;
+
+    fn try_from(time: OffsetDateTime) -> Result<Self, OutOfRangeError> {
+        Timestamp::from_total_nanos(time.unix_timestamp_nanos()).ok_or(OutOfRangeError(()))
+    }
+}
+
+/// Fails if the timestamp is not [valid](Timestamp::is_valid).
+impl TryFrom<Timestamp> for OffsetDateTime {
+    type Error = OutOfRangeError;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, OutOfRangeError> {
+        if !timestamp.is_valid() {
+            return Err(OutOfRangeError(()));
+        }
+        OffsetDateTime::from_unix_timestamp_nanos(timestamp.total_nanos())
+            .map_err(|_| OutOfRangeError(()))
+    }
+}
+
+/// Fails if the duration is longer than [`Duration::MAX_SECONDS`].
+impl TryFrom<::time::Duration> for Duration {
+    type Error = OutOfRangeError;
+
+    fn try_from(duration: ::time::Duration) -> Result<Self, OutOfRangeError> {
+        Duration::from_total_nanos(duration.whole_nanoseconds()).ok_or(OutOfRangeError(()))
+    }
+}
+
+/// Fails if the duration is not [valid](Duration::is_valid).
+impl TryFrom<Duration> for ::time::Duration {
+    type Error = OutOfRangeError;
+
+    fn try_from(duration: Duration) -> Result<Self, OutOfRangeError> {
+        if !duration.is_valid() {
+            return Err(OutOfRangeError(()));
+        }
+        Ok(::time::Duration::new(duration.seconds, duration.nanos))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use time::OffsetDateTime;
+
+    use crate::well_known_types::Duration;
+    use crate::well_known_types::Timestamp;
+
+    #[test]
+    fn timestamp() {
+        for nanos in &[0, 1_612_321_506_007_000_000, -500_000_000] {
+            let time = OffsetDateTime::from_unix_timestamp_nanos(*nanos).unwrap();
+            let timestamp = Timestamp::try_from(time).unwrap();
+            assert!(timestamp.is_valid());
+            assert_eq!(time, OffsetDateTime::try_from(timestamp).unwrap());
+        }
+        let time = OffsetDateTime::from_unix_timestamp_nanos(-500_000_000).unwrap();
+        assert_eq!(
+            "1969-12-31T23:59:59.500Z",
+            Timestamp::try_from(time).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn timestamp_out_of_range() {
+        let time = OffsetDateTime::from_unix_timestamp(Timestamp::MIN_SECONDS - 1).unwrap();
+        assert!(Timestamp::try_from(time).is_err());
+
+        let mut timestamp = Timestamp::new();
+        timestamp.nanos = 1_000_000_000;
+        assert!(OffsetDateTime::try_from(timestamp).is_err());
+    }
+
+    #[test]
+    fn duration() {
+        for d in &[
+            time::Duration::ZERO,
+            time::Duration::milliseconds(1500),
+            time::Duration::milliseconds(-1500),
+            time::Duration::nanoseconds(-1),
+        ] {
+            let duration = Duration::try_from(*d).unwrap();
+            assert!(duration.is_valid());
+            assert_eq!(*d, time::Duration::try_from(duration).unwrap());
+        }
+    }
+
+    #[test]
+    fn duration_out_of_range() {
+        assert!(Duration::try_from(time::Duration::MAX).is_err());
+        let mut duration = Duration::new();
+        duration.seconds = -1;
+        duration.nanos = 1;
+        assert!(time::Duration::try_from(duration).is_err());
+    }
+}
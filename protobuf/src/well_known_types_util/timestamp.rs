@@ -1,7 +1,32 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Sub;
+use std::ops::SubAssign;
+use std::str::FromStr;
+use std::time::SystemTime;
+
 use crate::cached_size::CachedSize;
+use crate::json::rfc_3339::Rfc3339ParseError;
+use crate::json::rfc_3339::TmUtc;
+use crate::well_known_types::Duration;
 use crate::well_known_types::Timestamp;
+use crate::well_known_types_util::duration::write_subsec_nanos;
+use crate::well_known_types_util::duration::NANOS_PER_SECOND;
 use crate::UnknownFields;
-use std::time::{Duration, SystemTime};
+
+/// Error returned when parsing [`Timestamp`] from string fails.
+#[derive(Debug)]
+pub struct TimestampParseError(Rfc3339ParseError);
+
+impl fmt::Display for TimestampParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for TimestampParseError {}
 
 impl Timestamp {
     /// Unix epoch value of timestamp.
@@ -12,10 +37,176 @@ impl Timestamp {
         cached_size: CachedSize::new(),
     };
 
+    /// Minimum valid `seconds` value, `0001-01-01T00:00:00Z`.
+    pub const MIN_SECONDS: i64 = -62_135_596_800;
+
+    /// Maximum valid `seconds` value, `9999-12-31T23:59:59Z`.
+    pub const MAX_SECONDS: i64 = 253_402_300_799;
+
     /// Return current time as `Timestamp`.
     pub fn now() -> Timestamp {
         Timestamp::from(SystemTime::now())
     }
+
+    /// Total number of nanoseconds since epoch.
+    pub(crate) fn total_nanos(&self) -> i128 {
+        self.seconds as i128 * NANOS_PER_SECOND as i128 + self.nanos as i128
+    }
+
+    /// Timestamp from nanoseconds since epoch, `None` if outside of the valid range.
+    pub(crate) fn from_total_nanos(nanos: i128) -> Option<Timestamp> {
+        let seconds = nanos.div_euclid(NANOS_PER_SECOND as i128);
+        if seconds < Timestamp::MIN_SECONDS as i128 || seconds > Timestamp::MAX_SECONDS as i128 {
+            return None;
+        }
+        Some(Timestamp {
+            seconds: seconds as i64,
+            nanos: nanos.rem_euclid(NANOS_PER_SECOND as i128) as i32,
+            ..Default::default()
+        })
+    }
+
+    /// Check the timestamp is within years 1 to 9999 inclusive,
+    /// and `nanos` is within `0..=999_999_999`.
+    pub fn is_valid(&self) -> bool {
+        self.seconds >= Timestamp::MIN_SECONDS
+            && self.seconds <= Timestamp::MAX_SECONDS
+            && self.nanos >= 0
+            && self.nanos < NANOS_PER_SECOND
+    }
+
+    /// Move whole seconds from `nanos` to `seconds` so `nanos` is within `0..=999_999_999`.
+    ///
+    /// Range of `seconds` is not checked.
+    pub fn normalize(&mut self) {
+        self.seconds = self
+            .seconds
+            .saturating_add(self.nanos.div_euclid(NANOS_PER_SECOND) as i64);
+        self.nanos = self.nanos.rem_euclid(NANOS_PER_SECOND);
+    }
+
+    /// Timestamp after given duration, `None` if the result is not [valid](Timestamp::is_valid).
+    pub fn checked_add(&self, duration: &Duration) -> Option<Timestamp> {
+        Timestamp::from_total_nanos(self.total_nanos() + duration.total_nanos())
+    }
+
+    /// Timestamp before given duration, `None` if the result is not [valid](Timestamp::is_valid).
+    pub fn checked_sub(&self, duration: &Duration) -> Option<Timestamp> {
+        Timestamp::from_total_nanos(self.total_nanos() - duration.total_nanos())
+    }
+
+    /// Duration from `earlier` to `self`, negative if `earlier` is later.
+    ///
+    /// `None` if the result is not [valid](Duration::is_valid).
+    pub fn checked_duration_since(&self, earlier: &Timestamp) -> Option<Duration> {
+        Duration::from_total_nanos(self.total_nanos() - earlier.total_nanos())
+    }
+}
+
+/// Compare `seconds` then `nanos`,
+/// which is meaningful for [normalized](Timestamp::normalize) timestamps.
+///
+/// Unknown fields are compared last, so the order is consistent with `PartialEq`.
+impl Ord for Timestamp {
+    fn cmp(&self, other: &Timestamp) -> Ordering {
+        (self.seconds, self.nanos, &self.unknown_fields).cmp(&(
+            other.seconds,
+            other.nanos,
+            &other.unknown_fields,
+        ))
+    }
+}
+
+impl PartialOrd for Timestamp {
+    fn partial_cmp(&self, other: &Timestamp) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for Timestamp {}
+
+/// # Panics
+///
+/// If the result is not [valid](Timestamp::is_valid).
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, duration: Duration) -> Timestamp {
+        self.checked_add(&duration)
+            .expect("overflow when adding duration to timestamp")
+    }
+}
+
+/// # Panics
+///
+/// If the result is not [valid](Timestamp::is_valid).
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, duration: Duration) -> Timestamp {
+        self.checked_sub(&duration)
+            .expect("overflow when subtracting duration from timestamp")
+    }
+}
+
+/// # Panics
+///
+/// If the result is not [valid](Duration::is_valid).
+impl Sub for Timestamp {
+    type Output = Duration;
+
+    fn sub(self, earlier: Timestamp) -> Duration {
+        self.checked_duration_since(&earlier)
+            .expect("overflow when subtracting timestamps")
+    }
+}
+
+impl AddAssign<Duration> for Timestamp {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = self.clone() + duration;
+    }
+}
+
+impl SubAssign<Duration> for Timestamp {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = self.clone() - duration;
+    }
+}
+
+/// Format as RFC 3339 string like `1972-01-01T10:00:20.021Z`.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut t = self.clone();
+        t.normalize();
+        let tm_utc = TmUtc::from_protobuf_timestamp(t.seconds, t.nanos as u32);
+        let s = format!("{:.0}", tm_utc);
+        // Insert fraction before `Z`
+        write!(f, "{}", &s[..s.len() - 1])?;
+        write_subsec_nanos(f, t.nanos as u32)?;
+        write!(f, "Z")
+    }
+}
+
+/// Parse RFC 3339 string like `1972-01-01T10:00:20.021-05:00`.
+///
+/// Timestamps which are not [valid](Timestamp::is_valid) are rejected.
+impl FromStr for Timestamp {
+    type Err = TimestampParseError;
+
+    fn from_str(s: &str) -> Result<Timestamp, TimestampParseError> {
+        let (seconds, nanos) = TmUtc::parse_rfc_3339(s).map_err(TimestampParseError)?;
+        let timestamp = Timestamp {
+            seconds,
+            nanos: nanos as i32,
+            ..Default::default()
+        };
+        if !timestamp.is_valid() {
+            return Err(TimestampParseError(
+                Rfc3339ParseError::DateTimeFieldOutOfRange,
+            ));
+        }
+        Ok(timestamp)
+    }
 }
 
 /// Convert from [`Timestamp`].
@@ -56,12 +247,12 @@ impl From<SystemTime> for Timestamp {
 impl Into<SystemTime> for Timestamp {
     fn into(self) -> SystemTime {
         if self.seconds >= 0 {
-            let duration =
-                Duration::from_secs(self.seconds as u64) + Duration::from_nanos(self.nanos as u64);
+            let duration = std::time::Duration::from_secs(self.seconds as u64)
+                + std::time::Duration::from_nanos(self.nanos as u64);
             SystemTime::UNIX_EPOCH + duration
         } else {
-            let duration =
-                Duration::from_secs(-self.seconds as u64) - Duration::from_nanos(self.nanos as u64);
+            let duration = std::time::Duration::from_secs(-self.seconds as u64)
+                - std::time::Duration::from_nanos(self.nanos as u64);
             SystemTime::UNIX_EPOCH - duration
         }
     }
@@ -70,6 +261,8 @@ impl Into<SystemTime> for Timestamp {
 #[cfg(test)]
 mod test {
     use crate::well_known_types::Timestamp;
+    use crate::Message;
+    use std::cmp::Ordering;
     use std::time::Duration;
     use std::time::SystemTime;
 
@@ -114,4 +307,100 @@ mod test {
             SystemTime::UNIX_EPOCH - Duration::from_millis(3_200),
         );
     }
+
+    fn timestamp(seconds: i64, nanos: i32) -> Timestamp {
+        Timestamp {
+            seconds,
+            nanos,
+            ..Default::default()
+        }
+    }
+
+    fn duration(seconds: i64, nanos: i32) -> crate::well_known_types::Duration {
+        crate::well_known_types::Duration {
+            seconds,
+            nanos,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn normalize() {
+        let mut t = timestamp(1, -1);
+        t.normalize();
+        assert_eq!(timestamp(0, 999_999_999), t);
+        let mut t = timestamp(-1, 1_500_000_000);
+        t.normalize();
+        assert_eq!(timestamp(0, 500_000_000), t);
+    }
+
+    #[test]
+    fn is_valid() {
+        assert!(timestamp(0, 0).is_valid());
+        assert!(timestamp(Timestamp::MIN_SECONDS, 0).is_valid());
+        assert!(timestamp(Timestamp::MAX_SECONDS, 999_999_999).is_valid());
+        assert!(!timestamp(Timestamp::MIN_SECONDS - 1, 999_999_999).is_valid());
+        assert!(!timestamp(Timestamp::MAX_SECONDS + 1, 0).is_valid());
+        assert!(!timestamp(0, -1).is_valid());
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(
+            timestamp(-1, 500_000_000),
+            timestamp(0, 200_000_000) - duration(0, 700_000_000)
+        );
+        assert_eq!(
+            timestamp(11, 100_000_000),
+            timestamp(10, 900_000_000) + duration(0, 200_000_000)
+        );
+        assert_eq!(
+            duration(-1, -500_000_000),
+            timestamp(1, 0) - timestamp(2, 500_000_000)
+        );
+        assert_eq!(
+            None,
+            timestamp(Timestamp::MAX_SECONDS, 0).checked_add(&duration(1, 0))
+        );
+        let mut t = timestamp(0, 0);
+        t += duration(2, 0);
+        t -= duration(0, 1);
+        assert_eq!(timestamp(1, 999_999_999), t);
+    }
+
+    #[test]
+    fn ord() {
+        assert!(timestamp(-1, 999_999_999) < timestamp(0, 0));
+        assert!(timestamp(0, 1) > timestamp(0, 0));
+
+        let mut with_unknown = timestamp(0, 0);
+        with_unknown.mut_unknown_fields().add_varint(10, 1);
+        assert_ne!(timestamp(0, 0), with_unknown);
+        assert_ne!(Ordering::Equal, timestamp(0, 0).cmp(&with_unknown));
+        assert!(timestamp(0, 1) > with_unknown);
+    }
+
+    #[test]
+    fn display_from_str() {
+        fn check(s: &str, t: Timestamp) {
+            assert_eq!(s, t.to_string());
+            assert_eq!(t, s.parse().unwrap());
+        }
+
+        check("1970-01-01T00:00:00Z", Timestamp::UNIX_EPOCH);
+        check("1970-01-01T00:00:01.000000001Z", timestamp(1, 1));
+        check("1969-12-31T23:59:59.500Z", timestamp(-1, 500_000_000));
+        check("0001-01-01T00:00:00Z", timestamp(Timestamp::MIN_SECONDS, 0));
+        check(
+            "9999-12-31T23:59:59.999999Z",
+            timestamp(Timestamp::MAX_SECONDS, 999_999_000),
+        );
+        assert_eq!(
+            timestamp(3600, 0),
+            "1970-01-01T00:00:00-01:00".parse().unwrap()
+        );
+        assert!("1970-01-01".parse::<Timestamp>().is_err());
+        assert!("0001-01-01T00:00:00+01:00".parse::<Timestamp>().is_err());
+        assert!("9999-12-31T23:59:59-01:00".parse::<Timestamp>().is_err());
+    }
 }
@@ -0,0 +1,450 @@
+use crate::descriptor::field_descriptor_proto;
+use crate::descriptor::DescriptorProto;
+use crate::descriptor::EnumDescriptorProto;
+use crate::descriptor::EnumValueDescriptorProto;
+use crate::descriptor::FieldDescriptorProto;
+use crate::descriptor::FileDescriptorProto;
+use crate::descriptor::OneofDescriptorProto;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::EnumDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeTypeBox;
+use crate::well_known_types::Any;
+use crate::well_known_types::BoolValue;
+use crate::well_known_types::BytesValue;
+use crate::well_known_types::DoubleValue;
+use crate::well_known_types::Enum;
+use crate::well_known_types::EnumValue;
+use crate::well_known_types::Field;
+use crate::well_known_types::FloatValue;
+use crate::well_known_types::Int32Value;
+use crate::well_known_types::Int64Value;
+use crate::well_known_types::Option as OptionPb;
+use crate::well_known_types::SourceContext;
+use crate::well_known_types::StringValue;
+use crate::well_known_types::Syntax;
+use crate::well_known_types::Type;
+use crate::well_known_types::UInt32Value;
+use crate::well_known_types::UInt64Value;
+use crate::Message;
+use crate::MessageField;
+use crate::ProtobufEnum;
+use crate::ProtobufEnumOrUnknown;
+
+/// Type URL prefix used for `type_url` fields, same as the one used by [`Any::pack`].
+const TYPE_URL_PREFIX: &str = "type.googleapis.com";
+
+/// Type URL of a message or enum with given protobuf name (with or without leading dot).
+pub(crate) fn type_url(type_name: &str) -> String {
+    format!("{}/{}", TYPE_URL_PREFIX, type_name.trim_start_matches('.'))
+}
+
+/// Absolute protobuf name (with leading dot) of a type referenced by type URL.
+pub(crate) fn type_name_from_type_url(type_url: &str) -> String {
+    let name = match type_url.rfind('/') {
+        Some(i) => &type_url[i + 1..],
+        None => type_url,
+    };
+    format!(".{}", name)
+}
+
+/// Last component of dotted name.
+pub(crate) fn simple_name(full_name: &str) -> &str {
+    match full_name.rfind('.') {
+        Some(i) => &full_name[i + 1..],
+        None => full_name,
+    }
+}
+
+pub(crate) fn syntax_of_file(file: &FileDescriptorProto) -> Syntax {
+    match file.get_syntax() {
+        "proto3" => Syntax::SYNTAX_PROTO3,
+        _ => Syntax::SYNTAX_PROTO2,
+    }
+}
+
+pub(crate) fn source_context_of_file(file: &FileDescriptorProto) -> MessageField<SourceContext> {
+    MessageField::some(SourceContext {
+        file_name: file.get_name().to_owned(),
+        ..Default::default()
+    })
+}
+
+/// Convert options message (like `MessageOptions`) to the list of `Option`.
+///
+/// Each set singular field becomes an `Option` with the field name
+/// and the value packed into `Any` as a wrapper type (e. g. `BoolValue`)
+/// or as is for message fields. Repeated fields (like `uninterpreted_option`)
+/// and extensions are not converted.
+pub(crate) fn options_to_type_options(options: &dyn MessageDyn) -> Vec<OptionPb> {
+    let mut r = Vec::new();
+    for field in options.descriptor_dyn().fields() {
+        if !field.is_singular() {
+            continue;
+        }
+        let value = match field.get_singular(options) {
+            Some(value) => value,
+            None => continue,
+        };
+        let any = match value {
+            ReflectValueRef::U32(v) => Any::pack(&UInt32Value::from(v)),
+            ReflectValueRef::U64(v) => Any::pack(&UInt64Value::from(v)),
+            ReflectValueRef::I32(v) => Any::pack(&Int32Value::from(v)),
+            ReflectValueRef::I64(v) => Any::pack(&Int64Value::from(v)),
+            ReflectValueRef::F32(v) => Any::pack(&FloatValue::from(v)),
+            ReflectValueRef::F64(v) => Any::pack(&DoubleValue::from(v)),
+            ReflectValueRef::Bool(v) => Any::pack(&BoolValue::from(v)),
+            ReflectValueRef::String(v) => Any::pack(&StringValue::from(v.to_owned())),
+            ReflectValueRef::Bytes(v) => Any::pack(&BytesValue::from(v.to_owned())),
+            ReflectValueRef::Enum(_, v) => Any::pack(&Int32Value::from(v)),
+            ReflectValueRef::Message(m) => Any::pack_dyn(&*m),
+        };
+        if let Ok(any) = any {
+            r.push(OptionPb {
+                name: field.get_name().to_owned(),
+                value: MessageField::some(any),
+                ..Default::default()
+            });
+        }
+    }
+    r
+}
+
+fn unpack_option_value(any: &Any, field_type: &RuntimeTypeBox) -> Option<ReflectValueBox> {
+    fn unpack<M: Message, T: From<M>>(any: &Any) -> Option<T> {
+        any.unpack::<M>().ok().flatten().map(T::from)
+    }
+
+    Some(match field_type {
+        RuntimeTypeBox::U32 => ReflectValueBox::U32(unpack::<UInt32Value, _>(any)?),
+        RuntimeTypeBox::U64 => ReflectValueBox::U64(unpack::<UInt64Value, _>(any)?),
+        RuntimeTypeBox::I32 => ReflectValueBox::I32(unpack::<Int32Value, _>(any)?),
+        RuntimeTypeBox::I64 => ReflectValueBox::I64(unpack::<Int64Value, _>(any)?),
+        RuntimeTypeBox::F32 => ReflectValueBox::F32(unpack::<FloatValue, _>(any)?),
+        RuntimeTypeBox::F64 => ReflectValueBox::F64(unpack::<DoubleValue, _>(any)?),
+        RuntimeTypeBox::Bool => ReflectValueBox::Bool(unpack::<BoolValue, _>(any)?),
+        RuntimeTypeBox::String => ReflectValueBox::String(unpack::<StringValue, _>(any)?),
+        RuntimeTypeBox::VecU8 => ReflectValueBox::Bytes(unpack::<BytesValue, _>(any)?),
+        RuntimeTypeBox::Enum(e) => ReflectValueBox::Enum(e.clone(), unpack::<Int32Value, _>(any)?),
+        RuntimeTypeBox::Message(m) => ReflectValueBox::Message(any.unpack_dyn(m).ok()??),
+    })
+}
+
+/// Convert the list of `Option` back to options message (like `MessageOptions`).
+///
+/// Options which do not name a singular field of the options message
+/// or which cannot be unpacked are ignored.
+pub(crate) fn type_options_to_options<M: Message>(options: &[OptionPb]) -> MessageField<M> {
+    if options.is_empty() {
+        return MessageField::none();
+    }
+    let mut r = M::new();
+    let descriptor = M::descriptor_static();
+    for option in options {
+        let field = match descriptor.get_field_by_name(&option.name) {
+            Some(field) if field.is_singular() => field,
+            _ => continue,
+        };
+        let any = match option.value.as_ref() {
+            Some(any) => any,
+            None => continue,
+        };
+        if let Some(value) = unpack_option_value(any, &field.singular_runtime_type()) {
+            field.set_singular_field(&mut r, value);
+        }
+    }
+    MessageField::some(r)
+}
+
+fn is_packable(field_type: field_descriptor_proto::Type) -> bool {
+    match field_type {
+        field_descriptor_proto::Type::TYPE_STRING
+        | field_descriptor_proto::Type::TYPE_BYTES
+        | field_descriptor_proto::Type::TYPE_MESSAGE
+        | field_descriptor_proto::Type::TYPE_GROUP => false,
+        _ => true,
+    }
+}
+
+impl Field {
+    fn from_field_descriptor_proto(field: &FieldDescriptorProto, syntax: Syntax) -> Field {
+        let repeated = field.get_label() == field_descriptor_proto::Label::LABEL_REPEATED;
+        let options = field.options.get_or_default();
+        let packed = repeated
+            && is_packable(field.get_field_type())
+            && if options.has_packed() {
+                options.get_packed()
+            } else {
+                syntax == Syntax::SYNTAX_PROTO3
+            };
+        let type_url = match field.get_field_type() {
+            field_descriptor_proto::Type::TYPE_MESSAGE
+            | field_descriptor_proto::Type::TYPE_ENUM
+            | field_descriptor_proto::Type::TYPE_GROUP => type_url(field.get_type_name()),
+            _ => String::new(),
+        };
+        Field {
+            kind: ProtobufEnumOrUnknown::from_i32(field.get_field_type().value()),
+            cardinality: ProtobufEnumOrUnknown::from_i32(field.get_label().value()),
+            number: field.get_number(),
+            name: field.get_name().to_owned(),
+            type_url,
+            oneof_index: if field.has_oneof_index() {
+                field.get_oneof_index() + 1
+            } else {
+                0
+            },
+            packed,
+            options: options_to_type_options(options),
+            json_name: field.get_json_name().to_owned(),
+            default_value: field.get_default_value().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    fn to_field_descriptor_proto(&self, syntax: Syntax) -> FieldDescriptorProto {
+        let mut r = FieldDescriptorProto::new();
+        r.set_name(self.name.clone());
+        r.set_number(self.number);
+        if let Some(label) = field_descriptor_proto::Label::from_i32(self.cardinality.value()) {
+            r.set_label(label);
+        }
+        if let Some(field_type) = field_descriptor_proto::Type::from_i32(self.kind.value()) {
+            r.set_field_type(field_type);
+        }
+        if !self.type_url.is_empty() {
+            r.set_type_name(type_name_from_type_url(&self.type_url));
+        }
+        if self.oneof_index > 0 {
+            r.set_oneof_index(self.oneof_index - 1);
+        }
+        if !self.json_name.is_empty() {
+            r.set_json_name(self.json_name.clone());
+        }
+        if !self.default_value.is_empty() {
+            r.set_default_value(self.default_value.clone());
+        }
+        r.options = type_options_to_options(&self.options);
+        // Only store `packed` when it differs from the syntax default
+        let packed_by_default = r.get_label() == field_descriptor_proto::Label::LABEL_REPEATED
+            && is_packable(r.get_field_type())
+            && syntax == Syntax::SYNTAX_PROTO3;
+        if self.packed != packed_by_default && !r.options.get_or_default().has_packed() {
+            r.options.mut_or_default().set_packed(self.packed);
+        }
+        r
+    }
+}
+
+impl Type {
+    /// Describe a message in `google.protobuf.Type` form.
+    ///
+    /// Message and enum fields reference their types by type URL
+    /// with `type.googleapis.com` prefix. Nested types are not included,
+    /// they should be converted separately.
+    pub fn from_descriptor(descriptor: &MessageDescriptor) -> Type {
+        let proto = descriptor.get_proto();
+        let file = descriptor.file_descriptor_proto();
+        let syntax = syntax_of_file(file);
+        Type {
+            name: descriptor.full_name().to_owned(),
+            fields: proto
+                .field
+                .iter()
+                .map(|f| Field::from_field_descriptor_proto(f, syntax))
+                .collect(),
+            oneofs: proto
+                .oneof_decl
+                .iter()
+                .map(|o| o.get_name().to_owned())
+                .collect(),
+            options: options_to_type_options(proto.options.get_or_default()),
+            source_context: source_context_of_file(file),
+            syntax: ProtobufEnumOrUnknown::new(syntax),
+            ..Default::default()
+        }
+    }
+
+    /// Convert this type back to a message descriptor proto.
+    ///
+    /// Descriptor name is the last component of the type name,
+    /// field type names are fully qualified names taken from type URLs.
+    pub fn to_descriptor_proto(&self) -> DescriptorProto {
+        let syntax = self.syntax.enum_value_or_default();
+        let mut r = DescriptorProto::new();
+        r.set_name(simple_name(&self.name).to_owned());
+        r.field = self
+            .fields
+            .iter()
+            .map(|f| f.to_field_descriptor_proto(syntax))
+            .collect();
+        r.oneof_decl = self
+            .oneofs
+            .iter()
+            .map(|name| {
+                let mut oneof = OneofDescriptorProto::new();
+                oneof.set_name(name.clone());
+                oneof
+            })
+            .collect();
+        r.options = type_options_to_options(&self.options);
+        r
+    }
+}
+
+impl Enum {
+    /// Describe an enum in `google.protobuf.Enum` form.
+    pub fn from_descriptor(descriptor: &EnumDescriptor) -> Enum {
+        let proto = descriptor.get_proto();
+        let file = descriptor.file_descriptor_proto();
+        Enum {
+            name: descriptor.full_name().to_owned(),
+            enumvalue: proto
+                .value
+                .iter()
+                .map(|v| EnumValue {
+                    name: v.get_name().to_owned(),
+                    number: v.get_number(),
+                    options: options_to_type_options(v.options.get_or_default()),
+                    ..Default::default()
+                })
+                .collect(),
+            options: options_to_type_options(proto.options.get_or_default()),
+            source_context: source_context_of_file(file),
+            syntax: ProtobufEnumOrUnknown::new(syntax_of_file(file)),
+            ..Default::default()
+        }
+    }
+
+    /// Convert this enum back to an enum descriptor proto.
+    pub fn to_descriptor_proto(&self) -> EnumDescriptorProto {
+        let mut r = EnumDescriptorProto::new();
+        r.set_name(simple_name(&self.name).to_owned());
+        r.value = self
+            .enumvalue
+            .iter()
+            .map(|v| {
+                let mut value = EnumValueDescriptorProto::new();
+                value.set_name(v.name.clone());
+                value.set_number(v.number);
+                value.options = type_options_to_options(&v.options);
+                value
+            })
+            .collect();
+        r.options = type_options_to_options(&self.options);
+        r
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::FieldOptions;
+    use crate::descriptor::MessageOptions;
+    use crate::well_known_types::field::Cardinality;
+    use crate::well_known_types::field::Kind;
+
+    #[test]
+    fn type_from_proto2_descriptor() {
+        let t = Type::from_descriptor(&FieldDescriptorProto::descriptor_static());
+        assert_eq!("google.protobuf.FieldDescriptorProto", t.name);
+        assert_eq!(Syntax::SYNTAX_PROTO2, t.syntax.enum_value_or_default());
+        assert_eq!(
+            "google/protobuf/descriptor.proto",
+            t.source_context.get_or_default().file_name
+        );
+        let label = t.fields.iter().find(|f| f.name == "label").unwrap();
+        assert_eq!(Kind::TYPE_ENUM, label.kind.enum_value_or_default());
+        assert_eq!(
+            Cardinality::CARDINALITY_OPTIONAL,
+            label.cardinality.enum_value_or_default()
+        );
+        assert_eq!(
+            "type.googleapis.com/google.protobuf.FieldDescriptorProto.Label",
+            label.type_url
+        );
+        assert_eq!(4, label.number);
+    }
+
+    #[test]
+    fn type_from_proto3_descriptor() {
+        let t = Type::from_descriptor(&Type::descriptor_static());
+        assert_eq!(Syntax::SYNTAX_PROTO3, t.syntax.enum_value_or_default());
+        let fields = t.fields.iter().find(|f| f.name == "fields").unwrap();
+        assert_eq!(
+            Cardinality::CARDINALITY_REPEATED,
+            fields.cardinality.enum_value_or_default()
+        );
+        assert_eq!("type.googleapis.com/google.protobuf.Field", fields.type_url);
+        assert!(!fields.packed);
+    }
+
+    #[test]
+    fn type_round_trip() {
+        let descriptor = FieldDescriptorProto::descriptor_static();
+        let proto = Type::from_descriptor(&descriptor).to_descriptor_proto();
+        assert_eq!(descriptor.get_proto().get_name(), proto.get_name());
+        assert_eq!(descriptor.get_proto().field.len(), proto.field.len());
+        for (expected, actual) in descriptor.get_proto().field.iter().zip(proto.field.iter()) {
+            assert_eq!(expected.get_name(), actual.get_name());
+            assert_eq!(expected.get_number(), actual.get_number());
+            assert_eq!(expected.get_label(), actual.get_label());
+            assert_eq!(expected.get_field_type(), actual.get_field_type());
+            assert_eq!(expected.get_type_name(), actual.get_type_name());
+            assert_eq!(expected.get_default_value(), actual.get_default_value());
+        }
+    }
+
+    #[test]
+    fn packed_only_when_not_default() {
+        let field = Field {
+            kind: ProtobufEnumOrUnknown::new(Kind::TYPE_INT32),
+            cardinality: ProtobufEnumOrUnknown::new(Cardinality::CARDINALITY_REPEATED),
+            packed: true,
+            ..Default::default()
+        };
+        let proto3 = field.to_field_descriptor_proto(Syntax::SYNTAX_PROTO3);
+        assert!(proto3.options.is_none());
+        let proto2 = field.to_field_descriptor_proto(Syntax::SYNTAX_PROTO2);
+        assert_eq!(true, proto2.options.get_or_default().get_packed());
+
+        let unpacked = Field {
+            packed: false,
+            ..field
+        };
+        let proto3 = unpacked.to_field_descriptor_proto(Syntax::SYNTAX_PROTO3);
+        assert!(proto3.options.get_or_default().has_packed());
+        assert_eq!(false, proto3.options.get_or_default().get_packed());
+        let proto2 = unpacked.to_field_descriptor_proto(Syntax::SYNTAX_PROTO2);
+        assert!(proto2.options.is_none());
+    }
+
+    #[test]
+    fn options_round_trip() {
+        let mut options = FieldOptions::new();
+        options.set_packed(true);
+        options.set_deprecated(false);
+        options.set_ctype(crate::descriptor::field_options::CType::CORD);
+        let type_options = options_to_type_options(&options);
+        assert_eq!(3, type_options.len());
+        let back = type_options_to_options::<FieldOptions>(&type_options);
+        assert_eq!(&options, back.get_or_default());
+
+        assert_eq!(
+            MessageField::none(),
+            type_options_to_options::<MessageOptions>(&[])
+        );
+    }
+
+    #[test]
+    fn enum_round_trip() {
+        let descriptor = Kind::enum_descriptor_static();
+        let e = Enum::from_descriptor(&descriptor);
+        assert_eq!("google.protobuf.Field.Kind", e.name);
+        assert_eq!("TYPE_DOUBLE", e.enumvalue[1].name);
+        assert_eq!(1, e.enumvalue[1].number);
+        assert_eq!(descriptor.get_proto(), &e.to_descriptor_proto());
+    }
+}
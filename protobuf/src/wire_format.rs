@@ -19,9 +19,9 @@ pub enum WireType {
     WireTypeFixed64 = 1,
     /// Length-delimited field
     WireTypeLengthDelimited = 2,
-    /// Groups are not supported in rust-protobuf
+    /// Start of a group (proto2 only)
     WireTypeStartGroup = 3,
-    /// Groups are not supported in rust-protobuf
+    /// End of a group (proto2 only)
     WireTypeEndGroup = 4,
     /// 32-bit field (e. g. `fixed32` or `float`)
     WireTypeFixed32 = 5,